@@ -0,0 +1,131 @@
+/*
+ * C bindings for the edabits conversion of ocelot.
+ *
+ * Ownership rules:
+ * - A handle returned by `edabits_prover_new` (resp. `edabits_verifier_new`)
+ *   is owned by the caller, and must be released exactly once with
+ *   `edabits_prover_free` (resp. `edabits_verifier_free`). Freeing NULL does
+ *   nothing.
+ * - The socket given to `*_new` stays owned by the caller: the handle uses a
+ *   duplicate of it, closed when the handle is freed.
+ * - Arrays and structs passed to a function are only borrowed for the
+ *   duration of the call. Nothing allocated by the library other than the
+ *   handles is handed out.
+ * - A handle must not be used by several threads at the same time.
+ *
+ * Errors: every fallible function returns an `EdabitsStatus`, and writes a
+ * NUL-terminated description of the error, truncated to fit, into the
+ * `msg` buffer of `msg_len` bytes when `msg` is not NULL (an empty string on
+ * success). Panics are caught and reported as `EDABITS_PANIC`. After any
+ * error other than `EDABITS_INVALID_ARGUMENT`, the parties may be out of
+ * sync and the handle can only be freed.
+ */
+#ifndef EDABITS_H
+#define EDABITS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    EDABITS_OK = 0,
+    /* An argument is invalid; nothing was sent and the handle is usable. */
+    EDABITS_INVALID_ARGUMENT = 1,
+    /* Reading from or writing to the socket failed. */
+    EDABITS_IO = 2,
+    /* The protocol failed, e.g. the verifier rejected the conversion. */
+    EDABITS_PROTOCOL = 3,
+    /* The library panicked. */
+    EDABITS_PANIC = 4,
+} EdabitsStatus;
+
+/* The prime field the edabits are converted to. */
+typedef enum {
+    EDABITS_FIELD_F61P = 0,
+    EDABITS_FIELD_F127P = 1,
+} EdabitsField;
+
+/* The size of the LPN parameters of the VOLEs; both parties must agree. */
+typedef enum {
+    EDABITS_LPN_SMALL = 0,
+    EDABITS_LPN_MEDIUM = 1,
+    EDABITS_LPN_LARGE = 2,
+} EdabitsLpnSize;
+
+typedef struct {
+    size_t num_bucket;
+    size_t num_cut;
+    /* Non-zero for the QuickSilver multiplication check, zero for Wolverine. */
+    int with_quicksilver;
+} EdabitsConvConfig;
+
+/* Communication of a conversion on the socket, the VOLE extensions included. */
+typedef struct {
+    uint64_t bytes_read;
+    uint64_t bytes_written;
+} EdabitsConvStats;
+
+typedef struct EdabitsProver EdabitsProver;
+typedef struct EdabitsVerifier EdabitsVerifier;
+
+/* 5 buckets, 5 cut, QuickSilver. */
+EdabitsConvConfig edabits_conv_config_default(void);
+
+/*
+ * Run the setup of the prover over the connected socket `fd`, whose peer
+ * calls `edabits_verifier_new` with the same field and LPN size.
+ */
+EdabitsStatus edabits_prover_new(int fd, EdabitsField field, EdabitsLpnSize lpn_size,
+                                 EdabitsProver **out, char *msg, size_t msg_len);
+/* Commit to `num` random edabits of `nb_bits` bits. */
+EdabitsStatus edabits_prover_random(EdabitsProver *prover, size_t nb_bits, size_t num,
+                                    char *msg, size_t msg_len);
+/*
+ * Commit to `num` edabits of `nb_bits` bits given by their bit
+ * decompositions: `bits` holds `num * nb_bits` bytes, each 0 or 1, the bits
+ * of every value being least significant first.
+ */
+EdabitsStatus edabits_prover_submit_bits(EdabitsProver *prover, const uint8_t *bits,
+                                         size_t nb_bits, size_t num, char *msg,
+                                         size_t msg_len);
+/* Commit to `num` edabits of `nb_bits` bits holding `values`. */
+EdabitsStatus edabits_prover_submit_values(EdabitsProver *prover, const uint64_t *values,
+                                           size_t nb_bits, size_t num, char *msg,
+                                           size_t msg_len);
+/*
+ * Prove the conversion of the edabits committed since the last conversion,
+ * and write its statistics to `stats` if not NULL. All the edabits of a
+ * conversion must have the same number of bits.
+ */
+EdabitsStatus edabits_prover_conv(EdabitsProver *prover, const EdabitsConvConfig *config,
+                                  EdabitsConvStats *stats, char *msg, size_t msg_len);
+/* The number of edabits committed since the last conversion. */
+size_t edabits_prover_pending(const EdabitsProver *prover);
+void edabits_prover_free(EdabitsProver *prover);
+
+/* Counterpart of `edabits_prover_new`. */
+EdabitsStatus edabits_verifier_new(int fd, EdabitsField field, EdabitsLpnSize lpn_size,
+                                   EdabitsVerifier **out, char *msg, size_t msg_len);
+/* Counterpart of `edabits_prover_random`. */
+EdabitsStatus edabits_verifier_random(EdabitsVerifier *verifier, size_t nb_bits, size_t num,
+                                      char *msg, size_t msg_len);
+/* Counterpart of both `edabits_prover_submit_bits` and `edabits_prover_submit_values`. */
+EdabitsStatus edabits_verifier_submit(EdabitsVerifier *verifier, size_t nb_bits, size_t num,
+                                      char *msg, size_t msg_len);
+/*
+ * Check the conversion of the edabits committed since the last conversion.
+ * Returns `EDABITS_PROTOCOL` if the check fails.
+ */
+EdabitsStatus edabits_verifier_conv(EdabitsVerifier *verifier, const EdabitsConvConfig *config,
+                                    EdabitsConvStats *stats, char *msg, size_t msg_len);
+size_t edabits_verifier_pending(const EdabitsVerifier *verifier);
+void edabits_verifier_free(EdabitsVerifier *verifier);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* EDABITS_H */
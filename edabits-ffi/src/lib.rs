@@ -0,0 +1,709 @@
+//! C bindings for the edabits conversion of `ocelot`.
+//!
+//! The API is declared in `include/edabits.h`, which also states the
+//! ownership rules. Every exported function catches panics, and reports
+//! errors as an [`EdabitsStatus`] along with a message written into a
+//! caller-provided buffer.
+
+#![cfg(unix)]
+
+use ocelot::edabits::{
+    ConvConfig, EdabitsProver, EdabitsVerifier, MultCheck, ProverConv, VerifierConv,
+};
+use ocelot::svole::wykw::{
+    LpnParams, LPN_EXTEND_LARGE, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_SETUP_LARGE,
+    LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
+};
+use ocelot::Error;
+use scuttlebutt::{
+    field::{F127p, F61p, FiniteField, F2},
+    ring::FiniteRing,
+    AesRng, SyncChannel, TrackChannel,
+};
+use std::{
+    ffi::c_char,
+    fmt,
+    fs::File,
+    io::{BufReader, BufWriter},
+    os::unix::io::{BorrowedFd, RawFd},
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Status returned by the fallible functions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdabitsStatus {
+    /// Success.
+    Ok = 0,
+    /// An argument is invalid; nothing was sent.
+    InvalidArgument = 1,
+    /// Reading from or writing to the socket failed.
+    Io = 2,
+    /// The protocol failed, e.g. the verifier rejected the conversion.
+    Protocol = 3,
+    /// The library panicked.
+    Panic = 4,
+}
+
+/// The prime field the edabits are converted to.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdabitsField {
+    /// [`F61p`].
+    F61p = 0,
+    /// [`F127p`].
+    F127p = 1,
+}
+
+/// Size of the LPN parameters of the VOLEs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdabitsLpnSize {
+    /// [`LPN_SETUP_SMALL`] and [`LPN_EXTEND_SMALL`].
+    Small = 0,
+    /// [`LPN_SETUP_MEDIUM`] and [`LPN_EXTEND_MEDIUM`].
+    Medium = 1,
+    /// [`LPN_SETUP_LARGE`] and [`LPN_EXTEND_LARGE`].
+    Large = 2,
+}
+
+/// Parameters of a conversion.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdabitsConvConfig {
+    /// Number of buckets.
+    pub num_bucket: usize,
+    /// Number of edabits opened by the cut-and-choose.
+    pub num_cut: usize,
+    /// Non-zero for the QuickSilver multiplication check, zero for
+    /// Wolverine.
+    pub with_quicksilver: i32,
+}
+
+/// Communication of a conversion on the socket.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EdabitsConvStats {
+    /// Bytes read.
+    pub bytes_read: u64,
+    /// Bytes written.
+    pub bytes_written: u64,
+}
+
+/// Return the default conversion parameters: 5 buckets, 5 cut, QuickSilver.
+#[no_mangle]
+pub extern "C" fn edabits_conv_config_default() -> EdabitsConvConfig {
+    let config = ConvConfig::default();
+    EdabitsConvConfig {
+        num_bucket: config.num_bucket(),
+        num_cut: config.num_cut(),
+        with_quicksilver: (config.mult_check() == MultCheck::QuickSilver) as i32,
+    }
+}
+
+impl EdabitsConvConfig {
+    fn to_conv_config(&self) -> ConvConfig {
+        let strategy = if self.with_quicksilver != 0 {
+            MultCheck::QuickSilver
+        } else {
+            MultCheck::Wolverine
+        };
+        ConvConfig::new()
+            .buckets(self.num_bucket)
+            .cut(self.num_cut)
+            .strategy(strategy)
+    }
+}
+
+enum FfiError {
+    InvalidArgument(String),
+    Conv(Error),
+}
+
+impl FfiError {
+    fn status(&self) -> EdabitsStatus {
+        match self {
+            FfiError::InvalidArgument(_) => EdabitsStatus::InvalidArgument,
+            FfiError::Conv(Error::IoError(_)) | FfiError::Conv(Error::PeerDisconnected) => {
+                EdabitsStatus::Io
+            }
+            FfiError::Conv(_) => EdabitsStatus::Protocol,
+        }
+    }
+}
+
+impl fmt::Display for FfiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FfiError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+            FfiError::Conv(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(e: Error) -> Self {
+        FfiError::Conv(e)
+    }
+}
+
+impl From<std::io::Error> for FfiError {
+    fn from(e: std::io::Error) -> Self {
+        FfiError::Conv(Error::IoError(e))
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> FfiError {
+    FfiError::InvalidArgument(msg.into())
+}
+
+// Write `text` into the buffer `msg` of `msg_len` bytes, truncated and
+// NUL-terminated.
+unsafe fn write_message(msg: *mut c_char, msg_len: usize, text: &str) {
+    if msg.is_null() || msg_len == 0 {
+        return;
+    }
+    let n = text.len().min(msg_len - 1);
+    std::ptr::copy_nonoverlapping(text.as_ptr(), msg as *mut u8, n);
+    *msg.add(n) = 0;
+}
+
+// Run `f`, catching panics, and report its outcome.
+fn ffi_call(
+    msg: *mut c_char,
+    msg_len: usize,
+    f: impl FnOnce() -> Result<(), FfiError>,
+) -> EdabitsStatus {
+    let (status, text) = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => (EdabitsStatus::Ok, String::new()),
+        Ok(Err(e)) => (e.status(), e.to_string()),
+        Err(payload) => {
+            let text = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "unknown panic".to_string()
+            };
+            (EdabitsStatus::Panic, format!("panic: {}", text))
+        }
+    };
+    unsafe { write_message(msg, msg_len, &text) };
+    status
+}
+
+type FdChannel = TrackChannel<SyncChannel<BufReader<File>, BufWriter<File>>>;
+
+fn fd_channel(fd: RawFd) -> Result<FdChannel, FfiError> {
+    if fd < 0 {
+        return Err(invalid("negative file descriptor"));
+    }
+    // The caller keeps the ownership of `fd`.
+    let file = File::from(unsafe { BorrowedFd::borrow_raw(fd) }.try_clone_to_owned()?);
+    let reader = BufReader::new(file.try_clone()?);
+    let writer = BufWriter::new(file);
+    Ok(TrackChannel::new(SyncChannel::new(reader, writer)))
+}
+
+unsafe fn slice<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], FfiError> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(invalid("null array"))
+    } else {
+        Ok(std::slice::from_raw_parts(ptr, len))
+    }
+}
+
+fn array_len(nb_bits: usize, num: usize) -> Result<usize, FfiError> {
+    nb_bits
+        .checked_mul(num)
+        .ok_or_else(|| invalid("array too large"))
+}
+
+fn stats_of(stats: &ocelot::edabits::ConvStats) -> EdabitsConvStats {
+    let total = stats.comm.as_ref().map(|comm| comm.total());
+    EdabitsConvStats {
+        bytes_read: total.map_or(0, |t| t.bytes_read as u64),
+        bytes_written: total.map_or(0, |t| t.bytes_written as u64),
+    }
+}
+
+// The edabits committed since the last conversion, which must all have the
+// same number of bits.
+struct Pending<E> {
+    nb_bits: usize,
+    edabits: Vec<E>,
+}
+
+impl<E> Pending<E> {
+    fn new() -> Self {
+        Self {
+            nb_bits: 0,
+            edabits: Vec::new(),
+        }
+    }
+
+    fn check(&self, nb_bits: usize) -> Result<(), FfiError> {
+        if !self.edabits.is_empty() && self.nb_bits != nb_bits {
+            return Err(invalid(format!(
+                "{} bits, while the pending edabits have {}",
+                nb_bits, self.nb_bits
+            )));
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, nb_bits: usize, edabits: Vec<E>) {
+        self.nb_bits = nb_bits;
+        self.edabits.extend(edabits);
+    }
+}
+
+trait Prover {
+    fn random(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError>;
+    fn submit(&mut self, bits: &[u8], nb_bits: usize) -> Result<(), FfiError>;
+    fn conv(&mut self, config: &EdabitsConvConfig) -> Result<EdabitsConvStats, FfiError>;
+    fn pending(&self) -> usize;
+}
+
+struct ProverState<FE: FiniteField<PrimeField = FE>> {
+    fconv: ProverConv<FE>,
+    channel: FdChannel,
+    rng: AesRng,
+    pending: Pending<EdabitsProver<FE>>,
+}
+
+impl<FE: FiniteField<PrimeField = FE>> ProverState<FE> {
+    fn new(
+        mut channel: FdChannel,
+        (lpn_setup, lpn_extend): (LpnParams, LpnParams),
+    ) -> Result<Self, FfiError> {
+        let mut rng = AesRng::new();
+        let fconv = ProverConv::init(&mut channel, &mut rng, lpn_setup, lpn_extend)?;
+        Ok(Self {
+            fconv,
+            channel,
+            rng,
+            pending: Pending::new(),
+        })
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>> Prover for ProverState<FE> {
+    fn random(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError> {
+        self.pending.check(nb_bits)?;
+        let edabits = self
+            .fconv
+            .random_edabits(&mut self.channel, &mut self.rng, nb_bits, num)?;
+        self.pending.push(nb_bits, edabits);
+        Ok(())
+    }
+
+    // `bits` holds the bit decompositions of the values, `nb_bits` bytes
+    // each, which are checked before anything is sent.
+    fn submit(&mut self, bits: &[u8], nb_bits: usize) -> Result<(), FfiError> {
+        self.pending.check(nb_bits)?;
+        if nb_bits == 0 || nb_bits >= FE::bit_decomposition_len() {
+            return Err(invalid(format!("nb_bits={} is out of range", nb_bits)));
+        }
+        let mut f2s = vec![F2::ZERO; nb_bits];
+        let values = bits
+            .chunks_exact(nb_bits)
+            .map(|bits| {
+                for (dst, bit) in f2s.iter_mut().zip(bits) {
+                    *dst = match bit {
+                        0 => F2::ZERO,
+                        1 => F2::ONE,
+                        _ => return Err(invalid("bits must be 0 or 1")),
+                    };
+                }
+                // `nb_bits` is smaller than the modulus, so this cannot fail.
+                Ok(FE::from_le_bits(&f2s).unwrap())
+            })
+            .collect::<Result<Vec<FE>, _>>()?;
+        let edabits =
+            self.fconv
+                .input_edabits(&mut self.channel, &mut self.rng, &values, nb_bits)?;
+        self.pending.push(nb_bits, edabits);
+        Ok(())
+    }
+
+    fn conv(&mut self, config: &EdabitsConvConfig) -> Result<EdabitsConvStats, FfiError> {
+        let edabits = std::mem::take(&mut self.pending.edabits);
+        let stats = self.fconv.conv(
+            &mut self.channel,
+            &mut self.rng,
+            &edabits,
+            &config.to_conv_config(),
+        )?;
+        Ok(stats_of(&stats))
+    }
+
+    fn pending(&self) -> usize {
+        self.pending.edabits.len()
+    }
+}
+
+trait Verifier {
+    fn random(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError>;
+    fn submit(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError>;
+    fn conv(&mut self, config: &EdabitsConvConfig) -> Result<EdabitsConvStats, FfiError>;
+    fn pending(&self) -> usize;
+}
+
+struct VerifierState<FE: FiniteField<PrimeField = FE>> {
+    fconv: VerifierConv<FE>,
+    channel: FdChannel,
+    rng: AesRng,
+    pending: Pending<EdabitsVerifier<FE>>,
+}
+
+impl<FE: FiniteField<PrimeField = FE>> VerifierState<FE> {
+    fn new(
+        mut channel: FdChannel,
+        (lpn_setup, lpn_extend): (LpnParams, LpnParams),
+    ) -> Result<Self, FfiError> {
+        let mut rng = AesRng::new();
+        let fconv = VerifierConv::init(&mut channel, &mut rng, lpn_setup, lpn_extend)?;
+        Ok(Self {
+            fconv,
+            channel,
+            rng,
+            pending: Pending::new(),
+        })
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>> Verifier for VerifierState<FE> {
+    fn random(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError> {
+        self.pending.check(nb_bits)?;
+        let edabits = self
+            .fconv
+            .random_edabits(&mut self.channel, &mut self.rng, nb_bits, num)?;
+        self.pending.push(nb_bits, edabits);
+        Ok(())
+    }
+
+    fn submit(&mut self, nb_bits: usize, num: usize) -> Result<(), FfiError> {
+        self.pending.check(nb_bits)?;
+        if nb_bits == 0 || nb_bits >= FE::bit_decomposition_len() {
+            return Err(invalid(format!("nb_bits={} is out of range", nb_bits)));
+        }
+        let edabits = self
+            .fconv
+            .input_edabits(&mut self.channel, &mut self.rng, nb_bits, num)?;
+        self.pending.push(nb_bits, edabits);
+        Ok(())
+    }
+
+    fn conv(&mut self, config: &EdabitsConvConfig) -> Result<EdabitsConvStats, FfiError> {
+        let edabits = std::mem::take(&mut self.pending.edabits);
+        let stats = self.fconv.conv(
+            &mut self.channel,
+            &mut self.rng,
+            &edabits,
+            &config.to_conv_config(),
+        )?;
+        Ok(stats_of(&stats))
+    }
+
+    fn pending(&self) -> usize {
+        self.pending.edabits.len()
+    }
+}
+
+/// Opaque handle of a prover.
+pub struct EdabitsProverHandle(Box<dyn Prover>);
+
+/// Opaque handle of a verifier.
+pub struct EdabitsVerifierHandle(Box<dyn Verifier>);
+
+fn field_of(field: i32) -> Result<EdabitsField, FfiError> {
+    match field {
+        0 => Ok(EdabitsField::F61p),
+        1 => Ok(EdabitsField::F127p),
+        _ => Err(invalid(format!("unknown field {}", field))),
+    }
+}
+
+fn lpn_params_of(lpn_size: i32) -> Result<(LpnParams, LpnParams), FfiError> {
+    match lpn_size {
+        0 => Ok((LPN_SETUP_SMALL, LPN_EXTEND_SMALL)),
+        1 => Ok((LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM)),
+        2 => Ok((LPN_SETUP_LARGE, LPN_EXTEND_LARGE)),
+        _ => Err(invalid(format!("unknown LPN size {}", lpn_size))),
+    }
+}
+
+unsafe fn handle<'a, T>(ptr: *mut T) -> Result<&'a mut T, FfiError> {
+    ptr.as_mut().ok_or_else(|| invalid("null handle"))
+}
+
+unsafe fn write_stats(stats: *mut EdabitsConvStats, value: EdabitsConvStats) {
+    if let Some(stats) = stats.as_mut() {
+        *stats = value;
+    }
+}
+
+unsafe fn read_config<'a>(
+    config: *const EdabitsConvConfig,
+) -> Result<&'a EdabitsConvConfig, FfiError> {
+    config.as_ref().ok_or_else(|| invalid("null config"))
+}
+
+/// Run the setup of the prover over the connected socket `fd`.
+///
+/// # Safety
+/// `out` must be valid for writes, and `msg` for writes of `msg_len` bytes
+/// if not null. The field and the LPN size are taken as `i32`s, so that
+/// invalid values from C are reported instead of being undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_new(
+    fd: RawFd,
+    field: i32,
+    lpn_size: i32,
+    out: *mut *mut EdabitsProverHandle,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        if out.is_null() {
+            return Err(invalid("null output handle"));
+        }
+        let field = field_of(field)?;
+        let lpn_params = lpn_params_of(lpn_size)?;
+        let channel = fd_channel(fd)?;
+        let prover: Box<dyn Prover> = match field {
+            EdabitsField::F61p => Box::new(ProverState::<F61p>::new(channel, lpn_params)?),
+            EdabitsField::F127p => Box::new(ProverState::<F127p>::new(channel, lpn_params)?),
+        };
+        *out = Box::into_raw(Box::new(EdabitsProverHandle(prover)));
+        Ok(())
+    })
+}
+
+/// Commit to `num` random edabits of `nb_bits` bits.
+///
+/// # Safety
+/// `prover` must be null or a live handle, and `msg` valid for writes of
+/// `msg_len` bytes if not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_random(
+    prover: *mut EdabitsProverHandle,
+    nb_bits: usize,
+    num: usize,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || handle(prover)?.0.random(nb_bits, num))
+}
+
+/// Commit to `num` edabits of `nb_bits` bits given by their bit
+/// decompositions.
+///
+/// # Safety
+/// `prover` must be null or a live handle, `bits` valid for reads of
+/// `nb_bits * num` bytes, and `msg` valid for writes of `msg_len` bytes if
+/// not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_submit_bits(
+    prover: *mut EdabitsProverHandle,
+    bits: *const u8,
+    nb_bits: usize,
+    num: usize,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        let prover = handle(prover)?;
+        let bits = slice(bits, array_len(nb_bits, num)?)?;
+        prover.0.submit(bits, nb_bits)
+    })
+}
+
+/// Commit to `num` edabits of `nb_bits` bits holding `values`.
+///
+/// # Safety
+/// `prover` must be null or a live handle, `values` valid for reads of `num`
+/// values, and `msg` valid for writes of `msg_len` bytes if not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_submit_values(
+    prover: *mut EdabitsProverHandle,
+    values: *const u64,
+    nb_bits: usize,
+    num: usize,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        let prover = handle(prover)?;
+        if nb_bits == 0 {
+            return Err(invalid("nb_bits must be positive"));
+        }
+        let values = slice(values, num)?;
+        let mut bits = vec![0u8; array_len(nb_bits, num)?];
+        for (i, (value, bits)) in values
+            .iter()
+            .zip(bits.chunks_exact_mut(nb_bits))
+            .enumerate()
+        {
+            if nb_bits < 64 && value >> nb_bits != 0 {
+                return Err(invalid(format!(
+                    "the value at index {} does not fit in {} bits",
+                    i, nb_bits
+                )));
+            }
+            for (j, bit) in bits.iter_mut().take(64).enumerate() {
+                *bit = ((value >> j) & 1) as u8;
+            }
+        }
+        prover.0.submit(&bits, nb_bits)
+    })
+}
+
+/// Prove the conversion of the edabits committed since the last conversion.
+///
+/// # Safety
+/// `prover` must be null or a live handle, `config` valid for reads,
+/// `stats` valid for writes if not null, and `msg` valid for writes of
+/// `msg_len` bytes if not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_conv(
+    prover: *mut EdabitsProverHandle,
+    config: *const EdabitsConvConfig,
+    stats: *mut EdabitsConvStats,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        let prover = handle(prover)?;
+        let value = prover.0.conv(read_config(config)?)?;
+        write_stats(stats, value);
+        Ok(())
+    })
+}
+
+/// Return the number of edabits committed since the last conversion, zero
+/// for a null handle.
+///
+/// # Safety
+/// `prover` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_pending(prover: *const EdabitsProverHandle) -> usize {
+    prover.as_ref().map_or(0, |prover| prover.0.pending())
+}
+
+/// Release a prover.
+///
+/// # Safety
+/// `prover` must be null or a live handle, which is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_prover_free(prover: *mut EdabitsProverHandle) {
+    if !prover.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(prover))));
+    }
+}
+
+/// Run the setup of the verifier over the connected socket `fd`.
+///
+/// # Safety
+/// See [`edabits_prover_new`].
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_new(
+    fd: RawFd,
+    field: i32,
+    lpn_size: i32,
+    out: *mut *mut EdabitsVerifierHandle,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        if out.is_null() {
+            return Err(invalid("null output handle"));
+        }
+        let field = field_of(field)?;
+        let lpn_params = lpn_params_of(lpn_size)?;
+        let channel = fd_channel(fd)?;
+        let verifier: Box<dyn Verifier> = match field {
+            EdabitsField::F61p => Box::new(VerifierState::<F61p>::new(channel, lpn_params)?),
+            EdabitsField::F127p => Box::new(VerifierState::<F127p>::new(channel, lpn_params)?),
+        };
+        *out = Box::into_raw(Box::new(EdabitsVerifierHandle(verifier)));
+        Ok(())
+    })
+}
+
+/// Counterpart of [`edabits_prover_random`].
+///
+/// # Safety
+/// `verifier` must be null or a live handle, and `msg` valid for writes of
+/// `msg_len` bytes if not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_random(
+    verifier: *mut EdabitsVerifierHandle,
+    nb_bits: usize,
+    num: usize,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || handle(verifier)?.0.random(nb_bits, num))
+}
+
+/// Counterpart of [`edabits_prover_submit_bits`] and
+/// [`edabits_prover_submit_values`].
+///
+/// # Safety
+/// `verifier` must be null or a live handle, and `msg` valid for writes of
+/// `msg_len` bytes if not null.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_submit(
+    verifier: *mut EdabitsVerifierHandle,
+    nb_bits: usize,
+    num: usize,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || handle(verifier)?.0.submit(nb_bits, num))
+}
+
+/// Check the conversion of the edabits committed since the last conversion.
+///
+/// # Safety
+/// See [`edabits_prover_conv`].
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_conv(
+    verifier: *mut EdabitsVerifierHandle,
+    config: *const EdabitsConvConfig,
+    stats: *mut EdabitsConvStats,
+    msg: *mut c_char,
+    msg_len: usize,
+) -> EdabitsStatus {
+    ffi_call(msg, msg_len, || {
+        let verifier = handle(verifier)?;
+        let value = verifier.0.conv(read_config(config)?)?;
+        write_stats(stats, value);
+        Ok(())
+    })
+}
+
+/// Return the number of edabits committed since the last conversion, zero
+/// for a null handle.
+///
+/// # Safety
+/// `verifier` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_pending(verifier: *const EdabitsVerifierHandle) -> usize {
+    verifier.as_ref().map_or(0, |verifier| verifier.0.pending())
+}
+
+/// Release a verifier.
+///
+/// # Safety
+/// `verifier` must be null or a live handle, which is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn edabits_verifier_free(verifier: *mut EdabitsVerifierHandle) {
+    if !verifier.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(verifier))));
+    }
+}
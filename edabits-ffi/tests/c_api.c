@@ -0,0 +1,142 @@
+/* Exercise the C API: a conversion between two threads over a socketpair, and
+ * the error paths. Returns 0 on success. */
+#include <pthread.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/socket.h>
+#include <unistd.h>
+
+#include "edabits.h"
+
+#define NB_BITS 38
+#define NUM 100
+#define NUM_RANDOM 50
+
+#define CHECK(cond)                                                             \
+    do {                                                                        \
+        if (!(cond)) {                                                          \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,    \
+                    #cond);                                                     \
+            exit(1);                                                            \
+        }                                                                       \
+    } while (0)
+
+#define CHECK_OK(call)                                                          \
+    do {                                                                        \
+        char msg_[256];                                                         \
+        EdabitsStatus status_ = call(msg_, sizeof(msg_));                       \
+        if (status_ != EDABITS_OK) {                                            \
+            fprintf(stderr, "%s:%d: status %d: %s\n", __FILE__, __LINE__,       \
+                    (int)status_, msg_);                                        \
+            exit(1);                                                            \
+        }                                                                       \
+    } while (0)
+
+static void *run_prover(void *arg) {
+    int fd = *(int *)arg;
+    EdabitsProver *prover = NULL;
+    char msg[256];
+    EdabitsStatus status;
+
+    status = edabits_prover_new(fd, EDABITS_FIELD_F61P, EDABITS_LPN_SMALL, &prover, msg,
+                                sizeof(msg));
+    if (status != EDABITS_OK) {
+        fprintf(stderr, "prover setup: %s\n", msg);
+        exit(1);
+    }
+
+    uint64_t values[NUM];
+    for (size_t i = 0; i < NUM; i++) {
+        values[i] = (i * 0x9e3779b97f4a7c15ULL) >> (64 - NB_BITS);
+    }
+    /* A value of more than `NB_BITS` bits is rejected before anything is sent. */
+    uint64_t too_large = 1ULL << NB_BITS;
+    status = edabits_prover_submit_values(prover, &too_large, NB_BITS, 1, msg, sizeof(msg));
+    CHECK(status == EDABITS_INVALID_ARGUMENT);
+    CHECK(strlen(msg) > 0);
+    CHECK(edabits_prover_pending(prover) == 0);
+
+#define SUBMIT_VALUES(m, l) edabits_prover_submit_values(prover, values, NB_BITS, NUM, m, l)
+    CHECK_OK(SUBMIT_VALUES);
+
+    uint8_t bits[NB_BITS];
+    for (size_t j = 0; j < NB_BITS; j++) {
+        bits[j] = (uint8_t)(j % 3 == 0);
+    }
+#define SUBMIT_BITS(m, l) edabits_prover_submit_bits(prover, bits, NB_BITS, 1, m, l)
+    CHECK_OK(SUBMIT_BITS);
+#define RANDOM(m, l) edabits_prover_random(prover, NB_BITS, NUM_RANDOM, m, l)
+    CHECK_OK(RANDOM);
+    CHECK(edabits_prover_pending(prover) == NUM + 1 + NUM_RANDOM);
+
+    EdabitsConvConfig config = edabits_conv_config_default();
+    EdabitsConvStats stats = {0, 0};
+#define CONV(m, l) edabits_prover_conv(prover, &config, &stats, m, l)
+    CHECK_OK(CONV);
+    CHECK(stats.bytes_written > 0);
+    CHECK(edabits_prover_pending(prover) == 0);
+
+    edabits_prover_free(prover);
+    return NULL;
+}
+
+int main(void) {
+    char msg[256];
+    EdabitsStatus status;
+
+    /* Invalid arguments. */
+    EdabitsVerifier *invalid = NULL;
+    status = edabits_verifier_new(-1, (EdabitsField)42, EDABITS_LPN_SMALL, &invalid, msg,
+                                  sizeof(msg));
+    CHECK(status == EDABITS_INVALID_ARGUMENT);
+    CHECK(strlen(msg) > 0);
+    CHECK(invalid == NULL);
+    status = edabits_verifier_random(NULL, NB_BITS, 1, msg, sizeof(msg));
+    CHECK(status == EDABITS_INVALID_ARGUMENT);
+    /* A truncated message is still NUL-terminated. */
+    status = edabits_prover_random(NULL, NB_BITS, 1, msg, 4);
+    CHECK(status == EDABITS_INVALID_ARGUMENT);
+    CHECK(strlen(msg) == 3);
+    status = edabits_prover_random(NULL, NB_BITS, 1, NULL, 0);
+    CHECK(status == EDABITS_INVALID_ARGUMENT);
+    CHECK(edabits_prover_pending(NULL) == 0);
+    edabits_prover_free(NULL);
+    edabits_verifier_free(NULL);
+
+    /* A conversion. */
+    int fds[2];
+    CHECK(socketpair(AF_UNIX, SOCK_STREAM, 0, fds) == 0);
+    pthread_t prover_thread;
+    CHECK(pthread_create(&prover_thread, NULL, run_prover, &fds[0]) == 0);
+
+    EdabitsVerifier *verifier = NULL;
+    status = edabits_verifier_new(fds[1], EDABITS_FIELD_F61P, EDABITS_LPN_SMALL, &verifier,
+                                  msg, sizeof(msg));
+    if (status != EDABITS_OK) {
+        fprintf(stderr, "verifier setup: %s\n", msg);
+        return 1;
+    }
+#define V_SUBMIT_VALUES(m, l) edabits_verifier_submit(verifier, NB_BITS, NUM, m, l)
+    CHECK_OK(V_SUBMIT_VALUES);
+#define V_SUBMIT_BITS(m, l) edabits_verifier_submit(verifier, NB_BITS, 1, m, l)
+    CHECK_OK(V_SUBMIT_BITS);
+#define V_RANDOM(m, l) edabits_verifier_random(verifier, NB_BITS, NUM_RANDOM, m, l)
+    CHECK_OK(V_RANDOM);
+    CHECK(edabits_verifier_pending(verifier) == NUM + 1 + NUM_RANDOM);
+
+    EdabitsConvConfig config = edabits_conv_config_default();
+    EdabitsConvStats stats = {0, 0};
+#define V_CONV(m, l) edabits_verifier_conv(verifier, &config, &stats, m, l)
+    CHECK_OK(V_CONV);
+    CHECK(stats.bytes_read > 0);
+    CHECK(stats.bytes_written > 0);
+    CHECK(edabits_verifier_pending(verifier) == 0);
+
+    CHECK(pthread_join(prover_thread, NULL) == 0);
+    edabits_verifier_free(verifier);
+    /* The handles used duplicates of the sockets. */
+    CHECK(close(fds[0]) == 0);
+    CHECK(close(fds[1]) == 0);
+    return 0;
+}
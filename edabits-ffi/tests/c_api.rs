@@ -0,0 +1,35 @@
+//! Compile `c_api.c` against the shared library and run it.
+#![cfg(unix)]
+
+use std::{env, path::PathBuf, process::Command};
+
+#[test]
+fn test_c_api() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The test executable lives in `target/<profile>/deps`, and the shared
+    // library in `target/<profile>`.
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().and_then(|deps| deps.parent()).unwrap();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("edabits_c_api");
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .arg(manifest_dir.join("tests").join("c_api.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-ledabits_ffi", "-lpthread", "-o"])
+        .arg(&program)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "failed to compile c_api.c");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "c_api failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
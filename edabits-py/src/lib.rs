@@ -0,0 +1,396 @@
+//! Python bindings for the edabits conversion of `ocelot`, to script
+//! parameter sweeps and run the protocol from notebooks.
+//!
+//! The bindings are only built with the `python` feature. The module is built
+//! and installed in the current virtual environment with `maturin develop`.
+//! The calls running the protocol release the GIL, so that the two parties
+//! can be run by two threads of the same interpreter.
+#![cfg(feature = "python")]
+
+use ocelot::edabits::{
+    ConvConfig, ConvStats, EdabitsProver, EdabitsVerifier, MultCheck, ProverConv as Prover,
+    VerifierConv as Verifier,
+};
+use ocelot::svole::wykw::{
+    LpnParams, LPN_EXTEND_LARGE, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_SETUP_LARGE,
+    LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
+};
+use ocelot::Error;
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyIOError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+use scuttlebutt::{
+    field::{F127p, F61p, FiniteField},
+    AesRng, SyncChannel, TrackChannel,
+};
+use std::{
+    any::Any,
+    io::{BufReader, BufWriter},
+    net::{TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+create_exception!(
+    edabits,
+    ProtocolError,
+    PyException,
+    "The conversion protocol failed, e.g. the verifier rejected it."
+);
+
+type Channel = TrackChannel<SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>>;
+
+fn to_py_err(e: Error) -> PyErr {
+    match e {
+        Error::IoError(e) => PyIOError::new_err(e.to_string()),
+        e => ProtocolError::new_err(e.to_string()),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    F61p,
+    F127p,
+}
+
+fn parse_field(field: &str) -> PyResult<Field> {
+    match field {
+        "f61p" => Ok(Field::F61p),
+        "f127p" => Ok(Field::F127p),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown field {:?}, expected \"f61p\" or \"f127p\"",
+            field
+        ))),
+    }
+}
+
+fn parse_lpn_size(lpn_size: &str) -> PyResult<(LpnParams, LpnParams)> {
+    match lpn_size {
+        "small" => Ok((LPN_SETUP_SMALL, LPN_EXTEND_SMALL)),
+        "medium" => Ok((LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM)),
+        "large" => Ok((LPN_SETUP_LARGE, LPN_EXTEND_LARGE)),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown LPN size {:?}, expected \"small\", \"medium\" or \"large\"",
+            lpn_size
+        ))),
+    }
+}
+
+// Accept a connection on `addr` if `listen`, and otherwise connect to it,
+// retrying for a while so that the listening party may be started last.
+fn open_channel(addr: &str, listen: bool) -> std::io::Result<Channel> {
+    let stream = if listen {
+        TcpListener::bind(addr)?.accept()?.0
+    } else {
+        let mut attempts = 0;
+        loop {
+            match TcpStream::connect(addr) {
+                Ok(stream) => break stream,
+                Err(e) if attempts >= 50 => return Err(e),
+                Err(_) => {
+                    attempts += 1;
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+    };
+    stream.set_nodelay(true)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    let writer = BufWriter::new(stream);
+    Ok(TrackChannel::new(SyncChannel::new(reader, writer)))
+}
+
+fn conv_config(num_bucket: usize, num_cut: usize, with_quicksilver: bool) -> ConvConfig {
+    let strategy = if with_quicksilver {
+        MultCheck::QuickSilver
+    } else {
+        MultCheck::Wolverine
+    };
+    ConvConfig::new()
+        .buckets(num_bucket)
+        .cut(num_cut)
+        .strategy(strategy)
+}
+
+// A prover or a verifier over one of the supported fields.
+trait Party: Send {
+    fn random_edabits(
+        &mut self,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Box<dyn Any + Send + Sync>, Error>;
+    fn conv(&mut self, edabits: &dyn Any, config: &ConvConfig) -> PyResult<ConvStats>;
+}
+
+struct State<P> {
+    party: P,
+    channel: Channel,
+    rng: AesRng,
+}
+
+fn wrong_edabits() -> PyErr {
+    PyValueError::new_err("the edabits were generated by a party of another kind or field")
+}
+
+impl<FE: FiniteField<PrimeField = FE>> Party for State<Prover<FE>> {
+    fn random_edabits(
+        &mut self,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Box<dyn Any + Send + Sync>, Error> {
+        let edabits = self
+            .party
+            .random_edabits(&mut self.channel, &mut self.rng, nb_bits, num)?;
+        Ok(Box::new(edabits))
+    }
+
+    fn conv(&mut self, edabits: &dyn Any, config: &ConvConfig) -> PyResult<ConvStats> {
+        let edabits = edabits
+            .downcast_ref::<Vec<EdabitsProver<FE>>>()
+            .ok_or_else(wrong_edabits)?;
+        self.party
+            .conv(&mut self.channel, &mut self.rng, edabits, config)
+            .map_err(to_py_err)
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>> Party for State<Verifier<FE>> {
+    fn random_edabits(
+        &mut self,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Box<dyn Any + Send + Sync>, Error> {
+        let edabits = self
+            .party
+            .random_edabits(&mut self.channel, &mut self.rng, nb_bits, num)?;
+        Ok(Box::new(edabits))
+    }
+
+    fn conv(&mut self, edabits: &dyn Any, config: &ConvConfig) -> PyResult<ConvStats> {
+        let edabits = edabits
+            .downcast_ref::<Vec<EdabitsVerifier<FE>>>()
+            .ok_or_else(wrong_edabits)?;
+        self.party
+            .conv(&mut self.channel, &mut self.rng, edabits, config)
+            .map_err(to_py_err)
+    }
+}
+
+fn init_prover(addr: &str, listen: bool, field: Field, lpn_size: &str) -> PyResult<Box<dyn Party>> {
+    let (lpn_setup, lpn_extend) = parse_lpn_size(lpn_size)?;
+    let mut channel = open_channel(addr, listen)?;
+    let mut rng = AesRng::new();
+    let party: Box<dyn Party> = match field {
+        Field::F61p => Box::new(State {
+            party: Prover::<F61p>::init(&mut channel, &mut rng, lpn_setup, lpn_extend)
+                .map_err(to_py_err)?,
+            channel,
+            rng,
+        }),
+        Field::F127p => Box::new(State {
+            party: Prover::<F127p>::init(&mut channel, &mut rng, lpn_setup, lpn_extend)
+                .map_err(to_py_err)?,
+            channel,
+            rng,
+        }),
+    };
+    Ok(party)
+}
+
+fn init_verifier(
+    addr: &str,
+    listen: bool,
+    field: Field,
+    lpn_size: &str,
+) -> PyResult<Box<dyn Party>> {
+    let (lpn_setup, lpn_extend) = parse_lpn_size(lpn_size)?;
+    let mut channel = open_channel(addr, listen)?;
+    let mut rng = AesRng::new();
+    let party: Box<dyn Party> = match field {
+        Field::F61p => Box::new(State {
+            party: Verifier::<F61p>::init(&mut channel, &mut rng, lpn_setup, lpn_extend)
+                .map_err(to_py_err)?,
+            channel,
+            rng,
+        }),
+        Field::F127p => Box::new(State {
+            party: Verifier::<F127p>::init(&mut channel, &mut rng, lpn_setup, lpn_extend)
+                .map_err(to_py_err)?,
+            channel,
+            rng,
+        }),
+    };
+    Ok(party)
+}
+
+// The statistics of a conversion as a dict with the elapsed `seconds` and,
+// when known, the total `bytes_read` and `bytes_written` and their breakdown
+// per `phases`.
+fn stats_dict<'py>(py: Python<'py>, stats: &ConvStats, seconds: f64) -> PyResult<&'py PyDict> {
+    let dict = PyDict::new(py);
+    dict.set_item("seconds", seconds)?;
+    if let Some(comm) = &stats.comm {
+        let total = comm.total();
+        dict.set_item("bytes_read", total.bytes_read)?;
+        dict.set_item("bytes_written", total.bytes_written)?;
+        let phases = PyDict::new(py);
+        for (label, scope) in comm.iter() {
+            let phase = PyDict::new(py);
+            phase.set_item("bytes_read", scope.bytes_read)?;
+            phase.set_item("bytes_written", scope.bytes_written)?;
+            phases.set_item(label, phase)?;
+        }
+        dict.set_item("phases", phases)?;
+    }
+    Ok(dict)
+}
+
+/// Committed edabits, to be converted by the party that generated them.
+#[pyclass(frozen)]
+struct Edabits {
+    nb_bits: usize,
+    len: usize,
+    inner: Box<dyn Any + Send + Sync>,
+}
+
+#[pymethods]
+impl Edabits {
+    /// The number of bits of the edabits.
+    #[getter]
+    fn nb_bits(&self) -> usize {
+        self.nb_bits
+    }
+
+    fn __len__(&self) -> usize {
+        self.len
+    }
+}
+
+fn random_edabits(
+    py: Python<'_>,
+    party: &mut dyn Party,
+    nb_bits: usize,
+    num: usize,
+) -> PyResult<Edabits> {
+    let inner = py
+        .allow_threads(|| party.random_edabits(nb_bits, num))
+        .map_err(to_py_err)?;
+    Ok(Edabits {
+        nb_bits,
+        len: num,
+        inner,
+    })
+}
+
+fn conv<'py>(
+    py: Python<'py>,
+    party: &mut dyn Party,
+    edabits: &Edabits,
+    config: &ConvConfig,
+) -> PyResult<&'py PyDict> {
+    let start = Instant::now();
+    let stats = py.allow_threads(|| party.conv(&*edabits.inner, config))?;
+    stats_dict(py, &stats, start.elapsed().as_secs_f64())
+}
+
+/// The prover of the conversion.
+///
+/// `ProverConv(addr, *, listen=False, field="f61p", lpn_size="small")`
+/// connects to the verifier at `addr`, or accepts its connection if `listen`,
+/// and runs the setup. The field is `"f61p"` or `"f127p"`, and the LPN size
+/// `"small"`, `"medium"` or `"large"`; both parties must agree on them.
+#[pyclass(name = "ProverConv")]
+struct PyProverConv {
+    party: Box<dyn Party>,
+}
+
+#[pymethods]
+impl PyProverConv {
+    #[new]
+    #[pyo3(signature = (addr, *, listen = false, field = "f61p", lpn_size = "small"))]
+    fn new(
+        py: Python<'_>,
+        addr: &str,
+        listen: bool,
+        field: &str,
+        lpn_size: &str,
+    ) -> PyResult<Self> {
+        let field = parse_field(field)?;
+        let party = py.allow_threads(|| init_prover(addr, listen, field, lpn_size))?;
+        Ok(Self { party })
+    }
+
+    /// Commit to `num` random edabits of `nb_bits` bits.
+    fn random_edabits(&mut self, py: Python<'_>, nb_bits: usize, num: usize) -> PyResult<Edabits> {
+        random_edabits(py, self.party.as_mut(), nb_bits, num)
+    }
+
+    /// Prove the conversion of `edabits`, and return its statistics.
+    #[pyo3(signature = (edabits, *, num_bucket = 5, num_cut = 5, with_quicksilver = true))]
+    fn conv<'py>(
+        &mut self,
+        py: Python<'py>,
+        edabits: &Edabits,
+        num_bucket: usize,
+        num_cut: usize,
+        with_quicksilver: bool,
+    ) -> PyResult<&'py PyDict> {
+        let config = conv_config(num_bucket, num_cut, with_quicksilver);
+        conv(py, self.party.as_mut(), edabits, &config)
+    }
+}
+
+/// The verifier of the conversion, see `ProverConv`.
+///
+/// `conv` raises `ProtocolError` if the verifier rejects the conversion.
+#[pyclass(name = "VerifierConv")]
+struct PyVerifierConv {
+    party: Box<dyn Party>,
+}
+
+#[pymethods]
+impl PyVerifierConv {
+    #[new]
+    #[pyo3(signature = (addr, *, listen = false, field = "f61p", lpn_size = "small"))]
+    fn new(
+        py: Python<'_>,
+        addr: &str,
+        listen: bool,
+        field: &str,
+        lpn_size: &str,
+    ) -> PyResult<Self> {
+        let field = parse_field(field)?;
+        let party = py.allow_threads(|| init_verifier(addr, listen, field, lpn_size))?;
+        Ok(Self { party })
+    }
+
+    /// Commit to `num` random edabits of `nb_bits` bits.
+    fn random_edabits(&mut self, py: Python<'_>, nb_bits: usize, num: usize) -> PyResult<Edabits> {
+        random_edabits(py, self.party.as_mut(), nb_bits, num)
+    }
+
+    /// Check the conversion of `edabits`, and return its statistics.
+    #[pyo3(signature = (edabits, *, num_bucket = 5, num_cut = 5, with_quicksilver = true))]
+    fn conv<'py>(
+        &mut self,
+        py: Python<'py>,
+        edabits: &Edabits,
+        num_bucket: usize,
+        num_cut: usize,
+        with_quicksilver: bool,
+    ) -> PyResult<&'py PyDict> {
+        let config = conv_config(num_bucket, num_cut, with_quicksilver);
+        conv(py, self.party.as_mut(), edabits, &config)
+    }
+}
+
+#[pymodule]
+fn edabits(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyProverConv>()?;
+    m.add_class::<PyVerifierConv>()?;
+    m.add_class::<Edabits>()?;
+    m.add("ProtocolError", py.get_type::<ProtocolError>())?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+"""Run both parties of the conversion in-process over the loopback.
+
+Build the module first with `maturin develop`, then run `pytest tests`.
+"""
+import socket
+import threading
+
+import pytest
+
+import edabits
+
+NB_BITS = 38
+NUM = 100
+
+
+def free_addr():
+    with socket.socket() as s:
+        s.bind(("127.0.0.1", 0))
+        return "127.0.0.1:%d" % s.getsockname()[1]
+
+
+def run_parties(prover, verifier):
+    """Run `prover` in a thread and `verifier` in this one, and return their
+    results."""
+    results = {}
+
+    def run():
+        try:
+            results["prover"] = prover()
+        except BaseException as e:
+            results["prover_error"] = e
+
+    thread = threading.Thread(target=run)
+    thread.start()
+    try:
+        results["verifier"] = verifier()
+    finally:
+        thread.join()
+    if "prover_error" in results:
+        raise results["prover_error"]
+    return results["prover"], results["verifier"]
+
+
+@pytest.mark.parametrize("field", ["f61p", "f127p"])
+@pytest.mark.parametrize("with_quicksilver", [True, False])
+def test_conv_accepts(field, with_quicksilver):
+    addr = free_addr()
+
+    def party(cls, listen):
+        def run():
+            conv = cls(addr, listen=listen, field=field)
+            stats = []
+            for nb_bits in [NB_BITS, 8]:
+                edabits_ = conv.random_edabits(nb_bits, NUM)
+                assert len(edabits_) == NUM
+                assert edabits_.nb_bits == nb_bits
+                stats.append(
+                    conv.conv(
+                        edabits_, num_bucket=4, num_cut=4, with_quicksilver=with_quicksilver
+                    )
+                )
+            return stats
+
+        return run
+
+    prover_stats, verifier_stats = run_parties(
+        party(edabits.ProverConv, False), party(edabits.VerifierConv, True)
+    )
+    for stats in prover_stats + verifier_stats:
+        assert stats["seconds"] >= 0
+        assert stats["bytes_read"] > 0
+        assert stats["bytes_written"] > 0
+        assert sum(p["bytes_written"] for p in stats["phases"].values()) == stats[
+            "bytes_written"
+        ]
+    # What one party writes, the other reads.
+    for p, v in zip(prover_stats, verifier_stats):
+        assert p["bytes_written"] == v["bytes_read"]
+        assert p["bytes_read"] == v["bytes_written"]
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        edabits.ProverConv(free_addr(), field="f2")
+    with pytest.raises(ValueError):
+        edabits.VerifierConv(free_addr(), listen=True, lpn_size="tiny")
+
+
+def test_edabits_of_the_other_party():
+    addr = free_addr()
+
+    def prover():
+        conv = edabits.ProverConv(addr)
+        return conv, conv.random_edabits(NB_BITS, NUM)
+
+    def verifier():
+        conv = edabits.VerifierConv(addr, listen=True)
+        return conv, conv.random_edabits(NB_BITS, NUM)
+
+    (prover_conv, prover_edabits), (verifier_conv, verifier_edabits) = run_parties(
+        prover, verifier
+    )
+    # The edabits are checked before anything is sent.
+    with pytest.raises(ValueError):
+        prover_conv.conv(verifier_edabits)
+    with pytest.raises(ValueError):
+        verifier_conv.conv(prover_edabits)
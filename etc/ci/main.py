@@ -311,6 +311,22 @@ def ci(nightly: bool = False):
                 raise typer.Exit(code=1)
             cache_key_file.parent.mkdir(exist_ok=True)
             cache_key_file.write_text("")
+    with gitlab_ci_section("wasm32 Build"):
+        # The prover of the edabits conversion must build for the browser.
+        restore_cargo_config()
+        pretty_check_call(
+            [
+                "cargo",
+                "build",
+                "-p",
+                "ocelot",
+                "--lib",
+                "--no-default-features",
+                "--target",
+                "wasm32-unknown-unknown",
+                "--verbose",
+            ]
+        )
     with gitlab_ci_section("Functionality Tests"):
         if nightly:
             for force_haswell in [True, False]:
@@ -0,0 +1,287 @@
+//! Benchmarks of the conversion of edabits from `F2` to a prime field using
+//! `criterion`. The benchmarks are generic over the [`Converter`], so that
+//! other conversion protocols are benchmarked the same way.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ocelot::edabits::{
+    ConvConfig, ConvParams, ConvStats, Converter, FComProver, FComVerifier, ProverConv,
+    VerifierConv,
+};
+use ocelot::svole::wykw::{LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+use scuttlebutt::{
+    field::{F127p, F40b, F61p},
+    unix_channel_pair, AbstractChannel, AesRng, Channel,
+};
+use std::{
+    io::{BufReader, BufWriter},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use uds_windows::UnixStream;
+
+const NUM: usize = 1_000;
+const NUM_BUCKET: usize = 5;
+const NUM_CUT: usize = 5;
+
+fn conv_params() -> ConvParams {
+    ConvParams {
+        lpn_setup: LPN_SETUP_SMALL,
+        lpn_extend: LPN_EXTEND_SMALL,
+        config: ConvConfig::new().buckets(NUM_BUCKET).cut(NUM_CUT),
+    }
+}
+
+fn conv_init<P, V>(params: V::Params) -> (P, V)
+where
+    P: Converter + Send + 'static,
+    V: Converter<Params = P::Params>,
+    V::Params: Clone + Send + 'static,
+{
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let prover_params = params.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        P::init(&mut channel, &mut rng, &prover_params).unwrap()
+    });
+    let mut rng = AesRng::new();
+    let reader = BufReader::new(receiver.try_clone().unwrap());
+    let writer = BufWriter::new(receiver);
+    let mut channel = Channel::new(reader, writer);
+    let verifier = V::init(&mut channel, &mut rng, &params).unwrap();
+    let prover = handle.join().unwrap();
+    (prover, verifier)
+}
+
+// Commit to `NUM` random conversions of `nb_bits` bits and check them, and
+// return the statistics of the verifier.
+fn bench_conv<P, V>(prover: &Arc<Mutex<P>>, verifier: &Arc<Mutex<V>>, nb_bits: usize) -> V::Stats
+where
+    P: Converter + Send + 'static,
+    V: Converter,
+    V::Stats: Clone,
+{
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let prover = prover.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        let mut prover = prover.lock().unwrap();
+        let conversions = prover
+            .random_conversions(&mut channel, &mut rng, nb_bits, NUM)
+            .unwrap();
+        prover.verify(&mut channel, &mut rng, &conversions).unwrap();
+    });
+    let mut rng = AesRng::new();
+    let reader = BufReader::new(receiver.try_clone().unwrap());
+    let writer = BufWriter::new(receiver);
+    let mut channel = Channel::new(reader, writer);
+    let mut verifier = verifier.lock().unwrap();
+    let conversions = verifier
+        .random_conversions(&mut channel, &mut rng, nb_bits, NUM)
+        .unwrap();
+    verifier
+        .verify(&mut channel, &mut rng, &conversions)
+        .unwrap();
+    handle.join().unwrap();
+    verifier.stats().unwrap().clone()
+}
+
+// With the `serde` feature, write the statistics of the last run of the
+// benchmark `name` as JSON to the directory `EDABITS_STATS_DIR`, if set, so
+// that the performance is tracked across commits by collecting the files.
+#[cfg(feature = "serde")]
+fn write_stats(name: &str, stats: &ConvStats) {
+    if let Some(dir) = std::env::var_os("EDABITS_STATS_DIR") {
+        let path = std::path::Path::new(&dir).join(format!("{}.json", name.replace("::", "-")));
+        std::fs::write(path, stats.to_json()).unwrap();
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_stats(_name: &str, _stats: &ConvStats) {}
+
+fn bench_conv_f61p(c: &mut Criterion) {
+    let (fconv_prover, fconv_verifier) =
+        conv_init::<ProverConv<F61p>, VerifierConv<F61p>>(conv_params());
+    let fconv_prover = Arc::new(Mutex::new(fconv_prover));
+    let fconv_verifier = Arc::new(Mutex::new(fconv_verifier));
+    let name = "edabits::conv::38::F61p";
+    let mut stats = ConvStats::default();
+    c.bench_function(name, |bench| {
+        bench.iter(|| stats = black_box(bench_conv(&fconv_prover, &fconv_verifier, 38)))
+    });
+    write_stats(name, &stats);
+}
+
+fn bench_conv_f127p(c: &mut Criterion) {
+    let (fconv_prover, fconv_verifier) =
+        conv_init::<ProverConv<F127p>, VerifierConv<F127p>>(conv_params());
+    let fconv_prover = Arc::new(Mutex::new(fconv_prover));
+    let fconv_verifier = Arc::new(Mutex::new(fconv_verifier));
+    for nb_bits in [64, 96] {
+        let name = format!("edabits::conv::{}::F127p", nb_bits);
+        let mut stats = ConvStats::default();
+        c.bench_function(&name, |bench| {
+            bench.iter(|| stats = black_box(bench_conv(&fconv_prover, &fconv_verifier, nb_bits)))
+        });
+        write_stats(&name, &stats);
+    }
+}
+
+// Check `NUM` random edabits of 38 bits with the buckets split across
+// `threads` threads, multiplexed over a single connection.
+fn bench_conv_multiplexed_f61p(c: &mut Criterion) {
+    let (fconv_prover, mut fconv_verifier) =
+        conv_init::<ProverConv<F61p>, VerifierConv<F61p>>(conv_params());
+    let fconv_prover = Arc::new(Mutex::new(fconv_prover));
+    for threads in [1, NUM_BUCKET] {
+        let config = conv_params().config.threads(threads);
+        let name = format!("edabits::conv_multiplexed::38::F61p::{}", threads);
+        c.bench_function(&name, |bench| {
+            bench.iter(|| {
+                let (mut sender, mut receiver) = unix_channel_pair();
+                let prover = fconv_prover.clone();
+                let prover_config = config.clone();
+                let handle = std::thread::spawn(move || {
+                    let mut rng = AesRng::new();
+                    let mut prover = prover.lock().unwrap();
+                    let edabits = prover
+                        .random_edabits(&mut sender, &mut rng, 38, NUM)
+                        .unwrap();
+                    prover
+                        .conv_multiplexed(&mut sender, &mut rng, &edabits, &prover_config)
+                        .unwrap();
+                });
+                let mut rng = AesRng::new();
+                let edabits = fconv_verifier
+                    .random_edabits(&mut receiver, &mut rng, 38, NUM)
+                    .unwrap();
+                let stats = fconv_verifier
+                    .conv_multiplexed(&mut receiver, &mut rng, &edabits, &config)
+                    .unwrap();
+                handle.join().unwrap();
+                black_box(stats)
+            })
+        });
+    }
+}
+
+// Initialize a converter to `F61p`, which runs the base OTs once for its two
+// functionalities, against initializing the two functionalities separately.
+fn bench_init_f61p(c: &mut Criterion) {
+    c.bench_function("edabits::init::F61p", |bench| {
+        bench.iter(|| {
+            let (mut sender, mut receiver) = unix_channel_pair();
+            let handle = std::thread::spawn(move || {
+                let mut rng = AesRng::new();
+                black_box(
+                    ProverConv::<F61p>::init(
+                        &mut sender,
+                        &mut rng,
+                        LPN_SETUP_SMALL,
+                        LPN_EXTEND_SMALL,
+                    )
+                    .unwrap(),
+                );
+            });
+            let mut rng = AesRng::new();
+            black_box(
+                VerifierConv::<F61p>::init(
+                    &mut receiver,
+                    &mut rng,
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .unwrap(),
+            );
+            handle.join().unwrap();
+        })
+    });
+    c.bench_function("edabits::init_separate::F61p", |bench| {
+        bench.iter(|| {
+            let (mut sender, mut receiver) = unix_channel_pair();
+            let handle = std::thread::spawn(move || {
+                let mut rng = AesRng::new();
+                let fcom_f2 = FComProver::<F40b>::init(
+                    &mut sender,
+                    &mut rng,
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .unwrap();
+                let fcom = FComProver::<F61p>::init(
+                    &mut sender,
+                    &mut rng,
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .unwrap();
+                black_box(ProverConv::init_with(fcom_f2, fcom));
+            });
+            let mut rng = AesRng::new();
+            let fcom_f2 = FComVerifier::<F40b>::init(
+                &mut receiver,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let fcom = FComVerifier::<F61p>::init(
+                &mut receiver,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            black_box(VerifierConv::init_with(fcom_f2, fcom));
+            handle.join().unwrap();
+        })
+    });
+}
+
+// Commit to 10,000 random edabits of 38 bits on both sides.
+fn bench_random_edabits_f61p(c: &mut Criterion) {
+    let (fconv_prover, mut fconv_verifier) =
+        conv_init::<ProverConv<F61p>, VerifierConv<F61p>>(conv_params());
+    let fconv_prover = Arc::new(Mutex::new(fconv_prover));
+    c.bench_function("edabits::random_edabits::38::F61p", |bench| {
+        bench.iter(|| {
+            let (mut sender, mut receiver) = unix_channel_pair();
+            let prover = fconv_prover.clone();
+            let handle = std::thread::spawn(move || {
+                let mut rng = AesRng::new();
+                let mut prover = prover.lock().unwrap();
+                black_box(
+                    prover
+                        .random_edabits(&mut sender, &mut rng, 38, 10_000)
+                        .unwrap(),
+                );
+                sender.flush().unwrap();
+            });
+            let mut rng = AesRng::new();
+            black_box(
+                fconv_verifier
+                    .random_edabits(&mut receiver, &mut rng, 38, 10_000)
+                    .unwrap(),
+            );
+            handle.join().unwrap();
+        })
+    });
+}
+
+criterion_group! {
+    name = edabits;
+    config = Criterion::default().warm_up_time(Duration::from_millis(100)).sample_size(10);
+    targets =
+        bench_conv_f61p,
+        bench_conv_f127p,
+        bench_conv_multiplexed_f61p,
+        bench_random_edabits_f61p,
+        bench_init_f61p,
+}
+criterion_main!(edabits);
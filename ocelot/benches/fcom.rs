@@ -0,0 +1,274 @@
+//! Benchmarks of the affine operations and of the multiplication check of
+//! the homomorphic commitment functionality using `criterion`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ocelot::edabits::{FComProver, FComVerifier, MacProver, MacVerifier};
+use ocelot::svole::wykw::{
+    LpnParams, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
+};
+use rand::SeedableRng;
+use scuttlebutt::{
+    field::{F40b, F61p, FiniteField},
+    ring::FiniteRing,
+    AesRng, Channel, SyncChannel,
+};
+use std::{
+    io::{BufReader, BufWriter},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use uds_windows::UnixStream;
+
+const NUM: usize = 1_000_000;
+
+fn fcom_init<FE: FiniteField>() -> (FComProver<FE>, FComVerifier<FE>) {
+    fcom_init_with::<FE>(LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM, false)
+}
+
+// Initialize the functionality, with the extensions running in the
+// background over a dedicated channel when `pipelined` is set.
+fn fcom_init_with<FE: FiniteField>(
+    lpn_setup: LpnParams,
+    lpn_extend: LpnParams,
+    pipelined: bool,
+) -> (FComProver<FE>, FComVerifier<FE>) {
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let (sender_ext, receiver_ext) = UnixStream::pair().unwrap();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        let mut fcom =
+            FComProver::<FE>::init(&mut channel, &mut rng, lpn_setup, lpn_extend).unwrap();
+        if pipelined {
+            let ext_channel = SyncChannel::new(
+                BufReader::new(sender_ext.try_clone().unwrap()),
+                BufWriter::new(sender_ext),
+            );
+            fcom.start_pipeline(&mut channel, &mut rng, ext_channel, 2)
+                .unwrap();
+        }
+        fcom
+    });
+    let mut rng = AesRng::new();
+    let reader = BufReader::new(receiver.try_clone().unwrap());
+    let writer = BufWriter::new(receiver);
+    let mut channel = Channel::new(reader, writer);
+    let mut fcom_verifier =
+        FComVerifier::<FE>::init(&mut channel, &mut rng, lpn_setup, lpn_extend).unwrap();
+    if pipelined {
+        let ext_channel = SyncChannel::new(
+            BufReader::new(receiver_ext.try_clone().unwrap()),
+            BufWriter::new(receiver_ext),
+        );
+        fcom_verifier
+            .start_pipeline(&mut channel, &mut rng, ext_channel, 2)
+            .unwrap();
+    }
+    let fcom_prover = handle.join().unwrap();
+    (fcom_prover, fcom_verifier)
+}
+
+fn bench_fcom_affine<FE: FiniteField>(c: &mut Criterion, name: &str) {
+    let (fcom_prover, fcom_verifier) = fcom_init::<FE>();
+    let mut rng = AesRng::new();
+    let cst = FE::PrimeField::random(&mut rng);
+    let xs: Vec<MacProver<FE>> = (0..NUM)
+        .map(|_| MacProver(FE::PrimeField::random(&mut rng), FE::random(&mut rng)))
+        .collect();
+    let keys: Vec<MacVerifier<FE>> = (0..NUM)
+        .map(|_| MacVerifier(FE::random(&mut rng)))
+        .collect();
+
+    c.bench_function(
+        &format!("fcom::prover::fma_cst::scalar::{}", name),
+        |bench| {
+            let mut acc = xs.clone();
+            bench.iter(|| {
+                for i in 0..NUM {
+                    acc[i] = fcom_prover.add(acc[i], fcom_prover.affine_mult_cst(cst, xs[i]));
+                }
+                black_box(&acc);
+            })
+        },
+    );
+    c.bench_function(
+        &format!("fcom::prover::fma_cst::slice::{}", name),
+        |bench| {
+            let mut acc = xs.clone();
+            bench.iter(|| {
+                fcom_prover.fma_cst_slice(cst, &xs, &mut acc);
+                black_box(&acc);
+            })
+        },
+    );
+    c.bench_function(
+        &format!("fcom::verifier::add_cst::scalar::{}", name),
+        |bench| {
+            let mut out = Vec::with_capacity(NUM);
+            bench.iter(|| {
+                out.clear();
+                for key in keys.iter() {
+                    out.push(fcom_verifier.affine_add_cst(cst, *key));
+                }
+                black_box(&out);
+            })
+        },
+    );
+    c.bench_function(
+        &format!("fcom::verifier::add_cst::slice::{}", name),
+        |bench| {
+            let mut out = Vec::with_capacity(NUM);
+            bench.iter(|| {
+                out.clear();
+                fcom_verifier.affine_add_cst_slice(cst, &keys, &mut out);
+                black_box(&out);
+            })
+        },
+    );
+    c.bench_function(
+        &format!("fcom::verifier::fma_cst::slice::{}", name),
+        |bench| {
+            let mut acc = keys.clone();
+            bench.iter(|| {
+                fcom_verifier.fma_cst_slice(cst, &keys, &mut acc);
+                black_box(&acc);
+            })
+        },
+    );
+}
+
+fn bench_fcom_affine_f61p(c: &mut Criterion) {
+    bench_fcom_affine::<F61p>(c, "F61p");
+}
+
+fn bench_fcom_affine_f40b(c: &mut Criterion) {
+    bench_fcom_affine::<F40b>(c, "F40b");
+}
+
+// Run the quicksilver check on `NUM` triples produced from a seeded rng,
+// either collected in a vector first or consumed as they are produced. The
+// triples are not valid multiplications: only the cost of the check is
+// measured, not its outcome.
+fn bench_quicksilver<FE: FiniteField>(
+    fcom_prover: &Arc<Mutex<FComProver<FE>>>,
+    fcom_verifier: &Arc<Mutex<FComVerifier<FE>>>,
+    collect: bool,
+) {
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let fcom_prover = fcom_prover.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        let mut fcom = fcom_prover.lock().unwrap();
+        let mut triples_rng = AesRng::from_seed(Default::default());
+        let triples = (0..NUM).map(|_| {
+            let mut mac = || MacProver(FE::PrimeField::random(&mut triples_rng), FE::ZERO);
+            (mac(), mac(), mac())
+        });
+        if collect {
+            let triples: Vec<_> = triples.collect();
+            let _ = fcom.quicksilver_check_multiply(&mut channel, &mut rng, &triples);
+        } else {
+            let _ = fcom.quicksilver_check_multiply_iter(&mut channel, &mut rng, triples);
+        }
+    });
+    let mut rng = AesRng::new();
+    let reader = BufReader::new(receiver.try_clone().unwrap());
+    let writer = BufWriter::new(receiver);
+    let mut channel = Channel::new(reader, writer);
+    let mut fcom = fcom_verifier.lock().unwrap();
+    let mut triples_rng = AesRng::from_seed(Default::default());
+    let triples = (0..NUM).map(|_| {
+        let mut key = || MacVerifier(FE::random(&mut triples_rng));
+        (key(), key(), key())
+    });
+    if collect {
+        let triples: Vec<_> = triples.collect();
+        let _ = black_box(fcom.quicksilver_check_multiply(&mut channel, &mut rng, &triples));
+    } else {
+        let _ = black_box(fcom.quicksilver_check_multiply_iter(&mut channel, &mut rng, triples));
+    }
+    handle.join().unwrap();
+}
+
+fn bench_quicksilver_f40b(c: &mut Criterion) {
+    let (fcom_prover, fcom_verifier) = fcom_init::<F40b>();
+    let fcom_prover = Arc::new(Mutex::new(fcom_prover));
+    let fcom_verifier = Arc::new(Mutex::new(fcom_verifier));
+    c.bench_function("fcom::quicksilver::vec::F40b", |bench| {
+        bench.iter(|| bench_quicksilver(&fcom_prover, &fcom_verifier, true))
+    });
+    c.bench_function("fcom::quicksilver::iter::F40b", |bench| {
+        bench.iter(|| bench_quicksilver(&fcom_prover, &fcom_verifier, false))
+    });
+}
+
+// Draw `NUM` random Macs on both sides, one at a time or in a single batch,
+// which runs several extensions with the small LPN parameters. When they are
+// pipelined, the extensions do not stall the drawing at steady state.
+fn bench_random<FE: FiniteField>(
+    fcom_prover: &Arc<Mutex<FComProver<FE>>>,
+    fcom_verifier: &Arc<Mutex<FComVerifier<FE>>>,
+    batched: bool,
+) {
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let fcom_prover = fcom_prover.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        let mut fcom = fcom_prover.lock().unwrap();
+        if batched {
+            black_box(fcom.random_batch(&mut channel, &mut rng, NUM).unwrap());
+        } else {
+            for _ in 0..NUM {
+                black_box(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+        }
+    });
+    let mut rng = AesRng::new();
+    let reader = BufReader::new(receiver.try_clone().unwrap());
+    let writer = BufWriter::new(receiver);
+    let mut channel = Channel::new(reader, writer);
+    let mut fcom = fcom_verifier.lock().unwrap();
+    if batched {
+        black_box(fcom.random_batch(&mut channel, &mut rng, NUM).unwrap());
+    } else {
+        for _ in 0..NUM {
+            black_box(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+    }
+    handle.join().unwrap();
+}
+
+fn bench_random_f61p(c: &mut Criterion) {
+    for pipelined in [false, true] {
+        let (fcom_prover, fcom_verifier) =
+            fcom_init_with::<F61p>(LPN_SETUP_SMALL, LPN_EXTEND_SMALL, pipelined);
+        let fcom_prover = Arc::new(Mutex::new(fcom_prover));
+        let fcom_verifier = Arc::new(Mutex::new(fcom_verifier));
+        let name = if pipelined { "pipelined" } else { "blocking" };
+        c.bench_function(&format!("fcom::random::{}::F61p", name), |bench| {
+            bench.iter(|| bench_random(&fcom_prover, &fcom_verifier, false))
+        });
+        c.bench_function(&format!("fcom::random_batch::{}::F61p", name), |bench| {
+            bench.iter(|| bench_random(&fcom_prover, &fcom_verifier, true))
+        });
+    }
+}
+
+criterion_group! {
+    name = fcom;
+    config = Criterion::default().warm_up_time(Duration::from_millis(100)).sample_size(10);
+    targets =
+        bench_fcom_affine_f61p,
+        bench_fcom_affine_f40b,
+        bench_quicksilver_f40b,
+        bench_random_f61p,
+}
+criterion_main!(fcom);
@@ -0,0 +1,65 @@
+//! Communication of the edabits conversion over a compressed channel.
+//!
+//! Both parties wrap their channel in a `CompressedChannel`, and the bytes
+//! are counted before and after compression.
+use ocelot::edabits::{ConvConfig, ProverConv, VerifierConv};
+use ocelot::svole::wykw::{LPN_EXTEND_MEDIUM, LPN_SETUP_MEDIUM};
+use scuttlebutt::{
+    channel::unix_channel_pair, field::F61p, AbstractChannel, AesRng, CompressedChannel,
+    TrackChannel,
+};
+use std::time::Instant;
+
+type Prover = ProverConv<F61p>;
+type Verifier = VerifierConv<F61p>;
+
+fn run() {
+    let (sender, receiver) = unix_channel_pair();
+    let nb_bits: usize = 38;
+    let n = 100_000;
+    let config = ConvConfig::new().buckets(5).cut(5);
+    let sender_config = config.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rng = AesRng::new();
+        let mut sender = TrackChannel::new(CompressedChannel::new(sender));
+        let mut fconv_sender =
+            Prover::init(&mut sender, &mut rng, LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM).unwrap();
+        let edabits = fconv_sender
+            .random_edabits(&mut sender, &mut rng, nb_bits, n)
+            .unwrap();
+        fconv_sender
+            .conv(&mut sender, &mut rng, &edabits, &sender_config)
+            .unwrap();
+    });
+    let mut rng = AesRng::new();
+    // `compressed` shares its counters with the one wrapped in `receiver`
+    let mut compressed = TrackChannel::new(receiver);
+    let mut receiver = TrackChannel::new(CompressedChannel::new(compressed.clone()));
+    let mut fconv_receiver =
+        Verifier::init(&mut receiver, &mut rng, LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM).unwrap();
+    let edabits_mac = fconv_receiver
+        .random_edabits(&mut receiver, &mut rng, nb_bits, n)
+        .unwrap();
+    receiver.clear();
+    compressed.clear();
+    let start = Instant::now();
+    let stats = fconv_receiver
+        .conv(&mut receiver, &mut rng, &edabits_mac, &config)
+        .unwrap();
+    println!("Receive time (conv): {:?}", start.elapsed());
+    println!(
+        "Communication (conv): {:.2} kB uncompressed, {:.2} kB compressed ({:.1}%)",
+        receiver.total_kilobytes(),
+        compressed.total_kilobytes(),
+        100.0 * compressed.total_kilobytes() / receiver.total_kilobytes()
+    );
+    if let Some(comm) = stats.comm {
+        println!("Uncompressed communication per phase (conv):\n{}", comm);
+    }
+    handle.join().unwrap();
+}
+
+fn main() {
+    println!("\nField: F61p \n");
+    run()
+}
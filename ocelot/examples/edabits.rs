@@ -1,18 +1,22 @@
-use ocelot::edabits::{ProverConv, VerifierConv};
+use ocelot::edabits::{ConvConfig, ProverConv, VerifierConv};
 use ocelot::svole::wykw::{LPN_EXTEND_MEDIUM, LPN_SETUP_MEDIUM};
-use scuttlebutt::{channel::track_unix_channel_pair, field::F61p, AesRng};
+use scuttlebutt::{
+    channel::track_unix_channel_pair, field::F61p, sim_pair, AbstractChannel, AesRng, SimParams,
+    TrackChannel,
+};
 use std::time::Instant;
 
 type Prover = ProverConv<F61p>;
 type Verifier = VerifierConv<F61p>;
 
-fn run() {
-    let (mut sender, mut receiver) = track_unix_channel_pair();
+fn run<C: AbstractChannel + Send + 'static>(
+    mut sender: TrackChannel<C>,
+    mut receiver: TrackChannel<C>,
+    n: usize,
+) {
     let nb_bits: usize = 8;
-    let n = 1000_000;
-    let num_bucket = 3;
-    let num_cut = num_bucket;
-    let with_quicksilver = true;
+    let config = ConvConfig::new().buckets(3).cut(3);
+    let sender_config = config.clone();
     let handle = std::thread::spawn(move || {
         #[cfg(target_os = "linux")]
         {
@@ -32,15 +36,7 @@ fn run() {
         println!("Send time (random edabits): {:?}", start.elapsed());
         let start = Instant::now();
         let _ = fconv_sender
-            .conv(
-                &mut sender,
-                &mut rng,
-                num_bucket,
-                num_cut,
-                &edabits,
-                None,
-                with_quicksilver,
-            )
+            .conv(&mut sender, &mut rng, &edabits, &sender_config)
             .unwrap();
         println!("Send time (conv): {:?}", start.elapsed());
     });
@@ -79,16 +75,8 @@ fn run() {
     );
     receiver.clear();
     let start = Instant::now();
-    fconv_receiver
-        .conv(
-            &mut receiver,
-            &mut rng,
-            num_bucket,
-            num_cut,
-            &edabits_mac,
-            None,
-            with_quicksilver,
-        )
+    let stats = fconv_receiver
+        .conv(&mut receiver, &mut rng, &edabits_mac, &config)
         .unwrap();
     println!("Receive time (conv): {:?}", start.elapsed());
     println!(
@@ -99,10 +87,27 @@ fn run() {
         "Receive communication (conv): {:.4} Mb",
         receiver.kilobits_written() / 1000.0
     );
+    if let Some(comm) = stats.comm {
+        println!("Verifier communication per phase (conv):\n{}", comm);
+    }
     handle.join().unwrap();
 }
 
 fn main() {
     println!("\nField: F61p \n");
-    run()
+    let (sender, receiver) = track_unix_channel_pair();
+    run(sender, receiver, 1_000_000);
+
+    // Pass `--wan` to also measure over a simulated wide area network, which
+    // is what round-complexity changes should report.
+    if std::env::args().any(|arg| arg == "--wan") {
+        let params = SimParams::wan();
+        println!("\nSimulated WAN: {:?}\n", params);
+        let (sender, receiver) = sim_pair(params);
+        run(
+            TrackChannel::new(sender),
+            TrackChannel::new(receiver),
+            100_000,
+        );
+    }
 }
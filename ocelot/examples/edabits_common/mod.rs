@@ -0,0 +1,340 @@
+//! Command line, networking and the harness running a [`Converter`], shared
+//! by the `edabits_prover`, `edabits_verifier` and `edabits_tcp` examples.
+
+// Each example only uses part of this module.
+#![allow(dead_code)]
+
+use clap::{Parser, ValueEnum};
+use ocelot::{
+    edabits::{ConvConfig, ConvParams, ConvStats, Converter, MultCheck, ProverConv, VerifierConv},
+    svole::wykw::{
+        LpnParams, LPN_EXTEND_LARGE, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_SETUP_LARGE,
+        LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
+    },
+};
+use scuttlebutt::{
+    field::{F127p, F128p, F61p, FiniteField},
+    AbstractChannel, AesRng, CommBreakdown, SyncChannel, TrackChannel,
+};
+use std::{
+    io::{BufReader, BufWriter},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// The channel the conversion runs on: plain buffered TCP, tracking the
+/// communication.
+pub type MainChannel = TrackChannel<SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>>;
+/// The channels the buckets are checked on, one per thread.
+pub type BucketChannel = SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>;
+
+/// The field the edabits are converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Field {
+    F61p,
+    F127p,
+    F128p,
+}
+
+/// The party of the conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Role {
+    Prover,
+    Verifier,
+}
+
+/// The LPN parameters of the sVOLE setup and extensions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Lpn {
+    Small,
+    Medium,
+    Large,
+}
+
+impl Lpn {
+    /// The LPN parameters of the setup and of the extensions.
+    pub fn params(self) -> (LpnParams, LpnParams) {
+        match self {
+            Lpn::Small => (LPN_SETUP_SMALL, LPN_EXTEND_SMALL),
+            Lpn::Medium => (LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM),
+            Lpn::Large => (LPN_SETUP_LARGE, LPN_EXTEND_LARGE),
+        }
+    }
+}
+
+/// How the multiplications of the buckets are checked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Strategy {
+    /// The QuickSilver multiplication check.
+    Quicksilver,
+    /// Cut-and-choose on random multiplication triples, as in Wolverine.
+    Wolverine,
+    /// The faster of the two others, timed at the start of the session.
+    Auto,
+}
+
+/// Both parties must be given the same parameters, the address being the
+/// one which a party listens on and the other connects to.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Address of the party listening, the verifier by default.
+    #[clap(long, default_value = "127.0.0.1:5527")]
+    pub addr: String,
+    /// Field the edabits are converted to.
+    #[clap(long, value_enum, default_value_t = Field::F61p)]
+    pub field: Field,
+    /// Number of bits of the edabits.
+    #[clap(long, default_value_t = 38)]
+    pub nb_bits: usize,
+    /// Number of edabits to convert.
+    #[clap(long, alias = "num-edabits", default_value_t = 10_000)]
+    pub num: usize,
+    /// Number of buckets.
+    #[clap(long, alias = "bucket", default_value_t = 5)]
+    pub num_bucket: usize,
+    /// Number of edabits opened by the cut-and-choose, the number of buckets
+    /// if not given.
+    #[clap(long, alias = "cut")]
+    pub num_cut: Option<usize>,
+    /// How the multiplications are checked.
+    #[clap(long, value_enum, default_value_t = Strategy::Quicksilver)]
+    pub strategy: Strategy,
+    /// Number of threads checking the buckets, at most the number of
+    /// buckets. With more than one thread, every thread checks its buckets
+    /// on its own connection.
+    #[clap(long, default_value_t = 1)]
+    pub threads: usize,
+    /// LPN parameters of the sVOLEs.
+    #[clap(long, value_enum, default_value_t = Lpn::Medium)]
+    pub lpn: Lpn,
+    /// File the statistics of the conversion are written to as JSON, `-`
+    /// for the standard output, which requires the `serde` feature.
+    #[clap(long)]
+    pub json: Option<PathBuf>,
+}
+
+impl Args {
+    /// Parse the arguments, exiting with a usage message when they are
+    /// invalid.
+    pub fn parse_checked() -> Self {
+        let args = Args::parse();
+        args.check();
+        args
+    }
+
+    /// Exit with a usage message when the arguments are invalid.
+    pub fn check(&self) {
+        if let Err(e) = self.config().validate() {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+        if cfg!(not(feature = "serde")) && self.json.is_some() {
+            eprintln!("--json requires the `serde` feature");
+            std::process::exit(2);
+        }
+    }
+
+    /// The configuration of the conversion.
+    pub fn config(&self) -> ConvConfig {
+        let strategy = match self.strategy {
+            Strategy::Quicksilver => MultCheck::QuickSilver,
+            Strategy::Wolverine => MultCheck::Wolverine,
+            Strategy::Auto => MultCheck::Auto,
+        };
+        ConvConfig::new()
+            .buckets(self.num_bucket)
+            .cut(self.num_cut.unwrap_or(self.num_bucket))
+            .strategy(strategy)
+            .threads(self.threads)
+    }
+
+    /// The number of extra connections used by the bucket threads.
+    pub fn num_bucket_connections(&self) -> usize {
+        if self.threads > 1 {
+            self.threads
+        } else {
+            0
+        }
+    }
+
+    /// The parameters of the edabits conversion.
+    pub fn params(&self) -> ConvParams {
+        let (lpn_setup, lpn_extend) = self.lpn.params();
+        ConvParams {
+            lpn_setup,
+            lpn_extend,
+            config: self.config(),
+        }
+    }
+}
+
+/// Wrap `stream` into a buffered channel.
+pub fn bucket_channel(stream: TcpStream) -> std::io::Result<BucketChannel> {
+    stream.set_nodelay(true)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    let writer = BufWriter::new(stream);
+    Ok(SyncChannel::new(reader, writer))
+}
+
+/// Wrap `stream` into a buffered channel tracking its communication.
+pub fn main_channel(stream: TcpStream) -> std::io::Result<MainChannel> {
+    Ok(TrackChannel::new(bucket_channel(stream)?))
+}
+
+/// Connect to `addr`, retrying for a while so that the prover may be started
+/// before the verifier listens.
+pub fn connect(addr: &str) -> std::io::Result<TcpStream> {
+    let mut attempts = 0;
+    loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if attempts >= 50 => return Err(e),
+            Err(_) => {
+                attempts += 1;
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+    }
+}
+
+/// Open the main connection then the bucket ones, in the same order on both
+/// sides, listening on `args.addr` if `listen` and connecting to it
+/// otherwise.
+pub fn open_channels(
+    args: &Args,
+    listen: bool,
+) -> std::io::Result<(MainChannel, Vec<BucketChannel>)> {
+    let nb_streams = 1 + args.num_bucket_connections();
+    let mut streams = Vec::with_capacity(nb_streams);
+    if listen {
+        let listener = TcpListener::bind(&args.addr)?;
+        println!("listening on {}", args.addr);
+        for _ in 0..nb_streams {
+            streams.push(listener.accept()?.0);
+        }
+    } else {
+        for _ in 0..nb_streams {
+            streams.push(connect(&args.addr)?);
+        }
+    }
+    let mut streams = streams.into_iter();
+    let channel = main_channel(streams.next().unwrap())?;
+    let bucket_channels = streams.map(bucket_channel).collect::<Result<_, _>>()?;
+    Ok((channel, bucket_channels))
+}
+
+/// Run the party `role` of the edabits conversion with `args`, over the
+/// channels opened by [`open_channels`].
+pub fn run(role: Role, args: &Args, listen: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.field {
+        Field::F61p => run_in::<F61p>(role, args, listen),
+        Field::F127p => run_in::<F127p>(role, args, listen),
+        Field::F128p => run_in::<F128p>(role, args, listen),
+    }
+}
+
+fn run_in<FE: FiniteField<PrimeField = FE>>(
+    role: Role,
+    args: &Args,
+    listen: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut channel, bucket_channels) = open_channels(args, listen)?;
+    let params = args.params();
+    match role {
+        Role::Prover => {
+            run_converter::<ProverConv<FE>>("Prover", args, &params, &mut channel, bucket_channels)
+        }
+        Role::Verifier => run_converter::<VerifierConv<FE>>(
+            "Verifier",
+            args,
+            &params,
+            &mut channel,
+            bucket_channels,
+        ),
+    }
+}
+
+/// Statistics of a [`Converter`] which can be written with `--json`.
+pub trait JsonStats {
+    /// Serialize the statistics to JSON, with the `serde` feature.
+    fn to_json(&self) -> Option<String>;
+}
+
+impl JsonStats for ConvStats {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Option<String> {
+        Some(ConvStats::to_json(self))
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn to_json(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Run the party `party` of the converter `V` with `params` on `channel`:
+/// initialize it, commit to random conversions and check them, over
+/// `bucket_channels` if there are any, then print the timings and the
+/// communication, and write the statistics of the check to the file given
+/// with `--json`, if any.
+pub fn run_converter<V: Converter>(
+    party: &str,
+    args: &Args,
+    params: &V::Params,
+    channel: &mut MainChannel,
+    bucket_channels: Vec<BucketChannel>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    V::Stats: JsonStats,
+{
+    let mut rng = AesRng::new();
+
+    let start = Instant::now();
+    let mut converter = V::init(channel, &mut rng, params)?;
+    let init = start.elapsed();
+
+    let start = Instant::now();
+    let conversions = converter.random_conversions(channel, &mut rng, args.nb_bits, args.num)?;
+    let input = start.elapsed();
+
+    let start = Instant::now();
+    let comm = channel.comm_breakdown().unwrap();
+    converter.verify_with_bucket_channels(channel, &mut rng, &conversions, bucket_channels)?;
+    let verify = start.elapsed();
+    let comm = channel.comm_breakdown().unwrap().since(&comm);
+
+    print_stats(
+        party,
+        &[
+            ("init", init),
+            ("input random conversions", input),
+            ("verify", verify),
+        ],
+        &comm,
+    );
+    if let (Some(path), Some(stats)) = (&args.json, converter.stats()) {
+        if let Some(json) = stats.to_json() {
+            if path == Path::new("-") {
+                println!("{}", json);
+            } else {
+                std::fs::write(path, json)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the phase timings and the communication of the check.
+pub fn print_stats(party: &str, timings: &[(&str, Duration)], comm: &CommBreakdown) {
+    for (phase, time) in timings {
+        println!("{} time ({}): {:?}", party, phase, time);
+    }
+    println!("{} communication of the check on the main channel:", party);
+    print!("{}", comm);
+    let total = comm.total();
+    println!(
+        "total: {} bytes read, {} bytes written",
+        total.bytes_read, total.bytes_written
+    );
+}
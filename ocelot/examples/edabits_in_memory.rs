@@ -0,0 +1,127 @@
+//! The edabits conversion over a user-supplied `AbstractChannel`.
+//!
+//! `MessageChannel` sends the bytes written since the last flush as one
+//! message, and reads the incoming messages as a stream of bytes, as a
+//! channel over a WebSocket would. Here the messages are carried in memory
+//! between two threads. The prover only needs the channel, so that it builds
+//! for `wasm32-unknown-unknown` with the default features of `ocelot`
+//! disabled.
+
+use ocelot::edabits::{ConvConfig, ProverConv, VerifierConv};
+use ocelot::svole::wykw::{LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+use scuttlebutt::{field::F61p, AbstractChannel, AesRng};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    io::{Error, ErrorKind, Result},
+    rc::Rc,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+const NB_BITS: usize = 38;
+const NUM: usize = 1000;
+const NUM_BUCKET: usize = 5;
+const NUM_CUT: usize = 5;
+
+struct Transport {
+    outgoing: Sender<Vec<u8>>,
+    incoming: Receiver<Vec<u8>>,
+    write_buf: Vec<u8>,
+    read_buf: VecDeque<u8>,
+}
+
+/// A channel over a message transport, shared by its clones.
+struct MessageChannel(Rc<RefCell<Transport>>);
+
+impl MessageChannel {
+    fn new(outgoing: Sender<Vec<u8>>, incoming: Receiver<Vec<u8>>) -> Self {
+        Self(Rc::new(RefCell::new(Transport {
+            outgoing,
+            incoming,
+            write_buf: Vec::new(),
+            read_buf: VecDeque::new(),
+        })))
+    }
+}
+
+impl AbstractChannel for MessageChannel {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut transport = self.0.borrow_mut();
+        while transport.read_buf.len() < bytes.len() {
+            let message = transport
+                .incoming
+                .recv()
+                .map_err(|_| Error::new(ErrorKind::UnexpectedEof, "the peer is gone"))?;
+            transport.read_buf.extend(message);
+        }
+        let len = bytes.len();
+        for (b, x) in bytes.iter_mut().zip(transport.read_buf.drain(..len)) {
+            *b = x;
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.0.borrow_mut().write_buf.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut transport = self.0.borrow_mut();
+        if !transport.write_buf.is_empty() {
+            let message = std::mem::take(&mut transport.write_buf);
+            transport
+                .outgoing
+                .send(message)
+                .map_err(|_| Error::new(ErrorKind::BrokenPipe, "the peer is gone"))?;
+        }
+        Ok(())
+    }
+
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+fn main() {
+    let (to_verifier, from_prover) = channel();
+    let (to_prover, from_verifier) = channel();
+
+    let handle = std::thread::spawn(move || {
+        let mut channel = MessageChannel::new(to_verifier, from_verifier);
+        let mut rng = AesRng::new();
+        let mut fconv =
+            ProverConv::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, NUM)
+            .unwrap();
+        fconv
+            .conv(
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &ConvConfig::new().buckets(NUM_BUCKET).cut(NUM_CUT),
+            )
+            .unwrap();
+    });
+
+    let mut channel = MessageChannel::new(to_prover, from_prover);
+    let mut rng = AesRng::new();
+    let mut fconv =
+        VerifierConv::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+            .unwrap();
+    let edabits = fconv
+        .random_edabits(&mut channel, &mut rng, NB_BITS, NUM)
+        .unwrap();
+    fconv
+        .conv(
+            &mut channel,
+            &mut rng,
+            &edabits,
+            &ConvConfig::new().buckets(NUM_BUCKET).cut(NUM_CUT),
+        )
+        .unwrap();
+    handle.join().unwrap();
+    println!("The verifier accepted the conversion of {} edabits", NUM);
+}
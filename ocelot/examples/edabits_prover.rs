@@ -0,0 +1,17 @@
+//! Prover of the edabits conversion over TCP, to be run along with the
+//! `edabits_verifier` example given the same parameters, e.g.
+//!
+//! ```text
+//! cargo run --release --example edabits_verifier -- --field f127p --nb-bits 64
+//! cargo run --release --example edabits_prover -- --field f127p --nb-bits 64
+//! ```
+
+mod edabits_common;
+
+use edabits_common::{run, Args, Role};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse_checked();
+    println!("Prover: {:?}", args);
+    run(Role::Prover, &args, false)
+}
@@ -0,0 +1,38 @@
+//! Either party of the edabits conversion over TCP, to benchmark it against
+//! other implementations. One party listens and the other connects, given
+//! the same parameters otherwise, e.g.
+//!
+//! ```text
+//! cargo run --release --example edabits_tcp -- --role verifier --listen --addr 0.0.0.0:5527
+//! cargo run --release --example edabits_tcp -- --role prover --addr 10.0.0.1:5527
+//! ```
+//!
+//! With `--threads`, the buckets are checked over one extra connection per
+//! thread. With `--json -` and the `serde` feature, the statistics of the
+//! check are printed as JSON.
+
+mod edabits_common;
+
+use clap::Parser;
+use edabits_common::{run, Args, Role};
+
+/// Both parties must be given the same parameters, except for the role and
+/// `--listen`.
+#[derive(Debug, Parser)]
+struct TcpArgs {
+    /// Party run by this process.
+    #[clap(long, value_enum)]
+    role: Role,
+    /// Listen on the address for the other party, instead of connecting to
+    /// it.
+    #[clap(long)]
+    listen: bool,
+    #[clap(flatten)]
+    args: Args,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let tcp_args = TcpArgs::parse();
+    tcp_args.args.check();
+    run(tcp_args.role, &tcp_args.args, tcp_args.listen)
+}
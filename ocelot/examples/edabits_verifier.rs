@@ -0,0 +1,14 @@
+//! Verifier of the edabits conversion over TCP, to be run along with the
+//! `edabits_prover` example given the same parameters, see there.
+
+mod edabits_common;
+
+use edabits_common::{run, Args, Role};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse_checked();
+    println!("Verifier: {:?}", args);
+    run(Role::Verifier, &args, true)?;
+    println!("Verifier: conversion checked");
+    Ok(())
+}
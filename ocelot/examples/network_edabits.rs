@@ -1,7 +1,7 @@
 use clap::{Arg, ArgAction, Command};
-use ocelot::edabits::{ProverConv, VerifierConv};
+use ocelot::edabits::{ConvConfig, MultCheck, ProverConv, VerifierConv};
 use ocelot::svole::wykw::{LPN_EXTEND_MEDIUM, LPN_SETUP_MEDIUM};
-use scuttlebutt::{field::F61p, AesRng, SyncChannel, TrackChannel};
+use scuttlebutt::{field::F61p, tcp_channel, AesRng, ChannelConfig, SyncChannel, TrackChannel};
 use std::fs;
 use std::io::Write;
 use std::io::{BufReader, BufWriter};
@@ -16,13 +16,39 @@ const DEFAULT_ADDR: &str = "127.0.0.1:5527";
 const DEFAULT_NB_BITS: &str = "38";
 const DEFAULT_NUM_EDABITS: &str = "10000";
 const DEFAULT_NUM_BUCKET: &str = "5";
+const DEFAULT_CHANNEL: &str = "default";
 
 const VERIFIER: &str = "VERIFIER";
 const PROVER: &str = "PROVER";
 
+// The channel configurations selectable with `--channel`, run in this order
+// by `--channel sweep`.
+fn channel_configs() -> Vec<(&'static str, ChannelConfig)> {
+    vec![
+        ("default", ChannelConfig::default()),
+        ("lan", ChannelConfig::lan()),
+        ("wan", ChannelConfig::wan()),
+    ]
+}
+
+// Bucket channels are still plain buffered TCP channels, so only their
+// buffer sizes and `TCP_NODELAY` follow the configuration.
+fn bucket_channel(
+    stream: TcpStream,
+    config: ChannelConfig,
+) -> std::io::Result<SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>> {
+    stream.set_nodelay(config.nodelay)?;
+    let reader = BufReader::with_capacity(config.read_buffer_size, stream.try_clone()?);
+    let writer = BufWriter::with_capacity(config.write_buffer_size, stream);
+    Ok(SyncChannel::new(reader, writer))
+}
+
 fn run(
     whoami: &str,
+    listener: Option<&TcpListener>,
     connection_addr: &str,
+    channel_name: &str,
+    channel_config: ChannelConfig,
     nb_bits: usize,
     num_edabits: usize,
     num_bucket: usize,
@@ -37,6 +63,16 @@ fn run(
     println!("num_bucket: {:?}", num_bucket);
     println!("with_quicksilver: {:?}", with_quicksilver);
     println!("multithreaded: {:?}", multithreaded);
+    println!("channel: {} {:?}", channel_name, channel_config);
+    let config = ConvConfig::new()
+        .buckets(num_bucket)
+        .cut(num_cut)
+        .strategy(if with_quicksilver {
+            MultCheck::QuickSilver
+        } else {
+            MultCheck::Wolverine
+        })
+        .threads(if multithreaded { num_bucket } else { 1 });
 
     if whoami == VERIFIER {
         let filename = "/tmp/bench_result.txt";
@@ -57,17 +93,14 @@ fn run(
         }
 
         println!("Verifier started");
+        file.write_all(format!("channel={}, ", channel_name).as_bytes())?;
 
-        let listener = TcpListener::bind(connection_addr)?;
+        let listener = listener.unwrap();
 
         match listener.accept() {
             Ok((stream_verifier, _addr)) => {
                 println!("Verifier received a connection");
-                let reader = BufReader::new(stream_verifier.try_clone().unwrap());
-                let writer = BufWriter::new(stream_verifier);
-                let mut channel: TrackChannel<
-                    SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>,
-                > = TrackChannel::new(SyncChannel::new(reader, writer));
+                let mut channel = TrackChannel::new(tcp_channel(stream_verifier, channel_config)?);
 
                 let mut bucket_connections = None;
                 if multithreaded {
@@ -76,10 +109,7 @@ fn run(
                         match listener.accept() {
                             Ok((mstream, _addr)) => {
                                 println!("V: receive bucket connection {:?}", _addr);
-                                let bucket_stream = mstream;
-                                let reader = BufReader::new(bucket_stream.try_clone().unwrap());
-                                let writer = BufWriter::new(bucket_stream);
-                                let bucket_channel = SyncChannel::new(reader, writer);
+                                let bucket_channel = bucket_channel(mstream, channel_config)?;
                                 bucket_connections_verifier.push(bucket_channel);
                             }
                             Err(e) => println!("couldn't get client: {:?}", e),
@@ -112,17 +142,17 @@ fn run(
                 channel.clear();
 
                 let start = Instant::now();
-                let _r = fconv
-                    .conv(
+                let _r = match bucket_connections {
+                    None => fconv.conv(&mut channel, &mut rng, &edabits, &config),
+                    Some(bucket_connections) => fconv.conv_with_bucket_channels(
                         &mut channel,
                         &mut rng,
-                        num_bucket,
-                        num_cut,
                         &edabits,
+                        &config,
                         bucket_connections,
-                        with_quicksilver,
-                    )
-                    .unwrap();
+                    ),
+                }
+                .unwrap();
                 let end = start.elapsed();
                 println!("Verifier time (conv): {:?}", start.elapsed());
                 file.write_all(format!("conv={:?}, ", end).as_bytes())?;
@@ -178,9 +208,7 @@ fn run(
     } else {
         println!("Prover started");
         let stream_prover = TcpStream::connect(connection_addr)?;
-        let reader = BufReader::new(stream_prover.try_clone().unwrap());
-        let writer = BufWriter::new(stream_prover);
-        let mut channel = TrackChannel::new(SyncChannel::new(reader, writer));
+        let mut channel = TrackChannel::new(tcp_channel(stream_prover, channel_config)?);
 
         let mut bucket_connections = None;
         if multithreaded {
@@ -189,9 +217,7 @@ fn run(
                 println!("P: attempt bucket connection");
                 let bucket_stream = TcpStream::connect(connection_addr)?;
                 println!("PEER ADDR {:?}", bucket_stream.peer_addr());
-                let reader = BufReader::new(bucket_stream.try_clone().unwrap());
-                let writer = BufWriter::new(bucket_stream);
-                let bucket_channel = SyncChannel::new(reader, writer);
+                let bucket_channel = bucket_channel(bucket_stream, channel_config)?;
                 bucket_connections_prover.push(bucket_channel);
             }
             bucket_connections = Some(bucket_connections_prover);
@@ -210,17 +236,17 @@ fn run(
         println!("Prover time (input random edabits): {:?}", start.elapsed());
 
         let start = Instant::now();
-        let _ = fconv
-            .conv(
+        let _ = match bucket_connections {
+            None => fconv.conv(&mut channel, &mut rng, &edabits, &config),
+            Some(bucket_connections) => fconv.conv_with_bucket_channels(
                 &mut channel,
                 &mut rng,
-                num_bucket,
-                num_cut,
                 &edabits,
+                &config,
                 bucket_connections,
-                with_quicksilver,
-            )
-            .unwrap();
+            ),
+        }
+        .unwrap();
         println!("Prover time (conv): {:?}", start.elapsed());
     }
     Ok(())
@@ -283,6 +309,13 @@ fn main() -> std::io::Result<()> {
                 .long("multithreaded")
                 .help("Using multithreading on B-loop"),
         )
+        .arg(
+            Arg::new("channel")
+                .long("channel")
+                .value_name("CHANNEL")
+                .help("Set the channel configuration: default, lan, wan, or sweep to run them all")
+                .default_value(DEFAULT_CHANNEL),
+        )
         .get_matches();
     let whoami;
     if !matches.contains_id("prover") {
@@ -301,14 +334,36 @@ fn main() -> std::io::Result<()> {
     let multithreaded = matches.contains_id("multithreaded");
     let num_cut = num_bucket;
     let with_quicksilver = matches.contains_id("with_quicksilver");
-    run(
-        whoami,
-        connection_addr,
-        nb_bits,
-        num_edabits,
-        num_bucket,
-        num_cut,
-        multithreaded,
-        with_quicksilver,
-    )
+    let channel = matches.get_one::<String>("channel").unwrap();
+    let configs: Vec<_> = channel_configs()
+        .into_iter()
+        .filter(|(name, _)| channel == "sweep" || channel == name)
+        .collect();
+    if configs.is_empty() {
+        panic!("unknown channel configuration: {}", channel);
+    }
+
+    // The verifier listens once for the whole sweep, so that the prover's
+    // connections for the next configuration queue up instead of failing.
+    let listener = if whoami == VERIFIER {
+        Some(TcpListener::bind(connection_addr)?)
+    } else {
+        None
+    };
+    for (channel_name, channel_config) in configs {
+        run(
+            whoami,
+            listener.as_ref(),
+            connection_addr,
+            channel_name,
+            channel_config,
+            nb_bits,
+            num_edabits,
+            num_bucket,
+            num_cut,
+            multithreaded,
+            with_quicksilver,
+        )?;
+    }
+    Ok(())
 }
@@ -0,0 +1,556 @@
+//! Asynchronous entry points of the conversion protocol.
+//!
+//! The protocol itself is blocking: the asynchronous entry points run it on
+//! the blocking thread pool of tokio and perform its I/O on an
+//! [`AsyncAbstractChannel`] from the calling task, see
+//! [`scuttlebutt::channel::run_blocking`]. This does not block the runtime,
+//! so that many sessions can be multiplexed on it, but every running
+//! conversion still occupies a thread of the blocking pool. The buckets are
+//! checked with the pools of the instance, as with `conv`, so the
+//! configuration must use a single thread.
+//!
+//! The messages are those of the blocking versions, so that an asynchronous
+//! party talks to a blocking one. The F_com calls used by the conversion
+//! have asynchronous versions too, to build other protocols on them.
+
+use super::edabits::{
+    ConvConfig, ConvStats, EdabitsProver, EdabitsVerifier, ProverConv, VerifierConv,
+};
+use super::homcom::{FComProver, FComVerifier, MacProver, MacVerifier};
+use crate::{
+    errors::Error,
+    svole::wykw::{BaseOt, LpnParams},
+};
+use rand::{CryptoRng, Rng};
+use scuttlebutt::{
+    channel::{run_blocking, BridgeChannel},
+    field::{FiniteField, IsSubFieldOf, F2},
+    AsyncAbstractChannel,
+};
+
+// Run `f` on `state` on the blocking thread pool, with its I/O performed on
+// `channel`, and return `state` along with the output of `f`.
+async fn run_with<A, S, T, F>(channel: &mut A, mut state: S, f: F) -> Result<(S, T), Error>
+where
+    A: AsyncAbstractChannel,
+    S: Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(&mut S, &mut BridgeChannel) -> Result<T, Error> + Send + 'static,
+{
+    let (state, output) = run_blocking(channel, move |channel| {
+        let output = f(&mut state, channel);
+        (state, output)
+    })
+    .await?;
+    Ok((state, output?))
+}
+
+impl<FE: FiniteField, B: BaseOt> FComProver<FE, B> {
+    /// Asynchronous version of [`FComProver::init`].
+    pub async fn init_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        channel: &mut A,
+        mut rng: RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        run_blocking(channel, move |channel| {
+            Self::init(channel, &mut rng, lpn_setup, lpn_extend)
+        })
+        .await?
+    }
+
+    /// Asynchronous version of [`FComProver::random`].
+    pub async fn random_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+    ) -> Result<(Self, MacProver<FE>), Error> {
+        run_with(channel, self, move |fcom, channel| {
+            fcom.random(channel, &mut rng)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`FComProver::input`].
+    pub async fn input_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        x: Vec<FE::PrimeField>,
+    ) -> Result<(Self, Vec<FE>), Error> {
+        run_with(channel, self, move |fcom, channel| {
+            fcom.input(channel, &mut rng, &x)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`FComProver::open`].
+    pub async fn open_async<A: AsyncAbstractChannel>(
+        self,
+        channel: &mut A,
+        batch: Vec<MacProver<FE>>,
+    ) -> Result<Self, Error> {
+        let (fcom, ()) = run_with(channel, self, move |fcom, channel| {
+            fcom.open(channel, &batch)
+        })
+        .await?;
+        Ok(fcom)
+    }
+
+    /// Asynchronous version of [`FComProver::check_zero`].
+    pub async fn check_zero_async<A: AsyncAbstractChannel>(
+        self,
+        channel: &mut A,
+        x_mac_batch: Vec<MacProver<FE>>,
+    ) -> Result<Self, Error> {
+        let (fcom, ()) = run_with(channel, self, move |fcom, channel| {
+            fcom.check_zero(channel, &x_mac_batch)
+        })
+        .await?;
+        Ok(fcom)
+    }
+
+    /// Asynchronous version of [`FComProver::quicksilver_check_multiply`].
+    pub async fn quicksilver_check_multiply_async<
+        A: AsyncAbstractChannel,
+        RNG: CryptoRng + Rng + Send + 'static,
+    >(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        triples: Vec<(MacProver<FE>, MacProver<FE>, MacProver<FE>)>,
+    ) -> Result<Self, Error> {
+        let (fcom, ()) = run_with(channel, self, move |fcom, channel| {
+            fcom.quicksilver_check_multiply(channel, &mut rng, &triples)
+        })
+        .await?;
+        Ok(fcom)
+    }
+}
+
+impl<FE: FiniteField, B: BaseOt> FComVerifier<FE, B> {
+    /// Asynchronous version of [`FComVerifier::init`].
+    pub async fn init_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        channel: &mut A,
+        mut rng: RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        run_blocking(channel, move |channel| {
+            Self::init(channel, &mut rng, lpn_setup, lpn_extend)
+        })
+        .await?
+    }
+
+    /// Asynchronous version of [`FComVerifier::random`].
+    pub async fn random_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+    ) -> Result<(Self, MacVerifier<FE>), Error> {
+        run_with(channel, self, move |fcom, channel| {
+            fcom.random(channel, &mut rng)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`FComVerifier::input`].
+    pub async fn input_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        num: usize,
+    ) -> Result<(Self, Vec<MacVerifier<FE>>), Error> {
+        run_with(channel, self, move |fcom, channel| {
+            fcom.input(channel, &mut rng, num)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`FComVerifier::open`], returning the opened
+    /// values.
+    pub async fn open_async<A: AsyncAbstractChannel>(
+        self,
+        channel: &mut A,
+        keys: Vec<MacVerifier<FE>>,
+    ) -> Result<(Self, Vec<FE::PrimeField>), Error> {
+        run_with(channel, self, move |fcom, channel| {
+            let mut out = Vec::with_capacity(keys.len());
+            fcom.open(channel, &keys, &mut out)?;
+            Ok(out)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`FComVerifier::check_zero`].
+    pub async fn check_zero_async<
+        A: AsyncAbstractChannel,
+        RNG: CryptoRng + Rng + Send + 'static,
+    >(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        key_batch: Vec<MacVerifier<FE>>,
+    ) -> Result<Self, Error> {
+        let (fcom, ()) = run_with(channel, self, move |fcom, channel| {
+            fcom.check_zero(channel, &mut rng, &key_batch)
+        })
+        .await?;
+        Ok(fcom)
+    }
+
+    /// Asynchronous version of [`FComVerifier::quicksilver_check_multiply`].
+    pub async fn quicksilver_check_multiply_async<
+        A: AsyncAbstractChannel,
+        RNG: CryptoRng + Rng + Send + 'static,
+    >(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        triples: Vec<(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)>,
+    ) -> Result<Self, Error> {
+        let (fcom, ()) = run_with(channel, self, move |fcom, channel| {
+            fcom.quicksilver_check_multiply(channel, &mut rng, &triples)
+        })
+        .await?;
+        Ok(fcom)
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    ProverConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    /// Asynchronous version of [`ProverConv::init`].
+    pub async fn init_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        channel: &mut A,
+        mut rng: RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        run_blocking(channel, move |channel| {
+            Self::init(channel, &mut rng, lpn_setup, lpn_extend)
+        })
+        .await?
+    }
+
+    /// Asynchronous version of [`ProverConv::random_edabits`].
+    pub async fn random_edabits_async<
+        A: AsyncAbstractChannel,
+        RNG: CryptoRng + Rng + Send + 'static,
+    >(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<(Self, Vec<EdabitsProver<FE, TF>>), Error> {
+        run_with(channel, self, move |fconv, channel| {
+            fconv.random_edabits(channel, &mut rng, nb_bits, num)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`ProverConv::conv`].
+    ///
+    /// The instance is moved to the blocking thread pool for the duration
+    /// of the conversion, and returned with the statistics.
+    pub async fn conv_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        edabits_vector: Vec<EdabitsProver<FE, TF>>,
+        config: &ConvConfig,
+    ) -> Result<(Self, ConvStats), Error> {
+        let config = config.clone();
+        run_with(channel, self, move |fconv, channel| {
+            fconv.conv(channel, &mut rng, &edabits_vector, &config)
+        })
+        .await
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    VerifierConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    /// Asynchronous version of [`VerifierConv::init`].
+    pub async fn init_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        channel: &mut A,
+        mut rng: RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        run_blocking(channel, move |channel| {
+            Self::init(channel, &mut rng, lpn_setup, lpn_extend)
+        })
+        .await?
+    }
+
+    /// Asynchronous version of [`VerifierConv::random_edabits`].
+    pub async fn random_edabits_async<
+        A: AsyncAbstractChannel,
+        RNG: CryptoRng + Rng + Send + 'static,
+    >(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<(Self, Vec<EdabitsVerifier<FE, TF>>), Error> {
+        run_with(channel, self, move |fconv, channel| {
+            fconv.random_edabits(channel, &mut rng, nb_bits, num)
+        })
+        .await
+    }
+
+    /// Asynchronous version of [`VerifierConv::conv`].
+    ///
+    /// The instance is moved to the blocking thread pool for the duration
+    /// of the conversion, and returned with the statistics.
+    pub async fn conv_async<A: AsyncAbstractChannel, RNG: CryptoRng + Rng + Send + 'static>(
+        self,
+        channel: &mut A,
+        mut rng: RNG,
+        edabits_vector_mac: Vec<EdabitsVerifier<FE, TF>>,
+        config: &ConvConfig,
+    ) -> Result<(Self, ConvStats), Error> {
+        let config = config.clone();
+        run_with(channel, self, move |fconv, channel| {
+            fconv.conv(channel, &mut rng, &edabits_vector_mac, &config)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::edabits::{ConvConfig, ProverConv, VerifierConv};
+    use super::super::homcom::{FComProver, FComVerifier, MacProver};
+    use crate::svole::wykw::{UnsafeInsecureTestOt, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use scuttlebutt::{
+        channel::ChannelFuture,
+        field::{F40b, F61p},
+        ring::FiniteRing,
+        unix_channel_pair, AbstractChannel, AesRng, AsyncAbstractChannel, AsyncChannel,
+    };
+
+    const NB_BITS: usize = 38;
+
+    // A blocking channel behind the asynchronous interface, so that an
+    // asynchronous party runs against a blocking one over a pair of
+    // sockets. It blocks the runtime, which only serves that party.
+    struct BlockingChannel<C>(C);
+
+    impl<C: AbstractChannel + Send> AsyncAbstractChannel for BlockingChannel<C> {
+        fn read_bytes<'a>(&'a mut self, bytes: &'a mut [u8]) -> ChannelFuture<'a, ()> {
+            Box::pin(std::future::ready(self.0.read_bytes(bytes)))
+        }
+
+        fn write_bytes<'a>(&'a mut self, bytes: &'a [u8]) -> ChannelFuture<'a, ()> {
+            Box::pin(std::future::ready(self.0.write_bytes(bytes)))
+        }
+
+        fn flush(&mut self) -> ChannelFuture<'_, ()> {
+            Box::pin(std::future::ready(self.0.flush()))
+        }
+    }
+
+    #[test]
+    fn test_conv_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (prover, verifier) = tokio::io::duplex(1 << 16);
+        let (reader, writer) = tokio::io::split(prover);
+        let mut prover_channel = AsyncChannel::new(reader, writer);
+        let (reader, writer) = tokio::io::split(verifier);
+        let mut verifier_channel = AsyncChannel::new(reader, writer);
+
+        let prover = async {
+            let fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init_async(
+                &mut prover_channel,
+                AesRng::new(),
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .await
+            .unwrap();
+            let (fconv, edabits) = fconv
+                .random_edabits_async(&mut prover_channel, AesRng::new(), NB_BITS, 10)
+                .await
+                .unwrap();
+            fconv
+                .conv_async(
+                    &mut prover_channel,
+                    AesRng::new(),
+                    edabits,
+                    &ConvConfig::new(),
+                )
+                .await
+                .unwrap()
+        };
+        let verifier = async {
+            let fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init_async(
+                &mut verifier_channel,
+                AesRng::new(),
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .await
+            .unwrap();
+            let (fconv, edabits) = fconv
+                .random_edabits_async(&mut verifier_channel, AesRng::new(), NB_BITS, 10)
+                .await
+                .unwrap();
+            fconv
+                .conv_async(
+                    &mut verifier_channel,
+                    AesRng::new(),
+                    edabits,
+                    &ConvConfig::new(),
+                )
+                .await
+                .unwrap()
+        };
+        // both sessions are multiplexed on a single runtime thread
+        runtime.block_on(async { tokio::join!(prover, verifier) });
+    }
+
+    // An asynchronous prover converts with a blocking verifier: both
+    // versions send the same messages.
+    #[test]
+    fn test_conv_async_prover_sync_verifier() {
+        let (sender, mut receiver) = unix_channel_pair();
+        let handle = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            let mut channel = BlockingChannel(sender);
+            runtime.block_on(async {
+                let fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init_async(
+                    &mut channel,
+                    AesRng::new(),
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .await
+                .unwrap();
+                let (fconv, edabits) = fconv
+                    .random_edabits_async(&mut channel, AesRng::new(), NB_BITS, 10)
+                    .await
+                    .unwrap();
+                fconv
+                    .conv_async(&mut channel, AesRng::new(), edabits, &ConvConfig::new())
+                    .await
+                    .unwrap()
+            })
+        });
+        let mut rng = AesRng::new();
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut receiver,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut receiver, &mut rng, NB_BITS, 10)
+            .unwrap();
+        fconv
+            .conv(&mut receiver, &mut rng, &edabits, &ConvConfig::new())
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    // The asynchronous F_com calls of the prover against the blocking ones
+    // of the verifier.
+    #[test]
+    fn test_fcom_async_prover_sync_verifier() {
+        let n = 10;
+        let (sender, mut receiver) = unix_channel_pair();
+        let handle = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap();
+            let mut channel = BlockingChannel(sender);
+            runtime.block_on(async {
+                let mut rng = AesRng::new();
+                let fcom = FComProver::<F61p, UnsafeInsecureTestOt>::init_async(
+                    &mut channel,
+                    AesRng::new(),
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .await
+                .unwrap();
+                let (fcom, r) = fcom
+                    .random_async(&mut channel, AesRng::new())
+                    .await
+                    .unwrap();
+                let x: Vec<F61p> = (0..n).map(|_| F61p::random(&mut rng)).collect();
+                let y: Vec<F61p> = (0..n).map(|_| F61p::random(&mut rng)).collect();
+                let z: Vec<F61p> = x.iter().zip(y.iter()).map(|(x, y)| *x * *y).collect();
+                let zeros = vec![F61p::ZERO; n];
+                let mut inputs = Vec::new();
+                let mut fcom = fcom;
+                for values in [x, y, z, zeros] {
+                    let (next, macs) = fcom
+                        .input_async(&mut channel, AesRng::new(), values.clone())
+                        .await
+                        .unwrap();
+                    fcom = next;
+                    let commitments: Vec<_> = values
+                        .into_iter()
+                        .zip(macs)
+                        .map(|(value, mac)| MacProver(value, mac))
+                        .collect();
+                    inputs.push(commitments);
+                }
+                let mut opened = inputs[0].clone();
+                opened.push(r);
+                let fcom = fcom.open_async(&mut channel, opened).await.unwrap();
+                let fcom = fcom
+                    .check_zero_async(&mut channel, inputs[3].clone())
+                    .await
+                    .unwrap();
+                let triples = (0..n)
+                    .map(|i| (inputs[0][i], inputs[1][i], inputs[2][i]))
+                    .collect();
+                fcom.quicksilver_check_multiply_async(&mut channel, AesRng::new(), triples)
+                    .await
+                    .unwrap();
+                channel.flush().await.unwrap();
+                let mut values: Vec<_> = inputs[0].iter().map(|x| x.0).collect();
+                values.push(r.0);
+                values
+            })
+        });
+        let mut rng = AesRng::new();
+        let mut fcom = FComVerifier::<F61p, UnsafeInsecureTestOt>::init(
+            &mut receiver,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let r = fcom.random(&mut receiver, &mut rng).unwrap();
+        let inputs: Vec<_> = (0..4)
+            .map(|_| fcom.input(&mut receiver, &mut rng, n).unwrap())
+            .collect();
+        let mut opened = inputs[0].clone();
+        opened.push(r);
+        let mut values = Vec::new();
+        fcom.open(&mut receiver, &opened, &mut values).unwrap();
+        fcom.check_zero(&mut receiver, &mut rng, &inputs[3])
+            .unwrap();
+        let triples: Vec<_> = (0..n)
+            .map(|i| (inputs[0][i], inputs[1][i], inputs[2][i]))
+            .collect();
+        fcom.quicksilver_check_multiply(&mut receiver, &mut rng, &triples)
+            .unwrap();
+        assert_eq!(values, handle.join().unwrap());
+    }
+}
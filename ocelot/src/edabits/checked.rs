@@ -0,0 +1,172 @@
+//! Sequence tags for the messages of the F_com functionality.
+//!
+//! With the `checked-channel` feature, every logical message sent by
+//! `FComProver` or `FComVerifier` is prefixed with the kind of operation
+//! that sent it and a sequence number, which the receiver checks against
+//! the operation it is running. A desynchronization of the two parties is
+//! then reported where it happens, instead of producing garbage field
+//! elements further down the protocol. This doubles as a debugging aid and
+//! is not meant to be used in production.
+//!
+//! Without the feature, tagging is a no-op.
+
+use crate::errors::Error;
+use scuttlebutt::AbstractChannel;
+
+/// Number of bytes of the tag prefixed to every message.
+#[cfg(test)]
+pub(crate) const TAG_LEN: usize = if cfg!(feature = "checked-channel") {
+    9
+} else {
+    0
+};
+
+/// Kind of operation sending a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Op {
+    Input,
+    CheckZeroSeed,
+    CheckZero,
+    Open,
+    ReauthenticateSeed,
+    QuicksilverChallenge,
+    Quicksilver,
+    Challenge,
+}
+
+#[cfg(feature = "checked-channel")]
+impl Op {
+    const ALL: [Op; 8] = [
+        Op::Input,
+        Op::CheckZeroSeed,
+        Op::CheckZero,
+        Op::Open,
+        Op::ReauthenticateSeed,
+        Op::QuicksilverChallenge,
+        Op::Quicksilver,
+        Op::Challenge,
+    ];
+
+    fn to_u8(self) -> u8 {
+        Op::ALL.iter().position(|op| *op == self).unwrap() as u8
+    }
+
+    fn from_u8(b: u8) -> Option<Op> {
+        Op::ALL.get(b as usize).copied()
+    }
+}
+
+/// Sequence of tagged messages of an F_com instance.
+///
+/// Both parties count the messages they send and receive, so their
+/// sequence numbers agree as long as they run the same operations.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Sequencer {
+    #[cfg(feature = "checked-channel")]
+    seq: u64,
+}
+
+#[cfg(feature = "checked-channel")]
+impl Sequencer {
+    /// Tag the next message sent by `op`.
+    pub(crate) fn send<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        op: Op,
+    ) -> Result<(), Error> {
+        channel.write_u8(op.to_u8())?;
+        channel.write_u64(self.seq)?;
+        self.seq += 1;
+        Ok(())
+    }
+
+    /// Check the tag of the next message, expected to be sent by `op`.
+    pub(crate) fn recv<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        op: Op,
+    ) -> Result<(), Error> {
+        let got_op = channel.read_u8()?;
+        let got_seq = channel.read_u64()?;
+        if got_op != op.to_u8() || got_seq != self.seq {
+            let got = match Op::from_u8(got_op) {
+                Some(got_op) => format!("{:?}#{}", got_op, got_seq),
+                None => format!("Unknown({})#{}", got_op, got_seq),
+            };
+            return Err(Error::Other(format!(
+                "channel desynchronized: expected {:?}#{} got {}",
+                op, self.seq, got
+            )));
+        }
+        self.seq += 1;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "checked-channel"))]
+impl Sequencer {
+    /// Tag the next message sent by `op`.
+    #[inline(always)]
+    pub(crate) fn send<C: AbstractChannel>(
+        &mut self,
+        _channel: &mut C,
+        _op: Op,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Check the tag of the next message, expected to be sent by `op`.
+    #[inline(always)]
+    pub(crate) fn recv<C: AbstractChannel>(
+        &mut self,
+        _channel: &mut C,
+        _op: Op,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "checked-channel"))]
+mod tests {
+    use super::{Op, Sequencer};
+    use scuttlebutt::{AbstractChannel, Channel};
+    use std::io::{BufReader, BufWriter, Cursor};
+
+    fn tagged(ops: &[Op]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        {
+            let mut channel = Channel::new(
+                BufReader::new(Cursor::new(vec![])),
+                BufWriter::new(&mut buf),
+            );
+            let mut seq = Sequencer::default();
+            for op in ops {
+                seq.send(&mut channel, *op).unwrap();
+            }
+            channel.flush().unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn test_sequencer_in_sync() {
+        let buf = tagged(&[Op::Input, Op::Open]);
+        let mut channel = Channel::new(BufReader::new(Cursor::new(buf)), BufWriter::new(vec![]));
+        let mut seq = Sequencer::default();
+        seq.recv(&mut channel, Op::Input).unwrap();
+        seq.recv(&mut channel, Op::Open).unwrap();
+    }
+
+    #[test]
+    fn test_sequencer_desync() {
+        let buf = tagged(&[Op::Input, Op::Open]);
+        let mut channel = Channel::new(BufReader::new(Cursor::new(buf)), BufWriter::new(vec![]));
+        let mut seq = Sequencer::default();
+        seq.recv(&mut channel, Op::Input).unwrap();
+        let err = seq.recv(&mut channel, Op::Input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "other error: channel desynchronized: expected Input#1 got Open#1"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Common interface of the protocols checking conversions between the binary
+//! and the arithmetic domains, so that the edabits conversion can be swapped
+//! for another protocol behind the same tooling.
+
+use super::edabits::{
+    ConvConfig, ConvStats, EdabitsProver, EdabitsVerifier, ProverConv, VerifierConv,
+};
+use super::homcom::{MacProver, MacVerifier};
+use crate::{
+    errors::Error,
+    svole::wykw::{BaseOt, LpnParams, LPN_EXTEND_MEDIUM, LPN_SETUP_MEDIUM},
+};
+use rand::{CryptoRng, Rng};
+use scuttlebutt::{
+    field::{FiniteField, IsSubFieldOf, F2},
+    AbstractChannel,
+};
+
+/// A party of a protocol checking that values committed in the binary domain
+/// and in the arithmetic domain are consistent, e.g. the edabits conversion.
+///
+/// Both parties implement the trait, and call the same methods in the same
+/// order with the same parameters: `verify` checks the conversions for the
+/// verifier and proves them for the prover.
+pub trait Converter: Sized {
+    /// Parameters of the protocol, set once by `init`.
+    type Params;
+    /// A conversion to check, e.g. an edabit.
+    type Conversion;
+    /// The arithmetic commitments of the checked conversions.
+    type Output;
+    /// Statistics of a check.
+    type Stats;
+
+    /// Initialize the party.
+    fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        params: &Self::Params,
+    ) -> Result<Self, Error>;
+
+    /// Commit to `num` random conversions of `nb_bits` bits.
+    fn random_conversions<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<Self::Conversion>, Error>;
+
+    /// Check `conversions`, and return their arithmetic commitments.
+    fn verify<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[Self::Conversion],
+    ) -> Result<Self::Output, Error>;
+
+    /// Check `conversions` as `verify`, with parts of the check run in
+    /// parallel over `bucket_channels`. By default, the check runs on
+    /// `channel` only, and bucket channels are rejected.
+    fn verify_with_bucket_channels<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[Self::Conversion],
+        bucket_channels: Vec<C2>,
+    ) -> Result<Self::Output, Error> {
+        if !bucket_channels.is_empty() {
+            return Err(Error::Other(
+                "this Converter checks the conversions on a single channel".to_string(),
+            ));
+        }
+        self.verify(channel, rng, conversions)
+    }
+
+    /// Return the statistics of the last successful `verify`, if any.
+    fn stats(&self) -> Option<&Self::Stats>;
+}
+
+/// Parameters of the edabits conversion as a [`Converter`].
+///
+/// With a single thread in `config`, the buckets are checked over the
+/// channel given to `verify`. With more, they are checked over one of the
+/// bucket channels given to `verify_with_bucket_channels` per thread, which
+/// requires the `threads` feature. Without bucket channels,
+/// `verify_with_bucket_channels` is `verify`. The default parameters use the medium
+/// LPN parameters and the default configuration.
+#[derive(Clone, Debug)]
+pub struct ConvParams {
+    /// LPN parameters of the setup of the sVOLEs.
+    pub lpn_setup: LpnParams,
+    /// LPN parameters of the extensions of the sVOLEs.
+    pub lpn_extend: LpnParams,
+    /// Configuration of `conv`.
+    pub config: ConvConfig,
+}
+
+impl Default for ConvParams {
+    fn default() -> Self {
+        ConvParams {
+            lpn_setup: LPN_SETUP_MEDIUM,
+            lpn_extend: LPN_EXTEND_MEDIUM,
+            config: ConvConfig::default(),
+        }
+    }
+}
+
+impl ConvParams {
+    // The configuration must be usable by `conv`. The bucket channels are
+    // checked against its threads by `conv` itself.
+    fn validate(&self) -> Result<(), Error> {
+        self.config.validate()
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt> Converter
+    for ProverConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    type Params = ConvParams;
+    type Conversion = EdabitsProver<FE, TF>;
+    type Output = Vec<MacProver<FE>>;
+    type Stats = ConvStats;
+
+    fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        params: &ConvParams,
+    ) -> Result<Self, Error> {
+        params.validate()?;
+        let mut fconv =
+            ProverConv::<FE, TF, B>::init(channel, rng, params.lpn_setup, params.lpn_extend)?;
+        fconv.config = params.config.clone();
+        Ok(fconv)
+    }
+
+    fn random_conversions<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<EdabitsProver<FE, TF>>, Error> {
+        self.random_edabits(channel, rng, nb_bits, num)
+    }
+
+    fn verify<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[EdabitsProver<FE, TF>],
+    ) -> Result<Vec<MacProver<FE>>, Error> {
+        let config = self.config.clone();
+        let stats = self.conv(channel, rng, conversions, &config)?;
+        self.stats = Some(stats);
+        Ok(conversions.iter().map(|e| e.value).collect())
+    }
+
+    fn verify_with_bucket_channels<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[EdabitsProver<FE, TF>],
+        bucket_channels: Vec<C2>,
+    ) -> Result<Vec<MacProver<FE>>, Error> {
+        if bucket_channels.is_empty() {
+            return self.verify(channel, rng, conversions);
+        }
+        let config = self.config.clone();
+        let stats =
+            self.conv_with_bucket_channels(channel, rng, conversions, &config, bucket_channels)?;
+        self.stats = Some(stats);
+        Ok(conversions.iter().map(|e| e.value).collect())
+    }
+
+    fn stats(&self) -> Option<&ConvStats> {
+        self.stats.as_ref()
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt> Converter
+    for VerifierConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    type Params = ConvParams;
+    type Conversion = EdabitsVerifier<FE, TF>;
+    type Output = Vec<MacVerifier<FE>>;
+    type Stats = ConvStats;
+
+    fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        params: &ConvParams,
+    ) -> Result<Self, Error> {
+        params.validate()?;
+        let mut fconv =
+            VerifierConv::<FE, TF, B>::init(channel, rng, params.lpn_setup, params.lpn_extend)?;
+        fconv.config = params.config.clone();
+        Ok(fconv)
+    }
+
+    fn random_conversions<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<EdabitsVerifier<FE, TF>>, Error> {
+        self.random_edabits(channel, rng, nb_bits, num)
+    }
+
+    fn verify<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[EdabitsVerifier<FE, TF>],
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        let config = self.config.clone();
+        let stats = self.conv(channel, rng, conversions, &config)?;
+        self.stats = Some(stats);
+        Ok(conversions.iter().map(|e| e.value).collect())
+    }
+
+    fn verify_with_bucket_channels<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        conversions: &[EdabitsVerifier<FE, TF>],
+        bucket_channels: Vec<C2>,
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        if bucket_channels.is_empty() {
+            return self.verify(channel, rng, conversions);
+        }
+        let config = self.config.clone();
+        let stats =
+            self.conv_with_bucket_channels(channel, rng, conversions, &config, bucket_channels)?;
+        self.stats = Some(stats);
+        Ok(conversions.iter().map(|e| e.value).collect())
+    }
+
+    fn stats(&self) -> Option<&ConvStats> {
+        self.stats.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConvParams, Converter};
+    use crate::edabits::{ConvConfig, ProverConv, VerifierConv};
+    use crate::svole::wykw::{UnsafeInsecureTestOt, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use scuttlebutt::{
+        field::{F40b, F61p},
+        local_pair, AesRng, LocalChannel,
+    };
+
+    type Prover = ProverConv<F61p, F40b, UnsafeInsecureTestOt>;
+    type Verifier = VerifierConv<F61p, F40b, UnsafeInsecureTestOt>;
+
+    // Check `num` random conversions with any pair of converters, as the
+    // tooling does, over `nb_bucket_channels` bucket channels if any, and
+    // return their outputs.
+    fn check<P, V>(
+        params: P::Params,
+        num: usize,
+        nb_bucket_channels: usize,
+    ) -> (P::Output, V::Output)
+    where
+        P: Converter + 'static,
+        P::Params: Clone + Send + 'static,
+        P::Output: Send + 'static,
+        V: Converter<Params = P::Params>,
+    {
+        let (mut sender, mut receiver) = local_pair();
+        let (bucket_senders, bucket_receivers): (Vec<LocalChannel>, Vec<LocalChannel>) =
+            (0..nb_bucket_channels).map(|_| local_pair()).unzip();
+        let prover_params = params.clone();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut converter = P::init(&mut sender, &mut rng, &prover_params).unwrap();
+            let conversions = converter
+                .random_conversions(&mut sender, &mut rng, 38, num)
+                .unwrap();
+            let output = converter
+                .verify_with_bucket_channels(&mut sender, &mut rng, &conversions, bucket_senders)
+                .unwrap();
+            assert!(converter.stats().is_some());
+            output
+        });
+        let mut rng = AesRng::new();
+        let mut converter = V::init(&mut receiver, &mut rng, &params).unwrap();
+        assert!(converter.stats().is_none());
+        let conversions = converter
+            .random_conversions(&mut receiver, &mut rng, 38, num)
+            .unwrap();
+        let output = converter
+            .verify_with_bucket_channels(&mut receiver, &mut rng, &conversions, bucket_receivers)
+            .unwrap();
+        assert!(converter.stats().is_some());
+        (handle.join().unwrap(), output)
+    }
+
+    #[test]
+    fn test_converter_edabits() {
+        let params = ConvParams {
+            lpn_setup: LPN_SETUP_SMALL,
+            lpn_extend: LPN_EXTEND_SMALL,
+            config: ConvConfig::default(),
+        };
+        let (prover, verifier) = check::<Prover, Verifier>(params, 10, 0);
+        assert_eq!(prover.len(), 10);
+        assert_eq!(verifier.len(), 10);
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn test_converter_bucket_channels() {
+        let params = ConvParams {
+            lpn_setup: LPN_SETUP_SMALL,
+            lpn_extend: LPN_EXTEND_SMALL,
+            config: ConvConfig::new().threads(2),
+        };
+        assert!(params.validate().is_ok());
+        let (prover, verifier) = check::<Prover, Verifier>(params, 10, 2);
+        assert_eq!(prover.len(), 10);
+        assert_eq!(verifier.len(), 10);
+    }
+}
@@ -1,107 +1,268 @@
 //! This is the implementation of field conversion
 
-use super::homcom::{FComProver, FComVerifier, MacProver, MacVerifier};
-use crate::{errors::Error, svole::wykw::LpnParams};
+use super::homcom::{
+    f2_to_fe, wipe, DabitProver, DabitVerifier, FComParty, FComProver, FComVerifier, MacProver,
+    MacVerifier, VolePoolConfig, VolePoolStats, WipeOnDrop,
+};
+use super::mult_check::{MultChecker, QuickSilverChecker, WolverineChecker};
+use super::observer::{
+    BucketSteps, ConvObserver, NoopObserver, PhaseStats, StatsObserver, TeeObserver,
+};
+use super::timer::Timer;
+use crate::{
+    errors::Error,
+    svole::wykw::{BaseOt, ChouOrlandiBaseOt, ExtendHooks, LpnParams, SetupReceiver, SetupSender},
+};
 use generic_array::typenum::Unsigned;
 use rand::{CryptoRng, Rng, SeedableRng};
 use scuttlebutt::{
-    field::{F40b, FiniteField, F2},
+    channel::{multiplex_with_pumps, MuxChannel, MuxPumps},
+    commitment::{HashCommitment, Opening},
+    field::{Degree, F40b, FiniteField, IsSubFieldOf, F2},
     ring::FiniteRing,
-    AbstractChannel, AesRng, Block, SyncChannel,
+    serialization::CanonicalSerialize,
+    AbstractChannel, AesRng, Block, CommBreakdown, ScopeComm, SyncChannel,
 };
-use std::io::{BufReader, BufWriter};
-use std::net::TcpStream;
-use std::time::Instant;
-use subtle::{ConditionallySelectable, ConstantTimeEq};
+use std::{sync::Arc, time::Duration};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// EdabitsProver struct
 #[derive(Clone)]
-pub struct EdabitsProver<FE: FiniteField> {
-    bits: Vec<MacProver<F40b>>,
-    value: MacProver<FE>,
+pub struct EdabitsProver<FE: FiniteField, TF: FiniteField = F40b> {
+    bits: Vec<MacProver<TF>>,
+    pub(super) value: MacProver<FE>,
+}
+
+/// EdabitsVerifier struct
+#[derive(Clone)]
+pub struct EdabitsVerifier<FE: FiniteField, TF: FiniteField = F40b> {
+    bits: Vec<MacVerifier<TF>>,
+    pub(super) value: MacVerifier<FE>,
 }
 
-fn copy_edabits_prover<FE: FiniteField>(edabits: &EdabitsProver<FE>) -> EdabitsProver<FE> {
-    let num_bits = edabits.bits.len();
-    let mut bits_par = Vec::with_capacity(num_bits);
-    for j in 0..num_bits {
-        bits_par.push(edabits.bits[j].clone());
+impl<FE: FiniteField, TF: FiniteField> EdabitsProver<FE, TF> {
+    /// Make an edabit from the commitments to its bits, least significant
+    /// first, and to its value, all made with the F_com instances of the
+    /// same `ProverConv`, see [`ProverConv::fcom`].
+    ///
+    /// The value may be any linear combination of commitments, e.g. the
+    /// sum of two values committed by different sub-protocols. The bits
+    /// are not checked against the value here: this is what `conv` checks.
+    pub fn new(bits: Vec<MacProver<TF>>, value: MacProver<FE>) -> Self {
+        EdabitsProver { bits, value }
+    }
+
+    /// The commitments to the bits, least significant first.
+    pub fn bits(&self) -> &[MacProver<TF>] {
+        &self.bits
+    }
+
+    /// The commitment to the value.
+    pub fn value(&self) -> MacProver<FE> {
+        self.value
     }
-    return EdabitsProver {
-        bits: bits_par,
-        value: edabits.value.clone(),
-    };
 }
 
-/// EdabitsVerifier struct
-#[derive(Clone)]
-pub struct EdabitsVerifier<FE: FiniteField> {
-    bits: Vec<MacVerifier<F40b>>,
-    value: MacVerifier<FE>,
+/// With the `zeroize` feature, wiping an edabit overwrites the commitment
+/// to its value and the whole buffer of its bits, which it leaves empty.
+#[cfg(feature = "zeroize")]
+impl<FE: FiniteField, TF: FiniteField> Zeroize for EdabitsProver<FE, TF> {
+    fn zeroize(&mut self) {
+        self.bits.zeroize();
+        self.value.zeroize();
+    }
 }
 
-fn copy_edabits_verifier<FE: FiniteField>(edabits: &EdabitsVerifier<FE>) -> EdabitsVerifier<FE> {
-    let num_bits = edabits.bits.len();
-    let mut bits_par = Vec::with_capacity(num_bits);
-    for j in 0..num_bits {
-        bits_par.push(edabits.bits[j].clone());
+/// Every edabit is wiped when it is dropped, the clones of an edabit too:
+/// each clone owns its bits.
+#[cfg(feature = "zeroize")]
+impl<FE: FiniteField, TF: FiniteField> Drop for EdabitsProver<FE, TF> {
+    fn drop(&mut self) {
+        self.zeroize();
     }
-    return EdabitsVerifier {
-        bits: bits_par,
-        value: edabits.value.clone(),
-    };
 }
 
-/// DabitProver struct
-#[derive(Clone)]
-struct DabitProver<FE: FiniteField> {
-    bit: MacProver<F40b>,
-    value: MacProver<FE>,
+#[cfg(feature = "zeroize")]
+impl<FE: FiniteField, TF: FiniteField> ZeroizeOnDrop for EdabitsProver<FE, TF> {}
+
+impl<FE: FiniteField, TF: FiniteField> EdabitsVerifier<FE, TF> {
+    /// Make an edabit from the commitments to its bits, least significant
+    /// first, and to its value, matching [`EdabitsProver::new`].
+    pub fn new(bits: Vec<MacVerifier<TF>>, value: MacVerifier<FE>) -> Self {
+        EdabitsVerifier { bits, value }
+    }
+
+    /// The commitments to the bits, least significant first.
+    pub fn bits(&self) -> &[MacVerifier<TF>] {
+        &self.bits
+    }
+
+    /// The commitment to the value.
+    pub fn value(&self) -> MacVerifier<FE> {
+        self.value
+    }
 }
 
-/// DabitVerifier struct
+/// An edabit checked by [`ProverConv::conv_checked`]: the commitments to its
+/// bits and to its value are known to hold the same number, so they can be
+/// used by the computations following the conversion.
+#[derive(Clone)]
+pub struct VerifiedEdabitsProver<FE: FiniteField, TF: FiniteField = F40b>(EdabitsProver<FE, TF>);
+
+/// An edabit checked by [`VerifierConv::conv_checked`], matching
+/// [`VerifiedEdabitsProver`].
 #[derive(Clone)]
-struct DabitVerifier<FE: FiniteField> {
-    bit: MacVerifier<F40b>,
-    value: MacVerifier<FE>,
+pub struct VerifiedEdabitsVerifier<FE: FiniteField, TF: FiniteField = F40b>(
+    EdabitsVerifier<FE, TF>,
+);
+
+impl<FE: FiniteField, TF: FiniteField> VerifiedEdabitsProver<FE, TF> {
+    /// The commitments to the bits, least significant first.
+    pub fn bits(&self) -> &[MacProver<TF>] {
+        &self.0.bits
+    }
+
+    /// The commitment to the value.
+    pub fn value(&self) -> MacProver<FE> {
+        self.0.value
+    }
+
+    /// The edabit, forgetting that it was checked.
+    pub fn into_edabits(self) -> EdabitsProver<FE, TF> {
+        self.0
+    }
 }
 
-const FDABIT_SECURITY_PARAMETER: usize = 38;
+impl<FE: FiniteField, TF: FiniteField> VerifiedEdabitsVerifier<FE, TF> {
+    /// The commitments to the bits, least significant first.
+    pub fn bits(&self) -> &[MacVerifier<TF>] {
+        &self.0.bits
+    }
 
-/// bit to field element
-fn f2_to_fe<FE: FiniteField>(b: F2) -> FE {
-    let choice = b.ct_eq(&F2::ZERO);
-    FE::conditional_select(&FE::ONE, &FE::ZERO, choice)
+    /// The commitment to the value.
+    pub fn value(&self) -> MacVerifier<FE> {
+        self.0.value
+    }
+
+    /// The edabit, forgetting that it was checked.
+    pub fn into_edabits(self) -> EdabitsVerifier<FE, TF> {
+        self.0
+    }
 }
 
-fn convert_bits_to_field<FE: FiniteField>(v: &[F2]) -> FE {
-    let mut res = FE::ZERO;
+/// A dabit checked by [`ProverConv::check_dabits`]: its bit is known to be
+/// committed to the same value in `TF` and in `FE`, so that it can lift a
+/// bit from `TF` to `FE` with [`ProverConv::lift_bits`].
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedDabitProver<FE: FiniteField, TF: FiniteField = F40b>(DabitProver<FE, TF>);
+
+/// A dabit checked by [`VerifierConv::check_dabits`], matching
+/// [`CheckedDabitProver`].
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedDabitVerifier<FE: FiniteField, TF: FiniteField = F40b>(DabitVerifier<FE, TF>);
+
+impl<FE: FiniteField, TF: FiniteField> CheckedDabitProver<FE, TF> {
+    /// The dabit, whose commitments can be used as any other.
+    pub fn dabit(&self) -> DabitProver<FE, TF> {
+        self.0
+    }
+}
 
-    for b in v.iter().rev() {
-        res += res; // double
-        res += f2_to_fe(*b);
+impl<FE: FiniteField, TF: FiniteField> CheckedDabitVerifier<FE, TF> {
+    /// The dabit, whose commitments can be used as any other.
+    pub fn dabit(&self) -> DabitVerifier<FE, TF> {
+        self.0
     }
-    res
 }
 
-fn convert_bits_to_field_mac<FE: FiniteField>(v: &[MacProver<F40b>]) -> FE {
-    let mut res = FE::ZERO;
+// Access to the commitments of edabits and dabits, so that the parts of the
+// protocol shared by the prover and the verifier are written only once.
+trait EdabitsShares<M> {
+    type Value: Copy;
+    fn bits(&self) -> &[M];
+    fn value(&self) -> Self::Value;
+}
 
-    for b in v.iter().rev() {
-        res += res; // double
-        res += f2_to_fe(b.0);
+impl<FE: FiniteField, TF: FiniteField> EdabitsShares<MacProver<TF>> for EdabitsProver<FE, TF> {
+    type Value = MacProver<FE>;
+    fn bits(&self) -> &[MacProver<TF>] {
+        &self.bits
+    }
+    fn value(&self) -> MacProver<FE> {
+        self.value
     }
-    res
 }
 
-fn power_two<FE: FiniteField>(m: usize) -> FE {
-    let mut res = FE::ONE;
+impl<FE: FiniteField, TF: FiniteField> EdabitsShares<MacVerifier<TF>> for EdabitsVerifier<FE, TF> {
+    type Value = MacVerifier<FE>;
+    fn bits(&self) -> &[MacVerifier<TF>] {
+        &self.bits
+    }
+    fn value(&self) -> MacVerifier<FE> {
+        self.value
+    }
+}
 
-    for _ in 0..m {
-        res += res;
+// A pair of an edabit and a commitment, as given to `check_truncation`,
+// has the bits of the edabit.
+impl<M, E: EdabitsShares<M>, V> EdabitsShares<M> for (E, V) {
+    type Value = E::Value;
+    fn bits(&self) -> &[M] {
+        self.0.bits()
+    }
+    fn value(&self) -> E::Value {
+        self.0.value()
+    }
+}
+
+trait DabitShares<B, V> {
+    fn bit(&self) -> B;
+    fn value(&self) -> V;
+}
+
+impl<FE: FiniteField, TF: FiniteField> DabitShares<MacProver<TF>, MacProver<FE>>
+    for DabitProver<FE, TF>
+{
+    fn bit(&self) -> MacProver<TF> {
+        self.bit
     }
+    fn value(&self) -> MacProver<FE> {
+        self.value
+    }
+}
 
-    res
+impl<FE: FiniteField, TF: FiniteField> DabitShares<MacVerifier<TF>, MacVerifier<FE>>
+    for DabitVerifier<FE, TF>
+{
+    fn bit(&self) -> MacVerifier<TF> {
+        self.bit
+    }
+    fn value(&self) -> MacVerifier<FE> {
+        self.value
+    }
+}
+
+const FDABIT_SECURITY_PARAMETER: usize = 38;
+
+fn convert_bits_to_field<FE: FiniteField>(v: &[F2]) -> Result<FE, Error> {
+    FE::from_le_bits(v).ok_or_else(|| {
+        Error::Other(format!(
+            "{} bits do not encode an element of the field",
+            v.len()
+        ))
+    })
+}
+
+// `2^m` in `FE`, by repeated doubling. The conversion only uses powers of
+// two below the modulus, see `check_nb_bits`.
+fn power_two<FE: FiniteField>(m: usize) -> FE {
+    debug_assert!(m < FE::bit_decomposition_len());
+    let mut x = FE::ONE;
+    for _ in 0..m {
+        x += x;
+    }
+    x
 }
 
 // Permutation pseudorandomly generated following Fisher-Yates method
@@ -141,1657 +302,7869 @@ fn check_parameters<FE: FiniteField>(n: usize, gamma: usize) -> Result<(), Error
             - 1
             - usize::try_from(x.leading_zeros()).expect("sizeof(usize) >= sizeof(u32)")
     }
-    if log2_floor(n + 1) + gamma >= FE::NumberOfBitsInBitDecomposition::USIZE - 1 {
-        Err(Error::Other(format!(
-            "Fdabit invalid parameter configuration: n={}, gamma={}, FE={}",
+    if log2_floor(n + 1) + gamma >= FE::bit_decomposition_len() - 1 {
+        Err(Error::InvalidFdabitParameters {
             n,
             gamma,
-            std::any::type_name::<FE>(),
-        )))
+            field: std::any::type_name::<FE>().to_string(),
+        })
     } else {
         Ok(())
     }
 }
 
-/// Prover for the edabits conversion protocol
-pub struct ProverConv<FE: FiniteField> {
-    fcom_f2: FComProver<F40b>,
-    fcom: FComProver<FE>,
+// The number of bits of the random values of `fdabit` on `n` dabits.
+fn fdabit_gamma(n: usize) -> usize {
+    std::mem::size_of::<usize>() * 8 - ((n + 1).leading_zeros() as usize)
 }
 
-// The Finite field is required to be a prime field because of the fdabit
-// protocol working only for prime finite fields.
-impl<FE: FiniteField<PrimeField = FE>> ProverConv<FE> {
-    /// initialize the prover
-    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        channel: &mut C,
-        rng: &mut RNG,
-        lpn_setup: LpnParams,
-        lpn_extend: LpnParams,
-    ) -> Result<Self, Error> {
-        let a = FComProver::init(channel, rng, lpn_setup, lpn_extend)?;
-        let b = FComProver::init(channel, rng, lpn_setup, lpn_extend)?;
-        Ok(Self {
-            fcom_f2: a,
-            fcom: b,
-        })
+// The largest number of dabits `fdabit` checks at once, so that
+// `check_parameters` holds in `FE`. Larger batches are checked in chunks of
+// this size: the check is sound on any subset of the dabits, and both
+// parties split them at the same boundaries.
+fn fdabit_chunk_size<FE: FiniteField>(n: usize) -> Result<usize, Error> {
+    let valid = |n: usize| check_parameters::<FE>(n, fdabit_gamma(n));
+    if valid(n).is_ok() {
+        return Ok(n);
+    }
+    valid(1)?;
+    // the largest valid size by bisection, `lo` being valid and `hi` not
+    let (mut lo, mut hi) = (1, n);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if valid(mid).is_ok() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
     }
+    Ok(lo)
+}
 
-    fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-    ) -> Result<Self, Error> {
-        Ok(Self {
-            fcom_f2: self.fcom_f2.duplicate(channel, rng)?,
-            fcom: self.fcom.duplicate(channel, rng)?,
+// The conversion adds two edabits of `nb_bits` bits in the field, so their
+// sum, which has `nb_bits + 1` bits, must stay below the modulus. A modulus
+// of `len` bits is at least `2^(len - 1)`, so this holds for any prime when
+// `nb_bits + 1 < len`: `nb_bits` may be at most 59 for `F61p` and at most
+// 125 for `F127p`. Both parties check it before communicating.
+pub(super) fn check_nb_bits<FE: FiniteField>(nb_bits: usize) -> Result<(), Error> {
+    if nb_bits == 0 || nb_bits + 1 >= FE::bit_decomposition_len() {
+        Err(Error::InvalidNbBits {
+            nb_bits,
+            field: std::any::type_name::<FE>().to_string(),
         })
+    } else {
+        Ok(())
     }
+}
 
-    fn convert_bit_2_field<C: AbstractChannel>(
-        &mut self,
-        channel: &mut C,
-        r_batch: &[DabitProver<FE>],
-        x_batch: &[MacProver<F40b>],
-        c_batch: &mut Vec<MacProver<F40b>>,
-        x_m_batch: &mut Vec<MacProver<FE>>,
-    ) -> Result<(), Error> {
-        let n = r_batch.len();
-        assert_eq!(n, x_batch.len());
-        c_batch.clear();
-        x_m_batch.clear();
-
-        for i in 0..n {
-            c_batch.push(self.fcom_f2.add(r_batch[i].bit, x_batch[i]));
+/// Version of the headers the two parties exchange to check that they run
+/// the conversion with the same parameters, when initializing it and at the
+/// start of `conv`. It changes with the content of the headers, so that a
+/// party with a different version is detected as well.
+pub const CONV_HEADER_VERSION: u64 = 2;
+
+// Send the named parameters of `header` to the other party, prefixed with
+// the version and the length of the header, and compare them to those it
+// sends. This fails with `Error::ConvParamsMismatch` on the first which
+// differs, once the whole header of the other party is read, so that the
+// parties stay in sync. A header more than twice as long as ours is rejected
+// before it is read, so that the other party cannot make us read an
+// unbounded number of values.
+fn exchange_header<C: AbstractChannel>(
+    channel: &mut C,
+    header: &[(&str, u64)],
+) -> Result<(), Error> {
+    channel.write_u64(CONV_HEADER_VERSION)?;
+    channel.write_u64(header.len() as u64)?;
+    for (_, value) in header {
+        channel.write_u64(*value)?;
+    }
+    channel.flush()?;
+    let version = channel.read_u64()?;
+    let len = channel.read_u64()?;
+    let mismatch = |param: &str, local: u64, remote: u64| {
+        Err(Error::ConvParamsMismatch {
+            param: param.to_string(),
+            local,
+            remote,
+        })
+    };
+    if len > 2 * header.len() as u64 {
+        return mismatch("header_len", header.len() as u64, len);
+    }
+    let mut remote = Vec::with_capacity(header.len());
+    for _ in 0..len {
+        remote.push(channel.read_u64()?);
+    }
+    if version != CONV_HEADER_VERSION {
+        return mismatch("header_version", CONV_HEADER_VERSION, version);
+    }
+    if len != header.len() as u64 {
+        return mismatch("header_len", header.len() as u64, len);
+    }
+    for ((param, local), remote) in header.iter().zip(remote) {
+        if *local != remote {
+            return mismatch(param, *local, remote);
         }
-        self.fcom_f2.open(channel, &c_batch)?;
+    }
+    Ok(())
+}
+
+// A fingerprint of the modulus of `FE`, so that two fields with as many
+// bits, e.g. two `DynPrimeField`s, are told apart: the first bytes of the
+// hash of `-1`, which is `p - 1` in a prime field, and of the coefficients of
+// the polynomial modulus.
+fn modulus_fingerprint<FE: FiniteField>() -> u64 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&(FE::ZERO - FE::ONE).to_bytes());
+    let modulus = FE::polynomial_modulus();
+    hasher.update(&modulus.constant.to_bytes());
+    for coefficient in modulus.coefficients.iter() {
+        hasher.update(&coefficient.to_bytes());
+    }
+    let mut fingerprint = [0u8; 8];
+    fingerprint.copy_from_slice(&hasher.finalize().as_bytes()[..8]);
+    u64::from_le_bytes(fingerprint)
+}
 
-        for i in 0..n {
-            let MacProver(c, _) = c_batch[i];
+// The header of the initialization of a converter from `TF` to `FE`, whose
+// F_com instances over `TF` and `FE` use the setup and extend parameters
+// `lpn_tag` and `lpn` respectively.
+fn init_header<FE: FiniteField, TF: FiniteField>(
+    lpn_tag: (LpnParams, LpnParams),
+    lpn: (LpnParams, LpnParams),
+) -> [(&'static str, u64); 16] {
+    let (tag_setup, tag_extend) = (lpn_tag.0.dimensions(), lpn_tag.1.dimensions());
+    let (setup, extend) = (lpn.0.dimensions(), lpn.1.dimensions());
+    [
+        ("field_bits", FE::bit_decomposition_len() as u64),
+        ("tag_field_bits", TF::bit_decomposition_len() as u64),
+        ("field_modulus", modulus_fingerprint::<FE>()),
+        ("tag_field_modulus", modulus_fingerprint::<TF>()),
+        ("tag_lpn_setup_rows", tag_setup.0 as u64),
+        ("tag_lpn_setup_cols", tag_setup.1 as u64),
+        ("tag_lpn_setup_weight", tag_setup.2 as u64),
+        ("tag_lpn_extend_rows", tag_extend.0 as u64),
+        ("tag_lpn_extend_cols", tag_extend.1 as u64),
+        ("tag_lpn_extend_weight", tag_extend.2 as u64),
+        ("lpn_setup_rows", setup.0 as u64),
+        ("lpn_setup_cols", setup.1 as u64),
+        ("lpn_setup_weight", setup.2 as u64),
+        ("lpn_extend_rows", extend.0 as u64),
+        ("lpn_extend_cols", extend.1 as u64),
+        ("lpn_extend_weight", extend.2 as u64),
+    ]
+}
 
-            let c_m = f2_to_fe::<FE::PrimeField>(c);
+// The header of `conv` on `num` edabits of `nb_bits` bits with `config`,
+// and a custom multiplication check with `custom_checker`. The threads
+// checking the buckets are not compared, since each party picks its own.
+fn conv_header(
+    version: ProtocolVersion,
+    config: &ConvConfig,
+    num: usize,
+    nb_bits: usize,
+    custom_checker: bool,
+) -> [(&'static str, u64); 7] {
+    let mult_check = match (custom_checker, config.strategy) {
+        (true, _) => 3,
+        (false, MultCheck::QuickSilver) => 0,
+        (false, MultCheck::Wolverine) => 1,
+        (false, MultCheck::Auto) => 2,
+    };
+    let version = match version {
+        ProtocolVersion::V1 => 1,
+        ProtocolVersion::V2 => 2,
+    };
+    [
+        ("protocol_version", version),
+        ("nb_bits", nb_bits as u64),
+        ("num", num as u64),
+        ("num_bucket", config.num_bucket as u64),
+        ("num_cut", config.num_cut as u64),
+        ("mult_check", mult_check),
+        ("triple_bucket", config.triple_bucket as u64),
+    ]
+}
 
-            let choice = c.ct_eq(&F2::ONE);
-            let beq = self
-                .fcom
-                .affine_add_cst(c_m, self.fcom.neg(r_batch[i].value));
-            let bneq = self.fcom.affine_add_cst(c_m, r_batch[i].value);
-            let x_m = MacProver::conditional_select(&bneq, &beq, choice);
+// Exchange the header of `conv` as `exchange_header`, attributing its
+// communication to `CONV_SCOPE_HEADER` rather than to the phase running
+// when it is sent. The scope of `channel` is restored afterwards, also when
+// the exchange fails.
+fn exchange_conv_header<C: AbstractChannel>(
+    channel: &mut C,
+    header: &[(&str, u64)],
+) -> Result<(), Error> {
+    let outer = channel.scope(CONV_SCOPE_HEADER);
+    let res = exchange_header(channel, header);
+    if let Some(outer) = outer {
+        channel.scope(outer);
+    }
+    res
+}
 
-            x_m_batch.push(x_m);
+// The number of bits of the edabits of `edabits_vector`, which must not be
+// empty and must all have the same number of bits. Both parties check it
+// before communicating, so that they reject the same batches.
+fn batch_nb_bits<M, E: EdabitsShares<M>>(edabits_vector: &[E]) -> Result<usize, Error> {
+    let nb_bits = match edabits_vector.first() {
+        Some(e) => e.bits().len(),
+        None => {
+            return Err(Error::Other(
+                "Conv invalid input: no edabits to convert".to_string(),
+            ))
         }
+    };
+    match edabits_vector
+        .iter()
+        .position(|e| e.bits().len() != nb_bits)
+    {
+        Some(i) => Err(Error::EdabitWidthMismatch {
+            index: i,
+            nb_bits: edabits_vector[i].bits().len(),
+            expected: nb_bits,
+        }),
+        None => Ok(nb_bits),
+    }
+}
 
-        assert_eq!(n, x_m_batch.len());
-        Ok(())
+// Check, before communicating, that the edabits `edabits` over `FE` can be
+// checked against `num_values` commitments over `FE2` by `convert_between`:
+// the converters share the MAC key of the bits, there is one commitment per
+// edabit, and the bits fit both fields.
+fn check_cross_field<FE: FiniteField, FE2: FiniteField, M, E: EdabitsShares<M>>(
+    shares_key: bool,
+    edabits: &[E],
+    num_values: usize,
+) -> Result<(), Error> {
+    if !shares_key {
+        return Err(Error::Other(
+            "convert_between: the converters do not share the MAC key of the bits".to_string(),
+        ));
     }
+    if edabits.len() != num_values {
+        return Err(Error::BatchLengthMismatch {
+            left: edabits.len(),
+            right: num_values,
+        });
+    }
+    let nb_bits = batch_nb_bits(edabits)?;
+    check_nb_bits::<FE>(nb_bits)?;
+    check_nb_bits::<FE2>(nb_bits)
+}
 
-    // This function applies the bit_add_carry to a batch of bits,
-    // contrary to the one in the paper that applies it on a pair of
-    // bits. This allows to the keep the rounds of communication equal
-    // to m for any vector of additions
-    fn bit_add_carry<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        x_batch: &[EdabitsProver<FE>],
-        y_batch: &[EdabitsProver<FE>],
-        random_triples: &[(MacProver<F40b>, MacProver<F40b>, MacProver<F40b>)],
-    ) -> Result<Vec<(Vec<MacProver<F40b>>, MacProver<F40b>)>, Error> {
-        let num = x_batch.len();
-        if num != y_batch.len() {
-            return Err(Error::Other(
-                "incompatible input vectors in bit_add_carry".to_string(),
-            ));
+// Run `conv_chunk` on the chunks of `chunk_size` edabits of `edabits` in
+// order, and return their statistics. A last chunk shorter than
+// `chunk_size` is merged into the one before, so that every chunk but a
+// single one has at least `chunk_size` edabits, and at most
+// `2 * chunk_size` edabits are held at once. The chunks only depend on
+// `chunk_size` and on the number of edabits.
+fn conv_chunks<E>(
+    edabits: impl IntoIterator<Item = E>,
+    chunk_size: usize,
+    mut conv_chunk: impl FnMut(&[E]) -> Result<ConvStats, Error>,
+) -> Result<Vec<ConvStats>, Error> {
+    if chunk_size == 0 {
+        return Err(Error::Other(
+            "Conv invalid parameter configuration: chunk_size=0".to_string(),
+        ));
+    }
+    let mut edabits = edabits.into_iter();
+    let mut chunk = Vec::with_capacity(2 * chunk_size);
+    let mut stats = Vec::new();
+    loop {
+        chunk.extend(edabits.by_ref().take(2 * chunk_size - chunk.len()));
+        if chunk.len() < 2 * chunk_size {
+            // the rest of the edabits
+            if !chunk.is_empty() {
+                stats.push(conv_chunk(&chunk)?);
+            }
+            return Ok(stats);
         }
+        stats.push(conv_chunk(&chunk[..chunk_size])?);
+        chunk.drain(..chunk_size);
+    }
+}
 
-        let m = x_batch[0].bits.len();
+/// Bucket sizes [`select_conv_params`] selects from, those analysed by the
+/// edabits paper. Larger buckets cost more than converting more edabits at
+/// once.
+pub const SELECTED_NUM_BUCKETS: std::ops::RangeInclusive<usize> = 3..=5;
+
+/// Select the number of buckets and of cut edabits of `conv` on
+/// `num_edabits` edabits for `security_bits` bits of statistical security.
+///
+/// With `B` edabits per bucket and `C = B` cut edabits, a prover converting
+/// `N` inconsistent edabits succeeds with probability at most
+/// `1 / binom(N * B, B)`, following the analysis of the cut-and-choose of
+/// the edabits paper. The smallest `B` of [`SELECTED_NUM_BUCKETS`] reaching
+/// the security is selected, e.g. `B = 5` for a few hundred edabits down to
+/// `B = 3` from ten thousand edabits for 40 bits. This fails when even the
+/// largest bucket size does not reach the security, since only converting
+/// more edabits at once can.
+pub fn select_conv_params(
+    num_edabits: usize,
+    security_bits: usize,
+) -> Result<(usize, usize), Error> {
+    SELECTED_NUM_BUCKETS
+        .into_iter()
+        .find(|num_bucket| bucket_security_bits(num_edabits, *num_bucket) >= security_bits as f64)
+        .map(|num_bucket| (num_bucket, num_bucket))
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "Conv cannot reach {} bits of security with {} edabits, convert more at once",
+                security_bits, num_edabits,
+            ))
+        })
+}
 
-        // input c0
-        let mut ci_batch = vec![F2::ZERO; num];
-        let mut ci_mac_batch = self.fcom_f2.input(channel, rng, &ci_batch)?;
-
-        // loop on the m bits over the batch of n addition
-        let mut triples = Vec::with_capacity(num * m);
-        let mut aux_batch = Vec::with_capacity(num);
-        let mut and_res_batch = Vec::with_capacity(num);
-        let mut z_batch = vec![Vec::with_capacity(m); num];
-        let mut and_res_mac_batch = Vec::with_capacity(num);
-        for i in 0..m {
-            and_res_batch.clear();
-            aux_batch.clear();
-            for n in 0..num {
-                let ci_clr = ci_batch[n];
-                let ci_mac = ci_mac_batch[n];
+// log2 of binom(N * B, B) for `num` items in buckets of `bucket`, computed
+// term by term: the inverse of the probability of a whole bucket of bad
+// items.
+fn bucket_security_bits(num: usize, bucket: usize) -> f64 {
+    let total = num.saturating_mul(bucket) as f64;
+    (0..bucket)
+        .map(|i| (total - i as f64).log2() - ((i + 1) as f64).log2())
+        .sum()
+}
+
+/// Bucket sizes of the random triples [`select_triple_bucket`] selects from.
+pub const SELECTED_TRIPLE_BUCKETS: std::ops::RangeInclusive<usize> = 1..=5;
+
+/// Select the number of random triples each multiplication of the Wolverine
+/// check of `conv` is checked against, on `num_edabits` edabits of
+/// `nb_bits` bits with `num_bucket` buckets, for `security_bits` bits of
+/// statistical security.
+///
+/// Each bucket checks `nb_bits` multiplications per edabit. As for the
+/// edabits, see [`select_conv_params`], a prover with bad random triples
+/// succeeds when one of the `M` multiplications is only checked against bad
+/// ones, with probability at most `1 / binom(M * T, T)` for `T` random
+/// triples per multiplication. The smallest `T` of
+/// [`SELECTED_TRIPLE_BUCKETS`] reaching the security is selected: for 40
+/// bits, `T = 3` from about ten thousand multiplications and `T = 2` from
+/// about a million, while `T = 1` never suffices.
+pub fn select_triple_bucket(
+    num_edabits: usize,
+    nb_bits: usize,
+    num_bucket: usize,
+    security_bits: usize,
+) -> Result<usize, Error> {
+    let num_mults = num_edabits
+        .saturating_mul(nb_bits)
+        .saturating_mul(num_bucket);
+    SELECTED_TRIPLE_BUCKETS
+        .into_iter()
+        .find(|triple_bucket| {
+            bucket_security_bits(num_mults, *triple_bucket) >= security_bits as f64
+        })
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "Conv cannot reach {} bits of security with {} multiplications",
+                security_bits, num_mults,
+            ))
+        })
+}
+
+// The channels over which the threads check the buckets.
+enum BucketChannels<C2> {
+    // One channel per thread, given by the caller.
+    Separate(Vec<C2>),
+    // One virtual channel per thread, multiplexed over the main channel by
+    // the function once the parties of the threads have been duplicated
+    // over it.
+    Multiplexed(usize, Box<dyn FnOnce() -> (Vec<C2>, MuxPumps)>),
+}
+
+impl<C2> BucketChannels<C2> {
+    fn len(&self) -> usize {
+        match self {
+            BucketChannels::Separate(channels) => channels.len(),
+            BucketChannels::Multiplexed(threads, _) => *threads,
+        }
+    }
+}
+
+impl BucketChannels<MuxChannel> {
+    // `threads` virtual channels over a clone of `channel`.
+    fn multiplexed<C: AbstractChannel + Send + 'static>(channel: &C, threads: usize) -> Self {
+        let channel = channel.clone();
+        BucketChannels::Multiplexed(
+            threads,
+            Box::new(move || multiplex_with_pumps(channel, threads)),
+        )
+    }
+}
 
-                let ci = MacProver(ci_clr, ci_mac);
+// The buckets are checked over the main channel with a single thread, and
+// over one bucket channel per thread otherwise. Without the `threads`
+// feature, they can only be checked over the main channel.
+fn check_bucket_channels<C2>(
+    config: &ConvConfig,
+    bucket_channels: &Option<BucketChannels<C2>>,
+) -> Result<(), Error> {
+    let nb_channels = bucket_channels.as_ref().map_or(1, |chs| chs.len());
+    if cfg!(not(feature = "threads")) && bucket_channels.is_some() {
+        Err(Error::Other(
+            "checking the buckets over bucket channels requires the `threads` feature".to_string(),
+        ))
+    } else if nb_channels != config.threads {
+        Err(Error::Other(format!(
+            "Conv invalid bucket channels: {} channels for threads={}",
+            nb_channels, config.threads,
+        )))
+    } else {
+        Ok(())
+    }
+}
 
-                let x = &x_batch[n].bits;
-                let y = &y_batch[n].bits;
+// The edabits made of the bits of the edabit of each of `pairs` from
+// `shift` on, with the commitment of the pair as value, which `conv` checks
+// to show that the commitment is the edabit shifted right by `shift` bits.
+fn truncated_edabits<M: Copy, V: Copy, E: EdabitsShares<M>, T>(
+    pairs: &[(E, V)],
+    shift: usize,
+    new: impl Fn(Vec<M>, V) -> T,
+) -> Result<Vec<T>, Error> {
+    let nb_bits = batch_nb_bits(pairs)?;
+    if shift >= nb_bits {
+        return Err(Error::Other(format!(
+            "Conv invalid truncation: shift={} for edabits of {} bits",
+            shift, nb_bits,
+        )));
+    }
+    Ok(pairs
+        .iter()
+        .map(|(x, y)| new(x.bits()[shift..].to_vec(), *y))
+        .collect())
+}
 
-                debug_assert_eq!(x.len(), m);
-                debug_assert_eq!(y.len(), m);
+// This function applies the bit_add_carry to a batch of bits,
+// contrary to the one in the paper that applies it on a pair of
+// bits. This allows to the keep the rounds of communication equal
+// to m for any vector of additions.
+// The multiplications are checked with `checker`. All the edabits must have
+// the same number of bits, and an empty batch is added without
+// communicating. The additions start from the carries `carry_in`, one per
+// addition, and from carries input as zero without them. With
+// `flip_carry = Some((n, i))`, the prover commits to the wrong product at
+// bit `i` of the `n`-th addition, see `ConvFault::Carry`.
+#[allow(clippy::too_many_arguments)]
+fn bit_add_carry<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    E: EdabitsShares<P::Mac>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+    K: MultChecker<TF, P>,
+>(
+    fcom_f2: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    x_batch: &[E],
+    y_batch: &[E],
+    carry_in: Option<&[P::Mac]>,
+    checker: &mut K,
+    flip_carry: Option<(usize, usize)>,
+) -> Result<Vec<(Vec<P::Mac>, P::Mac)>, Error> {
+    let sums = add_bits(
+        fcom_f2, channel, rng, x_batch, y_batch, carry_in, flip_carry,
+    )?;
+    if sums.is_empty() {
+        return Ok(sums);
+    }
+    let mut sum_bits: WipeOnDrop<P::Mac> =
+        WipeOnDrop::new(Vec::with_capacity(sums.len() * x_batch[0].bits().len()));
+    let mut carries = Vec::with_capacity(sums.len());
+    for (bits, carry) in sums.iter() {
+        sum_bits.extend_from_slice(bits);
+        carries.push(*carry);
+    }
 
-                let xi = x[i];
-                let yi = y[i];
+    // check all the multiplications in one batch
+    channel.flush()?;
+    checker.start(fcom_f2, channel, rng)?;
+    push_add_triples(fcom_f2, x_batch, y_batch, &sum_bits, &carries, checker);
+    checker.finalize(fcom_f2, channel, rng)?;
+    Ok(sums)
+}
 
-                let and1 = self.fcom_f2.add(xi, ci);
-                let MacProver(and1_clr, _) = and1;
-                let and2 = self.fcom_f2.add(yi, ci);
+// The additions of `bit_add_carry`, whose multiplications are left to check,
+// see `push_add_triples`.
+fn add_bits<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    E: EdabitsShares<P::Mac>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    fcom_f2: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    x_batch: &[E],
+    y_batch: &[E],
+    carry_in: Option<&[P::Mac]>,
+    flip_carry: Option<(usize, usize)>,
+) -> Result<Vec<(Vec<P::Mac>, P::Mac)>, Error> {
+    let num = x_batch.len();
+    if num != y_batch.len() {
+        return Err(Error::BatchLengthMismatch {
+            left: num,
+            right: y_batch.len(),
+        });
+    }
+    if let Some(carry_in) = carry_in {
+        if num != carry_in.len() {
+            return Err(Error::BatchLengthMismatch {
+                left: num,
+                right: carry_in.len(),
+            });
+        }
+    }
 
-                let and_res = and1_clr * and2.0;
+    if num == 0 {
+        return Ok(Vec::new());
+    }
+    let m = batch_nb_bits(x_batch)?;
+    if let Some(i) = y_batch.iter().position(|e| e.bits().len() != m) {
+        return Err(Error::EdabitWidthMismatch {
+            index: i,
+            nb_bits: y_batch[i].bits().len(),
+            expected: m,
+        });
+    }
 
-                let c = ci_clr + and_res;
-                // let c_mac = ci_mac + and_res_mac; // is done in the next step
-                ci_batch[n] = c;
+    let mut ci_batch = Vec::with_capacity(num);
+    match carry_in {
+        Some(carry_in) => ci_batch.extend_from_slice(carry_in),
+        // input c0
+        None => fcom_f2.input_into(channel, rng, num, &vec![F2::ZERO; num], &mut ci_batch)?,
+    }
 
-                let z = self.fcom_f2.add(and1, yi); // xi + yi + ci ;
-                z_batch[n].push(z);
+    // loop on the m bits over the batch of n addition
+    let mut and_res_batch: WipeOnDrop<F2> = WipeOnDrop::new(Vec::with_capacity(num));
+    let mut z_batch = vec![Vec::with_capacity(m); num];
+    let mut and_res_mac_batch: WipeOnDrop<P::Mac> = WipeOnDrop::new(Vec::with_capacity(num));
+    for i in 0..m {
+        and_res_batch.clear();
+        for n in 0..num {
+            let ci = ci_batch[n];
 
-                and_res_batch.push(and_res);
-                aux_batch.push((and1, and2));
-            }
-            and_res_mac_batch.clear();
-            self.fcom_f2
-                .input_low_level(channel, rng, &and_res_batch, &mut and_res_mac_batch)?;
+            let x = x_batch[n].bits();
+            let y = y_batch[n].bits();
 
-            for n in 0..num {
-                let (and1, and2) = aux_batch[n];
-                let and_res = and_res_batch[n];
-                let and_res_mac = and_res_mac_batch[n];
-                triples.push((and1, and2, MacProver(and_res, and_res_mac)));
+            let xi = x[i];
+            let yi = y[i];
 
-                let ci_mac = ci_mac_batch[n];
-                let c_mac = ci_mac + and_res_mac;
+            let and1 = fcom_f2.add(xi, ci);
+            let and2 = fcom_f2.add(yi, ci);
 
-                ci_mac_batch[n] = c_mac;
+            // only the prover knows the result of the and
+            if let (Some(and1_clr), Some(and2_clr)) = (P::value(&and1), P::value(&and2)) {
+                let flip = if flip_carry == Some((n, i)) {
+                    F2::ONE
+                } else {
+                    F2::ZERO
+                };
+                and_res_batch.push(and1_clr * and2_clr + flip);
             }
+
+            let z = fcom_f2.add(and1, yi); // xi + yi + ci ;
+            z_batch[n].push(z);
         }
+        and_res_mac_batch.clear();
+        fcom_f2.input_into(channel, rng, num, &and_res_batch, &mut and_res_mac_batch)?;
 
-        // check all the multiplications in one batch
-        channel.flush()?;
-        if random_triples.len() == 0 {
-            self.fcom_f2
-                .quicksilver_check_multiply(channel, rng, &triples)?;
-        } else {
-            self.fcom_f2
-                .wolverine_check_multiply(channel, &triples, &random_triples)?;
+        for n in 0..num {
+            ci_batch[n] = fcom_f2.add(ci_batch[n], and_res_mac_batch[n]);
         }
+    }
 
-        // reconstruct the solution
-        let mut res = Vec::with_capacity(num);
+    // reconstruct the solution
+    Ok(z_batch.into_iter().zip(ci_batch).collect())
+}
 
-        let mut i = 0;
-        for zs in z_batch.into_iter() {
-            res.push((zs, MacProver(ci_batch[i], ci_mac_batch[i])));
-            i += 1;
+// Push to `checker` the multiplications of the additions of `x_batch` and
+// `y_batch` by `add_bits`, recomputed from the bits of their sums
+// `sum_bits`, one addition after the other, and from their carries
+// `carries`, rather than stored during the additions.
+fn push_add_triples<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    E: EdabitsShares<P::Mac>,
+    K: MultChecker<TF, P>,
+>(
+    fcom_f2: &P,
+    x_batch: &[E],
+    y_batch: &[E],
+    sum_bits: &[P::Mac],
+    carries: &[P::Mac],
+    checker: &mut K,
+) {
+    let m = match x_batch.first() {
+        Some(x) => x.bits().len(),
+        None => return,
+    };
+    // carry `ci` of the n-th addition, recomputed from `z = xi + yi + ci`
+    let carry = |n: usize, i: usize| {
+        if i < m {
+            let xi_plus_ci = fcom_f2.sub(sum_bits[n * m + i], y_batch[n].bits()[i]);
+            fcom_f2.sub(xi_plus_ci, x_batch[n].bits()[i])
+        } else {
+            carries[n]
         }
-
-        Ok(res)
+    };
+    let mut triples = WipeOnDrop::new(Vec::with_capacity(m));
+    for n in 0..x_batch.len() {
+        triples.clear();
+        let mut ci = carry(n, 0);
+        for i in 0..m {
+            let c_next = carry(n, i + 1);
+            let and1 = fcom_f2.add(x_batch[n].bits()[i], ci);
+            let and2 = fcom_f2.add(y_batch[n].bits()[i], ci);
+            let and_res = fcom_f2.sub(c_next, ci);
+            triples.push((and1, and2, and_res));
+            ci = c_next;
+        }
+        checker.push_triples(&triples);
     }
+}
 
-    /// generate random edabits
-    pub fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        nb_bits: usize,
-        num: usize, // in the paper: NB + C
-    ) -> Result<Vec<EdabitsProver<FE>>, Error> {
-        let mut edabits_vec = Vec::with_capacity(num);
+// The bits of an edabit without its value, to add bits computed from the
+// bits of edabits, or given by the caller, with `bit_add_carry`.
+impl<M> EdabitsShares<M> for Vec<M> {
+    type Value = ();
+    fn bits(&self) -> &[M] {
+        self
+    }
+    fn value(&self) {}
+}
 
-        let mut aux_bits = Vec::with_capacity(num);
-        let mut aux_r_m = Vec::with_capacity(num);
-        for _ in 0..num {
-            let mut bits = Vec::with_capacity(nb_bits);
-            for _ in 0..nb_bits {
-                bits.push(self.fcom_f2.random(channel, rng)?);
+// The additions of the public `bit_add_carry` of the bit vectors `x_batch`
+// and `y_batch` from the carries `carry_in`. The multiplications are checked
+// with QuickSilver, or with the random triples `triples` as in Wolverine,
+// one per bit of each addition.
+fn add_bit_vectors<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    fcom_f2: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    x_batch: &[Vec<P::Mac>],
+    y_batch: &[Vec<P::Mac>],
+    carry_in: &[P::Mac],
+    triples: Option<&[(P::Mac, P::Mac, P::Mac)]>,
+) -> Result<Vec<(Vec<P::Mac>, P::Mac)>, Error> {
+    let carry_in = Some(carry_in);
+    match triples {
+        None => {
+            let mut checker = QuickSilverChecker::new();
+            bit_add_carry(
+                fcom_f2,
+                channel,
+                rng,
+                x_batch,
+                y_batch,
+                carry_in,
+                &mut checker,
+                None,
+            )
+        }
+        Some(triples) => {
+            // checked before communicating, rather than by the checker
+            let num_mults = x_batch.first().map_or(0, |x| x_batch.len() * x.len());
+            if triples.len() < num_mults {
+                return Err(Error::Other(format!(
+                    "bit_add_carry of {} multiplications with {} random triples",
+                    num_mults,
+                    triples.len()
+                )));
             }
-            let r_m: FE::PrimeField = convert_bits_to_field::<FE::PrimeField>(
-                bits.iter().map(|x| x.0).collect::<Vec<F2>>().as_slice(),
-            );
-            aux_bits.push(bits);
-            aux_r_m.push(r_m);
+            let mut checker = WolverineChecker::new(triples);
+            bit_add_carry(
+                fcom_f2,
+                channel,
+                rng,
+                x_batch,
+                y_batch,
+                carry_in,
+                &mut checker,
+                None,
+            )
         }
+    }
+}
 
-        let aux_r_m_mac: Vec<FE> = self.fcom.input(channel, rng, &aux_r_m)?;
+// The subtractions `x - y - b` of the public `bit_sub_borrow` of the bit
+// vectors `x_batch` and `y_batch` with the borrows `b` of `borrow_in`. With
+// the complement `!y = 2^m - 1 - y` of `y` on `m` bits, `x + !y + (1 - b)`
+// is `2^m + x - y - b`, which carries exactly when there is no borrow. The
+// complements are affine, so they are computed without communicating.
+fn sub_bit_vectors<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    fcom_f2: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    x_batch: &[Vec<P::Mac>],
+    y_batch: &[Vec<P::Mac>],
+    borrow_in: &[P::Mac],
+    triples: Option<&[(P::Mac, P::Mac, P::Mac)]>,
+) -> Result<Vec<(Vec<P::Mac>, P::Mac)>, Error> {
+    let not_y_batch: Vec<Vec<_>> = y_batch
+        .iter()
+        .map(|y| {
+            y.iter()
+                .map(|yi| fcom_f2.affine_add_cst(F2::ONE, *yi))
+                .collect()
+        })
+        .collect();
+    let carry_in: Vec<_> = borrow_in
+        .iter()
+        .map(|b| fcom_f2.affine_add_cst(F2::ONE, *b))
+        .collect();
+    let sums = add_bit_vectors(
+        fcom_f2,
+        channel,
+        rng,
+        x_batch,
+        &not_y_batch,
+        &carry_in,
+        triples,
+    )?;
+    Ok(sums
+        .into_iter()
+        .map(|(bits, carry)| (bits, fcom_f2.affine_add_cst(F2::ONE, carry)))
+        .collect())
+}
 
-        let mut i = 0;
-        for aux_bits in aux_bits.into_iter() {
-            edabits_vec.push(EdabitsProver {
-                bits: aux_bits,
-                value: MacProver(aux_r_m[i], aux_r_m_mac[i]),
+// The commitments to the bits `x < y` of the pairs of edabits of `x_batch`
+// and `y_batch`. With the complement `!x = 2^m - 1 - x` of `x` on `m` bits,
+// `y + !x` carries exactly when `y > x`, so the comparison bit is the carry
+// of `bit_add_carry` on `y` and `!x`. The complement is affine, so it is
+// computed without communicating.
+fn less_than<
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<TF>,
+    E: EdabitsShares<P::Mac>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    fcom_f2: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    x_batch: &[E],
+    y_batch: &[E],
+) -> Result<Vec<P::Mac>, Error> {
+    if x_batch.len() != y_batch.len() {
+        return Err(Error::BatchLengthMismatch {
+            left: x_batch.len(),
+            right: y_batch.len(),
+        });
+    }
+    let not_x_batch: Vec<_> = x_batch
+        .iter()
+        .map(|x| {
+            x.bits()
+                .iter()
+                .map(|xi| fcom_f2.affine_add_cst(F2::ONE, *xi))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let y_batch: Vec<_> = y_batch.iter().map(|y| y.bits().to_vec()).collect();
+    let mut checker = QuickSilverChecker::new();
+    let sums = bit_add_carry(
+        fcom_f2,
+        channel,
+        rng,
+        &y_batch,
+        &not_x_batch,
+        None,
+        &mut checker,
+        None,
+    )?;
+    Ok(sums.into_iter().map(|(_, carry)| carry).collect())
+}
+
+// Check that the bits of the dabits committed in `TF` and in `FE` are
+// equal. The multiplications over `FE` are checked with `checker`. The
+// prover also asserts that its dabits are consistent in debug builds, unless
+// they are `faulty` on purpose, see `ConvFault::Dabit`.
+fn fdabit<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: FComParty<FE>,
+    Q: FComParty<TF>,
+    D: DabitShares<Q::Mac, P::Mac>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+    K: MultChecker<FE, P>,
+>(
+    fcom: &mut P,
+    fcom_f2: &mut Q,
+    channel: &mut C,
+    rng: &mut RNG,
+    dabits: &[D],
+    checker: &mut K,
+    faulty: bool,
+) -> Result<(), Error> {
+    let s = FDABIT_SECURITY_PARAMETER;
+    let n = dabits.len();
+
+    let gamma = fdabit_gamma(n);
+
+    check_parameters::<FE>(n, gamma)?;
+
+    let mut res = true;
+
+    if !faulty {
+        for dabit in dabits.iter() {
+            // making sure the faulty dabits are not faulty
+            if let (Some(bit), Some(value)) = (Q::value(&dabit.bit()), P::value(&dabit.value())) {
+                debug_assert!(f2_to_fe::<FE>(bit) == value);
+            }
+        }
+    }
+
+    // step 1)
+    let mut c_m: Vec<WipeOnDrop<FE>> = (0..s).map(|_| WipeOnDrop::new(Vec::new())).collect();
+    let mut c1: WipeOnDrop<F2> = WipeOnDrop::new(Vec::with_capacity(s));
+    if P::IS_PROVER {
+        for c_m_k in c_m.iter_mut() {
+            for _ in 0..gamma {
+                let b: F2 = F2::random(rng);
+                c_m_k.push(f2_to_fe(b));
+            }
+            c1.push(if c_m_k[0] == FE::ZERO {
+                F2::ZERO
+            } else {
+                F2::ONE
             });
-            i += 1;
         }
-        Ok(edabits_vec)
     }
 
-    fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        num: usize,
-    ) -> Result<Vec<DabitProver<FE>>, Error> {
-        let mut dabit_vec = Vec::with_capacity(num);
-        let mut b_batch = Vec::with_capacity(num);
-        let mut b_m_batch = Vec::with_capacity(num);
+    let mut c_m_mac: Vec<WipeOnDrop<P::Mac>> = Vec::with_capacity(s);
+    for c_m_k in c_m.iter() {
+        let mut b_m_mac: WipeOnDrop<P::Mac> = WipeOnDrop::new(Vec::with_capacity(gamma));
+        fcom.input_into(channel, rng, gamma, c_m_k, &mut b_m_mac)?;
+        c_m_mac.push(b_m_mac);
+    }
 
-        for _ in 0..num {
-            let b = self.fcom_f2.random(channel, rng)?;
-            b_batch.push(b);
-            let b_m = f2_to_fe(b.0);
-            b_m_batch.push(b_m);
+    let mut c1_mac: WipeOnDrop<Q::Mac> = WipeOnDrop::new(Vec::with_capacity(s));
+    fcom_f2.input_into(channel, rng, s, &c1, &mut c1_mac)?;
+
+    // step 2)
+    let ci_batch: WipeOnDrop<P::Mac> =
+        WipeOnDrop::new(c_m_mac.iter().flat_map(|c| c.iter()).copied().collect());
+    let one_minus_ci_batch: WipeOnDrop<P::Mac> = WipeOnDrop::new(
+        ci_batch
+            .iter()
+            .map(|ci| fcom.affine_add_cst(FE::ONE, fcom.neg(*ci)))
+            .collect(),
+    );
+    let and_res_batch: WipeOnDrop<FE> = WipeOnDrop::new(
+        ci_batch
+            .iter()
+            .zip(one_minus_ci_batch.iter())
+            .filter_map(|(ci, one_minus_ci)| Some(P::value(ci)? * P::value(one_minus_ci)?))
+            .collect(),
+    );
+    let mut and_res_mac_batch: WipeOnDrop<P::Mac> = WipeOnDrop::new(Vec::with_capacity(gamma * s));
+    fcom.input_into(
+        channel,
+        rng,
+        gamma * s,
+        &and_res_batch,
+        &mut and_res_mac_batch,
+    )?;
+
+    // step 3)
+    let seed = fcom.challenge(channel, rng)?;
+    let mut e_rng = AesRng::from_seed(seed);
+    let e: Vec<Vec<F2>> = (0..s)
+        .map(|_| (0..n).map(|_| F2::random(&mut e_rng)).collect())
+        .collect();
+
+    // step 4)
+    let mut r_mac_batch = Vec::with_capacity(s);
+    for k in 0..s {
+        let mut r = c1_mac[k];
+        for (e_ki, dabit) in e[k].iter().zip(dabits.iter()) {
+            // TODO: do not need to do it when e[i] is ZERO
+            let tmp = fcom_f2.affine_mult_cst(*e_ki, dabit.bit());
+            r = fcom_f2.add(r, tmp);
         }
+        r_mac_batch.push(r);
+    }
 
-        let b_m_mac_batch = self.fcom.input(channel, rng, &b_m_batch)?;
-
-        for i in 0..num {
-            dabit_vec.push(DabitProver {
-                bit: b_batch[i],
-                value: MacProver(b_m_batch[i], b_m_mac_batch[i]),
-            });
+    // step 5) TODO: move this to the end
+    let r_batch = fcom_f2.open(channel, &r_mac_batch)?;
+
+    // step 6) and 7), with the powers of two of the `gamma` bits computed
+    // once
+    let powers_two: Vec<FE> = std::iter::successors(Some(FE::ONE), |x| Some(*x + *x))
+        .take(gamma)
+        .collect();
+    let mut tau_mac_batch = Vec::with_capacity(s);
+    for k in 0..s {
+        let mut tau = c_m_mac[k][0];
+        for (i, c_m_mac_ki) in c_m_mac[k].iter().enumerate().skip(1) {
+            tau = fcom.add(tau, fcom.affine_mult_cst(powers_two[i], *c_m_mac_ki));
         }
-        Ok(dabit_vec)
+        for (e_ki, dabit) in e[k].iter().zip(dabits.iter()) {
+            // TODO: do not need to do it when e[i] is ZERO
+            let b = f2_to_fe(*e_ki);
+            tau = fcom.add(tau, fcom.affine_mult_cst(b, dabit.value()));
+        }
+        tau_mac_batch.push(tau);
     }
 
-    /// Generate random triples
-    pub fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        num: usize,
-        out: &mut Vec<(MacProver<F40b>, MacProver<F40b>, MacProver<F40b>)>,
-    ) -> Result<(), Error> {
-        let mut pairs = Vec::with_capacity(num);
-        let mut zs = Vec::with_capacity(num);
-        for _ in 0..num {
-            let x = self.fcom_f2.random(channel, rng)?;
-            let y = self.fcom_f2.random(channel, rng)?;
-            let z = x.0 * y.0;
-            pairs.push((x, y));
-            zs.push(z);
-        }
-        let mut zs_mac = Vec::with_capacity(num);
-        self.fcom_f2
-            .input_low_level(channel, rng, &zs, &mut zs_mac)?;
+    let tau_batch = fcom.open(channel, &tau_mac_batch)?;
 
-        for i in 0..num {
-            let (x, y) = pairs[i];
-            let z = zs[i];
-            let z_mac = zs_mac[i];
-            out.push((x, y, MacProver(z, z_mac)));
-        }
-        channel.flush()?;
+    // step 8)
+    for k in 0..s {
+        // mod2 is computed using the first bit of the bit decomposition.
+        let mut tau_mod2 = [F2::ZERO];
+        tau_batch[k].to_le_bits_into(&mut tau_mod2);
+        res &= r_batch[k] == tau_mod2[0];
+    }
+    let triples: WipeOnDrop<_> = WipeOnDrop::new(
+        ci_batch
+            .iter()
+            .zip(one_minus_ci_batch.iter())
+            .zip(and_res_mac_batch.iter())
+            .map(|((ci, one_minus_ci), and_res)| (*ci, *one_minus_ci, *and_res))
+            .collect(),
+    );
+    checker.start(fcom, channel, rng)?;
+    checker.push_triples(&triples);
+    checker.finalize(fcom, channel, rng)?;
+
+    if res {
         Ok(())
+    } else {
+        Err(Error::FdabitCheckFailed)
     }
+}
 
-    fn fdabit<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        dabits: &Vec<DabitProver<FE>>,
-    ) -> Result<(), Error> {
-        let s = FDABIT_SECURITY_PARAMETER;
-        let n = dabits.len();
-
-        let num_bits = std::mem::size_of::<usize>() * 8;
-        let gamma = num_bits - ((n + 1).leading_zeros() as usize) - 1 + 1;
+// Open the values of the cut edabits, whose bits `bits` are already opened,
+// and check that every value matches its `nb_bits` bits.
+fn open_cut_edabits<FE: FiniteField<PrimeField = FE>, P: FComParty<FE>, C: AbstractChannel>(
+    fcom: &mut P,
+    channel: &mut C,
+    bits: &[F2],
+    values: &[P::Mac],
+    nb_bits: usize,
+) -> Result<(), Error> {
+    fcom.open_and_check(channel, values, |i, a| {
+        convert_bits_to_field::<FE>(&bits[i * nb_bits..(i + 1) * nb_bits]).ok() == Some(a)
+    })
+    .map_err(|e| match e {
+        Error::OpenCheckFailed { index, .. } => Error::EdabitOpenMismatch { index },
+        e => e,
+    })?;
+    Ok(())
+}
 
-        check_parameters::<FE>(n, gamma)?;
+// Bits of computational security of the LPN parameters selected by
+// `init_for_workload`.
+const LPN_SECURITY_BITS: usize = 128;
+
+/// Estimate of the number of VOLEs consumed by a conversion, used to select
+/// the LPN parameters with `init_for_workload`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConvCostEstimate {
+    /// Number of VOLEs over the tag field `TF`.
+    pub voles_f2: usize,
+    /// Number of VOLEs over the field `FE`.
+    pub voles_fe: usize,
+}
 
-        let mut res = true;
+impl ConvCostEstimate {
+    /// Estimate the cost of `conv` on `num` edabits of `nb_bits` bits with
+    /// the given bucketing parameters.
+    pub fn conv<FE: FiniteField, TF: FiniteField>(
+        num: usize,
+        nb_bits: usize,
+        num_bucket: usize,
+        num_cut: usize,
+        with_quicksilver: bool,
+    ) -> Self {
+        Self::conv_with_triple_bucket::<FE, TF>(
+            num,
+            nb_bits,
+            num_bucket,
+            num_cut,
+            1,
+            with_quicksilver,
+        )
+    }
 
-        for i in 0..n {
-            // making sure the faulty dabits are not faulty
-            debug_assert!(
-                ((dabits[i].bit.0 == F2::ZERO) & (dabits[i].value.0 == FE::PrimeField::ZERO))
-                    | ((dabits[i].bit.0 == F2::ONE) & (dabits[i].value.0 == FE::PrimeField::ONE))
-            );
+    /// Estimate the cost of `conv` as [`ConvCostEstimate::conv`], with
+    /// `triple_bucket` random triples per multiplication of the Wolverine
+    /// check.
+    pub fn conv_with_triple_bucket<FE: FiniteField, TF: FiniteField>(
+        num: usize,
+        nb_bits: usize,
+        num_bucket: usize,
+        num_cut: usize,
+        triple_bucket: usize,
+        with_quicksilver: bool,
+    ) -> Self {
+        let nb_random_edabits = num * num_bucket + num_cut;
+        let nb_random_dabits = num * num_bucket;
+        let s = FDABIT_SECURITY_PARAMETER;
+        // the dabits are checked in chunks when they are too many for `FE`
+        let chunk_size = fdabit_chunk_size::<FE>(nb_random_dabits)
+            .unwrap_or(nb_random_dabits)
+            .max(1);
+        let chunks = ((nb_random_dabits + chunk_size - 1) / chunk_size).max(1);
+        let gamma = fdabit_gamma(chunk_size.min(nb_random_dabits));
+
+        // random edabits, random dabits and fdabit
+        let mut voles_f2 = nb_random_edabits * nb_bits + nb_random_dabits + chunks * s;
+        let voles_fe =
+            nb_random_edabits + nb_random_dabits + chunks * (2 * s * gamma + Degree::<FE>::USIZE);
+        // bit_add_carry in every bucket
+        voles_f2 += num_bucket * num * (nb_bits + 1);
+        // one QuickSilver check per thread, at most one per bucket
+        if with_quicksilver {
+            voles_f2 += num_bucket * Degree::<TF>::USIZE;
+        } else {
+            voles_f2 += 3 * (triple_bucket * num * num_bucket + num_cut) * nb_bits;
         }
+        ConvCostEstimate { voles_f2, voles_fe }
+    }
+}
 
-        // step 1)
-        let mut c_m: Vec<Vec<FE::PrimeField>> = vec![Vec::with_capacity(gamma); s];
-        let mut c_m_mac: Vec<Vec<FE>> = Vec::with_capacity(s);
-        for k in 0..s {
-            for _ in 0..gamma {
-                let b: F2 = F2::random(rng);
-                let b_m = f2_to_fe(b);
-                c_m[k].push(b_m);
-            }
-        }
+/// Scope of the communication of `conv` topping up the pools of VOLEs.
+pub const CONV_SCOPE_VOLE: &str = "vole";
+/// Scope of the communication of `conv` committing the random edabits.
+pub const CONV_SCOPE_EDABITS: &str = "edabits";
+/// Scope of the communication of `conv` committing the random dabits.
+pub const CONV_SCOPE_DABITS: &str = "dabits";
+/// Scope of the communication of `conv` committing the random triples.
+pub const CONV_SCOPE_TRIPLES: &str = "triples";
+/// Scope of the communication of `conv` checking the dabits.
+pub const CONV_SCOPE_FDABIT: &str = "fdabit";
+/// Scope of the communication of `conv` sending the permutation seed.
+pub const CONV_SCOPE_SHUFFLE: &str = "shuffle";
+/// Scope of the communication of `conv` opening the cut edabits and
+/// triples.
+pub const CONV_SCOPE_CUT_AND_CHOOSE: &str = "cut-and-choose";
+/// Scope of the communication of `conv` checking the buckets.
+pub const CONV_SCOPE_BUCKETS: &str = "buckets";
+/// Scope of the communication of `conv` exchanging its parameters, before
+/// its phases. It is not a phase, so it is not in [`CONV_SCOPES`].
+pub const CONV_SCOPE_HEADER: &str = "header";
+/// Scopes of the phases of `conv`, in the order they run.
+pub const CONV_SCOPES: [&str; 8] = [
+    CONV_SCOPE_VOLE,
+    CONV_SCOPE_EDABITS,
+    CONV_SCOPE_DABITS,
+    CONV_SCOPE_TRIPLES,
+    CONV_SCOPE_FDABIT,
+    CONV_SCOPE_SHUFFLE,
+    CONV_SCOPE_CUT_AND_CHOOSE,
+    CONV_SCOPE_BUCKETS,
+];
+
+/// Version of the schema of the serialized [`ConvStats`], increased on
+/// every incompatible change of their fields.
+pub const CONV_STATS_SCHEMA_VERSION: u32 = 1;
+
+/// Placeholder type of the bucket channels of `conv_legacy` when it is given
+/// none, since it cannot be inferred from `None`.
+pub type NoBucketChannel = SyncChannel<std::io::Empty, std::io::Sink>;
+
+/// Statistics of a run of `conv`.
+///
+/// With the `serde` feature, they are serialized along with the parameters
+/// of the run, so that runs can be compared across versions and machines,
+/// see [`ConvStats::to_json`] and [`ConvStats::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvStats {
+    /// Version of the schema of the statistics,
+    /// [`CONV_STATS_SCHEMA_VERSION`] for those returned by `conv`.
+    pub schema_version: u32,
+    /// Version of `ocelot` which ran the conversion.
+    pub version: String,
+    /// Name of the type of the field the edabits are converted to.
+    pub field: String,
+    /// Configuration of the conversion.
+    pub config: ConvConfig,
+    /// Number of edabits converted.
+    pub num: usize,
+    /// Number of bits of the edabits.
+    pub nb_bits: usize,
+    /// Communication on the channel given to `conv` broken down per phase,
+    /// see the `CONV_SCOPE_*` labels, when the channel keeps track of it,
+    /// e.g. a [`TrackChannel`](scuttlebutt::TrackChannel). The
+    /// communication on the bucket channels is not included. It is not
+    /// serialized, the phases holding the same communication.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub comm: Option<CommBreakdown>,
+    /// The phases of the run, in the order they finished.
+    pub phases: Vec<PhaseStats>,
+    /// Number of extensions of the pools of VOLEs.
+    pub extensions: usize,
+    /// Number of buckets checked.
+    pub buckets_completed: usize,
+    /// Durations of the steps of the check of the buckets, summed over the
+    /// buckets, including those checked on the bucket channels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bucket_steps: BucketSteps,
+    /// The calibration which selected the multiplication check, with
+    /// [`MultCheck::Auto`].
+    pub calibration: Option<Calibration>,
+}
 
-        for k in 0..s {
-            let b_m_mac = self.fcom.input(channel, rng, c_m[k].as_slice())?;
-            c_m_mac.push(b_m_mac);
-        }
+/// Communication of `conv` grouped by the steps of the protocol, see
+/// [`ConvStats::comm_summary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConvCommSummary {
+    /// Extensions of the pools of VOLEs and commitments to the random
+    /// edabits, dabits and triples.
+    pub generation: ScopeComm,
+    /// Check of the dabits.
+    pub fdabit: ScopeComm,
+    /// Shuffle and opening of the cut edabits and triples.
+    pub cut_and_choose: ScopeComm,
+    /// Check of the buckets.
+    pub buckets: ScopeComm,
+}
 
-        let mut c1: Vec<F2> = Vec::with_capacity(s);
-        for k in 0..s {
-            if c_m[k][0] == FE::PrimeField::ZERO {
-                c1.push(F2::ZERO);
-            } else {
-                c1.push(F2::ONE);
-            }
-        }
-        let c1_mac = self.fcom_f2.input(channel, rng, &c1)?;
-
-        // step 2)
-        let mut triples = Vec::with_capacity(gamma * s);
-        let mut andl_batch = Vec::with_capacity(gamma * s);
-        let mut andl_mac_batch = Vec::with_capacity(gamma * s);
-        let mut one_minus_ci_batch = Vec::with_capacity(gamma * s);
-        let mut one_minus_ci_mac_batch = Vec::with_capacity(gamma * s);
-        let mut and_res_batch = Vec::with_capacity(gamma * s);
-        for k in 0..s {
-            for i in 0..gamma {
-                let andl: FE::PrimeField = c_m[k][i];
-                let andl_mac: FE = c_m_mac[k][i];
-                let MacProver(minus_ci, minus_ci_mac) = // -ci
-                    self.fcom.affine_mult_cst(-FE::PrimeField::ONE, MacProver(andl, andl_mac));
-                let MacProver(one_minus_ci, one_minus_ci_mac) = // 1 - ci
-                    self.fcom.affine_add_cst(FE::PrimeField::ONE, MacProver(minus_ci, minus_ci_mac));
-                let and_res = andl * one_minus_ci;
-                andl_batch.push(andl);
-                andl_mac_batch.push(andl_mac);
-                one_minus_ci_batch.push(one_minus_ci);
-                one_minus_ci_mac_batch.push(one_minus_ci_mac);
-                and_res_batch.push(and_res);
-            }
-        }
-        let and_res_mac_batch = self.fcom.input(channel, rng, &and_res_batch)?;
+impl ConvStats {
+    /// Return the communication of the run grouped by the steps of the
+    /// protocol, when the channel given to `conv` keeps track of it.
+    pub fn comm_summary(&self) -> Option<ConvCommSummary> {
+        let comm = self.comm.as_ref()?;
+        let sum = |labels: &[&str]| {
+            labels
+                .iter()
+                .map(|label| comm.get(label))
+                .fold(ScopeComm::default(), |acc, comm| ScopeComm {
+                    bytes_read: acc.bytes_read + comm.bytes_read,
+                    bytes_written: acc.bytes_written + comm.bytes_written,
+                })
+        };
+        Some(ConvCommSummary {
+            generation: sum(&[
+                CONV_SCOPE_VOLE,
+                CONV_SCOPE_EDABITS,
+                CONV_SCOPE_DABITS,
+                CONV_SCOPE_TRIPLES,
+            ]),
+            fdabit: sum(&[CONV_SCOPE_FDABIT]),
+            cut_and_choose: sum(&[CONV_SCOPE_SHUFFLE, CONV_SCOPE_CUT_AND_CHOOSE]),
+            buckets: sum(&[CONV_SCOPE_BUCKETS]),
+        })
+    }
 
-        for j in 0..s * gamma {
-            triples.push((
-                MacProver(andl_batch[j], andl_mac_batch[j]),
-                MacProver(one_minus_ci_batch[j], one_minus_ci_mac_batch[j]),
-                MacProver(and_res_batch[j], and_res_mac_batch[j]),
-            ));
-        }
+    /// Serialize the statistics to JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("the statistics are serializable")
+    }
 
-        // step 3)
-        channel.flush()?;
-        let seed = channel.read_block()?;
-        let mut e_rng = AesRng::from_seed(seed);
-        let mut e = vec![Vec::with_capacity(n); s];
-        for k in 0..s {
-            for _i in 0..n {
-                let b = F2::random(&mut e_rng);
-                e[k].push(b);
-            }
+    /// Deserialize statistics serialized with `to_json`.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let stats: Self = serde_json::from_str(json).map_err(|e| Error::Other(e.to_string()))?;
+        if stats.schema_version != CONV_STATS_SCHEMA_VERSION {
+            return Err(Error::Other(format!(
+                "unsupported schema version of the statistics: {}",
+                stats.schema_version
+            )));
         }
+        Ok(stats)
+    }
 
-        // step 4)
-        let mut r_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            let (mut r, mut r_mac) = (c1[k], c1_mac[k]);
-            for i in 0..n {
-                // TODO: do not need to do it when e[i] is ZERO
-                let MacProver(tmp, tmp_mac) = self.fcom_f2.affine_mult_cst(e[k][i], dabits[i].bit);
-                debug_assert!(
-                    ((e[k][i] == F2::ONE) & (tmp == dabits[i].bit.0)) | (tmp == F2::ZERO)
-                );
-                r += tmp;
-                r_mac += tmp_mac;
-            }
-            r_batch.push(MacProver(r, r_mac));
-        }
-
-        // step 5) TODO: move this to the end
-        let _ = self.fcom_f2.open(channel, &r_batch)?;
-
-        // step 6)
-        let mut r_prime_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            // step 6)
-            // NOTE: for performance maybe step 4 and 6 should be combined in one loop
-            let (mut r_prime, mut r_prime_mac) = (FE::PrimeField::ZERO, FE::ZERO);
-            for i in 0..n {
-                // TODO: do not need to do it when e[i] is ZERO
-                let b = f2_to_fe(e[k][i]);
-                let MacProver(tmp, tmp_mac) = self.fcom.affine_mult_cst(b, dabits[i].value);
-                debug_assert!(
-                    ((b == FE::PrimeField::ONE) & (tmp == dabits[i].value.0))
-                        | (tmp == FE::PrimeField::ZERO)
-                );
-                r_prime += tmp;
-                r_prime_mac += tmp_mac;
-            }
-            r_prime_batch.push((r_prime, r_prime_mac));
-        }
-
-        // step 7)
-        let mut tau_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            let (mut tau, mut tau_mac) = r_prime_batch[k];
-            let mut twos = FE::PrimeField::ONE;
-            for i in 0..gamma {
-                let MacProver(tmp, tmp_mac) = self
-                    .fcom
-                    .affine_mult_cst(twos, MacProver(c_m[k][i], c_m_mac[k][i]));
-                if i == 0 {
-                    debug_assert!(c_m[k][i] == tmp);
+    /// Return the metrics of the phases that are worse in `other` than in
+    /// these statistics by more than `threshold`, a fraction of the value
+    /// in these statistics: the duration, the number of bytes and the
+    /// number of flushes of every phase run in both.
+    pub fn diff(&self, other: &ConvStats, threshold: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for after in other.phases.iter() {
+            let before = match self.phases.iter().find(|p| p.phase == after.phase) {
+                Some(before) => before,
+                None => continue,
+            };
+            let metrics = [
+                (
+                    "seconds",
+                    before.duration.map(|d| d.as_secs_f64()),
+                    after.duration.map(|d| d.as_secs_f64()),
+                ),
+                (
+                    "bytes",
+                    before.comm.map(|c| c.total() as f64),
+                    after.comm.map(|c| c.total() as f64),
+                ),
+                (
+                    "flushes",
+                    before.rounds.map(|r| r as f64),
+                    after.rounds.map(|r| r as f64),
+                ),
+            ];
+            for (metric, b, a) in metrics {
+                if let (Some(b), Some(a)) = (b, a) {
+                    if a > b * (1.0 + threshold) {
+                        regressions.push(Regression {
+                            phase: after.phase,
+                            metric,
+                            before: b,
+                            after: a,
+                        });
+                    }
                 }
-                tau += tmp;
-                tau_mac += tmp_mac;
-                twos += twos;
             }
-            tau_batch.push(MacProver(tau, tau_mac));
         }
+        regressions
+    }
+}
 
-        let _ = self.fcom.open(channel, &tau_batch)?;
-
-        // step 8)
-        for k in 0..s {
-            // step 8)
-            // NOTE: This is not needed for the prover,
-            let b =
-                // mod2 is computed using the first bit of the bit decomposition.
-                // NOTE: This scales linearly with the size of the bit decomposition and could lead to potential inefficiencies
-                (r_batch[k].0 == F2::ONE) == tau_batch[k].0.bit_decomposition()[0];
-            res = res & b;
-        }
-        self.fcom
-            .quicksilver_check_multiply(channel, rng, &triples)?;
+/// A metric of a phase worse in a run than in another, see
+/// [`ConvStats::diff`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Regression {
+    /// The phase, labelled with its `CONV_SCOPE_*` scope.
+    pub phase: &'static str,
+    /// The metric: `"seconds"`, `"bytes"` or `"flushes"`.
+    pub metric: &'static str,
+    /// Value of the metric in the first run.
+    pub before: f64,
+    /// Value of the metric in the second run.
+    pub after: f64,
+}
 
-        if res {
-            Ok(())
-        } else {
-            Err(Error::Other("fail fdabit prover".to_string()))
-        }
+impl std::fmt::Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {} -> {} (+{:.1}%)",
+            self.phase,
+            self.metric,
+            self.before,
+            self.after,
+            (self.after / self.before - 1.0) * 100.0
+        )
     }
+}
 
-    fn conv_loop<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        edabits_vector: &[EdabitsProver<FE>],
-        r: &[EdabitsProver<FE>],
-        dabits: &[DabitProver<FE>],
-        convert_bit_2_field_aux: &mut Vec<MacProver<F40b>>,
-        e_m_batch: &mut Vec<MacProver<FE>>,
-        random_triples: &[(MacProver<F40b>, MacProver<F40b>, MacProver<F40b>)],
-    ) -> Result<(), Error> {
-        let n = edabits_vector.len();
-        let nb_bits = edabits_vector[0].bits.len();
-        let power_two_nb_bits = power_two::<FE::PrimeField>(nb_bits);
-        // step 6)b) batched and moved up
-        let e_batch = self.bit_add_carry(channel, rng, &edabits_vector, &r, &random_triples)?;
+// Run the phases of `conv` in `f`, which attributes their communication to
+// the `CONV_SCOPE_*` scopes of `channel` and reports them to the observer it
+// is given, and collect the statistics of the run. The observer also
+// forwards them to the observer of `config`. The scope of `channel` is
+// restored afterwards, also when `f` fails.
+fn conv_with_stats<FE: FiniteField, M, E: EdabitsShares<M>, C: AbstractChannel>(
+    channel: &mut C,
+    config: &ConvConfig,
+    edabits_vector: &[E],
+    f: impl FnOnce(&mut C, &Arc<dyn ConvObserver>) -> Result<Option<Calibration>, Error>,
+) -> Result<ConvStats, Error> {
+    let nb_bits = edabits_vector.first().map_or(0, |e| e.bits().len());
+    let (stats, ()) = phases_with_stats::<FE, _, _>(
+        channel,
+        config,
+        edabits_vector.len(),
+        nb_bits,
+        |channel, observer| Ok((f(channel, observer)?, ())),
+    )?;
+    Ok(stats)
+}
 
-        // step 6)c) batched and moved up
-        let mut e_carry_batch = Vec::with_capacity(n);
-        for (_, e_carry) in e_batch.iter() {
-            e_carry_batch.push(e_carry.clone());
-        }
+// Run phases of `conv` on `num` edabits of `nb_bits` bits in `f` as in
+// `conv_with_stats`, and also return the output of `f`.
+fn phases_with_stats<FE: FiniteField, C: AbstractChannel, T>(
+    channel: &mut C,
+    config: &ConvConfig,
+    num: usize,
+    nb_bits: usize,
+    f: impl FnOnce(&mut C, &Arc<dyn ConvObserver>) -> Result<(Option<Calibration>, T), Error>,
+) -> Result<(ConvStats, T), Error> {
+    let stats = Arc::new(StatsObserver::new());
+    let observer: Arc<dyn ConvObserver> = match &config.observer {
+        Some(observer) => Arc::new(TeeObserver(stats.clone(), observer.clone())),
+        None => stats.clone(),
+    };
+    let start = channel.comm_breakdown();
+    let outer = channel.scope(CONV_SCOPE_VOLE);
+    let res = f(channel, &observer);
+    if let Some(outer) = outer {
+        channel.scope(outer);
+    }
+    let (calibration, output) = res?;
+    let comm = match (start, channel.comm_breakdown()) {
+        (Some(start), Some(end)) => Some(end.since(&start)),
+        _ => None,
+    };
+    let stats = ConvStats {
+        schema_version: CONV_STATS_SCHEMA_VERSION,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        field: std::any::type_name::<FE>().to_string(),
+        config: config.clone(),
+        num,
+        nb_bits,
+        comm,
+        calibration,
+        ..stats.stats()
+    };
+    Ok((stats, output))
+}
 
-        self.convert_bit_2_field(
-            channel,
-            &dabits,
-            &e_carry_batch,
-            convert_bit_2_field_aux,
-            e_m_batch,
-        )?;
+/// Random edabits, dabits and triples prepared by `preprocess` before the
+/// edabits to convert are known, which `conv_online` consumes to convert
+/// them.
+///
+/// The material is checked and shuffled once, and every conversion consumes
+/// that of its buckets, also when its check fails. It must be used with the
+/// instance which prepared it, in the same order by both parties, and it
+/// cannot be cloned since reusing material breaks the soundness of the
+/// check.
+pub struct ConvPreprocessing<E, D, M> {
+    config: ConvConfig,
+    calibration: Option<Calibration>,
+    material: BucketMaterial<E, D, M>,
+    capacity: usize,
+    used: usize,
+    stats: ConvStats,
+}
 
-        // 6)a)
-        let mut e_prime_batch = Vec::with_capacity(n);
-        // 6)d)
-        let mut ei_batch = Vec::with_capacity(n * nb_bits);
-        for i in 0..n {
-            // 6)a)
-            let c_m = edabits_vector[i].value;
-            let r_m = r[i].value;
-            let c_plus_r = self.fcom.add(c_m, r_m);
+/// Preprocessing of the prover, see [`ProverConv::preprocess`].
+pub type ProverPreprocessing<FE, TF> =
+    ConvPreprocessing<EdabitsProver<FE, TF>, DabitProver<FE, TF>, MacProver<TF>>;
 
-            // 6)c) done earlier
-            let e_m = e_m_batch[i];
+/// Preprocessing of the verifier, see [`VerifierConv::preprocess`].
+pub type VerifierPreprocessing<FE, TF> =
+    ConvPreprocessing<EdabitsVerifier<FE, TF>, DabitVerifier<FE, TF>, MacVerifier<TF>>;
 
-            // 6)d)
-            let e_prime = self
-                .fcom
-                .add(c_plus_r, self.fcom.affine_mult_cst(-power_two_nb_bits, e_m));
-            e_prime_batch.push(e_prime);
-            ei_batch.extend(&e_batch[i].0);
-        }
+impl<E, D, M> ConvPreprocessing<E, D, M> {
+    /// Number of bits of the edabits it converts.
+    pub fn nb_bits(&self) -> usize {
+        self.material.nb_bits
+    }
 
-        // 6)e)
-        self.fcom_f2.open(channel, &ei_batch)?;
+    /// Number of edabits it was prepared for.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
 
-        let mut e_prime_minus_sum_batch = Vec::with_capacity(n);
-        for i in 0..n {
-            let sum = convert_bits_to_field_mac::<FE>(&ei_batch[i * nb_bits..(i + 1) * nb_bits]);
-            e_prime_minus_sum_batch.push(self.fcom.affine_add_cst(-sum, e_prime_batch[i]));
-        }
+    /// Number of edabits it can still convert.
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used
+    }
 
-        // Remark this is not necessary for the prover, bc cst addition dont show up in mac
-        // let s = convert_f2_to_field(ei);
-        self.fcom.check_zero(channel, &e_prime_minus_sum_batch)?;
-        Ok(())
+    /// Statistics of the preprocessing.
+    pub fn stats(&self) -> &ConvStats {
+        &self.stats
     }
 
-    /// conversion checking
-    pub fn conv<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        num_bucket: usize,
-        num_cut: usize,
-        edabits_vector: &[EdabitsProver<FE>],
-        bucket_channels: Option<Vec<SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>>>,
-        with_quicksilver: bool,
-    ) -> Result<(), Error> {
-        let n = edabits_vector.len();
-        let nb_bits = edabits_vector[0].bits.len();
-
-        let nb_random_edabits = n * num_bucket + num_cut;
-        let nb_random_dabits = n * num_bucket;
-
-        // step 1)a): commit random edabit
-        let mut r = self.random_edabits(channel, rng, nb_bits, nb_random_edabits)?;
-
-        // step 1)b)
-        let mut dabits = self.random_dabits(channel, rng, nb_random_dabits)?;
-
-        // step 1)c): multiplication triples
-        let mut random_triples = Vec::new();
-        if !with_quicksilver {
-            // with wolverine
-            let how_many = num_bucket * n * nb_bits + num_cut * nb_bits;
-            self.random_triples(channel, rng, how_many, &mut random_triples)?;
-        }
-
-        // step 2)
-        self.fdabit(channel, rng, &dabits)?;
-
-        // step 3) get seed for permutation
-        let seed = channel.read_block()?;
-        let mut shuffle_rng = AesRng::from_seed(seed);
-
-        // step 4): shuffle edabits, dabits and triples
-        generate_permutation(&mut shuffle_rng, &mut r);
-        generate_permutation(&mut shuffle_rng, &mut dabits);
-        generate_permutation(&mut shuffle_rng, &mut random_triples);
-
-        // step 5)a):
-        let base = n * num_bucket;
-        for i in 0..num_cut {
-            let idx = base + i;
-            let a = &r[idx];
-            self.fcom_f2.open(channel, &a.bits)?;
-            self.fcom.open(channel, &[a.value])?;
-        }
-
-        // step 5) b):
-        if !with_quicksilver {
-            let base = n * num_bucket * nb_bits;
-            for i in 0..num_cut * nb_bits {
-                let (x, y, z) = random_triples[base + i];
-                let _res = self.fcom_f2.open(channel, &[x, y])?;
-                let v = self.fcom_f2.affine_add_cst(-(x.0 * y.0), z);
-                self.fcom_f2.check_zero(channel, &[v])?;
-            }
+    // Consume the material of `n` edabits, and return the number of edabits
+    // converted before.
+    fn consume(&mut self, n: usize) -> Result<usize, Error> {
+        if n > self.remaining() {
+            return Err(Error::Other(format!(
+                "the preprocessing has material left for {} edabits, not {}",
+                self.remaining(),
+                n,
+            )));
         }
+        let start = self.used;
+        self.used += n;
+        Ok(start)
+    }
+}
 
-        // step 6)
-        if bucket_channels.is_none() {
-            let mut convert_bit_2_field_aux = Vec::with_capacity(n);
-            let mut e_m_batch = Vec::with_capacity(n);
-            for j in 0..num_bucket {
-                // base index for the window of `idx_base..idx_base + n` values
-                let idx_base = j * n;
-
-                if with_quicksilver {
-                    self.conv_loop(
-                        channel,
-                        rng,
-                        &edabits_vector,
-                        &r[idx_base..idx_base + n],
-                        &dabits[idx_base..idx_base + n],
-                        &mut convert_bit_2_field_aux,
-                        &mut e_m_batch,
-                        &Vec::new(),
-                    )?;
-                } else {
-                    self.conv_loop(
-                        channel,
-                        rng,
-                        &edabits_vector,
-                        &r[idx_base..idx_base + n],
-                        &dabits[idx_base..idx_base + n],
-                        &mut convert_bit_2_field_aux,
-                        &mut e_m_batch,
-                        &random_triples[idx_base * nb_bits..idx_base * nb_bits + n * nb_bits],
-                    )?;
-                }
-            }
-        } else {
-            let mut j = 0;
-            let mut handles = Vec::new();
-            for mut bucket_channel in bucket_channels.unwrap().into_iter() {
-                // splitting the vectors to spawn
-                let idx_base = j * n;
-                let mut edabits_vector_par = Vec::with_capacity(n);
-                for edabits in edabits_vector.iter() {
-                    edabits_vector_par.push(copy_edabits_prover(edabits));
-                }
-
-                let mut r_par = Vec::with_capacity(n);
-                for r_elm in r[idx_base..idx_base + n].iter() {
-                    r_par.push(copy_edabits_prover(r_elm));
-                }
-
-                let mut dabits_par = Vec::with_capacity(n);
-                for elm in dabits[idx_base..idx_base + n].iter() {
-                    dabits_par.push(elm.clone());
-                }
-
-                let mut random_triples_par = Vec::new(); //with_capacity(n * nb_bits);
-                if !with_quicksilver {
-                    //let mut random_triples_par = Vec::with_capacity(n * nb_bits);
-                    for elm in
-                        random_triples[idx_base * nb_bits..idx_base * nb_bits + n * nb_bits].iter()
-                    {
-                        random_triples_par.push(elm.clone());
-                    }
-                }
+// Prepare the conversion of `num` edabits of `nb_bits` bits, see
+// `ProverConv::preprocess`.
+fn conv_preprocess<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    nb_bits: usize,
+    num: usize,
+    config: &ConvConfig,
+) -> Result<ConvPreprocessing<P::Edabits, P::Dabit, P::MacF2>, Error> {
+    config.validate()?;
+    if config.threads > 1 {
+        return Err(Error::Other(
+            "the preprocessed buckets are checked on a single thread".to_string(),
+        ));
+    }
+    check_nb_bits::<FE>(nb_bits)?;
+    exchange_conv_header(
+        channel,
+        &conv_header(party.protocol_version(), config, num, nb_bits, false),
+    )?;
+    let (stats, material) =
+        phases_with_stats::<FE, _, _>(channel, config, num, nb_bits, |channel, observer| {
+            let (calibration, with_quicksilver) =
+                select_check(party, channel, rng, nb_bits, config, false)?;
+            let estimate = ConvCostEstimate::conv_with_triple_bucket::<FE, TF>(
+                num,
+                nb_bits,
+                config.num_bucket,
+                config.num_cut,
+                config.triple_bucket,
+                with_quicksilver,
+            );
+            top_up_phase(party, channel, rng, Some(estimate), observer)?;
+            let material = prepare_buckets(
+                party,
+                channel,
+                rng,
+                nb_bits,
+                num,
+                config.num_bucket,
+                config.num_cut,
+                config.triple_bucket,
+                with_quicksilver,
+                observer,
+            )?;
+            Ok((calibration, material))
+        })?;
+    Ok(ConvPreprocessing {
+        config: config.clone(),
+        calibration: stats.calibration,
+        material,
+        capacity: num,
+        used: 0,
+        stats,
+    })
+}
 
-                let mut new_prover = self.duplicate(channel, rng)?;
-                let handle = std::thread::spawn(move || {
-                    let mut convert_bit_2_field_aux = Vec::with_capacity(n);
-                    let mut e_m_batch = Vec::with_capacity(n);
-                    new_prover.conv_loop(
-                        &mut bucket_channel,
-                        &mut AesRng::new(),
-                        &edabits_vector_par,
-                        &r_par,
-                        &dabits_par,
-                        &mut convert_bit_2_field_aux,
-                        &mut e_m_batch,
-                        &random_triples_par,
-                    )
-                });
-                handles.push(handle);
+// Convert `edabits_vector` with the material of `preprocessing`, see
+// `ProverConv::conv_online`.
+fn conv_online_phases<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    preprocessing: &mut ConvPreprocessing<P::Edabits, P::Dabit, P::MacF2>,
+    edabits_vector: &[P::Edabits],
+) -> Result<ConvStats, Error> {
+    let n = edabits_vector.len();
+    let nb_bits = batch_nb_bits(edabits_vector)?;
+    if nb_bits != preprocessing.nb_bits() {
+        return Err(Error::Other(format!(
+            "the preprocessing converts edabits of {} bits, not {}",
+            preprocessing.nb_bits(),
+            nb_bits,
+        )));
+    }
+    let start = preprocessing.consume(n)?;
+    let preprocessing = &*preprocessing;
+    conv_with_stats::<FE, _, _, _>(
+        channel,
+        &preprocessing.config,
+        edabits_vector,
+        |channel, observer| {
+            check_buckets(
+                party,
+                channel,
+                rng,
+                edabits_vector,
+                preprocessing.material.buckets(start, n),
+                None::<BucketChannels<NoBucketChannel>>,
+                None::<NoMultChecker>,
+                observer,
+            )?;
+            Ok(preprocessing.calibration)
+        },
+    )
+}
 
-                j += 1;
-            }
+// A phase of `conv` in progress, reported to the observer when it finishes.
+struct ConvPhase {
+    phase: &'static str,
+    size: usize,
+    timer: Timer,
+    start: Option<CommBreakdown>,
+}
 
-            for handle in handles {
-                handle.join().unwrap().unwrap();
-            }
+impl ConvPhase {
+    // Attribute the communication on `channel` to `phase` from now on.
+    fn start<C: AbstractChannel>(
+        channel: &mut C,
+        observer: &dyn ConvObserver,
+        phase: &'static str,
+        size: usize,
+    ) -> Self {
+        channel.scope(phase);
+        observer.phase_started(phase, size);
+        ConvPhase {
+            phase,
+            size,
+            timer: Timer::start(),
+            start: channel.comm_breakdown(),
         }
+    }
 
-        Ok(())
+    fn finish<C: AbstractChannel>(self, channel: &C, observer: &dyn ConvObserver) {
+        let since = match (self.start, channel.comm_breakdown()) {
+            (Some(start), Some(end)) => Some(end.since(&start)),
+            _ => None,
+        };
+        observer.phase_finished(&PhaseStats {
+            phase: self.phase,
+            size: self.size,
+            duration: self.timer.elapsed(),
+            comm: since.as_ref().map(|since| since.total()),
+            rounds: since.as_ref().map(|since| since.flushes(self.phase)),
+        });
     }
 }
 
-/// Verifier for the edabits conversion protocol
-pub struct VerifierConv<FE: FiniteField> {
-    fcom_f2: FComVerifier<F40b>,
-    fcom: FComVerifier<FE>,
+// Report `extensions` extensions of the pools of VOLEs to `observer`.
+fn report_extensions(observer: &dyn ConvObserver, extensions: usize) {
+    for _ in 0..extensions {
+        observer.extension_triggered();
+    }
 }
 
-// The Finite field is required to be a prime field because of the fdabit
-// protocol working only for prime finite fields.
-impl<FE: FiniteField<PrimeField = FE>> VerifierConv<FE> {
-    /// initialize the verifier
-    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+// The parts of the conversion that differ between the prover and the
+// verifier, so that the conversion itself is written once for both parties
+// in `conv_phases` and `conv_loop`.
+trait ConvParty<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>:
+    Sized + Send + 'static
+{
+    // Commitment to a value of `FE`.
+    type Mac: Copy + Send + Sync + 'static;
+    // Commitment to a bit, authenticated in `TF`.
+    type MacF2: Copy + Send + Sync + 'static;
+    type Edabits: EdabitsShares<Self::MacF2, Value = Self::Mac> + Clone + Send + Sync + 'static;
+    type Dabit: DabitShares<Self::MacF2, Self::Mac> + Clone + Send + Sync + 'static;
+    type FCom: FComParty<FE, Mac = Self::Mac>;
+    type FComF2: FComParty<TF, Mac = Self::MacF2>;
+
+    // The F_com instances over `FE` and over `TF`.
+    fn fcoms(&mut self) -> (&mut Self::FCom, &mut Self::FComF2);
+
+    #[cfg(feature = "threads")]
+    fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
         channel: &mut C,
         rng: &mut RNG,
-        lpn_setup: LpnParams,
-        lpn_extend: LpnParams,
-    ) -> Result<Self, Error> {
-        let a = FComVerifier::init(channel, rng, lpn_setup, lpn_extend)?;
-        let b = FComVerifier::init(channel, rng, lpn_setup, lpn_extend)?;
-        Ok(Self {
-            fcom_f2: a,
-            fcom: b,
-        })
-    }
+    ) -> Result<Self, Error>;
 
-    fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
         rng: &mut RNG,
-    ) -> Result<Self, Error> {
-        Ok(Self {
-            fcom_f2: self.fcom_f2.duplicate(channel, rng)?,
-            fcom: self.fcom.duplicate(channel, rng)?,
-        })
-    }
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<Self::Edabits>, Error>;
 
-    fn convert_bit_2_field<C: AbstractChannel>(
+    fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
-        r_batch: &[DabitVerifier<FE>],
-        x_batch: &[MacVerifier<F40b>],
-        r_mac_plus_x_mac: &mut Vec<MacVerifier<F40b>>,
-        c_batch: &mut Vec<F2>,
-        x_m_batch: &mut Vec<MacVerifier<FE>>,
-    ) -> Result<(), Error> {
-        let n = r_batch.len();
-        debug_assert!(n == x_batch.len());
-        r_mac_plus_x_mac.clear();
-        x_m_batch.clear();
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<Self::Dabit>, Error>;
 
-        for i in 0..n {
-            r_mac_plus_x_mac.push(self.fcom_f2.add(r_batch[i].bit, x_batch[i]));
-        }
-        self.fcom_f2.open(channel, &r_mac_plus_x_mac, c_batch)?;
+    fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<(Self::MacF2, Self::MacF2, Self::MacF2)>,
+    ) -> Result<(), Error>;
 
-        for i in 0..n {
-            let c = c_batch[i];
+    // Lift `bits` into commitments in `FE` appended to `out`, consuming one
+    // dabit per bit.
+    fn lift_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        dabits: &[Self::Dabit],
+        bits: &[Self::MacF2],
+        out: &mut Vec<Self::Mac>,
+    ) -> Result<(), Error>;
+
+    // Open `bits` in the wire format of the protocol version, and store
+    // their values in `out`.
+    fn open_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        bits: &[Self::MacF2],
+        out: &mut Vec<F2>,
+    ) -> Result<(), Error>;
 
-            let c_m = f2_to_fe::<FE::PrimeField>(c);
+    // Toss coins with the other party for the seed of the permutation of
+    // the random edabits, dabits and triples.
+    fn shuffle_seed<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Block, Error>;
 
-            let choice = c.ct_eq(&F2::ONE);
-            let beq = self
-                .fcom
-                .affine_add_cst(c_m, self.fcom.neg(r_batch[i].value));
-            let bneq = self.fcom.affine_add_cst(c_m, r_batch[i].value);
-            let x_m = MacVerifier::conditional_select(&bneq, &beq, choice);
+    // The calibration of `MultCheck::Auto` of the session, once it ran.
+    fn calibration(&mut self) -> &mut Option<Calibration>;
 
-            x_m_batch.push(x_m);
-        }
+    // The version of the wire format of the protocol.
+    fn protocol_version(&self) -> ProtocolVersion;
 
-        assert_eq!(n, x_m_batch.len());
-        Ok(())
+    // Agree on the multiplication check selected by the calibration, from
+    // the durations of its runs with QuickSilver and with Wolverine: the
+    // verifier selects it and sends it to the prover.
+    fn select_mult_check<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        quicksilver: Option<Duration>,
+        wolverine: Option<Duration>,
+    ) -> Result<MultCheck, Error>;
+
+    // Refill the pools of VOLEs that are below their low watermark, and
+    // return the number of pools extended. This is called at the same points
+    // of the protocol by both parties.
+    fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<usize, Error> {
+        let (fcom, fcom_f2) = self.fcoms();
+        let refilled_f2 = fcom_f2.refill_if_low(channel, rng)?;
+        let refilled_fe = fcom.refill_if_low(channel, rng)?;
+        Ok(usize::from(refilled_f2) + usize::from(refilled_fe))
     }
 
-    fn bit_add_carry<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    // Refill the pools of VOLEs that are below their low watermark, and then
+    // extend them to hold the VOLEs consumed according to `estimate`, and
+    // return the number of extensions. This is called at the same points of
+    // the protocol by both parties.
+    fn top_up<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
         rng: &mut RNG,
-        x_batch: &[EdabitsVerifier<FE>],
-        y_batch: &[EdabitsVerifier<FE>],
-        random_triples: &[(MacVerifier<F40b>, MacVerifier<F40b>, MacVerifier<F40b>)],
-    ) -> Result<Vec<(Vec<MacVerifier<F40b>>, MacVerifier<F40b>)>, Error> {
-        let num = x_batch.len();
-        if num != y_batch.len() {
-            return Err(Error::Other(
-                "incompatible input vectors in bit_add_carry".to_string(),
-            ));
+        estimate: ConvCostEstimate,
+    ) -> Result<usize, Error> {
+        let mut extensions = self.refill_if_low(channel, rng)?;
+        let (fcom, fcom_f2) = self.fcoms();
+        let available_f2 = fcom_f2.available();
+        if available_f2 < estimate.voles_f2 {
+            fcom_f2.extend_at_least(channel, rng, estimate.voles_f2 - available_f2)?;
+            extensions += 1;
         }
+        let available_fe = fcom.available();
+        if available_fe < estimate.voles_fe {
+            fcom.extend_at_least(channel, rng, estimate.voles_fe - available_fe)?;
+            extensions += 1;
+        }
+        Ok(extensions)
+    }
 
-        let m = x_batch[0].bits.len();
-
-        // input c0
-        let mut ci_batch = self.fcom_f2.input(channel, rng, num)?;
-
-        // loop on the m bits over the batch of n addition
-        let mut triples = Vec::with_capacity(num * m);
-        let mut aux_batch = Vec::with_capacity(num);
-        let mut z_batch = vec![Vec::with_capacity(m); num];
-        let mut and_res_mac_batch = Vec::with_capacity(num);
-        for i in 0..m {
-            aux_batch.clear();
-            for n in 0..num {
-                let ci = ci_batch[n];
-
-                let x = &x_batch[n].bits;
-                let y = &y_batch[n].bits;
-
-                debug_assert!(x.len() == m && y.len() == m);
-
-                let xi = x[i];
-                let yi = y[i];
-
-                let and1 = self.fcom_f2.add(xi, ci);
-                let and2 = self.fcom_f2.add(yi, ci);
+    // The bit and the addition of the product committed wrongly by
+    // `bit_add_carry`, see `ConvFault::Carry`.
+    fn carry_fault(&self) -> Option<(usize, usize)> {
+        None
+    }
 
-                let z = self.fcom_f2.add(and1, yi); //xi_mac + yi_mac + ci_mac;
-                z_batch[n].push(z);
-                aux_batch.push((and1, and2));
-            }
-            and_res_mac_batch.clear();
-            self.fcom_f2
-                .input_low_level(channel, rng, num, &mut and_res_mac_batch)?;
-
-            for n in 0..num {
-                let (and1_mac, and2_mac) = aux_batch[n];
-                let and_res_mac = and_res_mac_batch[n];
-                triples.push((and1_mac, and2_mac, and_res_mac));
-
-                let ci = ci_batch[n];
-                let c_mac = self.fcom_f2.add(ci, and_res_mac);
-                ci_batch[n] = c_mac;
-            }
-        }
-        // check all the multiplications in one batch
-        if random_triples.len() == 0 {
-            self.fcom_f2
-                .quicksilver_check_multiply(channel, rng, &triples)?;
-        } else {
-            self.fcom_f2
-                .wolverine_check_multiply(channel, rng, &triples, &random_triples)?;
-        }
-        // reconstruct the solution
-        let mut res = Vec::with_capacity(num);
-        let mut i = 0;
-        for zs in z_batch.into_iter() {
-            res.push((zs, ci_batch[i]));
-            i += 1;
-        }
+    // Whether the dabits are committed wrongly, see `ConvFault::Dabit`.
+    fn dabit_fault(&self) -> bool {
+        false
+    }
 
-        Ok(res)
+    // The additions of `bit_add_carry` without the check of their
+    // multiplications, see `DeferredChecks`.
+    fn add_bits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[Self::Edabits],
+        y_batch: &[Self::Edabits],
+    ) -> Result<Vec<(Vec<Self::MacF2>, Self::MacF2)>, Error> {
+        let flip_carry = self.carry_fault();
+        let (_, fcom_f2) = self.fcoms();
+        add_bits(fcom_f2, channel, rng, x_batch, y_batch, None, flip_carry)
     }
 
-    /// generate random edabits
-    pub fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    // The multiplications of the check of the dabits are always checked
+    // with QuickSilver. Batches too large for the field are checked in
+    // chunks, see `fdabit_chunk_size`.
+    fn fdabit<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
         rng: &mut RNG,
-        nb_bits: usize,
-        num: usize, // in the paper: NB + C
-    ) -> Result<Vec<EdabitsVerifier<FE>>, Error> {
-        let mut edabits_vec_mac = Vec::with_capacity(num);
-        let mut aux_bits = Vec::with_capacity(num);
-        for _ in 0..num {
-            let mut bits = Vec::with_capacity(nb_bits);
-            for _ in 0..nb_bits {
-                bits.push(self.fcom_f2.random(channel, rng)?);
-            }
-            aux_bits.push(bits);
+        dabits: &[Self::Dabit],
+    ) -> Result<(), Error> {
+        let faulty = self.dabit_fault();
+        let (fcom, fcom_f2) = self.fcoms();
+        let chunk_size = fdabit_chunk_size::<FE>(dabits.len())?;
+        if chunk_size == dabits.len() {
+            let mut checker = QuickSilverChecker::new();
+            return fdabit(fcom, fcom_f2, channel, rng, dabits, &mut checker, faulty);
+        }
+        for chunk in dabits.chunks(chunk_size) {
+            let mut checker = QuickSilverChecker::new();
+            fdabit(fcom, fcom_f2, channel, rng, chunk, &mut checker, faulty)?;
         }
+        Ok(())
+    }
+}
 
-        let aux_r_m_mac = self.fcom.input(channel, rng, num)?;
+// The checks of the buckets checked in a row over one channel, deferred to
+// after the last of them so that they take a constant number of rounds
+// rather than a few rounds per bucket: the multiplications of the bit
+// additions, recomputed from the bits and the carries of the sums, and the
+// values which must be zero. A failed bucket is only detected at the end.
+struct DeferredChecks<M: Copy, MF2: Copy> {
+    sum_bits: WipeOnDrop<MF2>,
+    carries: WipeOnDrop<MF2>,
+    zeros: WipeOnDrop<M>,
+}
 
-        let mut i = 0;
-        for aux_bits in aux_bits.into_iter() {
-            edabits_vec_mac.push(EdabitsVerifier {
-                bits: aux_bits,
-                value: aux_r_m_mac[i],
-            });
-            i += 1;
+impl<M: Copy, MF2: Copy> DeferredChecks<M, MF2> {
+    fn new() -> Self {
+        DeferredChecks {
+            sum_bits: WipeOnDrop::new(Vec::new()),
+            carries: WipeOnDrop::new(Vec::new()),
+            zeros: WipeOnDrop::new(Vec::new()),
         }
-        Ok(edabits_vec_mac)
     }
 
-    fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
+    // Run the checks of the buckets `buckets`, in the order they were
+    // checked, the bucket `j` having added `edabits_vector` to
+    // `r[j * n..(j + 1) * n]`, with `checker` for the multiplications. The
+    // checks are consumed whatever the result, and their durations are
+    // returned as the steps of the buckets.
+    #[allow(clippy::too_many_arguments)]
+    fn finalize<
+        FE: FiniteField<PrimeField = FE>,
+        TF: FiniteField<PrimeField = F2>,
+        P: ConvParty<FE, TF, Mac = M, MacF2 = MF2>,
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        K: MultChecker<TF, P::FComF2>,
+    >(
+        self,
+        party: &mut P,
         channel: &mut C,
         rng: &mut RNG,
-        num: usize,
-    ) -> Result<Vec<DabitVerifier<FE>>, Error> {
-        let mut dabit_vec_mac = Vec::with_capacity(num);
-        let mut b_mac_batch = Vec::with_capacity(num);
-        for _ in 0..num {
-            b_mac_batch.push(self.fcom_f2.random(channel, rng)?);
+        edabits_vector: &[P::Edabits],
+        r: &[P::Edabits],
+        buckets: &[usize],
+        checker: &mut K,
+    ) -> Result<BucketSteps, Error> {
+        let n = edabits_vector.len();
+        let nb_bits = edabits_vector[0].bits().len();
+
+        // the multiplications over `TF` first, then the zeros over `FE`, in
+        // the same order on both sides
+        let timer = Timer::start();
+        let (_, fcom_f2) = party.fcoms();
+        channel.flush()?;
+        checker.start(fcom_f2, channel, rng)?;
+        for (k, j) in buckets.iter().enumerate() {
+            push_add_triples(
+                &*fcom_f2,
+                edabits_vector,
+                &r[j * n..(j + 1) * n],
+                &self.sum_bits[k * n * nb_bits..(k + 1) * n * nb_bits],
+                &self.carries[k * n..(k + 1) * n],
+                checker,
+            );
         }
-        let b_m_mac_batch = self.fcom.input(channel, rng, num)?;
-        for i in 0..num {
-            dabit_vec_mac.push(DabitVerifier {
-                bit: b_mac_batch[i],
-                value: b_m_mac_batch[i],
-            });
+        checker.finalize(fcom_f2, channel, rng)?;
+        let add = timer.elapsed();
+
+        let timer = Timer::start();
+        let (fcom, _) = party.fcoms();
+        fcom.check_zero(channel, rng, &self.zeros)?;
+        let check_zero = timer.elapsed();
+
+        Ok(BucketSteps {
+            add: add.unwrap_or_default(),
+            check_zero: check_zero.unwrap_or_default(),
+            ..BucketSteps::default()
+        })
+    }
+}
+
+// Step 6) for one bucket, adding `r` to `edabits_vector`. The checks of the
+// bucket are added to `deferred`.
+#[allow(clippy::too_many_arguments)]
+fn conv_loop<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    edabits_vector: &[P::Edabits],
+    r: &[P::Edabits],
+    dabits: &[P::Dabit],
+    e_m_batch: &mut Vec<P::Mac>,
+    ei_batch: &mut Vec<F2>,
+    deferred: &mut DeferredChecks<P::Mac, P::MacF2>,
+) -> Result<BucketSteps, Error> {
+    let n = edabits_vector.len();
+    let nb_bits = edabits_vector[0].bits().len();
+    let power_two_nb_bits = power_two::<FE>(nb_bits);
+
+    // step 6)b) batched and moved up
+    let timer = Timer::start();
+    let e_batch = party.add_bits(channel, rng, edabits_vector, r)?;
+    let add = timer.elapsed();
+
+    // step 6)c) batched and moved up, the bits of the sums being kept for
+    // 6)e)
+    let timer = Timer::start();
+    let carries_start = deferred.carries.len();
+    let sum_bits_start = deferred.sum_bits.len();
+    for (mut e_bits, e_carry) in e_batch {
+        deferred.carries.push(e_carry);
+        deferred.sum_bits.extend_from_slice(&e_bits);
+        wipe(&mut e_bits);
+    }
+
+    e_m_batch.clear();
+    party.lift_bits(
+        channel,
+        dabits,
+        &deferred.carries[carries_start..],
+        e_m_batch,
+    )?;
+    let a2b = timer.elapsed();
+
+    let (fcom, _) = party.fcoms();
+    // 6)a)
+    let mut e_prime_batch: WipeOnDrop<P::Mac> = WipeOnDrop::new(Vec::with_capacity(n));
+    for i in 0..n {
+        let c_m = edabits_vector[i].value();
+        let r_m = r[i].value();
+        e_prime_batch.push(fcom.add(c_m, r_m));
+    }
+    // 6)c) done earlier
+    // 6)d)
+    fcom.fma_cst_slice(-power_two_nb_bits, e_m_batch, &mut e_prime_batch);
+    // 6)e)
+    let timer = Timer::start();
+    party.open_bits(channel, &deferred.sum_bits[sum_bits_start..], ei_batch)?;
+    let open = timer.elapsed();
+
+    // the check that `e_prime - sum` is zero is deferred
+    let (fcom, _) = party.fcoms();
+    for i in 0..n {
+        let sum = convert_bits_to_field::<FE>(&ei_batch[i * nb_bits..(i + 1) * nb_bits])?;
+        deferred
+            .zeros
+            .push(fcom.affine_add_cst(-sum, e_prime_batch[i]));
+    }
+
+    Ok(BucketSteps {
+        add: add.unwrap_or_default(),
+        a2b: a2b.unwrap_or_default(),
+        open: open.unwrap_or_default(),
+        check_zero: Duration::ZERO,
+    })
+}
+
+// Placeholder type of the custom checker of `conv` when it is given none.
+#[derive(Clone, Copy, Debug)]
+enum NoMultChecker {}
+
+impl<FE: FiniteField, P: FComParty<FE>> MultChecker<FE, P> for NoMultChecker {
+    fn push_triples(&mut self, _triples: &[(P::Mac, P::Mac, P::Mac)]) {
+        match *self {}
+    }
+
+    fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        _fcom: &mut P,
+        _channel: &mut C,
+        _rng: &mut RNG,
+    ) -> Result<(), Error> {
+        match *self {}
+    }
+}
+
+// The checker of the multiplications of a bucket: the checker given to
+// `conv_with_checker`, or the one selected by the configuration.
+enum BucketChecker<'a, FE: FiniteField, P: FComParty<FE>, K> {
+    QuickSilver(QuickSilverChecker<FE, P>),
+    Wolverine(WolverineChecker<'a, P::Mac>),
+    Custom(K),
+}
+
+impl<'a, FE: FiniteField, P: FComParty<FE>, K: MultChecker<FE, P> + Clone>
+    BucketChecker<'a, FE, P, K>
+{
+    // The random triples, `triple_bucket` per multiplication, are only used
+    // by the Wolverine check.
+    fn new(
+        custom: &Option<K>,
+        random_triples: &'a [(P::Mac, P::Mac, P::Mac)],
+        triple_bucket: usize,
+    ) -> Self {
+        match custom {
+            Some(checker) => BucketChecker::Custom(checker.clone()),
+            None if random_triples.is_empty() => {
+                BucketChecker::QuickSilver(QuickSilverChecker::new())
+            }
+            None => BucketChecker::Wolverine(WolverineChecker::with_bucket(
+                random_triples,
+                triple_bucket,
+            )),
         }
-        Ok(dabit_vec_mac)
     }
+}
 
-    /// Generate random triples
-    pub fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+impl<'a, FE: FiniteField, P: FComParty<FE>, K: MultChecker<FE, P>> MultChecker<FE, P>
+    for BucketChecker<'a, FE, P, K>
+{
+    fn start<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
+        fcom: &mut P,
         channel: &mut C,
         rng: &mut RNG,
-        num: usize,
-        out: &mut Vec<(MacVerifier<F40b>, MacVerifier<F40b>, MacVerifier<F40b>)>,
     ) -> Result<(), Error> {
-        let mut pairs = Vec::with_capacity(num);
-        for _ in 0..num {
-            let x = self.fcom_f2.random(channel, rng)?;
-            let y = self.fcom_f2.random(channel, rng)?;
-            pairs.push((x, y));
+        match self {
+            BucketChecker::QuickSilver(checker) => checker.start(fcom, channel, rng),
+            BucketChecker::Wolverine(checker) => {
+                MultChecker::<FE, P>::start(checker, fcom, channel, rng)
+            }
+            BucketChecker::Custom(checker) => checker.start(fcom, channel, rng),
         }
-        let mut zs = Vec::with_capacity(num);
-        self.fcom_f2.input_low_level(channel, rng, num, &mut zs)?;
+    }
 
-        for i in 0..num {
-            let (x, y) = pairs[i];
-            let z = zs[i];
-            out.push((x, y, z));
+    fn push_triples(&mut self, triples: &[(P::Mac, P::Mac, P::Mac)]) {
+        match self {
+            BucketChecker::QuickSilver(checker) => checker.push_triples(triples),
+            BucketChecker::Wolverine(checker) => {
+                MultChecker::<FE, P>::push_triples(checker, triples)
+            }
+            BucketChecker::Custom(checker) => checker.push_triples(triples),
         }
-        Ok(())
     }
 
-    fn fdabit<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
+        fcom: &mut P,
         channel: &mut C,
         rng: &mut RNG,
-        dabits_mac: &Vec<DabitVerifier<FE>>,
     ) -> Result<(), Error> {
-        let s = FDABIT_SECURITY_PARAMETER;
-        let n = dabits_mac.len();
-
-        let num_bits = std::mem::size_of::<usize>() * 8;
-        let gamma = num_bits - ((n + 1).leading_zeros() as usize) - 1 + 1;
-
-        check_parameters::<FE>(n, gamma)?;
-
-        let mut res = true;
-
-        // step 1)
-        let mut c_m_mac: Vec<Vec<MacVerifier<FE>>> = Vec::with_capacity(s);
-        for _ in 0..s {
-            let b_m_mac = self.fcom.input(channel, rng, gamma)?;
-            c_m_mac.push(b_m_mac);
-        }
-
-        let c1_mac = self.fcom_f2.input(channel, rng, s)?;
-
-        // step 2)
-        let mut triples = Vec::with_capacity(gamma * s);
-        let mut andl_mac_batch = Vec::with_capacity(gamma * s);
-        let mut one_minus_ci_mac_batch = Vec::with_capacity(gamma * s);
-        for k in 0..s {
-            for i in 0..gamma {
-                let andl_mac = c_m_mac[k][i];
-                let minus_ci_mac = // -ci
-                    self.fcom.affine_mult_cst(-FE::PrimeField::ONE, andl_mac);
-                let one_minus_ci_mac = // 1 - ci
-                    self.fcom.affine_add_cst(FE::PrimeField::ONE, minus_ci_mac);
-                andl_mac_batch.push(andl_mac);
-                one_minus_ci_mac_batch.push(one_minus_ci_mac);
+        match self {
+            BucketChecker::QuickSilver(checker) => checker.finalize(fcom, channel, rng),
+            BucketChecker::Wolverine(checker) => {
+                MultChecker::<FE, P>::finalize(checker, fcom, channel, rng)
             }
+            BucketChecker::Custom(checker) => checker.finalize(fcom, channel, rng),
         }
+    }
+}
 
-        let and_res_mac_batch = self.fcom.input(channel, rng, gamma * s)?;
-        for j in 0..s * gamma {
-            triples.push((
-                andl_mac_batch[j],
-                one_minus_ci_mac_batch[j],
-                and_res_mac_batch[j],
-            ));
-        }
+// Run the calibration of `MultCheck::Auto`, see [`Calibration`]. Its runs
+// are not reported to the observer of `conv`.
+fn calibrate<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    nb_bits: usize,
+    config: &ConvConfig,
+) -> Result<Calibration, Error> {
+    let start = channel.comm_breakdown();
+    let observer: Arc<dyn ConvObserver> = Arc::new(NoopObserver);
+    let mut durations = [None; 2];
+    for (duration, strategy) in durations
+        .iter_mut()
+        .zip([MultCheck::QuickSilver, MultCheck::Wolverine])
+    {
+        let config = ConvConfig::new()
+            .buckets(config.num_bucket)
+            .cut(config.num_cut)
+            .triple_buckets(config.triple_bucket)
+            .strategy(strategy);
+        let edabits = party.random_edabits(channel, rng, nb_bits, CALIBRATION_NUM)?;
+        let timer = Timer::start();
+        conv_phases(
+            party,
+            channel,
+            rng,
+            &edabits,
+            &config,
+            None::<BucketChannels<NoBucketChannel>>,
+            None::<NoMultChecker>,
+            &observer,
+        )?;
+        *duration = timer.elapsed();
+    }
+    let [quicksilver, wolverine] = durations;
+    let selected = party.select_mult_check(channel, quicksilver, wolverine)?;
+    let comm = match (start, channel.comm_breakdown()) {
+        (Some(start), Some(end)) => Some(end.since(&start).total()),
+        _ => None,
+    };
+    Ok(Calibration {
+        selected,
+        quicksilver,
+        wolverine,
+        comm,
+    })
+}
 
-        // step 3)
-        let seed = rng.gen::<Block>();
-        channel.write_block(&seed)?;
-        channel.flush()?;
-        let mut e_rng = AesRng::from_seed(seed);
-        let mut e = vec![Vec::with_capacity(n); s];
-        for k in 0..s {
-            for _i in 0..n {
-                let b = F2::random(&mut e_rng);
-                e[k].push(b);
-            }
-        }
+#[allow(clippy::too_many_arguments)]
+fn conv_phases<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+    C2: AbstractChannel + Send + 'static,
+    K: MultChecker<TF, P::FComF2> + Clone + Send + 'static,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    edabits_vector: &[P::Edabits],
+    config: &ConvConfig,
+    bucket_channels: Option<BucketChannels<C2>>,
+    custom_checker: Option<K>,
+    observer: &Arc<dyn ConvObserver>,
+) -> Result<Option<Calibration>, Error> {
+    config.validate()?;
+    check_bucket_channels(config, &bucket_channels)?;
+    let num_bucket = config.num_bucket;
+    let num_cut = config.num_cut;
+    let n = edabits_vector.len();
+    let nb_bits = batch_nb_bits(edabits_vector)?;
+    check_nb_bits::<FE>(nb_bits)?;
+    // fail early, and on both sides, if the parties disagree on the
+    // parameters, rather than with a failed check or a deadlock later
+    exchange_conv_header(
+        channel,
+        &conv_header(
+            party.protocol_version(),
+            config,
+            n,
+            nb_bits,
+            custom_checker.is_some(),
+        ),
+    )?;
+
+    let (calibration, with_quicksilver) = select_check(
+        party,
+        channel,
+        rng,
+        nb_bits,
+        config,
+        custom_checker.is_some(),
+    )?;
+
+    let estimate = if bucket_channels.is_none() {
+        // the buckets are checked with the pools of this instance
+        Some(ConvCostEstimate::conv_with_triple_bucket::<FE, TF>(
+            n,
+            nb_bits,
+            num_bucket,
+            num_cut,
+            config.triple_bucket,
+            with_quicksilver,
+        ))
+    } else {
+        None
+    };
+    top_up_phase(party, channel, rng, estimate, observer)?;
+
+    let material = prepare_buckets(
+        party,
+        channel,
+        rng,
+        nb_bits,
+        n,
+        num_bucket,
+        num_cut,
+        config.triple_bucket,
+        with_quicksilver,
+        observer,
+    )?;
+    check_buckets(
+        party,
+        channel,
+        rng,
+        edabits_vector,
+        material.buckets(0, n),
+        bucket_channels,
+        custom_checker,
+        observer,
+    )?;
+
+    Ok(calibration)
+}
 
-        // step 4)
-        let mut r_mac_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            let mut r_mac = c1_mac[k].0;
-            for i in 0..n {
-                // TODO: do not need to do it when e[i] is ZERO
-                let MacVerifier(tmp_mac) = self.fcom_f2.affine_mult_cst(e[k][i], dabits_mac[i].bit);
-                r_mac += tmp_mac;
-            }
-            r_mac_batch.push(MacVerifier(r_mac));
-        }
-
-        // step 5)
-        let mut r_batch = Vec::with_capacity(s);
-        self.fcom_f2.open(channel, &r_mac_batch, &mut r_batch)?;
-
-        // step 6)
-        let mut r_prime_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            // NOTE: for performance maybe step 4 and 6 should be combined in one loop
-            let mut r_prime_mac = FE::ZERO;
-            for i in 0..n {
-                // TODO: do not need to do it when e[i] is ZERO
-                let b = f2_to_fe(e[k][i]);
-                let MacVerifier(tmp_mac) = self.fcom.affine_mult_cst(b, dabits_mac[i].value);
-                r_prime_mac += tmp_mac;
-            }
-            r_prime_batch.push(r_prime_mac);
-        }
-
-        // step 7)
-        let mut tau_mac_batch = Vec::with_capacity(s);
-        for k in 0..s {
-            let mut tau_mac = r_prime_batch[k];
-            let mut twos = FE::PrimeField::ONE;
-            for i in 0..gamma {
-                let MacVerifier(tmp_mac) = self.fcom.affine_mult_cst(twos, c_m_mac[k][i]);
-                tau_mac += tmp_mac;
-                twos += twos;
-            }
-            tau_mac_batch.push(MacVerifier(tau_mac));
+// Select the multiplication check of the buckets, running the calibration
+// of `MultCheck::Auto` once per session, and return the calibration and
+// whether the random triples of the Wolverine check are not committed. A
+// custom checker replaces the check, so it needs neither.
+fn select_check<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    nb_bits: usize,
+    config: &ConvConfig,
+    custom_checker: bool,
+) -> Result<(Option<Calibration>, bool), Error> {
+    let calibration = if config.strategy == MultCheck::Auto && !custom_checker {
+        if party.calibration().is_none() {
+            let calibration = calibrate(party, channel, rng, nb_bits, config)?;
+            *party.calibration() = Some(calibration);
         }
+        *party.calibration()
+    } else {
+        None
+    };
+    let strategy = calibration.map_or(config.strategy, |c| c.selected);
+    Ok((
+        calibration,
+        custom_checker || strategy == MultCheck::QuickSilver,
+    ))
+}
 
-        let mut tau_batch = Vec::with_capacity(s);
-        self.fcom.open(channel, &tau_mac_batch, &mut tau_batch)?;
+// Top up the pools of VOLEs for `estimate`, or only refill those that are
+// low without an estimate, in the `CONV_SCOPE_VOLE` phase.
+fn top_up_phase<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    estimate: Option<ConvCostEstimate>,
+    observer: &Arc<dyn ConvObserver>,
+) -> Result<(), Error> {
+    let size = estimate.map_or(0, |estimate| estimate.voles_f2 + estimate.voles_fe);
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_VOLE, size);
+    let extensions = match estimate {
+        Some(estimate) => party.top_up(channel, rng, estimate)?,
+        None => party.refill_if_low(channel, rng)?,
+    };
+    phase.finish(channel, &**observer);
+    report_extensions(&**observer, extensions);
+    Ok(())
+}
 
-        // step 8)
-        for k in 0..s {
-            let b =
-                // mod2 is computed using the first bit of the bit decomposition.
-                // NOTE: This scales linearly with the size of the bit decomposition and could lead to potential inefficiencies
-                (r_batch[k] == F2::ONE) == tau_batch[k].bit_decomposition()[0];
-            res = res & b;
-        }
-        self.fcom
-            .quicksilver_check_multiply(channel, rng, &triples)?;
+// The random edabits, dabits and triples left for the buckets of `num`
+// edabits once the cut ones are opened, shuffled: `num_bucket` random
+// edabits and dabits per edabit and, with the Wolverine check,
+// `triple_bucket * nb_bits` random triples per random edabit.
+struct BucketMaterial<E, D, M> {
+    num_bucket: usize,
+    nb_bits: usize,
+    triple_bucket: usize,
+    edabits: Vec<E>,
+    dabits: Vec<D>,
+    triples: Vec<(M, M, M)>,
+}
 
-        if res {
-            Ok(())
+impl<E, D, M> BucketMaterial<E, D, M> {
+    // The material of the buckets of `n` edabits, starting after that of
+    // `start` edabits.
+    fn buckets(&self, start: usize, n: usize) -> Buckets<'_, E, D, M> {
+        let (lo, hi) = (start * self.num_bucket, (start + n) * self.num_bucket);
+        let triples_per_edabit = self.triple_bucket * self.nb_bits;
+        let triples: &[_] = if self.triples.is_empty() {
+            &[]
         } else {
-            Err(Error::Other("fail fdabit verifier".to_string()))
+            &self.triples[lo * triples_per_edabit..hi * triples_per_edabit]
+        };
+        Buckets {
+            num_bucket: self.num_bucket,
+            triple_bucket: self.triple_bucket,
+            edabits: &self.edabits[lo..hi],
+            dabits: &self.dabits[lo..hi],
+            triples,
         }
     }
+}
 
-    fn conv_loop<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        edabits_vector_mac: &[EdabitsVerifier<FE>],
-        r_mac: &[EdabitsVerifier<FE>],
-        dabits_mac: &[DabitVerifier<FE>],
-        convert_bit_2_field_aux1: &mut Vec<MacVerifier<F40b>>,
-        convert_bit_2_field_aux2: &mut Vec<F2>,
-        e_m_batch: &mut Vec<MacVerifier<FE>>,
-        ei_batch: &mut Vec<F2>,
-        random_triples: &[(MacVerifier<F40b>, MacVerifier<F40b>, MacVerifier<F40b>)],
-    ) -> Result<(), Error> {
-        let n = edabits_vector_mac.len();
-        let nb_bits = edabits_vector_mac[0].bits.len();
-        let power_two_nb_bits = power_two::<FE::PrimeField>(nb_bits);
-
-        // step 6)b) batched and moved up
-        print!("ADD< ... ");
-        let start = Instant::now();
-        let e_batch =
-            self.bit_add_carry(channel, rng, edabits_vector_mac, &r_mac, &random_triples)?;
-        println!("ADD> {:?}", start.elapsed());
-
-        // step 6)c) batched and moved up
-        print!("A2B< ...");
-        let start = Instant::now();
-        let mut e_carry_mac_batch = Vec::with_capacity(n);
-        for (_, e_carry) in e_batch.iter() {
-            e_carry_mac_batch.push(e_carry.clone());
-        }
-
-        self.convert_bit_2_field(
-            channel,
-            &dabits_mac,
-            &e_carry_mac_batch,
-            convert_bit_2_field_aux1,
-            convert_bit_2_field_aux2,
-            e_m_batch,
-        )?;
-        println!("A2B> {:?}", start.elapsed());
-
-        // 6)a)
-        let mut e_prime_mac_batch = Vec::with_capacity(n);
-        // 6)d)
-        let mut ei_mac_batch = Vec::with_capacity(n * nb_bits);
-        for i in 0..n {
-            // 6)a)
-            let c_m = edabits_vector_mac[i].value;
-            let r_m = r_mac[i].value;
-            let c_plus_r = self.fcom.add(c_m, r_m);
-
-            // 6)c) done earlier
-            let e_m = e_m_batch[i];
-
-            // 6)d)
-            let e_prime = self
-                .fcom
-                .add(c_plus_r, self.fcom.affine_mult_cst(-power_two_nb_bits, e_m));
-            e_prime_mac_batch.push(e_prime);
-
-            // 6)e)
-            ei_mac_batch.extend(&e_batch[i].0);
-        }
-        // 6)e)
-        print!("OPEN< ... ");
-        let start = Instant::now();
-        self.fcom_f2.open(channel, &ei_mac_batch, ei_batch)?;
-        println!("OPEN> {:?}", start.elapsed());
-
-        let mut e_prime_minus_sum_batch = Vec::with_capacity(n);
-        for i in 0..n {
-            let sum =
-                convert_bits_to_field::<FE::PrimeField>(&ei_batch[i * nb_bits..(i + 1) * nb_bits]);
-            e_prime_minus_sum_batch.push(self.fcom.affine_add_cst(-sum, e_prime_mac_batch[i]));
-        }
-        print!("CHECK_Z< ... ");
-        let start = Instant::now();
-        self.fcom
-            .check_zero(channel, rng, &e_prime_minus_sum_batch)?;
-        println!("CHECK_Z> {:?}", start.elapsed());
+// The material of the buckets of `n` edabits: the bucket `j` checks them
+// with the random edabits and dabits `j * n..(j + 1) * n`, and with the
+// triples `j * t..(j + 1) * t` for `t = triple_bucket * n * nb_bits`, which
+// are empty with the QuickSilver check.
+struct Buckets<'a, E, D, M> {
+    num_bucket: usize,
+    triple_bucket: usize,
+    edabits: &'a [E],
+    dabits: &'a [D],
+    triples: &'a [(M, M, M)],
+}
 
-        Ok(())
+// Steps 1) to 5) of `conv` for `n` edabits: commit to the random edabits,
+// dabits and triples, check the dabits, shuffle them, and open the cut
+// edabits and triples.
+#[allow(clippy::too_many_arguments)]
+fn prepare_buckets<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    nb_bits: usize,
+    n: usize,
+    num_bucket: usize,
+    num_cut: usize,
+    triple_bucket: usize,
+    with_quicksilver: bool,
+    observer: &Arc<dyn ConvObserver>,
+) -> Result<BucketMaterial<P::Edabits, P::Dabit, P::MacF2>, Error> {
+    let nb_random_edabits = n * num_bucket + num_cut;
+    let nb_random_dabits = n * num_bucket;
+
+    // step 1)a): commit random edabit
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_EDABITS, nb_random_edabits);
+    let mut r = party.random_edabits(channel, rng, nb_bits, nb_random_edabits)?;
+    phase.finish(channel, &**observer);
+
+    // step 1)b)
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_DABITS, nb_random_dabits);
+    let mut dabits = party.random_dabits(channel, rng, nb_random_dabits)?;
+    phase.finish(channel, &**observer);
+
+    // step 1)c): multiplication triples, `triple_bucket` per multiplication
+    // of the buckets
+    let nb_bucket_triples = triple_bucket * num_bucket * n * nb_bits;
+    let how_many = if with_quicksilver {
+        0
+    } else {
+        nb_bucket_triples + num_cut * nb_bits
+    };
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_TRIPLES, how_many);
+    let mut random_triples = Vec::new();
+    if !with_quicksilver {
+        // with wolverine
+        party.random_triples(channel, rng, how_many, &mut random_triples)?;
     }
-
-    /// conversion checking
-    pub fn conv<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        num_bucket: usize,
-        num_cut: usize,
-        edabits_vector_mac: &[EdabitsVerifier<FE>],
-        bucket_channels: Option<Vec<SyncChannel<BufReader<TcpStream>, BufWriter<TcpStream>>>>,
-        with_quicksilver: bool,
-    ) -> Result<(), Error> {
-        let n = edabits_vector_mac.len();
-        let nb_bits = edabits_vector_mac[0].bits.len();
-        let nb_random_edabits = n * num_bucket + num_cut;
-        let nb_random_dabits = n * num_bucket;
-
-        let phase1 = Instant::now();
-        // step 1)a)
-        print!("Step 1)a) RANDOM EDABITS ... ");
-        let start = Instant::now();
-        let mut r_mac = self.random_edabits(channel, rng, nb_bits, nb_random_edabits)?;
-        println!("{:?}", start.elapsed());
-
-        // step 1)b)
-        print!("Step 1)b) RANDOM DABITS ... ");
-        let start = Instant::now();
-        let mut dabits_mac = self.random_dabits(channel, rng, nb_random_dabits)?;
-        println!("{:?}", start.elapsed());
-
-        // step 1)c):
-        print!("Step 1)c) RANDOM TRIPLES ... ");
-        let mut random_triples = Vec::new();
-        let start = Instant::now();
-        if !with_quicksilver {
-            // with wolverine
-            let how_many = num_bucket * n * nb_bits + num_cut * nb_bits;
-            self.random_triples(channel, rng, how_many, &mut random_triples)?;
-        }
-        println!("{:?}", start.elapsed());
-
-        // step 2)
-        print!("Step 2) CHECK DABITS ... ");
-        let start = Instant::now();
-        self.fdabit(channel, rng, &dabits_mac)?;
-        println!("{:?}", start.elapsed());
-
-        // step 3): get seed for permutation
-        let seed = rng.gen::<Block>();
-        channel.write_block(&seed)?;
-        channel.flush()?;
-        let mut shuffle_rng = AesRng::from_seed(seed);
-
-        // step 4): shuffle the edabits, dabits, triples
-        print!("Step 4) SHUFFLE ... ");
-        let start = Instant::now();
-        generate_permutation(&mut shuffle_rng, &mut r_mac);
-        generate_permutation(&mut shuffle_rng, &mut dabits_mac);
-        generate_permutation(&mut shuffle_rng, &mut random_triples);
-        println!("{:?}", start.elapsed());
-
-        // step 5)a):
-        print!("Step 5)a) OPEN edabits ... ");
-        let start = Instant::now();
-        let base = n * num_bucket;
-        let mut a_vec = Vec::with_capacity(nb_bits);
-        let mut a_m = Vec::with_capacity(1);
-        for i in 0..num_cut {
-            let idx = base + i;
-            let a_mac = &r_mac[idx];
-            self.fcom_f2.open(channel, &a_mac.bits, &mut a_vec)?;
-            self.fcom.open(channel, &[a_mac.value], &mut a_m)?;
-            if convert_bits_to_field::<FE::PrimeField>(&a_vec) != a_m[0] {
-                return Err(Error::Other("Wrong open random edabit".to_string()));
-            }
-        }
-        println!("{:?}", start.elapsed());
-
-        // step 5) b):
-        print!("Step 5)b) OPEN triples ... ");
-        let start = Instant::now();
-        if !with_quicksilver {
-            let mut res = Vec::with_capacity(2);
-            let base = n * num_bucket * nb_bits;
-            for i in 0..num_cut * nb_bits {
-                let (x_mac, y_mac, z_mac) = random_triples[base + i];
-                self.fcom_f2.open(channel, &[x_mac, y_mac], &mut res)?;
-                let x = res[0];
-                let y = res[1];
-                let v = self.fcom_f2.affine_add_cst(-(x * y), z_mac);
-                self.fcom_f2.check_zero(channel, rng, &[v])?;
-            }
+    phase.finish(channel, &**observer);
+
+    // step 2)
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_FDABIT, nb_random_dabits);
+    party.fdabit(channel, rng, &dabits)?;
+    phase.finish(channel, &**observer);
+
+    // step 3): get seed for permutation, tossing coins with the other party
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_SHUFFLE, nb_random_edabits);
+    let mut shuffle_rng = AesRng::from_seed(party.shuffle_seed(channel, rng)?);
+
+    // step 4): shuffle edabits, dabits and triples
+    generate_permutation(&mut shuffle_rng, &mut r);
+    generate_permutation(&mut shuffle_rng, &mut dabits);
+    generate_permutation(&mut shuffle_rng, &mut random_triples);
+    phase.finish(channel, &**observer);
+
+    // step 5)a): the verifier checks that the opened cut edabits are
+    // consistent
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_CUT_AND_CHOOSE, num_cut);
+    let base = n * num_bucket;
+    let mut a_bits_mac = Vec::with_capacity(num_cut * nb_bits);
+    let mut a_values_mac = Vec::with_capacity(num_cut);
+    for a in r[base..base + num_cut].iter() {
+        a_bits_mac.extend(a.bits().iter());
+        a_values_mac.push(a.value());
+    }
+    let mut a_bits = Vec::with_capacity(num_cut * nb_bits);
+    party.open_bits(channel, &a_bits_mac, &mut a_bits)?;
+    let (fcom, fcom_f2) = party.fcoms();
+    open_cut_edabits::<FE, _, _>(fcom, channel, &a_bits, &a_values_mac, nb_bits)?;
+
+    // step 5) b): the verifier checks that the opened cut triples are
+    // multiplication triples
+    if !with_quicksilver {
+        let base = nb_bucket_triples;
+        let cut_triples = &random_triples[base..base + num_cut * nb_bits];
+        let mut xy_mac = Vec::with_capacity(2 * cut_triples.len());
+        let mut z_mac = Vec::with_capacity(cut_triples.len());
+        for (x, y, z) in cut_triples.iter() {
+            xy_mac.push(*x);
+            xy_mac.push(*y);
+            z_mac.push(*z);
         }
-        println!("{:?}", start.elapsed());
-
-        println!("Total Steps 1-2-3-4-5: {:?}", phase1.elapsed());
+        let xy = fcom_f2.open(channel, &xy_mac)?;
+        fcom_f2.open_and_check(channel, &z_mac, |i, z| xy[2 * i] * xy[2 * i + 1] == z)?;
+    }
+    phase.finish(channel, &**observer);
+
+    // the cut edabits and triples are opened, only the others are left
+    r.truncate(n * num_bucket);
+    random_triples.truncate(nb_bucket_triples);
+    Ok(BucketMaterial {
+        num_bucket,
+        nb_bits,
+        triple_bucket,
+        edabits: r,
+        dabits,
+        triples: random_triples,
+    })
+}
 
-        let phase2 = Instant::now();
-        // step 6)
-        println!("step 6)a-e) bitADDcarry etc: ... ");
+// Report the buckets `buckets` checked in a row with `steps`, once their
+// deferred checks passed in `deferred`, whose durations are counted with the
+// last bucket.
+fn report_buckets(
+    observer: &dyn ConvObserver,
+    buckets: &[usize],
+    mut steps: Vec<BucketSteps>,
+    deferred: BucketSteps,
+) {
+    if let Some(last) = steps.last_mut() {
+        *last += deferred;
+    }
+    for (bucket, steps) in buckets.iter().zip(steps.iter()) {
+        observer.bucket_checked(*bucket, steps);
+        observer.bucket_completed(*bucket);
+    }
+}
 
-        if bucket_channels.is_none() {
-            let mut convert_bit_2_field_aux1 = Vec::with_capacity(n);
-            let mut convert_bit_2_field_aux2 = Vec::with_capacity(n);
+// Step 6) of `conv`: check the buckets of `edabits_vector`.
+#[allow(clippy::too_many_arguments)]
+fn check_buckets<
+    FE: FiniteField<PrimeField = FE>,
+    TF: FiniteField<PrimeField = F2>,
+    P: ConvParty<FE, TF>,
+    C: AbstractChannel,
+    RNG: CryptoRng + Rng,
+    C2: AbstractChannel + Send + 'static,
+    K: MultChecker<TF, P::FComF2> + Clone + Send + 'static,
+>(
+    party: &mut P,
+    channel: &mut C,
+    rng: &mut RNG,
+    edabits_vector: &[P::Edabits],
+    buckets: Buckets<'_, P::Edabits, P::Dabit, P::MacF2>,
+    bucket_channels: Option<BucketChannels<C2>>,
+    custom_checker: Option<K>,
+    observer: &Arc<dyn ConvObserver>,
+) -> Result<(), Error> {
+    let (num_bucket, triple_bucket) = (buckets.num_bucket, buckets.triple_bucket);
+    let n = edabits_vector.len();
+    let (r, dabits, random_triples) = (buckets.edabits, buckets.dabits, buckets.triples);
+
+    // step 6)
+    let phase = ConvPhase::start(channel, &**observer, CONV_SCOPE_BUCKETS, num_bucket);
+    match bucket_channels {
+        None => {
             let mut e_m_batch = Vec::with_capacity(n);
             let mut ei_batch = Vec::with_capacity(n);
+            let mut deferred = DeferredChecks::new();
+            let mut steps = Vec::with_capacity(num_bucket);
             for j in 0..num_bucket {
                 // base index for the window of `idx_base..idx_base + n` values
                 let idx_base = j * n;
 
-                if with_quicksilver {
-                    self.conv_loop(
-                        channel,
-                        rng,
-                        &edabits_vector_mac,
-                        &r_mac[idx_base..idx_base + n],
-                        &dabits_mac[idx_base..idx_base + n],
-                        &mut convert_bit_2_field_aux1,
-                        &mut convert_bit_2_field_aux2,
-                        &mut e_m_batch,
-                        &mut ei_batch,
-                        &Vec::new(),
-                    )?;
-                } else {
-                    self.conv_loop(
-                        channel,
-                        rng,
-                        &edabits_vector_mac,
-                        &r_mac[idx_base..idx_base + n],
-                        &dabits_mac[idx_base..idx_base + n],
-                        &mut convert_bit_2_field_aux1,
-                        &mut convert_bit_2_field_aux2,
-                        &mut e_m_batch,
-                        &mut ei_batch,
-                        &random_triples[idx_base * nb_bits..idx_base * nb_bits + n * nb_bits],
-                    )?;
-                }
+                report_extensions(&**observer, party.refill_if_low(channel, rng)?);
+                steps.push(conv_loop(
+                    party,
+                    channel,
+                    rng,
+                    edabits_vector,
+                    &r[idx_base..idx_base + n],
+                    &dabits[idx_base..idx_base + n],
+                    &mut e_m_batch,
+                    &mut ei_batch,
+                    &mut deferred,
+                )?);
             }
-        } else {
-            let mut j = 0;
-            let mut handles = Vec::new();
-            for mut bucket_channel in bucket_channels.unwrap().into_iter() {
-                // base index for the window of `idx_base..idx_base + n` values
-                let idx_base = j * n;
-
-                // splitting the vectors to spawn
-                let mut edabits_vector_mac_par = Vec::with_capacity(n);
-                for edabits in edabits_vector_mac.iter() {
-                    edabits_vector_mac_par.push(copy_edabits_verifier(edabits));
-                }
-
-                let mut r_mac_par = Vec::with_capacity(n);
-                for r_elm in r_mac[idx_base..idx_base + n].iter() {
-                    r_mac_par.push(copy_edabits_verifier(r_elm));
-                }
-
-                let mut dabits_mac_par = Vec::with_capacity(n);
-                for elm in dabits_mac[idx_base..idx_base + n].iter() {
-                    dabits_mac_par.push(elm.clone());
-                }
-
-                let mut random_triples_par = Vec::new(); //with_capacity(n * nb_bits);
-                if !with_quicksilver {
-                    //let mut random_triples_par = Vec::with_capacity(n * nb_bits);
-                    for elm in
-                        random_triples[idx_base * nb_bits..idx_base * nb_bits + n * nb_bits].iter()
-                    {
-                        random_triples_par.push(elm.clone());
-                    }
+            let buckets: Vec<_> = (0..num_bucket).collect();
+            let mut checker = BucketChecker::<TF, P::FComF2, K>::new(
+                &custom_checker,
+                random_triples,
+                triple_bucket,
+            );
+            let deferred_steps = deferred.finalize(
+                party,
+                channel,
+                rng,
+                edabits_vector,
+                r,
+                &buckets,
+                &mut checker,
+            )?;
+            report_buckets(&**observer, &buckets, steps, deferred_steps);
+        }
+        #[cfg(feature = "threads")]
+        Some(bucket_channels) => {
+            let nb_bits = edabits_vector[0].bits().len();
+            // The inputs are shared by the bucket threads rather than copied
+            // for each of them.
+            let edabits_shared: Arc<[P::Edabits]> = edabits_vector.into();
+            let r_shared: Arc<[P::Edabits]> = r.into();
+            let dabits_shared: Arc<[P::Dabit]> = dabits.into();
+            let triples_shared: Arc<[(P::MacF2, P::MacF2, P::MacF2)]> = random_triples.into();
+            let triples_per_bucket = if random_triples.is_empty() {
+                0
+            } else {
+                triple_bucket * n * nb_bits
+            };
+            let mut triples_start = 0;
+            // Every bucket thread gets its own stream derived from a single
+            // seed, so that `rng` alone determines the whole run.
+            let bucket_seed = rng.gen::<Block>();
+            let threads = bucket_channels.len();
+            // the parties are duplicated over `channel` before it may be
+            // multiplexed
+            let mut bucket_parties = Vec::with_capacity(threads);
+            for _ in 0..threads {
+                bucket_parties.push(party.duplicate(channel, rng)?);
+            }
+            let (bucket_channels, pumps) = match bucket_channels {
+                BucketChannels::Separate(bucket_channels) => (bucket_channels, None),
+                BucketChannels::Multiplexed(_, multiplex) => {
+                    let (bucket_channels, pumps) = multiplex();
+                    (bucket_channels, Some(pumps))
                 }
-
-                let mut new_verifier = self.duplicate(channel, rng)?;
-                let handle = std::thread::spawn(move || {
-                    let mut convert_bit_2_field_aux1 = Vec::with_capacity(n);
-                    let mut convert_bit_2_field_aux2 = Vec::with_capacity(n);
+            };
+            let mut handles = Vec::new();
+            for (i, (mut bucket_channel, mut bucket_party)) in
+                bucket_channels.into_iter().zip(bucket_parties).enumerate()
+            {
+                // the thread `i` checks the buckets `i`, `i + threads`, ...
+                let buckets = (i..num_bucket).step_by(threads).collect::<Vec<_>>();
+                // ... with the next random triples, as many as its buckets
+                // need
+                let triples = triples_start..triples_start + buckets.len() * triples_per_bucket;
+                triples_start = triples.end;
+
+                let edabits_vector = edabits_shared.clone();
+                let r = r_shared.clone();
+                let dabits = dabits_shared.clone();
+                let random_triples = triples_shared.clone();
+                let mut bucket_rng = AesRng::from_seed_and_stream(bucket_seed, i as u64);
+                let bucket_observer = observer.clone();
+                let bucket_checker = custom_checker.clone();
+                let handle = std::thread::spawn(move || -> Result<(), Error> {
                     let mut e_m_batch = Vec::with_capacity(n);
                     let mut ei_batch = Vec::with_capacity(n);
-                    new_verifier.conv_loop(
+                    let mut deferred = DeferredChecks::new();
+                    let mut steps = Vec::with_capacity(buckets.len());
+                    for (k, j) in buckets.iter().enumerate() {
+                        if k > 0 {
+                            let extensions =
+                                bucket_party.refill_if_low(&mut bucket_channel, &mut bucket_rng)?;
+                            report_extensions(&*bucket_observer, extensions);
+                        }
+                        // base index for the window of `idx_base..idx_base + n` values
+                        let idx_base = j * n;
+                        steps.push(conv_loop(
+                            &mut bucket_party,
+                            &mut bucket_channel,
+                            &mut bucket_rng,
+                            &edabits_vector,
+                            &r[idx_base..idx_base + n],
+                            &dabits[idx_base..idx_base + n],
+                            &mut e_m_batch,
+                            &mut ei_batch,
+                            &mut deferred,
+                        )?);
+                    }
+                    let mut checker = BucketChecker::<TF, P::FComF2, K>::new(
+                        &bucket_checker,
+                        &random_triples[triples],
+                        triple_bucket,
+                    );
+                    let deferred_steps = deferred.finalize(
+                        &mut bucket_party,
                         &mut bucket_channel,
-                        &mut AesRng::new(),
-                        &edabits_vector_mac_par,
-                        &r_mac_par,
-                        &dabits_mac_par,
-                        &mut convert_bit_2_field_aux1,
-                        &mut convert_bit_2_field_aux2,
-                        &mut e_m_batch,
-                        &mut ei_batch,
-                        &random_triples_par,
-                    )
+                        &mut bucket_rng,
+                        &edabits_vector,
+                        &r,
+                        &buckets,
+                        &mut checker,
+                    )?;
+                    report_buckets(&*bucket_observer, &buckets, steps, deferred_steps);
+                    Ok(())
                 });
                 handles.push(handle);
-
-                j += 1;
             }
 
+            // every thread is joined before reporting the first failure
+            let mut res = Ok(());
             for handle in handles {
-                handle.join().unwrap().unwrap();
+                let thread_res = handle.join().unwrap();
+                if res.is_ok() {
+                    res = thread_res;
+                }
             }
+            // `channel` is only used again once the multiplexer is closed
+            if let Some(pumps) = pumps {
+                let pumps_res = pumps.join().map_err(Error::from);
+                if res.is_ok() {
+                    res = pumps_res;
+                }
+            }
+            res?;
         }
-        println!("step 6)a-e) bitADDcarry etc: {:?}", phase2.elapsed());
-
-        Ok(())
+        #[cfg(not(feature = "threads"))]
+        Some(_) => unreachable!("rejected by `check_bucket_channels`"),
     }
+    phase.finish(channel, &**observer);
+
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
+/// Version of the wire format of the conversion protocol. Both parties must
+/// use the same version.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProtocolVersion {
+    /// The original format, with one opened bit per byte.
+    #[default]
+    V1,
+    /// The bits opened in the cut-and-choose and in the buckets are packed
+    /// eight per byte.
+    V2,
+}
 
-    use super::super::homcom::{MacProver, MacVerifier};
-    use super::{
-        f2_to_fe, DabitProver, DabitVerifier, EdabitsProver, EdabitsVerifier, ProverConv,
-        VerifierConv,
-    };
-    use crate::svole::wykw::{LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
-    use scuttlebutt::ring::FiniteRing;
-    use scuttlebutt::{
-        field::{F61p, FiniteField, F2},
-        AesRng, Channel,
-    };
-    use std::{
-        io::{BufReader, BufWriter},
-    };
-    use uds_windows::UnixStream;
-    
-    const DEFAULT_NUM_BUCKET: usize = 5;
-    const DEFAULT_NUM_CUT: usize = 5;
-    const NB_BITS: usize = 38;
+/// A deviation of a cheating prover from the conversion protocol, injected
+/// with [`ProverConv::set_fault`] to test that the verifier rejects it.
+///
+/// The indices are the positions in each batch generated while the fault is
+/// set, so that a fault at index 0 applies to every batch.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvFault {
+    /// The value of the random edabit `index` is committed with its bit
+    /// `bit` flipped.
+    EdabitBit {
+        /// The edabit in the batch.
+        index: usize,
+        /// The flipped bit, least significant first.
+        bit: usize,
+    },
+    /// The dabit `index` commits to the negation of its bit in `FE`.
+    Dabit {
+        /// The dabit in the batch.
+        index: usize,
+    },
+    /// The product of the carry at bit `bit` of the addition `index` is
+    /// flipped in the bit additions of the buckets.
+    Carry {
+        /// The addition in the batch.
+        index: usize,
+        /// The bit of the addition.
+        bit: usize,
+    },
+    /// The multiplication triple `index` commits to `x * y + 1`.
+    Triple {
+        /// The triple in the batch.
+        index: usize,
+    },
+}
 
-    fn test_convert_bit_2_field<FE: FiniteField<PrimeField = FE>>() -> () {
-        let count = 100;
-        let (sender, receiver) = UnixStream::pair().unwrap();
-        let handle = std::thread::spawn(move || {
-            let mut rng = AesRng::new();
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
-            let mut fconv =
-                ProverConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                    .unwrap();
+/// Check of the multiplications of the bit additions in the buckets. Other
+/// checks can be given to `conv_with_checker` as a [`MultChecker`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MultCheck {
+    /// A single random linear combination of all the multiplications of the
+    /// buckets checked over a channel.
+    #[default]
+    QuickSilver,
+    /// Multiplication triples, committed upfront and checked with
+    /// cut-and-choose, as many per multiplication as
+    /// `ConvConfig::triple_buckets`.
+    Wolverine,
+    /// One of the two others, selected at the first `conv` of the session
+    /// by a calibration, see [`Calibration`].
+    Auto,
+}
 
-            let mut res = Vec::new();
-            for _ in 0..count {
-                let MacProver(rb, rb_mac) = fconv.fcom_f2.random(&mut channel, &mut rng).unwrap();
-                let rm = f2_to_fe(rb);
-                let rm_mac = fconv.fcom.input(&mut channel, &mut rng, &[rm]).unwrap()[0];
-                let MacProver(x_f2, x_f2_mac) =
-                    fconv.fcom_f2.random(&mut channel, &mut rng).unwrap();
+/// Number of edabits converted with each multiplication check by the
+/// calibration of [`MultCheck::Auto`].
+pub const CALIBRATION_NUM: usize = 16;
+
+/// Calibration selecting the multiplication check of [`MultCheck::Auto`].
+///
+/// At the first `conv` of a session with `MultCheck::Auto`, both parties
+/// convert `CALIBRATION_NUM` random edabits with QuickSilver and then with
+/// Wolverine, with the bucketing parameters of the configuration, and the
+/// verifier selects the check of its faster run and sends it to the prover.
+/// The session then uses that check. Without the `std-time` feature the
+/// runs are not timed and QuickSilver is selected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    /// The check selected, `QuickSilver` or `Wolverine`.
+    pub selected: MultCheck,
+    /// Duration of the run with QuickSilver measured by this party, with
+    /// the `std-time` feature.
+    pub quicksilver: Option<Duration>,
+    /// Duration of the run with Wolverine measured by this party, with the
+    /// `std-time` feature.
+    pub wolverine: Option<Duration>,
+    /// Communication of the calibration on the channel given to `conv`,
+    /// when the channel keeps track of it. It is part of the communication
+    /// of the first `conv` of the session.
+    pub comm: Option<ScopeComm>,
+}
 
-                let mut convert_bit_2_field_aux = Vec::new();
-                let mut x_m_batch = Vec::new();
-                fconv
-                    .convert_bit_2_field(
-                        &mut channel,
-                        &[DabitProver {
-                            bit: MacProver(rb, rb_mac),
-                            value: MacProver(rm, rm_mac),
-                        }],
-                        &[MacProver(x_f2, x_f2_mac)],
-                        &mut convert_bit_2_field_aux,
-                        &mut x_m_batch,
-                    )
-                    .unwrap();
+/// Parameters of `conv`.
+///
+/// The default configuration checks 5 buckets and cuts 5 edabits with the
+/// QuickSilver multiplication check, on the calling thread, without an
+/// observer. With the Wolverine check, every multiplication is checked
+/// against one random triple by default. Both parties must use the same
+/// configuration, except for `threads` and the observer. The observer is
+/// ignored when comparing and serializing configurations.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ConvConfig {
+    num_bucket: usize,
+    num_cut: usize,
+    strategy: MultCheck,
+    triple_bucket: usize,
+    threads: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observer: Option<Arc<dyn ConvObserver>>,
+}
 
-                let _ = fconv.fcom.open(&mut channel, &x_m_batch).unwrap();
-                assert_eq!(f2_to_fe::<FE::PrimeField>(x_f2), x_m_batch[0].0);
-                res.push((x_f2, x_m_batch[0].0));
-            }
-            res
-        });
-        let mut rng = AesRng::new();
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
-        let mut fconv =
-            VerifierConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                .unwrap();
+impl Default for ConvConfig {
+    fn default() -> Self {
+        ConvConfig {
+            num_bucket: 5,
+            num_cut: 5,
+            strategy: MultCheck::QuickSilver,
+            triple_bucket: 1,
+            threads: 1,
+            observer: None,
+        }
+    }
+}
 
-        let mut res = Vec::new();
-        for _ in 0..count {
-            let rb_mac = fconv.fcom_f2.random(&mut channel, &mut rng).unwrap();
-            let r_m_mac = fconv.fcom.input(&mut channel, &mut rng, 1).unwrap()[0];
-            let x_f2_mac = fconv.fcom_f2.random(&mut channel, &mut rng).unwrap();
-
-            let mut convert_bit_2_field_aux1 = Vec::new();
-            let mut convert_bit_2_field_aux2 = Vec::new();
-            let mut x_m_batch = Vec::new();
-            fconv
-                .convert_bit_2_field(
-                    &mut channel,
-                    &[DabitVerifier {
-                        bit: rb_mac,
-                        value: r_m_mac,
-                    }],
-                    &[x_f2_mac],
-                    &mut convert_bit_2_field_aux1,
-                    &mut convert_bit_2_field_aux2,
-                    &mut x_m_batch,
-                )
-                .unwrap();
+impl std::fmt::Debug for ConvConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConvConfig")
+            .field("num_bucket", &self.num_bucket)
+            .field("num_cut", &self.num_cut)
+            .field("strategy", &self.strategy)
+            .field("triple_bucket", &self.triple_bucket)
+            .field("threads", &self.threads)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
 
-            let mut x_m = Vec::new();
-            fconv
-                .fcom
-                .open(&mut channel, &[x_m_batch[0]], &mut x_m)
-                .unwrap();
-            res.push(x_m[0]);
-        }
+impl PartialEq for ConvConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_bucket == other.num_bucket
+            && self.num_cut == other.num_cut
+            && self.strategy == other.strategy
+            && self.triple_bucket == other.triple_bucket
+            && self.threads == other.threads
+    }
+}
 
-        let resprover = handle.join().unwrap();
+impl Eq for ConvConfig {}
 
-        for i in 0..count {
-            assert_eq!(resprover[i].1, res[i]);
-        }
+impl ConvConfig {
+    /// Return the default configuration.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    fn test_bit_add_carry<FE: FiniteField<PrimeField = FE>>() -> () {
-        let power = 6;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+    /// Set the number of buckets.
+    pub fn buckets(mut self, num_bucket: usize) -> Self {
+        self.num_bucket = num_bucket;
+        self
+    }
 
-        // adding
-        //   110101
-        //   101110
-        // --------
-        //  1100011
-        let x = vec![F2::ONE, F2::ZERO, F2::ONE, F2::ZERO, F2::ONE, F2::ONE];
-        let y = vec![F2::ZERO, F2::ONE, F2::ONE, F2::ONE, F2::ZERO, F2::ONE];
-        let expected = vec![F2::ONE, F2::ONE, F2::ZERO, F2::ZERO, F2::ZERO, F2::ONE];
-        let carry = F2::ONE;
+    /// Set the number of edabits opened by the cut-and-choose.
+    pub fn cut(mut self, num_cut: usize) -> Self {
+        self.num_cut = num_cut;
+        self
+    }
 
-        let handle = std::thread::spawn(move || {
-            let mut rng = AesRng::new();
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
-            let mut fconv =
-                ProverConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                    .unwrap();
+    /// Set the multiplication check.
+    pub fn strategy(mut self, strategy: MultCheck) -> Self {
+        self.strategy = strategy;
+        self
+    }
 
-            let x_mac = fconv.fcom_f2.input(&mut channel, &mut rng, &x).unwrap();
-            let y_mac = fconv.fcom_f2.input(&mut channel, &mut rng, &y).unwrap();
+    /// Set the number of random triples each multiplication is checked
+    /// against with the Wolverine check, see [`select_triple_bucket`].
+    pub fn triple_buckets(mut self, triple_bucket: usize) -> Self {
+        self.triple_bucket = triple_bucket;
+        self
+    }
 
-            let mut vx = Vec::new();
-            for i in 0..power {
-                vx.push(MacProver(x[i], x_mac[i]));
-            }
+    /// Set the number of threads checking the buckets, each over its own
+    /// bucket channel, see `conv_with_bucket_channels`.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
 
-            let mut vy = Vec::new();
-            for i in 0..power {
-                vy.push(MacProver(y[i], y_mac[i]));
-            }
-            let default_fe = MacProver(FE::PrimeField::ZERO, FE::ZERO);
-            let (res, c) = fconv
-                .bit_add_carry(
-                    &mut channel,
-                    &mut rng,
-                    &[EdabitsProver {
-                        bits: vx,
-                        value: default_fe,
-                    }],
-                    &[EdabitsProver {
-                        bits: vy,
-                        value: default_fe,
-                    }],
-                    vec![].as_slice(),
-                )
-                .unwrap()[0]
-                .clone();
+    /// Set the observer notified of the progress of the conversion.
+    pub fn observer(mut self, observer: Arc<dyn ConvObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
 
-            fconv.fcom_f2.open(&mut channel, &res).unwrap();
+    /// Number of buckets.
+    pub fn num_bucket(&self) -> usize {
+        self.num_bucket
+    }
 
-            fconv.fcom_f2.open(&mut channel, &[c]).unwrap();
-            (res, c)
-        });
-        let mut rng = AesRng::new();
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
-        let mut fconv =
-            VerifierConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                .unwrap();
+    /// Number of edabits opened by the cut-and-choose.
+    pub fn num_cut(&self) -> usize {
+        self.num_cut
+    }
 
-        let x_mac = fconv.fcom_f2.input(&mut channel, &mut rng, power).unwrap();
-        let y_mac = fconv.fcom_f2.input(&mut channel, &mut rng, power).unwrap();
+    /// Multiplication check.
+    pub fn mult_check(&self) -> MultCheck {
+        self.strategy
+    }
 
-        let default_fe = MacVerifier(FE::ZERO);
-        let (res_mac, c_mac) = fconv
-            .bit_add_carry(
+    /// Number of random triples each multiplication is checked against with
+    /// the Wolverine check.
+    pub fn num_triple_bucket(&self) -> usize {
+        self.triple_bucket
+    }
+
+    /// Number of threads checking the buckets.
+    pub fn num_threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Check that the configuration is usable: there is at least one bucket
+    /// and one random triple per multiplication, between one thread and one
+    /// thread per bucket, and more than one thread requires the `threads`
+    /// feature.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.num_bucket == 0 || self.threads == 0 || self.threads > self.num_bucket {
+            Err(Error::Other(format!(
+                "Conv invalid parameter configuration: num_bucket={}, threads={}",
+                self.num_bucket, self.threads,
+            )))
+        } else if self.triple_bucket == 0 {
+            Err(Error::Other(
+                "Conv invalid parameter configuration: triple_bucket=0".to_string(),
+            ))
+        } else if cfg!(not(feature = "threads")) && self.threads > 1 {
+            Err(Error::Other(
+                "checking the buckets on several threads requires the `threads` feature"
+                    .to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    // The configuration of the deprecated positional `conv`.
+    fn from_legacy<C2>(
+        num_bucket: usize,
+        num_cut: usize,
+        with_quicksilver: bool,
+        bucket_channels: &Option<Vec<C2>>,
+    ) -> Self {
+        ConvConfig::new()
+            .buckets(num_bucket)
+            .cut(num_cut)
+            .strategy(if with_quicksilver {
+                MultCheck::QuickSilver
+            } else {
+                MultCheck::Wolverine
+            })
+            .threads(bucket_channels.as_ref().map_or(1, |chs| chs.len()))
+    }
+}
+
+/// Prover for the edabits conversion protocol
+///
+/// The bits of the edabits are authenticated with MACs in the binary
+/// extension field `TF`, which determines the soundness of the binary
+/// side of the protocol. It defaults to `F40b`. The base OTs of the sVOLE
+/// setup are run with `B`.
+pub struct ProverConv<FE: FiniteField, TF: FiniteField = F40b, B: BaseOt = ChouOrlandiBaseOt> {
+    fcom_f2: FComProver<TF, B>,
+    fcom: FComProver<FE, B>,
+    version: ProtocolVersion,
+    calibration: Option<Calibration>,
+    // The configuration and the statistics of the last check of the
+    // `Converter` implementation.
+    pub(super) config: ConvConfig,
+    pub(super) stats: Option<ConvStats>,
+    #[cfg(any(test, feature = "test-utils"))]
+    fault: Option<ConvFault>,
+}
+
+// The Finite field is required to be a prime field because of the fdabit
+// protocol working only for prime finite fields.
+// The `F2: IsSubFieldOf<TF>` bound is implied by `TF::PrimeField = F2` but
+// rustc does not see it through the normalization when checking `Send`.
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    ProverConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    /// initialize the prover
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        Self::init_with_hooks(channel, rng, lpn_setup, lpn_extend, ExtendHooks::default())
+    }
+
+    /// Make a prover from the functionalities `fcom_f2` and `fcom`, e.g.
+    /// those a caller already uses for other proofs, without running another
+    /// sVOLE setup. The verifier must be made with
+    /// [`VerifierConv::init_with`] from the matching instances.
+    pub fn init_with(fcom_f2: FComProver<TF, B>, fcom: FComProver<FE, B>) -> Self {
+        Self {
+            fcom_f2,
+            fcom,
+            version: ProtocolVersion::default(),
+            calibration: None,
+            config: ConvConfig::default(),
+            stats: None,
+            #[cfg(any(test, feature = "test-utils"))]
+            fault: None,
+        }
+    }
+
+    /// Initialize the prover with the progress observer and the
+    /// cancellation token `hooks`, which apply to the extensions run by the
+    /// initialization and to the later ones, see
+    /// [`FComProver::set_extend_hooks`].
+    pub fn init_with_hooks<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+        hooks: ExtendHooks,
+    ) -> Result<Self, Error> {
+        let lpn = (lpn_setup, lpn_extend);
+        exchange_header(channel, &init_header::<FE, TF>(lpn, lpn))?;
+        // The base OTs are run once for the two F_com instances.
+        let mut setup = SetupSender::init(channel, rng)?;
+        setup.set_extend_hooks(hooks);
+        let a = FComProver::init_with_setup(channel, rng, &mut setup, lpn_setup, lpn_extend)?;
+        let b = FComProver::init_with_setup(channel, rng, &mut setup, lpn_setup, lpn_extend)?;
+        Ok(Self::init_with(a, b))
+    }
+
+    /// Initialize the prover with LPN parameters selected from the vetted
+    /// presets for the workload `estimate`, see [`LpnParams::for_output`].
+    /// The verifier must be initialized with the same estimate.
+    pub fn init_for_workload<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        estimate: ConvCostEstimate,
+    ) -> Result<Self, Error> {
+        let extend_f2 = LpnParams::for_output(estimate.voles_f2, LPN_SECURITY_BITS)?;
+        let extend_fe = LpnParams::for_output(estimate.voles_fe, LPN_SECURITY_BITS)?;
+        let lpn_f2 = (extend_f2.setup_params()?, extend_f2);
+        let lpn_fe = (extend_fe.setup_params()?, extend_fe);
+        exchange_header(channel, &init_header::<FE, TF>(lpn_f2, lpn_fe))?;
+        let mut setup = SetupSender::init(channel, rng)?;
+        let a = FComProver::init_with_setup(channel, rng, &mut setup, lpn_f2.0, lpn_f2.1)?;
+        let b = FComProver::init_with_setup(channel, rng, &mut setup, lpn_fe.0, lpn_fe.1)?;
+        Ok(Self::init_with(a, b))
+    }
+
+    /// Set the version of the wire format of the protocol. The other party
+    /// must use the same version.
+    pub fn set_protocol_version(&mut self, version: ProtocolVersion) {
+        self.version = version;
+    }
+
+    /// Make the prover deviate from the protocol with `fault` from now on,
+    /// or follow it again with `None`. For testing the soundness of the
+    /// verifier only.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn set_fault(&mut self, fault: Option<ConvFault>) {
+        self.fault = fault;
+    }
+
+    /// The F_com instance committing the values of the edabits, e.g. to
+    /// combine commitments into the value of an edabit, see
+    /// [`EdabitsProver::new`].
+    pub fn fcom(&mut self) -> &mut FComProver<FE, B> {
+        &mut self.fcom
+    }
+
+    /// The F_com instance committing the bits of the edabits.
+    pub fn fcom_f2(&mut self) -> &mut FComProver<TF, B> {
+        &mut self.fcom_f2
+    }
+
+    /// Set the refill policy of the pools of VOLEs of both the binary and
+    /// the arithmetic F_com. The other party must use the same policy.
+    pub fn set_vole_pool_config(&mut self, config: VolePoolConfig) {
+        self.fcom_f2.set_pool_config(config);
+        self.fcom.set_pool_config(config);
+    }
+
+    /// Set the progress observer and the cancellation token of the
+    /// extensions of both the binary and the arithmetic F_com, see
+    /// [`FComProver::set_extend_hooks`].
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.fcom_f2.set_extend_hooks(hooks.clone());
+        self.fcom.set_extend_hooks(hooks);
+    }
+
+    /// Returns statistics about the pools of VOLEs of the binary and the
+    /// arithmetic F_com, in that order.
+    pub fn vole_pool_stats(&self) -> (VolePoolStats, VolePoolStats) {
+        (self.fcom_f2.pool_stats(), self.fcom.pool_stats())
+    }
+
+    /// Returns the number of VOLEs remaining in the pools of the binary and
+    /// the arithmetic F_com, in that order.
+    pub fn vole_available(&self) -> (usize, usize) {
+        (self.fcom_f2.available(), self.fcom.available())
+    }
+
+    /// generate random dabits
+    ///
+    /// The dabits are not checked: a prover may commit to different bits
+    /// in `TF` and in `FE`, until they are checked with
+    /// [`ProverConv::check_dabits`].
+    pub fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<DabitProver<FE, TF>>, Error> {
+        let mut dabit_vec = Vec::with_capacity(num);
+        let mut b_batch = Vec::with_capacity(num);
+        let mut b_m_batch = Vec::with_capacity(num);
+
+        for b in self.fcom_f2.random_batch(channel, rng, num)? {
+            b_batch.push(b);
+            let b_m = f2_to_fe(b.0);
+            b_m_batch.push(b_m);
+        }
+        #[cfg(any(test, feature = "test-utils"))]
+        if let Some(ConvFault::Dabit { index }) = self.fault {
+            if index < num {
+                b_m_batch[index] = f2_to_fe(b_batch[index].0 + F2::ONE);
+            }
+        }
+
+        let b_m_mac_batch = self.fcom.input(channel, rng, &b_m_batch)?;
+
+        for i in 0..num {
+            dabit_vec.push(DabitProver {
+                bit: b_batch[i],
+                value: MacProver(b_m_batch[i], b_m_mac_batch[i]),
+            });
+        }
+        Ok(dabit_vec)
+    }
+
+    /// Make dabits from the bits `bits` committed with
+    /// [`ProverConv::fcom_f2`], committing to them in `FE`. The dabits are
+    /// not checked, see [`ProverConv::random_dabits`].
+    pub fn dabits_from_bits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        bits: &[MacProver<TF>],
+    ) -> Result<Vec<DabitProver<FE, TF>>, Error> {
+        let values: Vec<FE> = bits.iter().map(|bit| f2_to_fe(bit.0)).collect();
+        let macs = self.fcom.input(channel, rng, &values)?;
+        Ok(bits
+            .iter()
+            .zip(values.into_iter().zip(macs))
+            .map(|(bit, (value, mac))| DabitProver {
+                bit: *bit,
+                value: MacProver(value, mac),
+            })
+            .collect())
+    }
+
+    /// Make a dabit from a single bit, see [`ProverConv::dabits_from_bits`].
+    pub fn dabit_from_bit<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        bit: MacProver<TF>,
+    ) -> Result<DabitProver<FE, TF>, Error> {
+        Ok(self.dabits_from_bits(channel, rng, &[bit])?[0])
+    }
+
+    /// Check that the dabits `dabits` commit to the same bits in `TF` and
+    /// in `FE`, and return them as checked dabits. The verifier calls
+    /// [`VerifierConv::check_dabits`].
+    pub fn check_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        dabits: Vec<DabitProver<FE, TF>>,
+    ) -> Result<Vec<CheckedDabitProver<FE, TF>>, Error> {
+        ConvParty::fdabit(self, channel, rng, &dabits)?;
+        Ok(dabits.into_iter().map(CheckedDabitProver).collect())
+    }
+
+    /// Lift the bits `bits` committed with [`ProverConv::fcom_f2`] to
+    /// commitments with [`ProverConv::fcom`], consuming one checked dabit
+    /// per bit. The results are appended to `out`.
+    pub fn lift_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        dabits: &[CheckedDabitProver<FE, TF>],
+        bits: &[MacProver<TF>],
+        out: &mut Vec<MacProver<FE>>,
+    ) -> Result<(), Error> {
+        let dabits: Vec<_> = dabits.iter().map(|dabit| dabit.0).collect();
+        self.fcom
+            .lift_bits(channel, &mut self.fcom_f2, &dabits, bits, out)
+    }
+
+    /// generate random edabits
+    pub fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize, // in the paper: NB + C
+    ) -> Result<Vec<EdabitsProver<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        let mut edabits_vec = Vec::with_capacity(num);
+
+        let mut aux_bits = Vec::with_capacity(num);
+        let mut aux_r_m = Vec::with_capacity(num);
+        let random_bits = self.fcom_f2.random_batch(channel, rng, num * nb_bits)?;
+        for i in 0..num {
+            let bits = &random_bits[i * nb_bits..(i + 1) * nb_bits];
+            #[allow(unused_mut)]
+            let mut r_bits = bits.iter().map(|x| x.0).collect::<Vec<F2>>();
+            #[cfg(any(test, feature = "test-utils"))]
+            if let Some(ConvFault::EdabitBit { index, bit }) = self.fault {
+                if index == i && bit < nb_bits {
+                    r_bits[bit] += F2::ONE;
+                }
+            }
+            let r_m: FE::PrimeField = convert_bits_to_field::<FE::PrimeField>(&r_bits)?;
+            aux_bits.push(bits.to_vec());
+            aux_r_m.push(r_m);
+        }
+
+        let aux_r_m_mac: Vec<FE> = self.fcom.input(channel, rng, &aux_r_m)?;
+
+        let mut i = 0;
+        for aux_bits in aux_bits.into_iter() {
+            edabits_vec.push(EdabitsProver {
+                bits: aux_bits,
+                value: MacProver(aux_r_m[i], aux_r_m_mac[i]),
+            });
+            i += 1;
+        }
+        Ok(edabits_vec)
+    }
+
+    /// Commit to edabits of `nb_bits` bits holding the given `values`.
+    ///
+    /// Fails without communicating if one of the `values` does not fit in
+    /// `nb_bits` bits. The channel is flushed, also sending the values
+    /// committed beforehand with [`ProverConv::fcom`]. The verifier calls
+    /// [`VerifierConv::input_edabits`] with the number of values.
+    pub fn input_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        values: &[FE::PrimeField],
+        nb_bits: usize,
+    ) -> Result<Vec<EdabitsProver<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        let mut decomposition = vec![false; FE::PrimeField::bit_decomposition_len()];
+        let mut bits = Vec::with_capacity(values.len() * nb_bits);
+        for (i, value) in values.iter().enumerate() {
+            value.bit_decomposition_into(&mut decomposition);
+            if decomposition[nb_bits..].iter().any(|b| *b) {
+                return Err(Error::Other(format!(
+                    "input_edabits: the value at index {} does not fit in {} bits",
+                    i, nb_bits
+                )));
+            }
+            bits.extend(decomposition[..nb_bits].iter().map(|b| F2::from(*b)));
+        }
+
+        let bits_mac = self.fcom_f2.input(channel, rng, &bits)?;
+        let values_mac = self.fcom.input(channel, rng, values)?;
+        channel.flush()?;
+
+        Ok(values
+            .iter()
+            .zip(values_mac)
+            .enumerate()
+            .map(|(i, (value, value_mac))| EdabitsProver {
+                bits: (i * nb_bits..(i + 1) * nb_bits)
+                    .map(|j| MacProver(bits[j], bits_mac[j]))
+                    .collect(),
+                value: MacProver(*value, value_mac),
+            })
+            .collect())
+    }
+
+    /// Convert the committed `values` to edabits of `nb_bits` bits, whose
+    /// bits are fresh commitments with [`ProverConv::fcom_f2`], checked with
+    /// `conv` and `config`.
+    ///
+    /// A value which does not fit in `nb_bits` bits has its bits committed
+    /// truncated, so that the check fails for both parties. The verifier
+    /// calls [`VerifierConv::convert_field_to_bits`].
+    pub fn convert_field_to_bits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        values: &[MacProver<FE>],
+        nb_bits: usize,
+        config: &ConvConfig,
+    ) -> Result<Vec<EdabitsProver<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        if values.is_empty() {
+            return Err(Error::Other(
+                "Conv invalid input: no values to convert".to_string(),
+            ));
+        }
+        let mut decomposition = vec![false; FE::PrimeField::bit_decomposition_len()];
+        let mut bits = Vec::with_capacity(values.len() * nb_bits);
+        for value in values.iter() {
+            value.0.bit_decomposition_into(&mut decomposition);
+            bits.extend(decomposition[..nb_bits].iter().map(|b| F2::from(*b)));
+        }
+        let bits_mac = self.fcom_f2.input(channel, rng, &bits)?;
+        channel.flush()?;
+
+        let edabits: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| EdabitsProver {
+                bits: (i * nb_bits..(i + 1) * nb_bits)
+                    .map(|j| MacProver(bits[j], bits_mac[j]))
+                    .collect(),
+                value: *value,
+            })
+            .collect();
+        self.conv(channel, rng, &edabits, config)?;
+        Ok(edabits)
+    }
+
+    /// Check that the commitment of each of `pairs` is the value of its
+    /// edabit shifted right by `shift` bits, with `conv` and `config`.
+    ///
+    /// The edabits must all have the same number of bits, more than
+    /// `shift`, and their bits are assumed to be checked, e.g. with `conv`.
+    /// The verifier calls [`VerifierConv::check_truncation`].
+    pub fn check_truncation<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        pairs: &[(EdabitsProver<FE, TF>, MacProver<FE>)],
+        shift: usize,
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let truncated = truncated_edabits(pairs, shift, EdabitsProver::new)?;
+        self.conv(channel, rng, &truncated, config)
+    }
+
+    /// Compare the pairs of edabits of `x_batch` and `y_batch`, which must
+    /// all have the same number of bits, and return the commitments to the
+    /// bits `x < y`, with [`ProverConv::fcom_f2`].
+    ///
+    /// The values of the edabits are not used, so the edabits must have
+    /// been checked, e.g. with `conv`. The verifier calls
+    /// [`VerifierConv::less_than`].
+    pub fn less_than<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[EdabitsProver<FE, TF>],
+        y_batch: &[EdabitsProver<FE, TF>],
+    ) -> Result<Vec<MacProver<TF>>, Error> {
+        less_than(&mut self.fcom_f2, channel, rng, x_batch, y_batch)
+    }
+
+    /// Add the pairs of bit vectors of `x_batch` and `y_batch`, the least
+    /// significant bit first, which must all have the same length, and
+    /// return the bits of each sum with its carry, committed with
+    /// [`ProverConv::fcom_f2`]. The additions start from the carries
+    /// `carry_in`, one per pair, and from zero without them.
+    ///
+    /// The multiplications of all the additions are checked in one batch,
+    /// with QuickSilver, or as in Wolverine with `triples`, one random
+    /// triple per bit of each pair, see [`ProverConv::random_triples`]. The
+    /// verifier calls [`VerifierConv::bit_add_carry`].
+    #[allow(clippy::type_complexity)]
+    pub fn bit_add_carry<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[Vec<MacProver<TF>>],
+        y_batch: &[Vec<MacProver<TF>>],
+        carry_in: Option<&[MacProver<TF>]>,
+        triples: Option<&[(MacProver<TF>, MacProver<TF>, MacProver<TF>)]>,
+    ) -> Result<Vec<(Vec<MacProver<TF>>, MacProver<TF>)>, Error> {
+        let zeros;
+        let carry_in = match carry_in {
+            Some(carry_in) => carry_in,
+            None => {
+                zeros = vec![MacProver(F2::ZERO, TF::ZERO); x_batch.len()];
+                &zeros
+            }
+        };
+        add_bit_vectors(
+            &mut self.fcom_f2,
+            channel,
+            rng,
+            x_batch,
+            y_batch,
+            carry_in,
+            triples,
+        )
+    }
+
+    /// Subtract the bit vectors of `y_batch` from those of `x_batch`
+    /// pairwise in two's complement, and return the bits of each difference
+    /// with its borrow, which is one when `x < y` plus the borrow in. The
+    /// subtractions start from the borrows `borrow_in`, one per pair, and
+    /// from zero without them. The vectors and the triples are as in
+    /// [`ProverConv::bit_add_carry`]. The verifier calls
+    /// [`VerifierConv::bit_sub_borrow`].
+    #[allow(clippy::type_complexity)]
+    pub fn bit_sub_borrow<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[Vec<MacProver<TF>>],
+        y_batch: &[Vec<MacProver<TF>>],
+        borrow_in: Option<&[MacProver<TF>]>,
+        triples: Option<&[(MacProver<TF>, MacProver<TF>, MacProver<TF>)]>,
+    ) -> Result<Vec<(Vec<MacProver<TF>>, MacProver<TF>)>, Error> {
+        let zeros;
+        let borrow_in = match borrow_in {
+            Some(borrow_in) => borrow_in,
+            None => {
+                zeros = vec![MacProver(F2::ZERO, TF::ZERO); x_batch.len()];
+                &zeros
+            }
+        };
+        sub_bit_vectors(
+            &mut self.fcom_f2,
+            channel,
+            rng,
+            x_batch,
+            y_batch,
+            borrow_in,
+            triples,
+        )
+    }
+
+    /// Generate random triples
+    pub fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<(MacProver<TF>, MacProver<TF>, MacProver<TF>)>,
+    ) -> Result<(), Error> {
+        let mut pairs = Vec::with_capacity(num);
+        let mut zs = Vec::with_capacity(num);
+        let random = self.fcom_f2.random_batch(channel, rng, 2 * num)?;
+        for xy in random.chunks_exact(2) {
+            let (x, y) = (xy[0], xy[1]);
+            // the product in `F2`, rather than the one of `F2` by `TF` which
+            // the bound `F2: IsSubFieldOf<TF>` would select
+            let z = std::ops::Mul::<F2>::mul(x.0, y.0);
+            pairs.push((x, y));
+            zs.push(z);
+        }
+        #[cfg(any(test, feature = "test-utils"))]
+        if let Some(ConvFault::Triple { index }) = self.fault {
+            if index < num {
+                zs[index] += F2::ONE;
+            }
+        }
+        let mut zs_mac = Vec::with_capacity(num);
+        self.fcom_f2.input_into(channel, rng, &zs, &mut zs_mac)?;
+
+        for i in 0..num {
+            let (x, y) = pairs[i];
+            let z = zs[i];
+            let z_mac = zs_mac[i];
+            out.push((x, y, MacProver(z, z_mac)));
+        }
+        channel.flush()?;
+        Ok(())
+    }
+
+    /// conversion checking
+    ///
+    /// Returns the statistics of the conversion, see [`ConvStats`]. The
+    /// buckets are checked over `channel`, so `config` must use a single
+    /// thread.
+    pub fn conv<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                config,
+                None::<BucketChannels<NoBucketChannel>>,
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking, as [`conv`](Self::conv), returning the checked
+    /// edabits so that their commitments are used after the conversion
+    pub fn conv_checked<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: Vec<EdabitsProver<FE, TF>>,
+        config: &ConvConfig,
+    ) -> Result<(Vec<VerifiedEdabitsProver<FE, TF>>, ConvStats), Error> {
+        let stats = self.conv(channel, rng, &edabits_vector, config)?;
+        let verified = edabits_vector
+            .into_iter()
+            .map(VerifiedEdabitsProver)
+            .collect();
+        Ok((verified, stats))
+    }
+
+    /// conversion checking with the number of buckets and of cut edabits
+    /// of `config` selected for `security_bits` bits of statistical
+    /// security, see [`select_conv_params`]
+    pub fn conv_with_security<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        security_bits: usize,
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let (num_bucket, num_cut) = select_conv_params(edabits_vector.len(), security_bits)?;
+        let config = config.clone().buckets(num_bucket).cut(num_cut);
+        self.conv(channel, rng, edabits_vector, &config)
+    }
+
+    /// conversion checking of `edabits` in chunks of `chunk_size` edabits,
+    /// each checked by `conv` with `config`, so that the random edabits,
+    /// dabits and triples held at once are bounded by the chunk size rather
+    /// than by the number of edabits
+    ///
+    /// A last chunk shorter than `chunk_size` is merged into the one before,
+    /// so the chunks have between `chunk_size` and `2 * chunk_size - 1`
+    /// edabits, unless there are fewer edabits in all. A prover cheating in
+    /// one of `k` chunks succeeds with at most `k` times the probability of
+    /// `conv` on `chunk_size` edabits, so `config` is selected for the chunk
+    /// size and `log2(k)` more bits of security, e.g. with
+    /// [`select_conv_params`]. Both parties must give as many edabits and
+    /// the same `chunk_size`. Returns the statistics of every chunk.
+    pub fn conv_streaming<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits: impl IntoIterator<Item = EdabitsProver<FE, TF>>,
+        chunk_size: usize,
+        config: &ConvConfig,
+    ) -> Result<Vec<ConvStats>, Error> {
+        conv_chunks(edabits, chunk_size, |chunk| {
+            self.conv(channel, rng, chunk, config)
+        })
+    }
+
+    /// Make a converter to the field `FE2` whose F_com instance over `TF`
+    /// uses the MAC key of the one of this converter, so that the two share
+    /// the bits of their edabits, see [`ProverConv::convert_between`]. The
+    /// F_com instance over `FE2` is set up anew. The verifier calls
+    /// [`VerifierConv::with_field`].
+    pub fn with_field<
+        FE2: FiniteField<PrimeField = FE2>,
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<ProverConv<FE2, TF, B>, Error> {
+        let fcom_f2 = self.fcom_f2.duplicate(channel, rng)?;
+        let fcom = FComProver::init(channel, rng, lpn_setup, lpn_extend)?;
+        Ok(ProverConv {
+            fcom_f2,
+            fcom,
+            version: self.version,
+            calibration: None,
+            config: ConvConfig::default(),
+            stats: None,
+            #[cfg(any(test, feature = "test-utils"))]
+            fault: None,
+        })
+    }
+
+    /// Check that the edabits `edabits` of this converter and the
+    /// commitments `values` of the converter `other` to the field `FE2`
+    /// hold the same numbers, moving them from one field to the other: the
+    /// bits of every edabit are checked by `conv` with `config` against its
+    /// value, and then, with `other`, against the matching commitment of
+    /// `values`.
+    ///
+    /// `other` is made by [`ProverConv::with_field`], and the bits must fit
+    /// the smaller of the two fields. Fails without communicating
+    /// otherwise. Returns the statistics of the two conversions. The
+    /// verifier calls [`VerifierConv::convert_between`].
+    pub fn convert_between<
+        FE2: FiniteField<PrimeField = FE2>,
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        other: &mut ProverConv<FE2, TF, B>,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits: &[EdabitsProver<FE, TF>],
+        values: &[MacProver<FE2>],
+        config: &ConvConfig,
+    ) -> Result<(ConvStats, ConvStats), Error> {
+        check_cross_field::<FE, FE2, _, _>(
+            self.fcom_f2.shares_key_with(&other.fcom_f2),
+            edabits,
+            values.len(),
+        )?;
+        let stats = self.conv(channel, rng, edabits, config)?;
+        let edabits_other: Vec<_> = edabits
+            .iter()
+            .zip(values)
+            .map(|(edabit, value)| EdabitsProver::new(edabit.bits.clone(), *value))
+            .collect();
+        let stats_other = other.conv(channel, rng, &edabits_other, config)?;
+        Ok((stats, stats_other))
+    }
+
+    /// preprocessing of the conversion checking of `num` edabits of
+    /// `nb_bits` bits, before they are known
+    ///
+    /// This runs the steps of `conv` which do not depend on the edabits:
+    /// the random edabits, dabits and triples are committed, the dabits are
+    /// checked, and the cut edabits and triples are opened. The edabits are
+    /// then converted with `conv_online`, in one or several batches. The
+    /// buckets are checked over the channel given to `conv_online`, so
+    /// `config` must use a single thread.
+    pub fn preprocess<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+        config: &ConvConfig,
+    ) -> Result<ProverPreprocessing<FE, TF>, Error> {
+        conv_preprocess(self, channel, rng, nb_bits, num, config)
+    }
+
+    /// conversion checking of `edabits_vector` with the material of
+    /// `preprocessing`, made by `preprocess` on this instance
+    ///
+    /// Only the buckets are checked. This fails without communicating when
+    /// `preprocessing` has not enough material left for `edabits_vector`.
+    pub fn conv_online<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        preprocessing: &mut ProverPreprocessing<FE, TF>,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+    ) -> Result<ConvStats, Error> {
+        conv_online_phases(self, channel, rng, preprocessing, edabits_vector)
+    }
+
+    /// conversion checking with the multiplications of the buckets checked
+    /// by `checker`, in place of the check selected by `config`
+    ///
+    /// The random triples of the Wolverine check are not committed. The
+    /// checker runs a single check of the multiplications of all the
+    /// buckets, and the other party must use a matching checker. The
+    /// buckets are checked over `channel`, so `config` must use a single
+    /// thread.
+    pub fn conv_with_checker<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        K: MultChecker<TF, FComProver<TF, B>> + Clone + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        config: &ConvConfig,
+        checker: K,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                config,
+                None::<BucketChannels<NoBucketChannel>>,
+                Some(checker),
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with the buckets checked in parallel, over one
+    /// of `bucket_channels` per thread of `config`
+    ///
+    /// This requires the `threads` feature. The traffic of the bucket
+    /// channels is not protected by a secure channel wrapping `channel` such
+    /// as a `NoiseChannel`.
+    pub fn conv_with_bucket_channels<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        config: &ConvConfig,
+        bucket_channels: Vec<C2>,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                config,
+                Some(BucketChannels::Separate(bucket_channels)),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with the buckets checked in parallel, on the
+    /// threads of `config`, with their traffic multiplexed over `channel`
+    ///
+    /// This requires the `threads` feature. Once the instances of the
+    /// threads have been set up over `channel`, it carries one virtual
+    /// channel per thread until the buckets are checked, see
+    /// [`multiplex_with_pumps`], so reading from a clone of `channel` must
+    /// not block writing to another, as for a `SyncChannel`. Every thread
+    /// sends the same messages as over its channel in
+    /// `conv_with_bucket_channels`, framed with its index. The other party
+    /// must call `conv_multiplexed` as well.
+    pub fn conv_multiplexed<C: AbstractChannel + Send + 'static, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let bucket_channels = BucketChannels::multiplexed(channel, config.threads);
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                config,
+                Some(bucket_channels),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with positional parameters
+    ///
+    /// With `bucket_channels`, one per bucket, the buckets are checked in
+    /// parallel. Without them, pass `None::<Vec<NoBucketChannel>>`.
+    #[deprecated(note = "use `conv` with a `ConvConfig` instead")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn conv_legacy<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num_bucket: usize,
+        num_cut: usize,
+        edabits_vector: &[EdabitsProver<FE, TF>],
+        bucket_channels: Option<Vec<C2>>,
+        with_quicksilver: bool,
+    ) -> Result<ConvStats, Error> {
+        let config =
+            ConvConfig::from_legacy(num_bucket, num_cut, with_quicksilver, &bucket_channels);
+        conv_with_stats::<FE, _, _, _>(channel, &config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                &config,
+                bucket_channels.map(BucketChannels::Separate),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    ConvParty<FE, TF> for ProverConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    type Mac = MacProver<FE>;
+    type MacF2 = MacProver<TF>;
+    type Edabits = EdabitsProver<FE, TF>;
+    type Dabit = DabitProver<FE, TF>;
+    type FCom = FComProver<FE, B>;
+    type FComF2 = FComProver<TF, B>;
+
+    fn fcoms(&mut self) -> (&mut FComProver<FE, B>, &mut FComProver<TF, B>) {
+        (&mut self.fcom, &mut self.fcom_f2)
+    }
+
+    #[cfg(feature = "threads")]
+    fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            fcom_f2: self.fcom_f2.duplicate(channel, rng)?,
+            fcom: self.fcom.duplicate(channel, rng)?,
+            version: self.version,
+            calibration: self.calibration,
+            config: self.config.clone(),
+            stats: None,
+            #[cfg(any(test, feature = "test-utils"))]
+            fault: self.fault,
+        })
+    }
+
+    fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<EdabitsProver<FE, TF>>, Error> {
+        ProverConv::random_edabits(self, channel, rng, nb_bits, num)
+    }
+
+    fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<DabitProver<FE, TF>>, Error> {
+        ProverConv::random_dabits(self, channel, rng, num)
+    }
+
+    fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<(MacProver<TF>, MacProver<TF>, MacProver<TF>)>,
+    ) -> Result<(), Error> {
+        ProverConv::random_triples(self, channel, rng, num, out)
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn carry_fault(&self) -> Option<(usize, usize)> {
+        match self.fault {
+            Some(ConvFault::Carry { index, bit }) => Some((index, bit)),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn dabit_fault(&self) -> bool {
+        matches!(self.fault, Some(ConvFault::Dabit { .. }))
+    }
+
+    fn lift_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        dabits: &[DabitProver<FE, TF>],
+        bits: &[MacProver<TF>],
+        out: &mut Vec<MacProver<FE>>,
+    ) -> Result<(), Error> {
+        self.fcom
+            .lift_bits(channel, &mut self.fcom_f2, dabits, bits, out)
+    }
+
+    fn open_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        bits: &[MacProver<TF>],
+        out: &mut Vec<F2>,
+    ) -> Result<(), Error> {
+        match self.version {
+            ProtocolVersion::V1 => self.fcom_f2.open(channel, bits)?,
+            ProtocolVersion::V2 => self.fcom_f2.open_packed(channel, bits)?,
+        }
+        out.clear();
+        out.extend(bits.iter().map(|b| b.0));
+        Ok(())
+    }
+
+    fn shuffle_seed<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Block, Error> {
+        let commitment = HashCommitment::read(channel)?;
+        let seed = rng.gen::<Block>();
+        channel.write_block(&seed)?;
+        channel.flush()?;
+        let verifier_seed = channel.read_block()?;
+        let opening = Opening::read(channel)?;
+        if !commitment.verify_block(&verifier_seed, &opening) {
+            return Err(Error::InvalidOpening);
+        }
+        Ok(seed ^ verifier_seed)
+    }
+
+    fn calibration(&mut self) -> &mut Option<Calibration> {
+        &mut self.calibration
+    }
+
+    fn protocol_version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    fn select_mult_check<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        _quicksilver: Option<Duration>,
+        _wolverine: Option<Duration>,
+    ) -> Result<MultCheck, Error> {
+        match channel.read_u8()? {
+            0 => Ok(MultCheck::QuickSilver),
+            1 => Ok(MultCheck::Wolverine),
+            b => Err(Error::Other(format!(
+                "invalid multiplication check selected by the verifier: {}",
+                b
+            ))),
+        }
+    }
+}
+
+/// Verifier for the edabits conversion protocol
+///
+/// See [`ProverConv`] for the meaning of the tag field `TF` and of the base
+/// OT `B`.
+pub struct VerifierConv<FE: FiniteField, TF: FiniteField = F40b, B: BaseOt = ChouOrlandiBaseOt> {
+    fcom_f2: FComVerifier<TF, B>,
+    pub(super) fcom: FComVerifier<FE, B>,
+    version: ProtocolVersion,
+    calibration: Option<Calibration>,
+    // The configuration and the statistics of the last check of the
+    // `Converter` implementation.
+    pub(super) config: ConvConfig,
+    pub(super) stats: Option<ConvStats>,
+}
+
+// The Finite field is required to be a prime field because of the fdabit
+// protocol working only for prime finite fields.
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    VerifierConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    /// initialize the verifier
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        Self::init_with_hooks(channel, rng, lpn_setup, lpn_extend, ExtendHooks::default())
+    }
+
+    /// Make a verifier from the functionalities `fcom_f2` and `fcom`, see
+    /// [`ProverConv::init_with`].
+    pub fn init_with(fcom_f2: FComVerifier<TF, B>, fcom: FComVerifier<FE, B>) -> Self {
+        Self {
+            fcom_f2,
+            fcom,
+            version: ProtocolVersion::default(),
+            calibration: None,
+            config: ConvConfig::default(),
+            stats: None,
+        }
+    }
+
+    /// Initialize the verifier with the progress observer and the
+    /// cancellation token `hooks`, which apply to the extensions run by the
+    /// initialization and to the later ones, see
+    /// [`FComVerifier::set_extend_hooks`].
+    pub fn init_with_hooks<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+        hooks: ExtendHooks,
+    ) -> Result<Self, Error> {
+        let lpn = (lpn_setup, lpn_extend);
+        exchange_header(channel, &init_header::<FE, TF>(lpn, lpn))?;
+        // The base OTs are run once for the two F_com instances.
+        let mut setup = SetupReceiver::init(channel, rng)?;
+        setup.set_extend_hooks(hooks);
+        let a = FComVerifier::init_with_setup(channel, rng, &mut setup, lpn_setup, lpn_extend)?;
+        let b = FComVerifier::init_with_setup(channel, rng, &mut setup, lpn_setup, lpn_extend)?;
+        Ok(Self::init_with(a, b))
+    }
+
+    /// Initialize the verifier with LPN parameters selected from the vetted
+    /// presets for the workload `estimate`, see [`LpnParams::for_output`].
+    /// The prover must be initialized with the same estimate.
+    pub fn init_for_workload<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        estimate: ConvCostEstimate,
+    ) -> Result<Self, Error> {
+        let extend_f2 = LpnParams::for_output(estimate.voles_f2, LPN_SECURITY_BITS)?;
+        let extend_fe = LpnParams::for_output(estimate.voles_fe, LPN_SECURITY_BITS)?;
+        let lpn_f2 = (extend_f2.setup_params()?, extend_f2);
+        let lpn_fe = (extend_fe.setup_params()?, extend_fe);
+        exchange_header(channel, &init_header::<FE, TF>(lpn_f2, lpn_fe))?;
+        let mut setup = SetupReceiver::init(channel, rng)?;
+        let a = FComVerifier::init_with_setup(channel, rng, &mut setup, lpn_f2.0, lpn_f2.1)?;
+        let b = FComVerifier::init_with_setup(channel, rng, &mut setup, lpn_fe.0, lpn_fe.1)?;
+        Ok(Self::init_with(a, b))
+    }
+
+    /// Set the version of the wire format of the protocol. The other party
+    /// must use the same version.
+    pub fn set_protocol_version(&mut self, version: ProtocolVersion) {
+        self.version = version;
+    }
+
+    /// The F_com instance committing the values of the edabits, see
+    /// [`ProverConv::fcom`].
+    pub fn fcom(&mut self) -> &mut FComVerifier<FE, B> {
+        &mut self.fcom
+    }
+
+    /// The F_com instance committing the bits of the edabits.
+    pub fn fcom_f2(&mut self) -> &mut FComVerifier<TF, B> {
+        &mut self.fcom_f2
+    }
+
+    /// Set the refill policy of the pools of VOLEs of both the binary and
+    /// the arithmetic F_com. The other party must use the same policy.
+    pub fn set_vole_pool_config(&mut self, config: VolePoolConfig) {
+        self.fcom_f2.set_pool_config(config);
+        self.fcom.set_pool_config(config);
+    }
+
+    /// Set the progress observer and the cancellation token of the
+    /// extensions of both the binary and the arithmetic F_com, see
+    /// [`FComVerifier::set_extend_hooks`].
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.fcom_f2.set_extend_hooks(hooks.clone());
+        self.fcom.set_extend_hooks(hooks);
+    }
+
+    /// Returns statistics about the pools of VOLEs of the binary and the
+    /// arithmetic F_com, in that order.
+    pub fn vole_pool_stats(&self) -> (VolePoolStats, VolePoolStats) {
+        (self.fcom_f2.pool_stats(), self.fcom.pool_stats())
+    }
+
+    /// Returns the number of VOLEs remaining in the pools of the binary and
+    /// the arithmetic F_com, in that order.
+    pub fn vole_available(&self) -> (usize, usize) {
+        (self.fcom_f2.available(), self.fcom.available())
+    }
+
+    /// generate random dabits, matching [`ProverConv::random_dabits`]
+    pub fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<DabitVerifier<FE, TF>>, Error> {
+        let mut dabit_vec_mac = Vec::with_capacity(num);
+        let b_mac_batch = self.fcom_f2.random_batch(channel, rng, num)?;
+        let b_m_mac_batch = self.fcom.input(channel, rng, num)?;
+        for i in 0..num {
+            dabit_vec_mac.push(DabitVerifier {
+                bit: b_mac_batch[i],
+                value: b_m_mac_batch[i],
+            });
+        }
+        Ok(dabit_vec_mac)
+    }
+
+    /// Make dabits from the bits `bits` committed with
+    /// [`VerifierConv::fcom_f2`], matching [`ProverConv::dabits_from_bits`].
+    pub fn dabits_from_bits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        bits: &[MacVerifier<TF>],
+    ) -> Result<Vec<DabitVerifier<FE, TF>>, Error> {
+        let values = self.fcom.input(channel, rng, bits.len())?;
+        Ok(bits
+            .iter()
+            .zip(values)
+            .map(|(bit, value)| DabitVerifier { bit: *bit, value })
+            .collect())
+    }
+
+    /// Make a dabit from a single bit, see
+    /// [`VerifierConv::dabits_from_bits`].
+    pub fn dabit_from_bit<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        bit: MacVerifier<TF>,
+    ) -> Result<DabitVerifier<FE, TF>, Error> {
+        Ok(self.dabits_from_bits(channel, rng, &[bit])?[0])
+    }
+
+    /// Check that the dabits `dabits` commit to the same bits in `TF` and
+    /// in `FE`, matching [`ProverConv::check_dabits`]. Fails with
+    /// `Error::FdabitCheckFailed` if they do not.
+    pub fn check_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        dabits: Vec<DabitVerifier<FE, TF>>,
+    ) -> Result<Vec<CheckedDabitVerifier<FE, TF>>, Error> {
+        ConvParty::fdabit(self, channel, rng, &dabits)?;
+        Ok(dabits.into_iter().map(CheckedDabitVerifier).collect())
+    }
+
+    /// Lift the bits `bits` committed with [`VerifierConv::fcom_f2`] to
+    /// commitments with [`VerifierConv::fcom`], matching
+    /// [`ProverConv::lift_bits`].
+    pub fn lift_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        dabits: &[CheckedDabitVerifier<FE, TF>],
+        bits: &[MacVerifier<TF>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        let dabits: Vec<_> = dabits.iter().map(|dabit| dabit.0).collect();
+        self.fcom
+            .lift_bits(channel, &mut self.fcom_f2, &dabits, bits, out)
+    }
+
+    /// generate random edabits
+    pub fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize, // in the paper: NB + C
+    ) -> Result<Vec<EdabitsVerifier<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        let mut edabits_vec_mac = Vec::with_capacity(num);
+        let mut aux_bits = Vec::with_capacity(num);
+        let random_bits = self.fcom_f2.random_batch(channel, rng, num * nb_bits)?;
+        for i in 0..num {
+            aux_bits.push(random_bits[i * nb_bits..(i + 1) * nb_bits].to_vec());
+        }
+
+        let aux_r_m_mac = self.fcom.input(channel, rng, num)?;
+
+        let mut i = 0;
+        for aux_bits in aux_bits.into_iter() {
+            edabits_vec_mac.push(EdabitsVerifier {
+                bits: aux_bits,
+                value: aux_r_m_mac[i],
+            });
+            i += 1;
+        }
+        Ok(edabits_vec_mac)
+    }
+
+    /// Receive the commitments to `num` edabits of `nb_bits` bits chosen by
+    /// the prover with [`ProverConv::input_edabits`].
+    pub fn input_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<EdabitsVerifier<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        let bits_mac = self.fcom_f2.input(channel, rng, num * nb_bits)?;
+        let values_mac = self.fcom.input(channel, rng, num)?;
+
+        Ok(values_mac
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| EdabitsVerifier {
+                bits: bits_mac[i * nb_bits..(i + 1) * nb_bits].to_vec(),
+                value,
+            })
+            .collect())
+    }
+
+    /// Convert the committed `values` to edabits of `nb_bits` bits with the
+    /// prover, see [`ProverConv::convert_field_to_bits`].
+    pub fn convert_field_to_bits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        values: &[MacVerifier<FE>],
+        nb_bits: usize,
+        config: &ConvConfig,
+    ) -> Result<Vec<EdabitsVerifier<FE, TF>>, Error> {
+        check_nb_bits::<FE>(nb_bits)?;
+        if values.is_empty() {
+            return Err(Error::Other(
+                "Conv invalid input: no values to convert".to_string(),
+            ));
+        }
+        let bits_mac = self.fcom_f2.input(channel, rng, values.len() * nb_bits)?;
+
+        let edabits: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| EdabitsVerifier {
+                bits: bits_mac[i * nb_bits..(i + 1) * nb_bits].to_vec(),
+                value: *value,
+            })
+            .collect();
+        self.conv(channel, rng, &edabits, config)?;
+        Ok(edabits)
+    }
+
+    /// Check that the commitment of each of `pairs` is the value of its
+    /// edabit shifted right by `shift` bits with the prover, see
+    /// [`ProverConv::check_truncation`].
+    pub fn check_truncation<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        pairs: &[(EdabitsVerifier<FE, TF>, MacVerifier<FE>)],
+        shift: usize,
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let truncated = truncated_edabits(pairs, shift, EdabitsVerifier::new)?;
+        self.conv(channel, rng, &truncated, config)
+    }
+
+    /// Compare the pairs of edabits of `x_batch` and `y_batch` with the
+    /// prover, see [`ProverConv::less_than`].
+    pub fn less_than<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[EdabitsVerifier<FE, TF>],
+        y_batch: &[EdabitsVerifier<FE, TF>],
+    ) -> Result<Vec<MacVerifier<TF>>, Error> {
+        less_than(&mut self.fcom_f2, channel, rng, x_batch, y_batch)
+    }
+
+    /// Add the pairs of bit vectors of `x_batch` and `y_batch` with the
+    /// prover, see [`ProverConv::bit_add_carry`].
+    #[allow(clippy::type_complexity)]
+    pub fn bit_add_carry<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[Vec<MacVerifier<TF>>],
+        y_batch: &[Vec<MacVerifier<TF>>],
+        carry_in: Option<&[MacVerifier<TF>]>,
+        triples: Option<&[(MacVerifier<TF>, MacVerifier<TF>, MacVerifier<TF>)]>,
+    ) -> Result<Vec<(Vec<MacVerifier<TF>>, MacVerifier<TF>)>, Error> {
+        let zeros;
+        let carry_in = match carry_in {
+            Some(carry_in) => carry_in,
+            None => {
+                zeros = vec![MacVerifier(TF::ZERO); x_batch.len()];
+                &zeros
+            }
+        };
+        add_bit_vectors(
+            &mut self.fcom_f2,
+            channel,
+            rng,
+            x_batch,
+            y_batch,
+            carry_in,
+            triples,
+        )
+    }
+
+    /// Subtract the bit vectors of `y_batch` from those of `x_batch` with
+    /// the prover, see [`ProverConv::bit_sub_borrow`].
+    #[allow(clippy::type_complexity)]
+    pub fn bit_sub_borrow<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x_batch: &[Vec<MacVerifier<TF>>],
+        y_batch: &[Vec<MacVerifier<TF>>],
+        borrow_in: Option<&[MacVerifier<TF>]>,
+        triples: Option<&[(MacVerifier<TF>, MacVerifier<TF>, MacVerifier<TF>)]>,
+    ) -> Result<Vec<(Vec<MacVerifier<TF>>, MacVerifier<TF>)>, Error> {
+        let zeros;
+        let borrow_in = match borrow_in {
+            Some(borrow_in) => borrow_in,
+            None => {
+                zeros = vec![MacVerifier(TF::ZERO); x_batch.len()];
+                &zeros
+            }
+        };
+        sub_bit_vectors(
+            &mut self.fcom_f2,
+            channel,
+            rng,
+            x_batch,
+            y_batch,
+            borrow_in,
+            triples,
+        )
+    }
+
+    /// Generate random triples
+    pub fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<(MacVerifier<TF>, MacVerifier<TF>, MacVerifier<TF>)>,
+    ) -> Result<(), Error> {
+        let mut pairs = Vec::with_capacity(num);
+        let random = self.fcom_f2.random_batch(channel, rng, 2 * num)?;
+        for xy in random.chunks_exact(2) {
+            pairs.push((xy[0], xy[1]));
+        }
+        let mut zs = Vec::with_capacity(num);
+        self.fcom_f2.input_into(channel, rng, num, &mut zs)?;
+
+        for i in 0..num {
+            let (x, y) = pairs[i];
+            let z = zs[i];
+            out.push((x, y, z));
+        }
+        Ok(())
+    }
+
+    /// conversion checking
+    ///
+    /// Returns the statistics of the conversion, see [`ConvStats`]. The
+    /// buckets are checked over `channel`, so `config` must use a single
+    /// thread.
+    pub fn conv<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector_mac: &[EdabitsVerifier<FE, TF>],
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector_mac, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector_mac,
+                config,
+                None::<BucketChannels<NoBucketChannel>>,
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking, as [`conv`](Self::conv), returning the checked
+    /// edabits so that their commitments are used after the conversion
+    pub fn conv_checked<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector_mac: Vec<EdabitsVerifier<FE, TF>>,
+        config: &ConvConfig,
+    ) -> Result<(Vec<VerifiedEdabitsVerifier<FE, TF>>, ConvStats), Error> {
+        let stats = self.conv(channel, rng, &edabits_vector_mac, config)?;
+        let verified = edabits_vector_mac
+            .into_iter()
+            .map(VerifiedEdabitsVerifier)
+            .collect();
+        Ok((verified, stats))
+    }
+
+    /// conversion checking with the number of buckets and of cut edabits
+    /// of `config` selected for `security_bits` bits of statistical
+    /// security, see [`select_conv_params`]
+    pub fn conv_with_security<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsVerifier<FE, TF>],
+        security_bits: usize,
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let (num_bucket, num_cut) = select_conv_params(edabits_vector.len(), security_bits)?;
+        let config = config.clone().buckets(num_bucket).cut(num_cut);
+        self.conv(channel, rng, edabits_vector, &config)
+    }
+
+    /// conversion checking of `edabits` in chunks of `chunk_size` edabits,
+    /// matching [`ProverConv::conv_streaming`]
+    pub fn conv_streaming<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits: impl IntoIterator<Item = EdabitsVerifier<FE, TF>>,
+        chunk_size: usize,
+        config: &ConvConfig,
+    ) -> Result<Vec<ConvStats>, Error> {
+        conv_chunks(edabits, chunk_size, |chunk| {
+            self.conv(channel, rng, chunk, config)
+        })
+    }
+
+    /// Make a converter to the field `FE2` sharing the MAC key of the bits
+    /// with this converter, matching [`ProverConv::with_field`].
+    pub fn with_field<
+        FE2: FiniteField<PrimeField = FE2>,
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<VerifierConv<FE2, TF, B>, Error> {
+        let fcom_f2 = self.fcom_f2.duplicate(channel, rng)?;
+        let fcom = FComVerifier::init(channel, rng, lpn_setup, lpn_extend)?;
+        Ok(VerifierConv {
+            fcom_f2,
+            fcom,
+            version: self.version,
+            calibration: None,
+            config: ConvConfig::default(),
+            stats: None,
+        })
+    }
+
+    /// Check that the edabits `edabits` of this converter and the
+    /// commitments `values` of the converter `other` to the field `FE2`
+    /// hold the same numbers, matching [`ProverConv::convert_between`].
+    pub fn convert_between<
+        FE2: FiniteField<PrimeField = FE2>,
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        other: &mut VerifierConv<FE2, TF, B>,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits: &[EdabitsVerifier<FE, TF>],
+        values: &[MacVerifier<FE2>],
+        config: &ConvConfig,
+    ) -> Result<(ConvStats, ConvStats), Error> {
+        check_cross_field::<FE, FE2, _, _>(
+            self.fcom_f2.shares_key_with(&other.fcom_f2),
+            edabits,
+            values.len(),
+        )?;
+        let stats = self.conv(channel, rng, edabits, config)?;
+        let edabits_other: Vec<_> = edabits
+            .iter()
+            .zip(values)
+            .map(|(edabit, value)| EdabitsVerifier::new(edabit.bits.clone(), *value))
+            .collect();
+        let stats_other = other.conv(channel, rng, &edabits_other, config)?;
+        Ok((stats, stats_other))
+    }
+
+    /// preprocessing of the conversion checking of `num` edabits of
+    /// `nb_bits` bits, before they are known
+    ///
+    /// This runs the steps of `conv` which do not depend on the edabits:
+    /// the random edabits, dabits and triples are committed, the dabits are
+    /// checked, and the cut edabits and triples are opened. The edabits are
+    /// then converted with `conv_online`, in one or several batches. The
+    /// buckets are checked over the channel given to `conv_online`, so
+    /// `config` must use a single thread.
+    pub fn preprocess<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+        config: &ConvConfig,
+    ) -> Result<VerifierPreprocessing<FE, TF>, Error> {
+        conv_preprocess(self, channel, rng, nb_bits, num, config)
+    }
+
+    /// conversion checking of `edabits_vector` with the material of
+    /// `preprocessing`, made by `preprocess` on this instance
+    ///
+    /// Only the buckets are checked. This fails without communicating when
+    /// `preprocessing` has not enough material left for `edabits_vector`.
+    pub fn conv_online<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        preprocessing: &mut VerifierPreprocessing<FE, TF>,
+        edabits_vector: &[EdabitsVerifier<FE, TF>],
+    ) -> Result<ConvStats, Error> {
+        conv_online_phases(self, channel, rng, preprocessing, edabits_vector)
+    }
+
+    /// conversion checking with the multiplications of the buckets checked
+    /// by `checker`, in place of the check selected by `config`
+    ///
+    /// The random triples of the Wolverine check are not committed. The
+    /// checker runs a single check of the multiplications of all the
+    /// buckets, and the other party must use a matching checker. The
+    /// buckets are checked over `channel`, so `config` must use a single
+    /// thread.
+    pub fn conv_with_checker<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        K: MultChecker<TF, FComVerifier<TF, B>> + Clone + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector: &[EdabitsVerifier<FE, TF>],
+        config: &ConvConfig,
+        checker: K,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector,
+                config,
+                None::<BucketChannels<NoBucketChannel>>,
+                Some(checker),
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with the buckets checked in parallel, over one
+    /// of `bucket_channels` per thread of `config`
+    ///
+    /// This requires the `threads` feature. The traffic of the bucket
+    /// channels is not protected by a secure channel wrapping `channel` such
+    /// as a `NoiseChannel`.
+    pub fn conv_with_bucket_channels<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector_mac: &[EdabitsVerifier<FE, TF>],
+        config: &ConvConfig,
+        bucket_channels: Vec<C2>,
+    ) -> Result<ConvStats, Error> {
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector_mac, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector_mac,
+                config,
+                Some(BucketChannels::Separate(bucket_channels)),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with the buckets checked in parallel, on the
+    /// threads of `config`, with their traffic multiplexed over `channel`
+    ///
+    /// This requires the `threads` feature. Once the instances of the
+    /// threads have been set up over `channel`, it carries one virtual
+    /// channel per thread until the buckets are checked, see
+    /// [`multiplex_with_pumps`], so reading from a clone of `channel` must
+    /// not block writing to another, as for a `SyncChannel`. Every thread
+    /// sends the same messages as over its channel in
+    /// `conv_with_bucket_channels`, framed with its index. The other party
+    /// must call `conv_multiplexed` as well.
+    pub fn conv_multiplexed<C: AbstractChannel + Send + 'static, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        edabits_vector_mac: &[EdabitsVerifier<FE, TF>],
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let bucket_channels = BucketChannels::multiplexed(channel, config.threads);
+        conv_with_stats::<FE, _, _, _>(channel, config, edabits_vector_mac, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector_mac,
+                config,
+                Some(bucket_channels),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+
+    /// conversion checking with positional parameters
+    ///
+    /// With `bucket_channels`, one per bucket, the buckets are checked in
+    /// parallel. Without them, pass `None::<Vec<NoBucketChannel>>`.
+    #[deprecated(note = "use `conv` with a `ConvConfig` instead")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn conv_legacy<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        C2: AbstractChannel + Send + 'static,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num_bucket: usize,
+        num_cut: usize,
+        edabits_vector_mac: &[EdabitsVerifier<FE, TF>],
+        bucket_channels: Option<Vec<C2>>,
+        with_quicksilver: bool,
+    ) -> Result<ConvStats, Error> {
+        let config =
+            ConvConfig::from_legacy(num_bucket, num_cut, with_quicksilver, &bucket_channels);
+        conv_with_stats::<FE, _, _, _>(channel, &config, edabits_vector_mac, |channel, observer| {
+            conv_phases(
+                self,
+                channel,
+                rng,
+                edabits_vector_mac,
+                &config,
+                bucket_channels.map(BucketChannels::Separate),
+                None::<NoMultChecker>,
+                observer,
+            )
+        })
+    }
+}
+
+impl<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>, B: BaseOt>
+    ConvParty<FE, TF> for VerifierConv<FE, TF, B>
+where
+    F2: IsSubFieldOf<TF>,
+{
+    type Mac = MacVerifier<FE>;
+    type MacF2 = MacVerifier<TF>;
+    type Edabits = EdabitsVerifier<FE, TF>;
+    type Dabit = DabitVerifier<FE, TF>;
+    type FCom = FComVerifier<FE, B>;
+    type FComF2 = FComVerifier<TF, B>;
+
+    fn fcoms(&mut self) -> (&mut FComVerifier<FE, B>, &mut FComVerifier<TF, B>) {
+        (&mut self.fcom, &mut self.fcom_f2)
+    }
+
+    #[cfg(feature = "threads")]
+    fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            fcom_f2: self.fcom_f2.duplicate(channel, rng)?,
+            fcom: self.fcom.duplicate(channel, rng)?,
+            version: self.version,
+            calibration: self.calibration,
+            config: self.config.clone(),
+            stats: None,
+        })
+    }
+
+    fn random_edabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        nb_bits: usize,
+        num: usize,
+    ) -> Result<Vec<EdabitsVerifier<FE, TF>>, Error> {
+        VerifierConv::random_edabits(self, channel, rng, nb_bits, num)
+    }
+
+    fn random_dabits<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<DabitVerifier<FE, TF>>, Error> {
+        VerifierConv::random_dabits(self, channel, rng, num)
+    }
+
+    fn random_triples<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<(MacVerifier<TF>, MacVerifier<TF>, MacVerifier<TF>)>,
+    ) -> Result<(), Error> {
+        VerifierConv::random_triples(self, channel, rng, num, out)
+    }
+
+    fn lift_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        dabits: &[DabitVerifier<FE, TF>],
+        bits: &[MacVerifier<TF>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        self.fcom
+            .lift_bits(channel, &mut self.fcom_f2, dabits, bits, out)
+    }
+
+    fn open_bits<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        bits: &[MacVerifier<TF>],
+        out: &mut Vec<F2>,
+    ) -> Result<(), Error> {
+        match self.version {
+            ProtocolVersion::V1 => self.fcom_f2.open(channel, bits, out),
+            ProtocolVersion::V2 => self.fcom_f2.open_packed(channel, bits, out),
+        }
+    }
+
+    fn shuffle_seed<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Block, Error> {
+        let seed = rng.gen::<Block>();
+        let (commitment, opening) = HashCommitment::commit_block(rng, &seed);
+        commitment.write(channel)?;
+        channel.flush()?;
+        let prover_seed = channel.read_block()?;
+        channel.write_block(&seed)?;
+        opening.write(channel)?;
+        channel.flush()?;
+        Ok(seed ^ prover_seed)
+    }
+
+    fn calibration(&mut self) -> &mut Option<Calibration> {
+        &mut self.calibration
+    }
+
+    fn protocol_version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    fn select_mult_check<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        quicksilver: Option<Duration>,
+        wolverine: Option<Duration>,
+    ) -> Result<MultCheck, Error> {
+        let selected = match (quicksilver, wolverine) {
+            (Some(quicksilver), Some(wolverine)) if wolverine < quicksilver => MultCheck::Wolverine,
+            _ => MultCheck::QuickSilver,
+        };
+        channel.write_u8((selected == MultCheck::Wolverine) as u8)?;
+        channel.flush()?;
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::checked::TAG_LEN;
+    #[cfg(feature = "zeroize")]
+    use super::super::homcom::wipe;
+    use super::super::homcom::{f2_to_fe, FComParty, FComProver, FComVerifier, MacProver};
+    use super::super::mult_check::{MultChecker, QuickSilverChecker};
+    use super::super::strict_channel::StrictChannel;
+    use super::{
+        check_bucket_channels, check_nb_bits, check_parameters, convert_bits_to_field,
+        exchange_header, fdabit_chunk_size, fdabit_gamma, init_header, open_cut_edabits,
+        select_conv_params, select_triple_bucket, BucketChannels, ConvConfig, ConvCostEstimate,
+        ConvFault, ConvParty, ConvStats, EdabitsProver, EdabitsVerifier, MultCheck,
+        NoBucketChannel, PhaseStats, ProtocolVersion, ProverConv, Regression, StatsObserver,
+        VerifierConv, CONV_SCOPE_BUCKETS, CONV_SCOPE_CUT_AND_CHOOSE, CONV_SCOPE_DABITS,
+        CONV_SCOPE_EDABITS, CONV_SCOPE_FDABIT, CONV_SCOPE_HEADER, CONV_SCOPE_SHUFFLE,
+        CONV_SCOPE_TRIPLES, CONV_SCOPE_VOLE, CONV_STATS_SCHEMA_VERSION,
+    };
+    use crate::errors::Error;
+    use crate::svole::wykw::{
+        LpnParams, UnsafeInsecureTestOt, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_SETUP_MEDIUM,
+        LPN_SETUP_SMALL,
+    };
+    use generic_array::typenum::Unsigned;
+    use rand::{CryptoRng, Rng, SeedableRng};
+    use scuttlebutt::ring::FiniteRing;
+    #[cfg(feature = "threads")]
+    use scuttlebutt::SyncChannel;
+    use scuttlebutt::{
+        commitment::{HashCommitment, Opening},
+        field::{
+            DynModulus, DynPrimeField, F127p, F40b, F61p, F63b, FiniteField, IsSubFieldOf, Modulus,
+            ModulusCell, F2,
+        },
+        local_pair,
+        serialization::CanonicalSerialize,
+        AbstractChannel, AesRng, Block, NoiseChannel, RecordChannel, ReplayChannel, ScopeComm,
+        TrackChannel, Transcript, TranscriptRecord,
+    };
+    #[cfg(feature = "threads")]
+    use std::io::{BufReader, BufWriter};
+    use std::{sync::Arc, time::Duration};
+    use uds_windows::UnixStream;
+
+    const DEFAULT_NUM_BUCKET: usize = 5;
+    const DEFAULT_NUM_CUT: usize = 5;
+
+    fn config(with_quicksilver: bool) -> ConvConfig {
+        let strategy = if with_quicksilver {
+            MultCheck::QuickSilver
+        } else {
+            MultCheck::Wolverine
+        };
+        ConvConfig::new()
+            .buckets(DEFAULT_NUM_BUCKET)
+            .cut(DEFAULT_NUM_CUT)
+            .strategy(strategy)
+    }
+    const NB_BITS: usize = 38;
+
+    static F61P_MODULUS: ModulusCell = ModulusCell::new();
+
+    // The modulus of `F61p`, set at runtime.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct F61pModulus;
+
+    impl Modulus for F61pModulus {
+        fn modulus() -> &'static DynModulus {
+            F61P_MODULUS.get_or_init(|| DynModulus::new(&((1u64 << 61) - 1).to_le_bytes()).unwrap())
+        }
+    }
+
+    type DynF61p = DynPrimeField<F61pModulus>;
+
+    static SMALL_MODULUS: ModulusCell = ModulusCell::new();
+
+    // A prime of 20 bits, so small that `fdabit` checks at most 510 dabits
+    // at once.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct SmallModulus;
+
+    impl Modulus for SmallModulus {
+        fn modulus() -> &'static DynModulus {
+            SMALL_MODULUS.get_or_init(|| DynModulus::new(&1048573u64.to_le_bytes()).unwrap())
+        }
+    }
+
+    type SmallField = DynPrimeField<SmallModulus>;
+
+    static OTHER_SMALL_MODULUS: ModulusCell = ModulusCell::new();
+
+    // Another prime of 20 bits, so that `SmallField` and `OtherSmallField`
+    // only differ in their modulus.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct OtherSmallModulus;
+
+    impl Modulus for OtherSmallModulus {
+        fn modulus() -> &'static DynModulus {
+            OTHER_SMALL_MODULUS.get_or_init(|| DynModulus::new(&1048571u64.to_le_bytes()).unwrap())
+        }
+    }
+
+    type OtherSmallField = DynPrimeField<OtherSmallModulus>;
+
+    fn test_bit_add_carry<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>()
+    where
+        F2: IsSubFieldOf<TF>,
+    {
+        let power = 6;
+        let (sender, receiver) = local_pair();
+
+        // adding
+        //   110101
+        //   101110
+        // --------
+        //  1100011
+        let x = vec![F2::ONE, F2::ZERO, F2::ONE, F2::ZERO, F2::ONE, F2::ONE];
+        let y = vec![F2::ZERO, F2::ONE, F2::ONE, F2::ONE, F2::ZERO, F2::ONE];
+        let expected = vec![F2::ONE, F2::ONE, F2::ZERO, F2::ZERO, F2::ZERO, F2::ONE];
+        let carry = F2::ONE;
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<FE, TF, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+
+            let x_mac = fconv.fcom_f2.input(&mut channel, &mut rng, &x).unwrap();
+            let y_mac = fconv.fcom_f2.input(&mut channel, &mut rng, &y).unwrap();
+
+            let mut vx = Vec::new();
+            for i in 0..power {
+                vx.push(MacProver(x[i], x_mac[i]));
+            }
+
+            let mut vy = Vec::new();
+            for i in 0..power {
+                vy.push(MacProver(y[i], y_mac[i]));
+            }
+            let (res, c) = fconv
+                .bit_add_carry(&mut channel, &mut rng, &[vx], &[vy], None, None)
+                .unwrap()[0]
+                .clone();
+
+            fconv.fcom_f2.open(&mut channel, &res).unwrap();
+
+            fconv.fcom_f2.open(&mut channel, &[c]).unwrap();
+            (res, c)
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<FE, TF, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+
+        let x_mac = fconv.fcom_f2.input(&mut channel, &mut rng, power).unwrap();
+        let y_mac = fconv.fcom_f2.input(&mut channel, &mut rng, power).unwrap();
+
+        let (res_mac, c_mac) = fconv
+            .bit_add_carry(&mut channel, &mut rng, &[x_mac], &[y_mac], None, None)
+            .unwrap()[0]
+            .clone();
+
+        let mut res = Vec::new();
+        fconv
+            .fcom_f2
+            .open(&mut channel, &res_mac, &mut res)
+            .unwrap();
+
+        let mut c = Vec::new();
+        fconv.fcom_f2.open(&mut channel, &[c_mac], &mut c).unwrap();
+
+        let _resprover = handle.join().unwrap();
+
+        for i in 0..power {
+            assert_eq!(expected[i], res[i]);
+        }
+        assert_eq!(carry, c[0]);
+    }
+
+    fn test_fdabit<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>(count: usize)
+    where
+        F2: IsSubFieldOf<TF>,
+    {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<FE, TF, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+
+            let dabits = fconv.random_dabits(&mut channel, &mut rng, count).unwrap();
+            let _ = fconv.fdabit(&mut channel, &mut rng, &dabits).unwrap();
+            ()
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<FE, TF, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+
+        let dabits_mac = fconv.random_dabits(&mut channel, &mut rng, count).unwrap();
+        let _ = fconv.fdabit(&mut channel, &mut rng, &dabits_mac).unwrap();
+
+        handle.join().unwrap();
+    }
+
+    fn test_conv<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>(
+        nb_bits: usize,
+        with_quicksilver: bool,
+    ) where
+        F2: IsSubFieldOf<TF>,
+    {
+        let nb_edabits = 50;
+        let (sender, receiver) = local_pair();
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<FE, TF, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+
+            for n in 1..nb_edabits {
+                let edabits = fconv
+                    .random_edabits(&mut channel, &mut rng, nb_bits, n)
+                    .unwrap();
+
+                let _ = fconv
+                    .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+                    .unwrap();
+            }
+            ()
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<FE, TF, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+
+        let mut res = Vec::new();
+        for n in 1..nb_edabits {
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, nb_bits, n)
+                .unwrap();
+
+            let r = fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+                .unwrap();
+            res.push(r);
+        }
+
+        let _resprover = handle.join().unwrap();
+        ()
+    }
+
+    fn test_conv_for_workload<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>()
+    where
+        F2: IsSubFieldOf<TF>,
+    {
+        let n = 100;
+        let estimate = ConvCostEstimate::conv::<FE, TF>(
+            n + 1,
+            NB_BITS,
+            DEFAULT_NUM_BUCKET,
+            DEFAULT_NUM_CUT,
+            true,
+        );
+        assert_eq!(
+            LpnParams::for_output(estimate.voles_f2, 128).unwrap(),
+            LPN_EXTEND_SMALL
+        );
+        let (sender, receiver) = local_pair();
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv =
+                ProverConv::<FE, TF>::init_for_workload(&mut channel, &mut rng, estimate).unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv =
+            VerifierConv::<FE, TF>::init_for_workload(&mut channel, &mut rng, estimate).unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    fn test_conv_comm<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>(
+        version: ProtocolVersion,
+        with_quicksilver: bool,
+    ) where
+        F2: IsSubFieldOf<TF>,
+    {
+        let n = 4;
+        let (sender, receiver) = local_pair();
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(TrackChannel::new(sender));
+            let mut fconv = ProverConv::<FE, TF, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            fconv.set_protocol_version(version);
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+                .unwrap()
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(TrackChannel::new(receiver));
+        let mut fconv = VerifierConv::<FE, TF, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        fconv.set_protocol_version(version);
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        let verifier_stats = fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+            .unwrap();
+        let prover_stats = handle.join().unwrap();
+
+        let prover = prover_stats.comm.unwrap();
+        let verifier = verifier_stats.comm.unwrap();
+        // every byte sent by a party is received by the other one
+        for (label, comm) in prover.iter() {
+            assert_eq!(
+                verifier.get(label),
+                ScopeComm {
+                    bytes_read: comm.bytes_written,
+                    bytes_written: comm.bytes_read,
+                },
+                "{}",
+                label
+            );
+        }
+        assert_eq!(prover.iter().count(), verifier.iter().count());
+
+        let fe_len = <FE as CanonicalSerialize>::ByteReprLen::USIZE;
+        let tf_len = <TF as CanonicalSerialize>::ByteReprLen::USIZE;
+        let f2_len = <F2 as CanonicalSerialize>::ByteReprLen::USIZE;
+        let nb_random_edabits = n * DEFAULT_NUM_BUCKET + DEFAULT_NUM_CUT;
+        let nb_random_dabits = n * DEFAULT_NUM_BUCKET;
+        let cut_bits_len = match version {
+            ProtocolVersion::V1 => DEFAULT_NUM_CUT * NB_BITS * f2_len,
+            ProtocolVersion::V2 => (DEFAULT_NUM_CUT * NB_BITS + 7) / 8,
+        };
+        let sent = |bytes_written| ScopeComm {
+            bytes_read: 0,
+            bytes_written,
+        };
+        assert_eq!(
+            prover.get(CONV_SCOPE_EDABITS),
+            sent(TAG_LEN + nb_random_edabits * fe_len)
+        );
+        assert_eq!(
+            prover.get(CONV_SCOPE_DABITS),
+            sent(TAG_LEN + nb_random_dabits * fe_len)
+        );
+        let nb_cut_bits = DEFAULT_NUM_CUT * NB_BITS;
+        let (triples_len, cut_triples_len) = if with_quicksilver {
+            (0, 0)
+        } else {
+            // the triples are input, and the cut ones are opened in two
+            // batches: the `x`s and `y`s, and then the `z`s
+            (
+                TAG_LEN + nb_random_edabits * NB_BITS * f2_len,
+                2 * TAG_LEN + 3 * nb_cut_bits * f2_len + 2 * tf_len,
+            )
+        };
+        assert_eq!(prover.get(CONV_SCOPE_TRIPLES), sent(triples_len));
+        let block_len = std::mem::size_of::<scuttlebutt::Block>();
+        assert_eq!(
+            verifier.get(CONV_SCOPE_SHUFFLE),
+            ScopeComm {
+                bytes_read: block_len,
+                bytes_written: HashCommitment::LEN + block_len + Opening::LEN,
+            }
+        );
+        assert_eq!(
+            prover.get(CONV_SCOPE_CUT_AND_CHOOSE),
+            sent(
+                2 * TAG_LEN
+                    + cut_bits_len
+                    + tf_len
+                    + DEFAULT_NUM_CUT * fe_len
+                    + fe_len
+                    + cut_triples_len
+            )
+        );
+        // the pools of VOLEs of `init` are large enough, so only the header
+        // is exchanged before the phases
+        let header_len = 8 * (2 + 7);
+        assert_eq!(
+            prover.get(CONV_SCOPE_HEADER),
+            ScopeComm {
+                bytes_read: header_len,
+                bytes_written: header_len,
+            }
+        );
+        assert_eq!(prover.get(CONV_SCOPE_VOLE).total(), 0);
+        for label in [CONV_SCOPE_FDABIT, CONV_SCOPE_BUCKETS] {
+            assert!(prover.get(label).total() > 0, "{}", label);
+        }
+    }
+
+    // the bucket channels are moved to the threads, so they are `SyncChannel`s
+    #[cfg(feature = "threads")]
+    type UnixChannel = SyncChannel<BufReader<UnixStream>, BufWriter<UnixStream>>;
+
+    #[cfg(feature = "threads")]
+    fn unix_channel_pair() -> (UnixChannel, UnixChannel) {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let channel = |stream: UnixStream| {
+            SyncChannel::new(
+                BufReader::new(stream.try_clone().unwrap()),
+                BufWriter::new(stream),
+            )
+        };
+        (channel(sender), channel(receiver))
+    }
+
+    // Check the buckets on `threads` threads, each over its own pair of
+    // UnixStreams.
+    #[cfg(feature = "threads")]
+    fn test_conv_bucket_channels(threads: usize, with_quicksilver: bool) {
+        let n = 10;
+        let config = config(with_quicksilver).threads(threads);
+        let (sender, receiver) = unix_channel_pair();
+        let (bucket_senders, bucket_receivers): (Vec<_>, Vec<_>) =
+            (0..threads).map(|_| unix_channel_pair()).unzip();
+        let prover_config = config.clone();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv_with_bucket_channels(
+                    &mut channel,
+                    &mut rng,
+                    &edabits,
+                    &prover_config,
+                    bucket_senders,
+                )
+                .unwrap()
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        // the bucket channels must match the threads of the configuration,
+        // which is checked before communicating
+        let no_channels: Vec<UnixChannel> = Vec::new();
+        assert!(fconv
+            .conv_with_bucket_channels(&mut channel, &mut rng, &edabits, &config, no_channels)
+            .is_err());
+        let stats = fconv
+            .conv_with_bucket_channels(&mut channel, &mut rng, &edabits, &config, bucket_receivers)
+            .unwrap();
+        let prover_stats = handle.join().unwrap();
+        assert_eq!(stats.buckets_completed, DEFAULT_NUM_BUCKET);
+        assert_eq!(prover_stats.buckets_completed, DEFAULT_NUM_BUCKET);
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn test_conv_bucket_channels_unix() {
+        test_conv_bucket_channels(DEFAULT_NUM_BUCKET, true);
+        test_conv_bucket_channels(2, true);
+        test_conv_bucket_channels(3, false);
+    }
+
+    // Check the buckets on `threads` threads, multiplexed over the single
+    // pair of UnixStreams, and then check a batch over it as usual. The
+    // pumps of the multiplexer read and write at the same time, so the
+    // channels are `SyncChannel`s.
+    #[cfg(feature = "threads")]
+    fn test_conv_multiplexed(config: ConvConfig) {
+        let n = 10;
+        let (mut sender, mut receiver) = scuttlebutt::unix_channel_pair();
+        let prover_config = config.clone();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut sender,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut sender, &mut rng, NB_BITS, n)
+                .unwrap();
+            let stats = fconv
+                .conv_multiplexed(&mut sender, &mut rng, &edabits, &prover_config)
+                .unwrap();
+            fconv
+                .conv(&mut sender, &mut rng, &edabits, &prover_config.threads(1))
+                .unwrap();
+            stats
+        });
+        let mut rng = AesRng::new();
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut receiver,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut receiver, &mut rng, NB_BITS, n)
+            .unwrap();
+        let stats = fconv
+            .conv_multiplexed(&mut receiver, &mut rng, &edabits, &config)
+            .unwrap();
+        // the channel is usable again once the buckets are checked
+        fconv
+            .conv(&mut receiver, &mut rng, &edabits, &config.threads(1))
+            .unwrap();
+        let prover_stats = handle.join().unwrap();
+        assert_eq!(stats.buckets_completed, DEFAULT_NUM_BUCKET);
+        assert_eq!(prover_stats.buckets_completed, DEFAULT_NUM_BUCKET);
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn test_conv_multiplexed_unix() {
+        test_conv_multiplexed(config(true).threads(DEFAULT_NUM_BUCKET));
+        test_conv_multiplexed(config(true).threads(2));
+        test_conv_multiplexed(config(false).threads(3));
+        // the threads slice the random triples of their buckets alike
+        test_conv_multiplexed(config(false).threads(2).triple_buckets(3));
+    }
+
+    fn test_conv_noise<FE: FiniteField<PrimeField = FE>, TF: FiniteField<PrimeField = F2>>()
+    where
+        F2: IsSubFieldOf<TF>,
+    {
+        let psk = [3u8; 32];
+        let n = 10;
+        let (sender, receiver) = UnixStream::pair().unwrap();
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(NoiseChannel::initiator(sender, &psk).unwrap());
+            let mut fconv = ProverConv::<FE, TF, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(NoiseChannel::responder(receiver, &psk).unwrap());
+        let mut fconv = VerifierConv::<FE, TF, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_bit_add_carry_f61p() {
+        test_bit_add_carry::<F61p, F40b>();
+    }
+
+    #[test]
+    fn test_fdabit_f61p() {
+        test_fdabit::<F61p, F40b>(100);
+    }
+
+    #[test]
+    fn test_conv_f61p() {
+        test_conv::<F61p, F40b>(NB_BITS, true);
+    }
+
+    #[test]
+    fn test_conv_wolverine_f61p() {
+        test_conv::<F61p, F40b>(NB_BITS, false);
+    }
+
+    #[test]
+    fn test_bit_add_carry_f61p_f63b() {
+        test_bit_add_carry::<F61p, F63b>();
+    }
+
+    #[test]
+    fn test_conv_f61p_f63b() {
+        test_conv::<F61p, F63b>(NB_BITS, true);
+    }
+
+    #[test]
+    fn test_conv_max_bits_f61p() {
+        test_conv::<F61p, F40b>(59, true);
+    }
+
+    #[test]
+    fn test_bit_add_carry_f127p() {
+        test_bit_add_carry::<F127p, F40b>();
+    }
+
+    #[test]
+    fn test_fdabit_f127p() {
+        test_fdabit::<F127p, F40b>(100);
+    }
+
+    #[test]
+    fn test_conv_64_bits_f127p() {
+        test_conv::<F127p, F40b>(64, true);
+    }
+
+    #[test]
+    fn test_conv_wolverine_64_bits_f127p() {
+        test_conv::<F127p, F40b>(64, false);
+    }
+
+    #[test]
+    fn test_conv_96_bits_f127p() {
+        test_conv::<F127p, F63b>(96, true);
+    }
+
+    #[test]
+    fn test_conv_max_bits_f127p() {
+        test_conv::<F127p, F40b>(125, true);
+    }
+
+    // Both parties reject edabits too large for the field before
+    // communicating, and go on with the largest ones.
+    #[test]
+    fn test_nb_bits_out_of_range_f127p() {
+        let n = 10;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F127p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            match fconv.random_edabits(&mut channel, &mut rng, 126, n) {
+                Err(Error::InvalidNbBits { nb_bits, .. }) => assert_eq!(nb_bits, 126),
+                res => panic!("expected 126 bits to be rejected: {:?}", res.map(|_| ())),
+            }
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, 125, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F127p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        match fconv.random_edabits(&mut channel, &mut rng, 126, n) {
+            Err(Error::InvalidNbBits { nb_bits, .. }) => assert_eq!(nb_bits, 126),
+            res => panic!("expected 126 bits to be rejected: {:?}", res.map(|_| ())),
+        }
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, 125, n)
+            .unwrap();
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_bit_add_carry_dyn_f61p() {
+        test_bit_add_carry::<DynF61p, F40b>();
+    }
+
+    #[test]
+    fn test_fdabit_dyn_f61p() {
+        test_fdabit::<DynF61p, F40b>(100);
+    }
+
+    #[test]
+    fn test_conv_dyn_f61p() {
+        test_conv::<DynF61p, F40b>(NB_BITS, true);
+    }
+
+    #[test]
+    fn test_conv_wolverine_dyn_f61p() {
+        test_conv::<DynF61p, F40b>(NB_BITS, false);
+    }
+
+    #[test]
+    fn test_conv_comm_dyn_f61p() {
+        test_conv_comm::<DynF61p, F40b>(ProtocolVersion::V1, true);
+    }
+
+    #[test]
+    fn test_dyn_f61p_same_as_f61p() {
+        let mut rng = AesRng::new();
+        for _ in 0..100 {
+            let bits: Vec<F2> = (0..60).map(|_| F2::random(&mut rng)).collect();
+            let x = convert_bits_to_field::<F61p>(&bits).unwrap();
+            let y = convert_bits_to_field::<DynF61p>(&bits).unwrap();
+            assert_eq!(x.to_bytes()[..], y.to_bytes()[..8]);
+        }
+        for nb_bits in [1, 59, 60, 61] {
+            assert_eq!(
+                check_nb_bits::<F61p>(nb_bits).is_ok(),
+                check_nb_bits::<DynF61p>(nb_bits).is_ok()
+            );
+        }
+        for gamma in [10, 50, 51, 60] {
+            assert_eq!(
+                check_parameters::<F61p>(1000, gamma).is_ok(),
+                check_parameters::<DynF61p>(1000, gamma).is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_nb_bits() {
+        assert!(check_nb_bits::<F61p>(59).is_ok());
+        assert!(check_nb_bits::<F61p>(60).is_err());
+        assert!(check_nb_bits::<F61p>(61).is_err());
+        assert!(check_nb_bits::<F127p>(64).is_ok());
+        assert!(check_nb_bits::<F127p>(125).is_ok());
+        assert!(check_nb_bits::<F127p>(126).is_err());
+        assert!(check_nb_bits::<F127p>(127).is_err());
+        assert!(check_nb_bits::<F127p>(0).is_err());
+    }
+
+    #[test]
+    fn test_conv_config() {
+        let config = ConvConfig::new();
+        assert_eq!(config, ConvConfig::default());
+        assert_eq!(config.num_bucket(), DEFAULT_NUM_BUCKET);
+        assert_eq!(config.num_cut(), DEFAULT_NUM_CUT);
+        assert_eq!(config.mult_check(), MultCheck::QuickSilver);
+        assert_eq!(config.num_threads(), 1);
+        assert!(config.validate().is_ok());
+
+        assert!(config.clone().buckets(0).validate().is_err());
+        assert!(config.clone().threads(0).validate().is_err());
+        assert!(config.clone().threads(6).validate().is_err());
+        assert_eq!(
+            config.clone().threads(5).validate().is_ok(),
+            cfg!(feature = "threads")
+        );
+
+        // one bucket channel per thread, and none with a single thread
+        let none = None::<BucketChannels<NoBucketChannel>>;
+        assert!(check_bucket_channels(&config, &none).is_ok());
+        assert!(check_bucket_channels(&config.clone().threads(2), &none).is_err());
+        let two = Some(BucketChannels::Separate(vec![(), ()]));
+        assert!(check_bucket_channels(&config, &two).is_err());
+        assert_eq!(
+            check_bucket_channels(&config.threads(2), &two).is_ok(),
+            cfg!(feature = "threads")
+        );
+    }
+
+    #[test]
+    fn test_conv_observer() {
+        let n = 4;
+        let (sender, receiver) = local_pair();
+
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(TrackChannel::new(receiver));
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        let observer = Arc::new(StatsObserver::new());
+        let stats = fconv
+            .conv(
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &config(true).observer(observer.clone()),
+            )
+            .unwrap();
+        handle.join().unwrap();
+
+        // the observer sees what `conv` returns, except for the breakdown
+        let observed = observer.stats();
+        assert_eq!(observed.comm, None);
+        assert_eq!(observed.phases, stats.phases);
+        assert_eq!(observed.extensions, stats.extensions);
+        assert_eq!(observed.buckets_completed, DEFAULT_NUM_BUCKET);
+        assert_eq!(observed.bucket_steps, stats.bucket_steps);
+        // the buckets are timed step by step, within the phase checking them
+        let steps = stats.bucket_steps;
+        assert_eq!(steps.add > Duration::ZERO, cfg!(feature = "std-time"));
+        if let Some(buckets) = stats.phases.last().unwrap().duration {
+            assert!(steps.add + steps.a2b + steps.open + steps.check_zero <= buckets);
+        }
+
+        let phases = stats.phases.iter().map(|p| p.phase).collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            [
+                CONV_SCOPE_VOLE,
+                CONV_SCOPE_EDABITS,
+                CONV_SCOPE_DABITS,
+                CONV_SCOPE_TRIPLES,
+                CONV_SCOPE_FDABIT,
+                CONV_SCOPE_SHUFFLE,
+                CONV_SCOPE_CUT_AND_CHOOSE,
+                CONV_SCOPE_BUCKETS,
+            ]
+        );
+        let comm = stats.comm.as_ref().unwrap();
+        // the header is counted apart from the phases
+        assert_eq!(comm.get(CONV_SCOPE_HEADER).bytes_written, 8 * (2 + 7));
+        for phase in stats.phases.iter() {
+            assert_eq!(phase.comm, Some(comm.get(phase.phase)), "{}", phase.phase);
+            assert_eq!(phase.duration.is_some(), cfg!(feature = "std-time"));
+        }
+        assert_eq!(
+            stats.phases[1].size,
+            n * DEFAULT_NUM_BUCKET + DEFAULT_NUM_CUT
+        );
+        assert_eq!(stats.phases[7].size, DEFAULT_NUM_BUCKET);
+        // the verifier flushes its challenges when checking the buckets
+        assert!(stats.phases[7].rounds.unwrap() > 0);
+
+        assert_eq!(stats.schema_version, CONV_STATS_SCHEMA_VERSION);
+        assert_eq!(stats.field, std::any::type_name::<F61p>());
+        assert_eq!(stats.config, config(true));
+        assert_eq!((stats.num, stats.nb_bits), (n, NB_BITS));
+        assert!(stats.diff(&stats, 0.0).is_empty());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = stats.to_json();
+            let parsed = ConvStats::from_json(&json).unwrap();
+            assert_eq!(
+                parsed,
+                ConvStats {
+                    comm: None,
+                    ..stats
+                }
+            );
+            assert!(ConvStats::from_json(&json.replace("\"vole\"", "\"unknown\"")).is_err());
+        }
+    }
+
+    #[test]
+    fn test_conv_stats_diff() {
+        let phase = |phase, bytes_written, secs| PhaseStats {
+            phase,
+            size: 1,
+            duration: Some(Duration::from_secs(secs)),
+            comm: Some(ScopeComm {
+                bytes_read: 0,
+                bytes_written,
+            }),
+            rounds: Some(1),
+        };
+        let before = ConvStats {
+            phases: vec![
+                phase(CONV_SCOPE_EDABITS, 100, 10),
+                phase(CONV_SCOPE_BUCKETS, 100, 10),
+            ],
+            ..ConvStats::default()
+        };
+        let after = ConvStats {
+            phases: vec![
+                phase(CONV_SCOPE_EDABITS, 105, 10),
+                phase(CONV_SCOPE_BUCKETS, 100, 20),
+            ],
+            ..ConvStats::default()
+        };
+        assert!(before.diff(&after, 1.0).is_empty());
+        assert_eq!(
+            before.diff(&after, 0.1),
+            [Regression {
+                phase: CONV_SCOPE_BUCKETS,
+                metric: "seconds",
+                before: 10.0,
+                after: 20.0,
+            }]
+        );
+        assert_eq!(before.diff(&after, 0.01).len(), 2);
+        // improvements are not regressions
+        assert!(after.diff(&before, 0.0).is_empty());
+    }
+
+    // The number of flushes of the verifier checking `num_bucket` buckets.
+    fn bucket_rounds(num_bucket: usize, with_quicksilver: bool) -> usize {
+        let n = 4;
+        let config = config(with_quicksilver).buckets(num_bucket);
+        let prover_config = config.clone();
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &prover_config)
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(TrackChannel::new(receiver));
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        let stats = fconv
+            .conv(&mut channel, &mut rng, &edabits, &config)
+            .unwrap();
+        handle.join().unwrap();
+        let buckets = stats.phases.last().unwrap();
+        assert_eq!(buckets.phase, CONV_SCOPE_BUCKETS);
+        buckets.rounds.unwrap()
+    }
+
+    #[test]
+    fn test_conv_bucket_rounds() {
+        // the checks of the buckets are deferred to the last one
+        for with_quicksilver in [true, false] {
+            let rounds = bucket_rounds(1, with_quicksilver);
+            assert!(rounds > 0);
+            assert_eq!(bucket_rounds(6, with_quicksilver), rounds);
+        }
+    }
+
+    // A checker rejecting every check, without communicating.
+    #[derive(Clone)]
+    struct BrokenChecker;
+
+    impl<FE: FiniteField, P: FComParty<FE>> MultChecker<FE, P> for BrokenChecker {
+        fn push_triples(&mut self, _triples: &[(P::Mac, P::Mac, P::Mac)]) {}
+
+        fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
+            &mut self,
+            _fcom: &mut P,
+            _channel: &mut C,
+            _rng: &mut RNG,
+        ) -> Result<(), Error> {
+            Err(Error::Other("broken checker".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_conv_with_checker() {
+        let nb_edabits = 10;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+
+            // the checker replaces the Wolverine check of the configuration
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, nb_edabits)
+                .unwrap();
+            fconv
+                .conv_with_checker(
+                    &mut channel,
+                    &mut rng,
+                    &edabits,
+                    &config(false),
+                    QuickSilverChecker::new(),
+                )
+                .unwrap();
+
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, nb_edabits)
+                .unwrap();
+            fconv
+                .conv_with_checker(
+                    &mut channel,
+                    &mut rng,
+                    &edabits,
+                    &config(true),
+                    BrokenChecker,
+                )
+                .unwrap_err()
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, nb_edabits)
+            .unwrap();
+        let stats = fconv
+            .conv_with_checker(
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &config(false),
+                QuickSilverChecker::new(),
+            )
+            .unwrap();
+        assert_eq!(stats.buckets_completed, DEFAULT_NUM_BUCKET);
+        let triples = stats.phases.iter().find(|p| p.phase == CONV_SCOPE_TRIPLES);
+        assert_eq!(triples.unwrap().size, 0);
+
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, nb_edabits)
+            .unwrap();
+        let err = fconv
+            .conv_with_checker(
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &config(true),
+                BrokenChecker,
+            )
+            .unwrap_err();
+
+        for err in [err, handle.join().unwrap()] {
+            assert!(matches!(err, Error::Other(ref e) if e == "broken checker"));
+        }
+    }
+
+    #[test]
+    fn test_conv_auto() {
+        let config = ConvConfig::new().strategy(MultCheck::Auto);
+        let (sender, receiver) = local_pair();
+        let prover_config = config.clone();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let mut calibrations = Vec::new();
+            for _ in 0..2 {
+                let edabits = fconv
+                    .random_edabits(&mut channel, &mut rng, NB_BITS, 10)
+                    .unwrap();
+                let stats = fconv
+                    .conv(&mut channel, &mut rng, &edabits, &prover_config)
+                    .unwrap();
+                calibrations.push(stats.calibration.unwrap());
+            }
+            calibrations
+        });
+        let mut rng = AesRng::new();
+        let mut channel = TrackChannel::new(StrictChannel::new(receiver));
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut calibrations = Vec::new();
+        for _ in 0..2 {
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 10)
+                .unwrap();
+            let stats = fconv
+                .conv(&mut channel, &mut rng, &edabits, &config)
+                .unwrap();
+            calibrations.push(stats.calibration.unwrap());
+        }
+        let prover_calibrations = handle.join().unwrap();
+
+        // the calibration runs once per session, and both parties select
+        // the same check
+        let calibration = calibrations[0];
+        assert_eq!(calibrations[1], calibration);
+        assert_eq!(prover_calibrations[1], prover_calibrations[0]);
+        assert_eq!(prover_calibrations[0].selected, calibration.selected);
+        assert_ne!(calibration.selected, MultCheck::Auto);
+        assert!(calibration.comm.unwrap().total() > 0);
+        if cfg!(feature = "std-time") {
+            let (quicksilver, wolverine) = (
+                calibration.quicksilver.unwrap(),
+                calibration.wolverine.unwrap(),
+            );
+            let expected = if wolverine < quicksilver {
+                MultCheck::Wolverine
+            } else {
+                MultCheck::QuickSilver
+            };
+            assert_eq!(calibration.selected, expected);
+        } else {
+            assert_eq!(calibration.selected, MultCheck::QuickSilver);
+        }
+    }
+
+    // Convert random edabits with the prover deviating from the protocol
+    // with `fault` during the check. Return the result of the verifier.
+    fn run_conv_with_fault(
+        fault: Option<ConvFault>,
+        config: &ConvConfig,
+    ) -> Result<ConvStats, Error> {
+        let num = 20;
+        let (sender, receiver) = local_pair();
+        let prover_config = config.clone();
+        let handle = std::thread::spawn(move || -> Result<ConvStats, Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            fconv.set_fault(fault);
+            fconv.conv(&mut channel, &mut rng, &edabits, &prover_config)
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<ConvStats, Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            fconv.conv(&mut channel, &mut rng, &edabits, config)
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if fault.is_none() {
+            prover_res.unwrap();
+        }
+        res
+    }
+
+    // Convert edabits whose values are the sums `a + b` of two values
+    // committed separately. With `cheat`, the prover commits to the bits of
+    // `a` instead of the bits of `a + b`. Return the result of the verifier.
+    fn run_split_values(cheat: bool) -> Result<ConvStats, Error> {
+        let num = 20;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || -> Result<ConvStats, Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let half = 1u128 << (NB_BITS - 2);
+            let a: Vec<F61p> = (0..num)
+                .map(|_| F61p::try_from(rng.gen_range(0..half)).unwrap())
+                .collect();
+            let b: Vec<F61p> = (0..num)
+                .map(|_| F61p::try_from(rng.gen_range(0..half)).unwrap())
+                .collect();
+            let a_mac = fconv.fcom().input(&mut channel, &mut rng, &a)?;
+            let b_mac = fconv.fcom().input(&mut channel, &mut rng, &b)?;
+            let sums: Vec<F61p> = a.iter().zip(b.iter()).map(|(a, b)| *a + *b).collect();
+            let bits = fconv.input_edabits(
+                &mut channel,
+                &mut rng,
+                if cheat { &a } else { &sums },
+                NB_BITS,
+            )?;
+            let edabits: Vec<_> = (0..num)
+                .map(|i| {
+                    let fcom = fconv.fcom();
+                    let value = fcom.add(MacProver(a[i], a_mac[i]), MacProver(b[i], b_mac[i]));
+                    EdabitsProver::new(bits[i].bits().to_vec(), value)
+                })
+                .collect();
+            fconv.conv(&mut channel, &mut rng, &edabits, &config(true))
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<ConvStats, Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let a_mac = fconv.fcom().input(&mut channel, &mut rng, num)?;
+            let b_mac = fconv.fcom().input(&mut channel, &mut rng, num)?;
+            let bits = fconv.input_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let edabits: Vec<_> = (0..num)
+                .map(|i| {
+                    let value = fconv.fcom().add(a_mac[i], b_mac[i]);
+                    EdabitsVerifier::new(bits[i].bits().to_vec(), value)
+                })
+                .collect();
+            fconv.conv(&mut channel, &mut rng, &edabits, &config(true))
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if !cheat {
+            prover_res.unwrap();
+        }
+        res
+    }
+
+    // The pairs compared by `test_less_than`: the edge cases, then random
+    // pairs, a third of them equal.
+    fn less_than_pairs() -> Vec<(u64, u64)> {
+        let max = (1 << NB_BITS) - 1;
+        let mut pairs = vec![(0, 0), (max, max), (0, max), (max, 0), (1, 0), (0, 1)];
+        let mut rng = AesRng::from_seed(Block::default());
+        for i in 0..300 {
+            let x = rng.gen_range(0..=max);
+            let y = if i % 3 == 0 {
+                x
+            } else {
+                rng.gen_range(0..=max)
+            };
+            pairs.push((x, y));
+        }
+        pairs
+    }
+
+    // Commit to the bits and the values of `values` as edabits.
+    fn input_edabits<C: AbstractChannel>(
+        fconv: &mut ProverConv<F61p, F40b, UnsafeInsecureTestOt>,
+        channel: &mut C,
+        rng: &mut AesRng,
+        values: &[u64],
+    ) -> Vec<EdabitsProver<F61p, F40b>> {
+        let bits: Vec<_> = values
+            .iter()
+            .flat_map(|x| (0..NB_BITS).map(move |i| F2::from((x >> i) & 1 == 1)))
+            .collect();
+        let bits_mac = fconv.fcom_f2().input(channel, rng, &bits).unwrap();
+        let values: Vec<_> = values
+            .iter()
+            .map(|x| F61p::try_from(*x as u128).unwrap())
+            .collect();
+        let values_mac = fconv.fcom().input(channel, rng, &values).unwrap();
+        (0..values.len())
+            .map(|i| {
+                EdabitsProver::new(
+                    (i * NB_BITS..(i + 1) * NB_BITS)
+                        .map(|j| MacProver(bits[j], bits_mac[j]))
+                        .collect(),
+                    MacProver(values[i], values_mac[i]),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_less_than() {
+        let pairs = less_than_pairs();
+        let expected: Vec<_> = pairs.iter().map(|(x, y)| F2::from(x < y)).collect();
+        let (sender, receiver) = local_pair();
+        let prover_pairs = pairs.clone();
+        let prover_expected = expected.clone();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let (xs, ys): (Vec<_>, Vec<_>) = prover_pairs.into_iter().unzip();
+            let x_batch = input_edabits(&mut fconv, &mut channel, &mut rng, &xs);
+            let y_batch = input_edabits(&mut fconv, &mut channel, &mut rng, &ys);
+            let lt = fconv
+                .less_than(&mut channel, &mut rng, &x_batch, &y_batch)
+                .unwrap();
+            let lt_bits: Vec<_> = lt.iter().map(|b| b.0).collect();
+            assert_eq!(lt_bits, prover_expected);
+            fconv.fcom_f2().open(&mut channel, &lt).unwrap();
+            channel.flush().unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let n = pairs.len();
+        let mut input = |fconv: &mut VerifierConv<F61p, F40b, UnsafeInsecureTestOt>| {
+            let bits_mac = fconv
+                .fcom_f2()
+                .input(&mut channel, &mut rng, n * NB_BITS)
+                .unwrap();
+            let values_mac = fconv.fcom().input(&mut channel, &mut rng, n).unwrap();
+            (0..n)
+                .map(|i| {
+                    EdabitsVerifier::new(
+                        bits_mac[i * NB_BITS..(i + 1) * NB_BITS].to_vec(),
+                        values_mac[i],
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let x_batch = input(&mut fconv);
+        let y_batch = input(&mut fconv);
+        // the batches must be of the same length, which is checked before
+        // communicating
+        match fconv.less_than(&mut channel, &mut rng, &x_batch[..1], &y_batch) {
+            Err(Error::BatchLengthMismatch { left, right }) => assert_eq!((left, right), (1, n)),
+            res => panic!("expected a length mismatch: {:?}", res),
+        }
+        let lt = fconv
+            .less_than(&mut channel, &mut rng, &x_batch, &y_batch)
+            .unwrap();
+        let mut lt_bits = Vec::new();
+        fconv
+            .fcom_f2()
+            .open(&mut channel, &lt, &mut lt_bits)
+            .unwrap();
+        assert_eq!(lt_bits, expected);
+        // both outcomes occur, along with the equal pairs
+        assert!(expected.contains(&F2::ZERO) && expected.contains(&F2::ONE));
+        handle.join().unwrap();
+    }
+
+    const ADD_SUB_WIDTHS: [usize; 4] = [1, 2, NB_BITS, 64];
+
+    // The operands of `test_bit_add_sub` on `m` bits, with their carries
+    // and borrows in: the edge cases, overflowing ones first, then random
+    // operands.
+    fn add_sub_operands(m: usize) -> Vec<(u128, u128, bool)> {
+        let max = (1 << m) - 1;
+        let mut operands = vec![
+            (max, max, true),
+            (max, 1, false),
+            (0, max, true),
+            (0, 0, false),
+        ];
+        let mut rng = AesRng::from_seed(Block::default());
+        for _ in 0..20 {
+            operands.push((rng.gen_range(0..=max), rng.gen_range(0..=max), rng.gen()));
+        }
+        operands
+    }
+
+    // The bits of `x` on `m` bits, the least significant first.
+    fn to_bits(x: u128, m: usize) -> Vec<F2> {
+        (0..m).map(|i| F2::from((x >> i) & 1 == 1)).collect()
+    }
+
+    // The bits of the sums and the differences of `add_sub_operands(m)`,
+    // followed by their carries and borrows, as opened by
+    // `test_bit_add_sub`.
+    fn add_sub_expected(m: usize) -> (Vec<F2>, Vec<F2>) {
+        let operands = add_sub_operands(m);
+        let modulus = 1 << m;
+        let mut sums = Vec::new();
+        let mut differences = Vec::new();
+        for (x, y, c) in operands.iter() {
+            sums.extend(to_bits(x + y + *c as u128, m));
+            differences.extend(to_bits(x + 2 * modulus - y - *c as u128, m));
+        }
+        for (x, y, c) in operands.iter() {
+            sums.push(F2::from(x + y + *c as u128 >= modulus));
+            differences.push(F2::from(*x < y + *c as u128));
+        }
+        (sums, differences)
+    }
+
+    // The bits of the results of `bit_add_carry` or `bit_sub_borrow`,
+    // followed by their carries or borrows.
+    fn result_bits<M: Copy>(results: &[(Vec<M>, M)]) -> Vec<M> {
+        let mut bits: Vec<_> = results.iter().flat_map(|(b, _)| b.clone()).collect();
+        bits.extend(results.iter().map(|(_, c)| *c));
+        bits
+    }
+
+    // Add and subtract committed operands of several widths, with carries
+    // and borrows in, and open the results. The multiplications are checked
+    // with QuickSilver for the even widths and with random triples for the
+    // odd ones.
+    #[test]
+    fn test_bit_add_sub() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            for (w, m) in ADD_SUB_WIDTHS.into_iter().enumerate() {
+                let operands = add_sub_operands(m);
+                let n = operands.len();
+                let mut input = |bits: Vec<F2>| {
+                    let macs = fconv
+                        .fcom_f2()
+                        .input(&mut channel, &mut rng, &bits)
+                        .unwrap();
+                    bits.into_iter()
+                        .zip(macs)
+                        .map(|(bit, mac)| MacProver(bit, mac))
+                        .collect::<Vec<_>>()
+                };
+                let x = input(operands.iter().flat_map(|o| to_bits(o.0, m)).collect());
+                let y = input(operands.iter().flat_map(|o| to_bits(o.1, m)).collect());
+                let c = input(operands.iter().map(|o| F2::from(o.2)).collect());
+                let x: Vec<_> = x.chunks_exact(m).map(|x| x.to_vec()).collect();
+                let y: Vec<_> = y.chunks_exact(m).map(|y| y.to_vec()).collect();
+                let with_triples = w % 2 == 1;
+                let (mut add_triples, mut sub_triples) = (Vec::new(), Vec::new());
+                if with_triples {
+                    fconv
+                        .random_triples(&mut channel, &mut rng, n * m, &mut add_triples)
+                        .unwrap();
+                    fconv
+                        .random_triples(&mut channel, &mut rng, n * m, &mut sub_triples)
+                        .unwrap();
+                }
+                let sums = fconv
+                    .bit_add_carry(
+                        &mut channel,
+                        &mut rng,
+                        &x,
+                        &y,
+                        Some(&c[..]),
+                        with_triples.then_some(&add_triples[..]),
+                    )
+                    .unwrap();
+                let differences = fconv
+                    .bit_sub_borrow(
+                        &mut channel,
+                        &mut rng,
+                        &x,
+                        &y,
+                        Some(&c[..]),
+                        with_triples.then_some(&sub_triples[..]),
+                    )
+                    .unwrap();
+                fconv
+                    .fcom_f2()
+                    .open(&mut channel, &result_bits(&sums))
+                    .unwrap();
+                fconv
+                    .fcom_f2()
+                    .open(&mut channel, &result_bits(&differences))
+                    .unwrap();
+                // without carry in, the sum of the overflowing pair carries
+                let sums = fconv
+                    .bit_add_carry(&mut channel, &mut rng, &x[..1], &y[..1], None, None)
+                    .unwrap();
+                fconv
+                    .fcom_f2()
+                    .open(&mut channel, &result_bits(&sums))
+                    .unwrap();
+                // the mismatched operands are rejected before communicating
+                assert!(matches!(
+                    fconv.bit_add_carry(&mut channel, &mut rng, &x[..1], &y, None, None),
+                    Err(Error::BatchLengthMismatch { .. })
+                ));
+                assert!(matches!(
+                    fconv.bit_sub_borrow(&mut channel, &mut rng, &x, &y, Some(&c[1..]), None),
+                    Err(Error::BatchLengthMismatch { .. })
+                ));
+            }
+            channel.flush().unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        for (w, m) in ADD_SUB_WIDTHS.into_iter().enumerate() {
+            let n = add_sub_operands(m).len();
+            let x = fconv
+                .fcom_f2()
+                .input(&mut channel, &mut rng, n * m)
+                .unwrap();
+            let y = fconv
+                .fcom_f2()
+                .input(&mut channel, &mut rng, n * m)
+                .unwrap();
+            let c = fconv.fcom_f2().input(&mut channel, &mut rng, n).unwrap();
+            let x: Vec<_> = x.chunks_exact(m).map(|x| x.to_vec()).collect();
+            let y: Vec<_> = y.chunks_exact(m).map(|y| y.to_vec()).collect();
+            let with_triples = w % 2 == 1;
+            let (mut add_triples, mut sub_triples) = (Vec::new(), Vec::new());
+            if with_triples {
+                fconv
+                    .random_triples(&mut channel, &mut rng, n * m, &mut add_triples)
+                    .unwrap();
+                fconv
+                    .random_triples(&mut channel, &mut rng, n * m, &mut sub_triples)
+                    .unwrap();
+            }
+            let sums = fconv
+                .bit_add_carry(
+                    &mut channel,
+                    &mut rng,
+                    &x,
+                    &y,
+                    Some(&c[..]),
+                    with_triples.then_some(&add_triples[..]),
+                )
+                .unwrap();
+            let differences = fconv
+                .bit_sub_borrow(
+                    &mut channel,
+                    &mut rng,
+                    &x,
+                    &y,
+                    Some(&c[..]),
+                    with_triples.then_some(&sub_triples[..]),
+                )
+                .unwrap();
+            let (mut sum_bits, mut difference_bits) = (Vec::new(), Vec::new());
+            fconv
+                .fcom_f2()
+                .open(&mut channel, &result_bits(&sums), &mut sum_bits)
+                .unwrap();
+            fconv
+                .fcom_f2()
+                .open(
+                    &mut channel,
+                    &result_bits(&differences),
+                    &mut difference_bits,
+                )
+                .unwrap();
+            assert_eq!((sum_bits, difference_bits), add_sub_expected(m), "{}", m);
+            let sums = fconv
+                .bit_add_carry(&mut channel, &mut rng, &x[..1], &y[..1], None, None)
+                .unwrap();
+            let mut sum_bits = Vec::new();
+            fconv
+                .fcom_f2()
+                .open(&mut channel, &result_bits(&sums), &mut sum_bits)
+                .unwrap();
+            // max + max = 2^(m + 1) - 2
+            let mut expected = vec![F2::ONE; m];
+            expected[0] = F2::ZERO;
+            expected.push(F2::ONE);
+            assert_eq!(sum_bits, expected);
+            match fconv.bit_add_carry(&mut channel, &mut rng, &x[..1], &y, None, None) {
+                Err(Error::BatchLengthMismatch { left, right }) => {
+                    assert_eq!((left, right), (1, n))
+                }
+                res => panic!("expected a length mismatch: {:?}", res.map(|_| ())),
+            }
+            match fconv.bit_sub_borrow(&mut channel, &mut rng, &x, &y, Some(&c[1..]), None) {
+                Err(Error::BatchLengthMismatch { left, right }) => {
+                    assert_eq!((left, right), (n, n - 1))
+                }
+                res => panic!("expected a length mismatch: {:?}", res.map(|_| ())),
+            }
+        }
+        handle.join().unwrap();
+    }
+
+    // Wipe the commitments of the prover, the clones of an edabit being
+    // wiped independently.
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_prover() {
+        use zeroize::Zeroize;
+        let mut rng = AesRng::new();
+        let zero = MacProver(F61p::ZERO, F61p::ZERO);
+        let mut value = MacProver(F61p::random(&mut rng), F61p::random(&mut rng));
+        let bits: Vec<_> = (0..NB_BITS)
+            .map(|_| MacProver(F2::random(&mut rng), F40b::random(&mut rng)))
+            .collect();
+        let mut edabit = EdabitsProver::<F61p, F40b>::new(bits.clone(), value);
+        let copy = edabit.clone();
+        edabit.zeroize();
+        assert!(edabit.bits().is_empty());
+        assert_eq!(edabit.value(), zero);
+        assert_eq!(copy.bits(), &bits[..]);
+        assert_eq!(copy.value(), value);
+        value.zeroize();
+        assert_eq!(value, zero);
+
+        let mut buf = bits;
+        let capacity = buf.capacity();
+        wipe(&mut buf);
+        assert!(buf.is_empty());
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    // The verifier names the cut edabit whose opened value does not match
+    // its opened bits.
+    #[test]
+    fn test_open_cut_edabits_mismatch() {
+        let values = [1u64, 2, 3];
+        // the bits of the second edabit are those of 5
+        let bits: Vec<_> = [1u64, 5, 3]
+            .iter()
+            .flat_map(|x| (0..NB_BITS).map(move |i| F2::from((x >> i) & 1 == 1)))
+            .collect();
+        let prover_bits = bits.clone();
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let values: Vec<_> = values
+                .iter()
+                .map(|x| F61p::try_from(*x as u128).unwrap())
+                .collect();
+            let values_mac = fconv.fcom().input(&mut channel, &mut rng, &values).unwrap();
+            let macs: Vec<_> = values
+                .iter()
+                .zip(values_mac)
+                .map(|(x, mac)| MacProver(*x, mac))
+                .collect();
+            open_cut_edabits::<F61p, _, _>(
+                fconv.fcom(),
+                &mut channel,
+                &prover_bits,
+                &macs,
+                NB_BITS,
+            )
+            .unwrap();
+            channel.flush().unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let macs = fconv
+            .fcom()
+            .input(&mut channel, &mut rng, values.len())
+            .unwrap();
+        match open_cut_edabits::<F61p, _, _>(fconv.fcom(), &mut channel, &bits, &macs, NB_BITS) {
+            Err(Error::EdabitOpenMismatch { index }) => assert_eq!(index, 1),
+            res => panic!("expected a mismatch of the edabit 1: {:?}", res),
+        }
+        handle.join().unwrap();
+    }
+
+    // Check the truncation by `shift` bits of random edabits, with the
+    // prover committing to a wrong truncated value if `cheat`, and return
+    // the result of the verifier.
+    fn run_check_truncation(shift: usize, cheat: bool) -> Result<ConvStats, Error> {
+        let num = 20;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || -> Result<(), Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let mut truncated = edabits
+                .iter()
+                .map(|e| {
+                    let bits: Vec<_> = e.bits()[shift..].iter().map(|b| b.0).collect();
+                    convert_bits_to_field::<F61p>(&bits)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if cheat {
+                truncated[3] += F61p::ONE;
+            }
+            let truncated_mac = fconv.fcom().input(&mut channel, &mut rng, &truncated)?;
+            let pairs: Vec<_> = edabits
+                .into_iter()
+                .zip(truncated.into_iter().zip(truncated_mac))
+                .map(|(e, (y, y_mac))| (e, MacProver(y, y_mac)))
+                .collect();
+            fconv.check_truncation(&mut channel, &mut rng, &pairs, shift, &config(true))?;
+            Ok(())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<ConvStats, Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let truncated = fconv.fcom().input(&mut channel, &mut rng, num)?;
+            let pairs: Vec<_> = edabits.into_iter().zip(truncated).collect();
+            // the shift must leave some bits, which is checked before
+            // communicating
+            assert!(fconv
+                .check_truncation(&mut channel, &mut rng, &pairs, NB_BITS, &config(true))
+                .is_err());
+            fconv.check_truncation(&mut channel, &mut rng, &pairs, shift, &config(true))
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if !cheat {
+            prover_res.unwrap();
+        }
+        res
+    }
+
+    #[test]
+    fn test_check_truncation() {
+        for shift in [0, 1, 16, NB_BITS - 1] {
+            let stats = run_check_truncation(shift, false).unwrap();
+            assert_eq!(stats.nb_bits, NB_BITS - shift);
+        }
+        assert!(run_check_truncation(16, true).is_err());
+    }
+
+    fn run_field_to_bits(cheat: bool) -> Result<(), Error> {
+        let num = 20;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || -> Result<(), Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let mut values: Vec<F61p> = (0..num)
+                .map(|_| F61p::try_from(rng.gen_range(0..1u128 << NB_BITS)).unwrap())
+                .collect();
+            if cheat {
+                values[1] = F61p::try_from(1u128 << NB_BITS).unwrap();
+            }
+            let values_mac = fconv.fcom().input(&mut channel, &mut rng, &values)?;
+            let values: Vec<_> = values
+                .into_iter()
+                .zip(values_mac)
+                .map(|(value, mac)| MacProver(value, mac))
+                .collect();
+            let edabits = fconv.convert_field_to_bits(
+                &mut channel,
+                &mut rng,
+                &values,
+                NB_BITS,
+                &config(true),
+            )?;
+            fconv.conv(&mut channel, &mut rng, &edabits, &config(false))?;
+            // the bits are commitments like any other
+            fconv.fcom_f2().open(&mut channel, edabits[0].bits())?;
+            fconv.fcom().open(&mut channel, &[edabits[0].value()])?;
+            channel.flush()?;
+            Ok(())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<(), Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let values = fconv.fcom().input(&mut channel, &mut rng, num)?;
+            let edabits = fconv.convert_field_to_bits(
+                &mut channel,
+                &mut rng,
+                &values,
+                NB_BITS,
+                &config(true),
+            )?;
+            assert_eq!(edabits.len(), num);
+            fconv.conv(&mut channel, &mut rng, &edabits, &config(false))?;
+            let mut bits = Vec::new();
+            fconv
+                .fcom_f2()
+                .open(&mut channel, edabits[0].bits(), &mut bits)?;
+            let mut value = Vec::new();
+            fconv
+                .fcom()
+                .open(&mut channel, &[edabits[0].value()], &mut value)?;
+            assert_eq!(convert_bits_to_field::<F61p>(&bits)?, value[0]);
+            Ok(())
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if !cheat {
+            prover_res.unwrap();
+        }
+        res
+    }
+
+    #[test]
+    fn test_convert_field_to_bits() {
+        run_field_to_bits(false).unwrap();
+        // a value which does not fit in the bits fails the check
+        assert!(run_field_to_bits(true).is_err());
+    }
+
+    #[test]
+    fn test_input_edabits_f61p() {
+        let num = 300;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+
+            // the edge values along with random ones
+            let max = (1u128 << NB_BITS) - 1;
+            let mut values = vec![0, 1, max];
+            values.extend((3..num).map(|_| rng.gen_range(0..=max)));
+            let values: Vec<F61p> = values
+                .into_iter()
+                .map(|v| F61p::try_from(v).unwrap())
+                .collect();
+
+            // a value out of range is rejected before anything is sent
+            let too_large = F61p::try_from(max + 1).unwrap();
+            let err = fconv
+                .input_edabits(&mut channel, &mut rng, &[F61p::ZERO, too_large], NB_BITS)
+                .err()
+                .unwrap();
+            assert!(matches!(err, Error::Other(ref e) if e.contains("index 1")));
+
+            let edabits = fconv
+                .input_edabits(&mut channel, &mut rng, &values, NB_BITS)
+                .unwrap();
+            for (edabit, value) in edabits.iter().zip(values.iter()) {
+                assert_eq!(edabit.value().0, *value);
+                let bits: Vec<F2> = edabit.bits().iter().map(|b| b.0).collect();
+                assert_eq!(convert_bits_to_field::<F61p>(&bits).unwrap(), *value);
+            }
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .input_edabits(&mut channel, &mut rng, NB_BITS, num)
+            .unwrap();
+        assert!(edabits.iter().all(|e| e.bits().len() == NB_BITS));
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_conv_split_values() {
+        run_split_values(false).unwrap();
+        assert!(run_split_values(true).is_err());
+    }
+
+    #[test]
+    fn test_conv_faults() {
+        run_conv_with_fault(None, &config(true)).unwrap();
+        run_conv_with_fault(None, &config(false)).unwrap();
+        let fault = ConvFault::EdabitBit { index: 0, bit: 3 };
+        assert!(run_conv_with_fault(Some(fault), &config(true)).is_err());
+        let fault = ConvFault::Dabit { index: 0 };
+        assert!(matches!(
+            run_conv_with_fault(Some(fault), &config(true)),
+            Err(Error::FdabitCheckFailed)
+        ));
+        let fault = ConvFault::Carry { index: 0, bit: 5 };
+        assert!(run_conv_with_fault(Some(fault), &config(true)).is_err());
+        let fault = ConvFault::Triple { index: 0 };
+        assert!(run_conv_with_fault(Some(fault), &config(false)).is_err());
+    }
+
+    #[test]
+    fn test_conv_triple_bucket() {
+        for triple_bucket in [1, 3] {
+            let config = config(false).triple_buckets(triple_bucket);
+            let stats = run_conv_with_fault(None, &config).unwrap();
+            assert_eq!(stats.config.num_triple_bucket(), triple_bucket);
+            let triples = stats.phases.iter().find(|p| p.phase == CONV_SCOPE_TRIPLES);
+            assert_eq!(
+                triples.unwrap().size,
+                (triple_bucket * DEFAULT_NUM_BUCKET * 20 + DEFAULT_NUM_CUT) * NB_BITS
+            );
+            // a bad random triple is caught whether it is cut or in a bucket
+            let fault = ConvFault::Triple { index: 0 };
+            assert!(run_conv_with_fault(Some(fault), &config).is_err());
+        }
+        assert!(config(false).triple_buckets(0).validate().is_err());
+    }
+
+    #[test]
+    fn test_conv_invalid_batches() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let mut edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 3)
+                .unwrap();
+            let short = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS - 1, 3)
+                .unwrap();
+            assert!(fconv
+                .conv(&mut channel, &mut rng, &[], &config(true))
+                .is_err());
+            let bits: Vec<_> = edabits.iter().map(|e| e.bits.clone()).collect();
+            let short_bits: Vec<_> = short.iter().map(|e| e.bits.clone()).collect();
+            assert!(fconv
+                .bit_add_carry(&mut channel, &mut rng, &bits, &short_bits, None, None)
+                .is_err());
+            let empty = fconv
+                .bit_add_carry(&mut channel, &mut rng, &[], &[], None, None)
+                .unwrap();
+            assert!(empty.is_empty());
+            let mut mixed = edabits.clone();
+            mixed.extend(short);
+            assert!(fconv
+                .conv(&mut channel, &mut rng, &mixed, &config(true))
+                .is_err());
+            edabits.truncate(2);
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 3)
+            .unwrap();
+        let short = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS - 1, 3)
+            .unwrap();
+        // both parties reject the batches before communicating, and go on
+        assert!(fconv
+            .conv(&mut channel, &mut rng, &[], &config(true))
+            .is_err());
+        let mut mixed = edabits.clone();
+        mixed.extend(short);
+        let err = fconv
+            .conv(&mut channel, &mut rng, &mixed, &config(true))
+            .unwrap_err();
+        assert!(err.to_string().contains("edabit 3"), "{}", err);
+        edabits.truncate(2);
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_select_conv_params() {
+        for (num, security_bits, expected) in [
+            (150, 40, (5, 5)),
+            (500, 40, (5, 5)),
+            (1000, 40, (4, 4)),
+            (5000, 40, (4, 4)),
+            (10_000, 40, (3, 3)),
+            (1 << 20, 40, (3, 3)),
+            (5000, 64, (5, 5)),
+            (1 << 16, 64, (4, 4)),
+            (1 << 16, 80, (5, 5)),
+        ] {
+            assert_eq!(
+                select_conv_params(num, security_bits).unwrap(),
+                expected,
+                "{} edabits for {} bits",
+                num,
+                security_bits
+            );
+        }
+        // too few edabits for the security
+        assert!(select_conv_params(0, 40).is_err());
+        assert!(select_conv_params(100, 40).is_err());
+        assert!(select_conv_params(1000, 64).is_err());
+    }
+
+    #[test]
+    fn test_select_triple_bucket() {
+        for (num, nb_bits, security_bits, expected) in [
+            (100, 38, 40, 3),
+            (10_000, 38, 40, 2),
+            (1000, 38, 20, 2),
+            (1 << 20, 64, 20, 1),
+            (10_000, 38, 80, 4),
+        ] {
+            assert_eq!(
+                select_triple_bucket(num, nb_bits, DEFAULT_NUM_BUCKET, security_bits).unwrap(),
+                expected,
+                "{} edabits of {} bits for {} bits",
+                num,
+                nb_bits,
+                security_bits
+            );
+        }
+        // too few multiplications for the security
+        assert!(select_triple_bucket(0, 38, DEFAULT_NUM_BUCKET, 40).is_err());
+        assert!(select_triple_bucket(10, 8, DEFAULT_NUM_BUCKET, 80).is_err());
+    }
+
+    #[test]
+    fn test_conv_with_security() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 150)
+                .unwrap();
+            assert!(fconv
+                .conv_with_security(&mut channel, &mut rng, &edabits[..100], 40, &config(true))
+                .is_err());
+            fconv
+                .conv_with_security(&mut channel, &mut rng, &edabits, 40, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 150)
+            .unwrap();
+        // rejected before communicating
+        assert!(fconv
+            .conv_with_security(&mut channel, &mut rng, &edabits[..100], 40, &config(true))
+            .is_err());
+        let stats = fconv
+            .conv_with_security(&mut channel, &mut rng, &edabits, 40, &config(true))
+            .unwrap();
+        assert_eq!((stats.config.num_bucket(), stats.config.num_cut()), (5, 5));
+        assert_eq!(stats.config.mult_check(), MultCheck::QuickSilver);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_conv_checked() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 100)
+                .unwrap();
+            let (verified, _) = fconv
+                .conv_checked(&mut channel, &mut rng, edabits, &config(true))
+                .unwrap();
+            let mut values: Vec<_> = verified.iter().map(|edabit| edabit.value()).collect();
+            let sum = fconv.fcom().add(values[0], values[1]);
+            values.push(fconv.fcom().affine_add_cst(F61p::ONE, sum));
+            fconv.fcom().open(&mut channel, &values).unwrap();
+            fconv
+                .fcom_f2()
+                .open(&mut channel, verified[0].bits())
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 100)
+            .unwrap();
+        let (verified, _) = fconv
+            .conv_checked(&mut channel, &mut rng, edabits, &config(true))
+            .unwrap();
+        let mut keys: Vec<_> = verified.iter().map(|edabit| edabit.value()).collect();
+        let sum = fconv.fcom().add(keys[0], keys[1]);
+        keys.push(fconv.fcom().affine_add_cst(F61p::ONE, sum));
+        let mut values = Vec::new();
+        fconv.fcom().open(&mut channel, &keys, &mut values).unwrap();
+        assert_eq!(values[100], values[0] + values[1] + F61p::ONE);
+        let mut bits = Vec::new();
+        fconv
+            .fcom_f2()
+            .open(&mut channel, verified[0].bits(), &mut bits)
+            .unwrap();
+        // the opened bits of the first edabit make up its opened value
+        let value = bits.iter().rev().fold(F61p::ZERO, |acc, bit| {
+            acc + acc
+                + if *bit == F2::ONE {
+                    F61p::ONE
+                } else {
+                    F61p::ZERO
+                }
+        });
+        assert_eq!(value, values[0]);
+        handle.join().unwrap();
+    }
+
+    // Initialize the two functionalities separately, and then a converter
+    // running the base OTs once for both, and compare their communication.
+    // Then run `conv` with a converter made from the separate instances, and
+    // with a duplicate of it as in the threaded path.
+    #[test]
+    fn test_conv_init_with() {
+        let n = 10;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = TrackChannel::new(sender);
+            let fcom_f2 = FComProver::<F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let fcom = FComProver::<F61p, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let separate = channel.breakdown();
+            ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let shared = channel.breakdown().since(&separate);
+
+            let mut fconv = ProverConv::init_with(fcom_f2, fcom);
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+            let mut duplicate = fconv.duplicate(&mut channel, &mut rng).unwrap();
+            let edabits = duplicate
+                .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+                .unwrap();
+            duplicate
+                .conv(&mut channel, &mut rng, &edabits, &config(false))
+                .unwrap();
+            (separate.total(), shared.total())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = TrackChannel::new(receiver);
+        let fcom_f2 = FComVerifier::<F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let fcom = FComVerifier::<F61p, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut fconv = VerifierConv::init_with(fcom_f2, fcom);
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
+            .unwrap();
+        let mut duplicate = fconv.duplicate(&mut channel, &mut rng).unwrap();
+        let edabits = duplicate
+            .random_edabits(&mut channel, &mut rng, NB_BITS, n)
+            .unwrap();
+        duplicate
+            .conv(&mut channel, &mut rng, &edabits, &config(false))
+            .unwrap();
+        let (separate, shared) = handle.join().unwrap();
+        // the base OTs of the shared setup are only run once
+        assert!(
+            shared.total() < separate.total(),
+            "{:?} {:?}",
+            shared,
+            separate
+        );
+    }
+
+    // The parameters of `conv` on each side, which differ in the parameter
+    // named first, as (nb_bits, num, config, version).
+    #[allow(clippy::type_complexity)]
+    fn mismatched_params() -> Vec<(
+        &'static str,
+        (usize, usize, ConvConfig, ProtocolVersion),
+        (usize, usize, ConvConfig, ProtocolVersion),
+    )> {
+        let v1 = ProtocolVersion::V1;
+        vec![
+            (
+                "nb_bits",
+                (NB_BITS, 20, config(true), v1),
+                (30, 20, config(true), v1),
+            ),
+            (
+                "num",
+                (NB_BITS, 20, config(true), v1),
+                (NB_BITS, 19, config(true), v1),
+            ),
+            (
+                "num_bucket",
+                (NB_BITS, 20, config(true), v1),
+                (NB_BITS, 20, config(true).buckets(4), v1),
+            ),
+            (
+                "num_cut",
+                (NB_BITS, 20, config(true), v1),
+                (NB_BITS, 20, config(true).cut(3), v1),
+            ),
+            (
+                "mult_check",
+                (NB_BITS, 20, config(true), v1),
+                (NB_BITS, 20, config(false), v1),
+            ),
+            (
+                "triple_bucket",
+                (NB_BITS, 20, config(false), v1),
+                (NB_BITS, 20, config(false).triple_buckets(3), v1),
+            ),
+            (
+                "protocol_version",
+                (NB_BITS, 20, config(true), ProtocolVersion::V2),
+                (NB_BITS, 20, config(true), v1),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_conv_params_mismatch() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
                 &mut channel,
                 &mut rng,
-                &[EdabitsVerifier {
-                    bits: x_mac,
-                    value: default_fe,
-                }],
-                &[EdabitsVerifier {
-                    bits: y_mac,
-                    value: default_fe,
-                }],
-                vec![].as_slice(),
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
             )
-            .unwrap()[0]
-            .clone();
-
-        let mut res = Vec::new();
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 20)
+                .unwrap();
+            let short = fconv
+                .random_edabits(&mut channel, &mut rng, 30, 20)
+                .unwrap();
+            let available = fconv.vole_available();
+            for (param, (nb_bits, num, config, version), _) in mismatched_params() {
+                let edabits = if nb_bits == NB_BITS { &edabits } else { &short };
+                fconv.set_protocol_version(version);
+                let res = fconv.conv(&mut channel, &mut rng, &edabits[..num], &config);
+                assert!(
+                    matches!(res, Err(Error::ConvParamsMismatch { param: ref p, .. }) if p == param)
+                );
+                assert_eq!(fconv.vole_available(), available);
+            }
+            fconv.set_protocol_version(ProtocolVersion::V1);
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 20)
+            .unwrap();
+        let short = fconv
+            .random_edabits(&mut channel, &mut rng, 30, 20)
+            .unwrap();
+        let available = fconv.vole_available();
+        for (param, prover, (nb_bits, num, config, version)) in mismatched_params() {
+            let edabits = if nb_bits == NB_BITS { &edabits } else { &short };
+            fconv.set_protocol_version(version);
+            match fconv.conv(&mut channel, &mut rng, &edabits[..num], &config) {
+                Err(Error::ConvParamsMismatch {
+                    param: p,
+                    local,
+                    remote,
+                }) => {
+                    assert_eq!(p, param);
+                    assert_ne!(local, remote);
+                    if param == "nb_bits" {
+                        assert_eq!((local, remote), (30, prover.0 as u64));
+                    }
+                }
+                res => panic!("unexpected result for {}: {:?}", param, res.map(|_| ())),
+            }
+            assert_eq!(fconv.vole_available(), available);
+        }
+        // the session is still usable with matching parameters
         fconv
-            .fcom_f2
-            .open(&mut channel, &res_mac, &mut res)
+            .conv(&mut channel, &mut rng, &edabits, &config(true))
             .unwrap();
+        handle.join().unwrap();
+    }
 
-        let mut c = Vec::new();
-        fconv.fcom_f2.open(&mut channel, &[c_mac], &mut c).unwrap();
+    #[test]
+    fn test_init_field_mismatch() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .map(|_| ())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = VerifierConv::<F127p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        );
+        assert!(matches!(
+            res,
+            Err(Error::ConvParamsMismatch { ref param, local: 127, remote: 61 })
+                if param == "field_bits"
+        ));
+        assert!(matches!(
+            handle.join().unwrap(),
+            Err(Error::ConvParamsMismatch { ref param, local: 61, remote: 127 })
+                if param == "field_bits"
+        ));
+    }
 
-        let _resprover = handle.join().unwrap();
+    // Initialize a prover over `FE` with `lpn_prover` and a verifier over
+    // `FE2` with `lpn_verifier`, and check that both fail on the parameter
+    // `param`.
+    fn test_init_mismatch<FE, FE2>(
+        lpn_prover: (LpnParams, LpnParams),
+        lpn_verifier: (LpnParams, LpnParams),
+        param: &'static str,
+    ) where
+        FE: FiniteField<PrimeField = FE>,
+        FE2: FiniteField<PrimeField = FE2>,
+    {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            ProverConv::<FE, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                lpn_prover.0,
+                lpn_prover.1,
+            )
+            .map(|_| ())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = VerifierConv::<FE2, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            lpn_verifier.0,
+            lpn_verifier.1,
+        );
+        assert!(
+            matches!(res, Err(Error::ConvParamsMismatch { param: ref p, .. }) if p == param),
+            "{}",
+            param
+        );
+        let res = handle.join().unwrap();
+        assert!(
+            matches!(res, Err(Error::ConvParamsMismatch { param: ref p, .. }) if p == param),
+            "{}",
+            param
+        );
+    }
 
-        for i in 0..power {
-            assert_eq!(expected[i], res[i]);
-        }
-        assert_eq!(carry, c[0]);
+    #[test]
+    fn test_init_lpn_mismatch() {
+        let small = (LPN_SETUP_SMALL, LPN_EXTEND_SMALL);
+        test_init_mismatch::<F61p, F61p>(
+            small,
+            (LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM),
+            "tag_lpn_setup_rows",
+        );
+        test_init_mismatch::<F61p, F61p>(
+            small,
+            (LPN_SETUP_SMALL, LPN_EXTEND_MEDIUM),
+            "tag_lpn_extend_rows",
+        );
     }
 
-    fn test_fdabit<FE: FiniteField<PrimeField = FE>>() -> () {
-        let count = 100;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+    #[test]
+    fn test_init_modulus_mismatch() {
+        let small = (LPN_SETUP_SMALL, LPN_EXTEND_SMALL);
+        assert_eq!(
+            SmallField::bit_decomposition_len(),
+            OtherSmallField::bit_decomposition_len()
+        );
+        test_init_mismatch::<SmallField, OtherSmallField>(small, small, "field_modulus");
+    }
+
+    // Exchange the header of the initialization with one differing in each
+    // of its parameters in turn, which must be the one reported.
+    #[test]
+    fn test_init_header_mismatch() {
+        let header = init_header::<F61p, F40b>(
+            (LPN_SETUP_SMALL, LPN_EXTEND_SMALL),
+            (LPN_SETUP_SMALL, LPN_EXTEND_SMALL),
+        );
+        let (mut sender, mut receiver) = local_pair();
         let handle = std::thread::spawn(move || {
-            let mut rng = AesRng::new();
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
-            let mut fconv =
-                ProverConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                    .unwrap();
+            for i in 0..header.len() {
+                let mut modified = header;
+                modified[i].1 += 1;
+                let res = exchange_header(&mut sender, &modified);
+                assert!(
+                    matches!(res, Err(Error::ConvParamsMismatch { ref param, local, remote })
+                        if param == header[i].0 && local == remote + 1),
+                    "{}",
+                    header[i].0
+                );
+            }
+        });
+        for (param, _) in header.iter() {
+            let res = exchange_header(&mut receiver, &header);
+            assert!(
+                matches!(res, Err(Error::ConvParamsMismatch { param: ref p, local, remote })
+                    if p == param && remote == local + 1),
+                "{}",
+                param
+            );
+        }
+        handle.join().unwrap();
+    }
 
-            let dabits = fconv.random_dabits(&mut channel, &mut rng, count).unwrap();
-            let _ = fconv.fdabit(&mut channel, &mut rng, &dabits).unwrap();
-            ()
+    // A header much longer than the local one is rejected from its length,
+    // without reading its values.
+    #[test]
+    fn test_header_len_bound() {
+        let header = init_header::<F61p, F40b>(
+            (LPN_SETUP_SMALL, LPN_EXTEND_SMALL),
+            (LPN_SETUP_SMALL, LPN_EXTEND_SMALL),
+        );
+        let (mut sender, mut receiver) = local_pair();
+        sender.write_u64(super::CONV_HEADER_VERSION).unwrap();
+        sender.write_u64(u64::MAX).unwrap();
+        sender.flush().unwrap();
+        let res = exchange_header(&mut receiver, &header);
+        assert!(matches!(
+            res,
+            Err(Error::ConvParamsMismatch { ref param, local: 16, remote: u64::MAX })
+                if param == "header_len"
+        ));
+    }
+
+    // Make dabits, random ones and ones from committed bits, check them,
+    // and lift committed bits to `F61p` with them. With `fault`, the prover
+    // commits to faulty random dabits. Return the result of the verifier.
+    fn run_dabits(fault: Option<ConvFault>) -> Result<(), Error> {
+        let (num, nb_lifted) = (50, 60);
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || -> Result<(), Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            fconv.set_fault(fault);
+            let mut dabits = fconv.random_dabits(&mut channel, &mut rng, num)?;
+            let bits: Vec<F2> = (0..nb_lifted).map(|_| F2::random(&mut rng)).collect();
+            let macs = fconv.fcom_f2().input(&mut channel, &mut rng, &bits)?;
+            let bits: Vec<_> = bits
+                .into_iter()
+                .zip(macs)
+                .map(|(bit, mac)| MacProver(bit, mac))
+                .collect();
+            dabits.extend(fconv.dabits_from_bits(&mut channel, &mut rng, &bits[..num])?);
+            dabits.push(fconv.dabit_from_bit(&mut channel, &mut rng, bits[num])?);
+            let dabits = fconv.check_dabits(&mut channel, &mut rng, dabits)?;
+            let mut lifted = Vec::new();
+            fconv.lift_bits(&mut channel, &dabits[..nb_lifted], &bits, &mut lifted)?;
+            fconv.fcom().open(&mut channel, &lifted)?;
+            fconv.fcom_f2().open(&mut channel, &bits)?;
+            Ok(())
         });
         let mut rng = AesRng::new();
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
-        let mut fconv =
-            VerifierConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                .unwrap();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<(), Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let mut dabits = fconv.random_dabits(&mut channel, &mut rng, num)?;
+            let bits = fconv.fcom_f2().input(&mut channel, &mut rng, nb_lifted)?;
+            dabits.extend(fconv.dabits_from_bits(&mut channel, &mut rng, &bits[..num])?);
+            dabits.push(fconv.dabit_from_bit(&mut channel, &mut rng, bits[num])?);
+            let dabits = fconv.check_dabits(&mut channel, &mut rng, dabits)?;
+            assert_eq!(dabits.len(), 2 * num + 1);
+            // one checked dabit per lifted bit
+            let mut lifted = Vec::new();
+            assert!(matches!(
+                fconv.lift_bits(&mut channel, &dabits[..1], &bits, &mut lifted),
+                Err(Error::InvalidInputLength)
+            ));
+            fconv.lift_bits(&mut channel, &dabits[..nb_lifted], &bits, &mut lifted)?;
+            let mut values = Vec::new();
+            fconv.fcom().open(&mut channel, &lifted, &mut values)?;
+            let mut opened_bits = Vec::new();
+            fconv
+                .fcom_f2()
+                .open(&mut channel, &bits, &mut opened_bits)?;
+            for (value, bit) in values.iter().zip(opened_bits.iter()) {
+                assert_eq!(*value, f2_to_fe::<F61p>(*bit));
+            }
+            Ok(())
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if fault.is_none() {
+            prover_res.unwrap();
+        }
+        res
+    }
 
-        let dabits_mac = fconv.random_dabits(&mut channel, &mut rng, count).unwrap();
-        let _ = fconv.fdabit(&mut channel, &mut rng, &dabits_mac).unwrap();
+    #[test]
+    fn test_dabits() {
+        run_dabits(None).unwrap();
+        let fault = ConvFault::Dabit { index: 3 };
+        assert!(matches!(
+            run_dabits(Some(fault)),
+            Err(Error::FdabitCheckFailed)
+        ));
+    }
 
-        handle.join().unwrap();
+    // The value of the bits `bits` in `FE`.
+    fn bits_value<FE: FiniteField>(bits: &[MacProver<F40b>]) -> FE {
+        let bits: Vec<F2> = bits.iter().map(|bit| bit.0).collect();
+        convert_bits_to_field(&bits).unwrap()
     }
 
-    fn test_conv<FE: FiniteField<PrimeField = FE>>() -> () {
-        let nb_edabits = 50;
-        let with_quicksilver = true;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+    // Move the values of edabits from `F61p` to `F127p` and back with
+    // `convert_between`. With `off_by_one`, the prover commits to the first
+    // value plus one in `F127p`. Return the result of the verifier.
+    fn run_convert_between(off_by_one: bool) -> Result<(), Error> {
+        let num = 20;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || -> Result<(), Error> {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let mut fconv_other = fconv.with_field::<F127p, _, _>(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let mut values: Vec<F127p> = edabits.iter().map(|e| bits_value(e.bits())).collect();
+            if off_by_one {
+                values[0] += F127p::ONE;
+            }
+            let macs = fconv_other.fcom().input(&mut channel, &mut rng, &values)?;
+            channel.flush()?;
+            let values: Vec<_> = values
+                .into_iter()
+                .zip(macs)
+                .map(|(x, mac)| MacProver(x, mac))
+                .collect();
+            fconv.convert_between(
+                &mut fconv_other,
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &values,
+                &config(true),
+            )?;
+
+            let edabits = fconv_other.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let values: Vec<F61p> = edabits.iter().map(|e| bits_value(e.bits())).collect();
+            let macs = fconv.fcom().input(&mut channel, &mut rng, &values)?;
+            channel.flush()?;
+            let values: Vec<_> = values
+                .into_iter()
+                .zip(macs)
+                .map(|(x, mac)| MacProver(x, mac))
+                .collect();
+            fconv_other.convert_between(
+                &mut fconv,
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &values,
+                &config(true),
+            )?;
+            Ok(())
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let res = (|| -> Result<(), Error> {
+            let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+            let mut fconv_other = fconv.with_field::<F127p, _, _>(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )?;
+
+            let edabits = fconv.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let values = fconv_other.fcom().input(&mut channel, &mut rng, num)?;
+            // rejected before communicating
+            assert!(matches!(
+                fconv.convert_between(
+                    &mut fconv_other,
+                    &mut channel,
+                    &mut rng,
+                    &edabits,
+                    &values[1..],
+                    &config(true),
+                ),
+                Err(Error::BatchLengthMismatch { .. })
+            ));
+            fconv.convert_between(
+                &mut fconv_other,
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &values,
+                &config(true),
+            )?;
+
+            let edabits = fconv_other.random_edabits(&mut channel, &mut rng, NB_BITS, num)?;
+            let values = fconv.fcom().input(&mut channel, &mut rng, num)?;
+            fconv_other.convert_between(
+                &mut fconv,
+                &mut channel,
+                &mut rng,
+                &edabits,
+                &values,
+                &config(true),
+            )?;
+            Ok(())
+        })();
+        // the prover sees the verifier hang up when it rejects
+        drop(channel);
+        let prover_res = handle.join().unwrap();
+        if !off_by_one {
+            prover_res.unwrap();
+        }
+        res
+    }
 
+    #[test]
+    fn test_convert_between() {
+        run_convert_between(false).unwrap();
+        assert!(run_convert_between(true).is_err());
+    }
+
+    #[test]
+    fn test_conv_streaming() {
+        let (num, chunk_size) = (250, 60);
+        let (sender, receiver) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::new();
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
-            let mut fconv =
-                ProverConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                    .unwrap();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, num)
+                .unwrap();
+            fconv
+                .conv_streaming(&mut channel, &mut rng, edabits, chunk_size, &config(true))
+                .unwrap();
+        });
+        let mut rng = AesRng::new();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, num)
+            .unwrap();
+        assert!(fconv
+            .conv_streaming(&mut channel, &mut rng, edabits.clone(), 0, &config(true))
+            .is_err());
+        let stats = fconv
+            .conv_streaming(&mut channel, &mut rng, edabits, chunk_size, &config(true))
+            .unwrap();
+        handle.join().unwrap();
+        // the last 10 edabits are checked with the chunk before
+        let chunks: Vec<_> = stats.iter().map(|stats| stats.num).collect();
+        assert_eq!(chunks, [60, 60, 60, 70]);
+        // the random material of a chunk is bounded by the chunk size
+        let max_random_edabits = (2 * chunk_size - 1) * DEFAULT_NUM_BUCKET + DEFAULT_NUM_CUT;
+        for stats in stats.iter() {
+            let edabits = stats.phases.iter().find(|p| p.phase == CONV_SCOPE_EDABITS);
+            assert!(edabits.unwrap().size <= max_random_edabits);
+        }
+    }
 
-            for n in 1..nb_edabits {
+    fn test_conv_preprocessing(with_quicksilver: bool) {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let mut preprocessing = fconv
+                .preprocess(
+                    &mut channel,
+                    &mut rng,
+                    NB_BITS,
+                    10,
+                    &config(with_quicksilver),
+                )
+                .unwrap();
+            let short = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS - 1, 2)
+                .unwrap();
+            assert!(fconv
+                .conv_online(&mut channel, &mut rng, &mut preprocessing, &short)
+                .is_err());
+            for n in [4, 6, 1] {
                 let edabits = fconv
                     .random_edabits(&mut channel, &mut rng, NB_BITS, n)
                     .unwrap();
-
-                let _ = fconv
-                    .conv(
-                        &mut channel,
-                        &mut rng,
-                        DEFAULT_NUM_BUCKET,
-                        DEFAULT_NUM_CUT,
-                        &edabits,
-                        None,
-                        with_quicksilver,
-                    )
-                    .unwrap();
+                let res = fconv.conv_online(&mut channel, &mut rng, &mut preprocessing, &edabits);
+                assert_eq!(res.is_ok(), n != 1);
             }
-            ()
+            // the session goes on after the rejected calls
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 3)
+                .unwrap();
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+                .unwrap();
         });
         let mut rng = AesRng::new();
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
-        let mut fconv =
-            VerifierConv::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
-                .unwrap();
+        let mut channel = StrictChannel::new(receiver);
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut preprocessing = fconv
+            .preprocess(
+                &mut channel,
+                &mut rng,
+                NB_BITS,
+                10,
+                &config(with_quicksilver),
+            )
+            .unwrap();
+        assert_eq!(preprocessing.nb_bits(), NB_BITS);
+        assert_eq!(preprocessing.capacity(), 10);
+        assert_eq!(preprocessing.stats().num, 10);
+        let phases = preprocessing
+            .stats()
+            .phases
+            .iter()
+            .map(|p| p.phase)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            [
+                CONV_SCOPE_VOLE,
+                CONV_SCOPE_EDABITS,
+                CONV_SCOPE_DABITS,
+                CONV_SCOPE_TRIPLES,
+                CONV_SCOPE_FDABIT,
+                CONV_SCOPE_SHUFFLE,
+                CONV_SCOPE_CUT_AND_CHOOSE,
+            ]
+        );
+
+        // edabits of another width are rejected without consuming material
+        let short = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS - 1, 2)
+            .unwrap();
+        assert!(fconv
+            .conv_online(&mut channel, &mut rng, &mut preprocessing, &short)
+            .is_err());
+        assert_eq!(preprocessing.remaining(), 10);
 
-        let mut res = Vec::new();
-        for n in 1..nb_edabits {
+        // one preprocessing serves several batches, up to its capacity
+        for (n, remaining) in [(4, 6), (6, 0)] {
             let edabits = fconv
                 .random_edabits(&mut channel, &mut rng, NB_BITS, n)
                 .unwrap();
+            let stats = fconv
+                .conv_online(&mut channel, &mut rng, &mut preprocessing, &edabits)
+                .unwrap();
+            assert_eq!(stats.num, n);
+            assert_eq!(stats.buckets_completed, DEFAULT_NUM_BUCKET);
+            assert_eq!(stats.phases.len(), 1);
+            assert_eq!(stats.phases[0].phase, CONV_SCOPE_BUCKETS);
+            assert_eq!(preprocessing.remaining(), remaining);
+        }
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 1)
+            .unwrap();
+        assert!(fconv
+            .conv_online(&mut channel, &mut rng, &mut preprocessing, &edabits)
+            .is_err());
 
-            let r = fconv
-                .conv(
-                    &mut channel,
-                    &mut rng,
-                    DEFAULT_NUM_BUCKET,
-                    DEFAULT_NUM_CUT,
-                    &edabits,
-                    None,
-                    with_quicksilver,
-                )
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, 3)
+            .unwrap();
+        fconv
+            .conv(&mut channel, &mut rng, &edabits, &config(with_quicksilver))
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_conv_preprocessing_f61p() {
+        test_conv_preprocessing(true);
+        test_conv_preprocessing(false);
+    }
+
+    // Run the verifier of a conversion on `channel`, with a fixed seed so
+    // that its transcript can be replayed.
+    fn run_seeded_verifier<C: AbstractChannel>(channel: &mut C) -> Result<ConvStats, Error> {
+        let mut rng = AesRng::from_seed(Block::default());
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )?;
+        let edabits = fconv.random_edabits(channel, &mut rng, NB_BITS, 10)?;
+        fconv.conv(channel, &mut rng, &edabits, &config(true))
+    }
+
+    #[test]
+    fn test_conv_record_replay() {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = StrictChannel::new(sender);
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, 10)
                 .unwrap();
-            res.push(r);
+            fconv
+                .conv(&mut channel, &mut rng, &edabits, &config(true))
+                .unwrap();
+        });
+        let mut channel = RecordChannel::new(receiver, Vec::new()).unwrap();
+        run_seeded_verifier(&mut channel).unwrap();
+        handle.join().unwrap();
+        let log = channel.into_log().unwrap();
+        let mut transcript = Transcript::read(&log[..]).unwrap();
+
+        // the verifier replays its run byte for byte
+        let mut replay = ReplayChannel::new(&transcript);
+        run_seeded_verifier(&mut replay).unwrap();
+        assert_eq!(replay.remaining(), (0, 0));
+
+        // tampering with the first byte written in the fdabit phase makes
+        // the replay diverge there
+        let is_scope = |r: &TranscriptRecord| matches!(r, TranscriptRecord::Scope { .. });
+        let fdabit = transcript
+            .records
+            .iter()
+            .position(|r| match r {
+                TranscriptRecord::Scope { label, .. } => label == CONV_SCOPE_FDABIT,
+                _ => false,
+            })
+            .unwrap();
+        let written = transcript.records[fdabit + 1..]
+            .iter_mut()
+            .take_while(|r| !is_scope(r))
+            .find_map(|r| match r {
+                TranscriptRecord::Write { bytes, .. } if !bytes.is_empty() => Some(bytes),
+                _ => None,
+            })
+            .expect("the verifier writes in the fdabit phase");
+        written[0] ^= 1;
+        let mut replay = ReplayChannel::new(&transcript);
+        match run_seeded_verifier(&mut replay).unwrap_err() {
+            Error::IoError(e) => {
+                assert!(e.to_string().contains(&format!("`{}`", CONV_SCOPE_FDABIT)))
+            }
+            e => panic!("unexpected error {}", e),
         }
+    }
 
-        let _resprover = handle.join().unwrap();
-        ()
+    #[test]
+    fn test_fdabit_chunks() {
+        assert_eq!(fdabit_chunk_size::<F61p>(1000).unwrap(), 1000);
+        assert_eq!(fdabit_chunk_size::<SmallField>(510).unwrap(), 510);
+        assert_eq!(fdabit_chunk_size::<SmallField>(1000).unwrap(), 510);
+        assert!(check_parameters::<SmallField>(1000, fdabit_gamma(1000)).is_err());
+        // three chunks, the last one smaller
+        test_fdabit::<SmallField, F40b>(1200);
     }
 
     #[test]
-    fn test_convert_bit_2_field_f61p() {
-        test_convert_bit_2_field::<F61p>();
+    fn test_check_parameters_f127p() {
+        // gamma = 60 for a 61-bit field is out of range, but not for F127p.
+        assert!(check_parameters::<F61p>(1000, 60).is_err());
+        assert!(check_parameters::<F127p>(1000, 60).is_ok());
+        assert!(check_parameters::<F127p>(1000, 117).is_err());
     }
 
     #[test]
-    fn test_bit_add_carry_f61p() {
-        test_bit_add_carry::<F61p>();
+    fn test_conv_noise_f61p() {
+        test_conv_noise::<F61p, F40b>();
     }
 
+    // Convert over a pair of UnixStreams keeping track of the communication,
+    // and check its summary.
     #[test]
-    fn test_fdabit_f61p() {
-        test_fdabit::<F61p>();
+    fn test_conv_comm_summary_unix() {
+        let n = 10;
+        let (mut sender, mut receiver) = scuttlebutt::track_unix_channel_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut sender,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut sender, &mut rng, NB_BITS, n)
+                .unwrap();
+            fconv
+                .conv(&mut sender, &mut rng, &edabits, &config(true))
+                .unwrap()
+        });
+        let mut rng = AesRng::new();
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut receiver,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut receiver, &mut rng, NB_BITS, n)
+            .unwrap();
+        let verifier_stats = fconv
+            .conv(&mut receiver, &mut rng, &edabits, &config(true))
+            .unwrap();
+        let prover_stats = handle.join().unwrap();
+
+        let prover = prover_stats.comm_summary().unwrap();
+        let verifier = verifier_stats.comm_summary().unwrap();
+        for (prover, verifier) in [
+            (prover.generation, verifier.generation),
+            (prover.fdabit, verifier.fdabit),
+            (prover.cut_and_choose, verifier.cut_and_choose),
+            (prover.buckets, verifier.buckets),
+        ] {
+            assert!(prover.total() > 0);
+            // every byte sent by a party is received by the other one
+            assert_eq!(prover.bytes_written, verifier.bytes_read);
+            assert_eq!(prover.bytes_read, verifier.bytes_written);
+        }
+        // the communication accumulated over the phases grows up to the
+        // communication of the whole run
+        for stats in [&prover_stats, &verifier_stats] {
+            let total = stats.comm.as_ref().unwrap().total().total();
+            let mut sent = 0;
+            for phase in stats.phases.iter() {
+                let comm = phase.comm.unwrap().total();
+                sent += comm;
+                assert!(sent <= total, "{}", phase.phase);
+            }
+            assert!(sent > 0);
+        }
     }
 
     #[test]
-    fn test_conv_f61p() {
-        test_conv::<F61p>();
+    fn test_conv_comm_f61p() {
+        test_conv_comm::<F61p, F40b>(ProtocolVersion::V1, true);
+    }
+
+    #[test]
+    fn test_conv_comm_wolverine_f61p() {
+        test_conv_comm::<F61p, F40b>(ProtocolVersion::V1, false);
+    }
+
+    #[test]
+    fn test_conv_comm_packed_f61p() {
+        test_conv_comm::<F61p, F40b>(ProtocolVersion::V2, true);
+    }
+
+    #[test]
+    fn test_conv_for_workload_f61p() {
+        test_conv_for_workload::<F61p, F40b>();
     }
 }
@@ -1,19 +1,25 @@
 //! This is the implementation of an homomorphic commitment
 //! functionality.  It includes `random`, `input`, affine operations,
 //! `check_zero`, `open` and `check_multiply` a la Wolverine or
-//! Quicksilver.  These functionalities are required for the edabits
-//! conversion protocol.
+//! Quicksilver, as well as the lifting of committed bits into an
+//! arbitrary field using dabits.  These functionalities are required for
+//! the edabits conversion protocol.
+use super::checked::{Op, Sequencer};
 use crate::errors::Error;
-use crate::svole::wykw::{LpnParams, Receiver, Sender};
+use crate::svole::wykw::{
+    BaseOt, ChouOrlandiBaseOt, ExtendHooks, LpnParams, PipelinedReceiver, PipelinedSender,
+    Receiver, Sender, SetupReceiver, SetupSender,
+};
 use crate::svole::{SVoleReceiver, SVoleSender};
 use generic_array::{typenum::Unsigned, GenericArray};
 use rand::{CryptoRng, Rng, SeedableRng};
-use scuttlebutt::field::Degree;
+use scuttlebutt::field::{Degree, F2};
 use scuttlebutt::ring::FiniteRing;
 use scuttlebutt::serialization::CanonicalSerialize;
 use scuttlebutt::{field::FiniteField, AbstractChannel, AesRng, Block};
-use std::time::Instant;
-use subtle::{Choice, ConditionallySelectable};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// The types `MacProver` and `MacVerifier` hold the data associated to
 /// a MAC between a prover and a verifier, following SVOLE style
@@ -51,10 +57,309 @@ impl<FE: FiniteField> ConditionallySelectable for MacVerifier<FE> {
     }
 }
 
-/// F_com protocol for the Prover
-pub struct FComProver<FE: FiniteField> {
-    svole_sender: Sender<FE>,
+/// A dabit held by the prover: the same bit committed both in the binary
+/// field `TF` and in the field `FE`.
+#[derive(Clone, Copy, Debug)]
+pub struct DabitProver<FE: FiniteField, TF: FiniteField> {
+    /// The bit committed in `TF`.
+    pub bit: MacProver<TF>,
+    /// The bit committed in `FE`.
+    pub value: MacProver<FE>,
+}
+
+/// A dabit held by the verifier, see [`DabitProver`].
+#[derive(Clone, Copy, Debug)]
+pub struct DabitVerifier<FE: FiniteField, TF: FiniteField> {
+    /// The key of the bit committed in `TF`.
+    pub bit: MacVerifier<TF>,
+    /// The key of the bit committed in `FE`.
+    pub value: MacVerifier<FE>,
+}
+
+/// Overwrite the value and the MAC with zeros. `MacProver` is `Copy`, so it
+/// cannot wipe itself when dropped: the types holding commitments of the
+/// prover, such as [`EdabitsProver`](super::EdabitsProver), do.
+#[cfg(feature = "zeroize")]
+impl<FE: FiniteField> Zeroize for MacProver<FE> {
+    fn zeroize(&mut self) {
+        // Safety: `self` is a valid and aligned pointer to a `MacProver`.
+        unsafe { std::ptr::write_volatile(self, MacProver(FE::PrimeField::ZERO, FE::ZERO)) };
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<FE: FiniteField, TF: FiniteField> Zeroize for DabitProver<FE, TF> {
+    fn zeroize(&mut self) {
+        self.bit.zeroize();
+        self.value.zeroize();
+    }
+}
+
+// Empty `buf` and overwrite its whole capacity with zeros, with the
+// `zeroize` feature, so that the secrets it held do not stay in memory once
+// it is freed.
+pub(crate) fn wipe<T: Copy>(buf: &mut Vec<T>) {
+    buf.clear();
+    #[cfg(feature = "zeroize")]
+    buf.spare_capacity_mut().zeroize();
+}
+
+// A temporary buffer of the protocols, wiped with `wipe` when it is dropped,
+// also when the protocol fails halfway.
+pub(crate) struct WipeOnDrop<T: Copy>(Vec<T>);
+
+impl<T: Copy> WipeOnDrop<T> {
+    pub(crate) fn new(buf: Vec<T>) -> Self {
+        WipeOnDrop(buf)
+    }
+}
+
+impl<T: Copy> std::ops::Deref for WipeOnDrop<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: Copy> std::ops::DerefMut for WipeOnDrop<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T: Copy> Drop for WipeOnDrop<T> {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// A pool of dabits consumed by the `lift_bits_from_pool` functions.
+///
+/// The dabits put into the pool must have been checked for consistency
+/// beforehand, otherwise lifting bits with them is not sound.
+#[derive(Clone, Debug, Default)]
+pub struct DabitPool<D> {
+    dabits: Vec<D>,
+}
+
+impl<D> DabitPool<D> {
+    /// Create a pool from a vector of dabits.
+    pub fn new(dabits: Vec<D>) -> Self {
+        Self { dabits }
+    }
+
+    /// Returns the number of dabits left in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.dabits.len()
+    }
+
+    /// Returns `true` if the pool contains no dabit.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dabits.is_empty()
+    }
+
+    /// Add dabits to the pool.
+    pub fn extend<I: IntoIterator<Item = D>>(&mut self, dabits: I) {
+        self.dabits.extend(dabits);
+    }
+
+    /// Remove `n` dabits from the pool.
+    pub fn draw(&mut self, n: usize) -> Result<Vec<D>, Error> {
+        if n > self.dabits.len() {
+            return Err(Error::Other(format!(
+                "not enough dabits in the pool: {} requested, {} available",
+                n,
+                self.dabits.len()
+            )));
+        }
+        Ok(self.dabits.split_off(self.dabits.len() - n))
+    }
+}
+
+// The statistical security parameter.
+const SSP: usize = 40;
+
+// Number of random linear combinations checked by the re-authentication
+// protocol, each of them failing to catch a cheating prover with
+// probability `1 / |FE::PrimeField|`.
+fn num_reauth_checks<FE: FiniteField>() -> usize {
+    let nbits = FE::PrimeField::bit_decomposition_len();
+    (SSP + nbits - 1) / nbits
+}
+
+/// bit to field element
+pub(crate) fn f2_to_fe<FE: FiniteField>(b: F2) -> FE {
+    let choice = b.ct_eq(&F2::ZERO);
+    FE::conditional_select(&FE::ONE, &FE::ZERO, choice)
+}
+
+/// Policy for refilling the pool of VOLEs backing the F_com functionality.
+///
+/// Without a policy the pool is extended when it runs dry, which may happen
+/// at any point of a protocol. Calling `refill_if_low` at points where an
+/// extension is convenient extends the pool beforehand instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VolePoolConfig {
+    /// The pool is refilled by `refill_if_low` when it holds fewer VOLEs.
+    pub low_watermark: usize,
+    /// Minimum number of VOLEs added by a refill, which extends the pool
+    /// with `extend_at_least`. At least one extension is run.
+    pub refill_size: usize,
+}
+
+/// Statistics about the pool of VOLEs backing the F_com functionality.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VolePoolStats {
+    /// Number of extensions of the pool, including the ones run by refills.
+    pub extensions: usize,
+    /// Number of refills triggered by the low watermark.
+    pub refills: usize,
+}
+
+/// Prover state of a Quicksilver multiplication check, created with
+/// [`FComProver::quicksilver_check_start`].
+#[derive(Clone)]
+pub struct QuicksilverCheckProver<FE: FiniteField> {
+    chi: FE,
+    chi_power: FE,
+    sum_a0: FE,
+    sum_a1: FE,
+}
+
+impl<FE: FiniteField> QuicksilverCheckProver<FE> {
+    /// Add a triple `(x, y, z)` to the check, with `z = x * y`.
+    #[inline]
+    pub fn push_triple(&mut self, x: MacProver<FE>, y: MacProver<FE>, z: MacProver<FE>) {
+        let MacProver(x, x_mac) = x;
+        let MacProver(y, y_mac) = y;
+        let MacProver(_z, z_mac) = z;
+        let a0 = x_mac * y_mac;
+        let a1 = y * x_mac + x * y_mac - z_mac;
+
+        self.sum_a0 += a0 * self.chi_power;
+        self.sum_a1 += a1 * self.chi_power;
+
+        self.chi_power *= self.chi;
+    }
+
+    /// Finish the check, using `fcom` to mask the result.
+    pub fn finish<B: BaseOt, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        self,
+        channel: &mut C,
+        rng: &mut RNG,
+        fcom: &mut FComProver<FE, B>,
+    ) -> Result<(), Error> {
+        // The following block implements VOPE(1)
+        let mut mask = FE::ZERO;
+        let mut mask_mac = FE::ZERO;
+
+        for i in 0..Degree::<FE>::USIZE {
+            let MacProver(u, u_mac) = fcom.random(channel, rng)?;
+            let x_i: FE = make_x_i(i);
+            mask += u * x_i;
+            mask_mac += u_mac * x_i;
+        }
+
+        let u = self.sum_a0 + mask_mac;
+        let v = self.sum_a1 + mask;
+
+        fcom.seq.send(channel, Op::Quicksilver)?;
+        channel.write_serializable(&u)?;
+        channel.write_serializable(&v)?;
+        channel.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Verifier state of a Quicksilver multiplication check, created with
+/// [`FComVerifier::quicksilver_check_start`].
+#[derive(Clone)]
+pub struct QuicksilverCheckVerifier<FE: FiniteField> {
+    delta: FE,
+    chi: FE,
+    power_chi: FE,
+    sum_b: FE,
+}
+
+impl<FE: FiniteField> QuicksilverCheckVerifier<FE> {
+    /// Add a triple `(x, y, z)` to the check, with `z = x * y`.
+    #[inline]
+    pub fn push_triple(&mut self, x: MacVerifier<FE>, y: MacVerifier<FE>, z: MacVerifier<FE>) {
+        let (MacVerifier(x_mac), MacVerifier(y_mac), MacVerifier(z_mac)) = (x, y, z);
+        //  should be `- (-delta)` with our conventions compared to
+        //  quicksilver but simplified out.
+        let b = x_mac * y_mac + self.delta * z_mac;
+
+        self.sum_b += b * self.power_chi;
+        self.power_chi *= self.chi;
+    }
+
+    /// Finish the check, using `fcom` to mask the result.
+    pub fn finish<B: BaseOt, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        self,
+        channel: &mut C,
+        rng: &mut RNG,
+        fcom: &mut FComVerifier<FE, B>,
+    ) -> Result<(), Error> {
+        // The following block implements VOPE(1)
+        let mut mask_mac = FE::ZERO;
+        for i in 0..Degree::<FE>::USIZE {
+            let MacVerifier(v_m) = fcom.random(channel, rng)?;
+            let x_i: FE = make_x_i(i);
+            mask_mac += v_m * x_i;
+        }
+
+        fcom.seq.recv(channel, Op::Quicksilver)?;
+        let u = channel.read_serializable::<FE>()?;
+        let v = channel.read_serializable::<FE>()?;
+
+        let b_plus = self.sum_b + mask_mac;
+        if b_plus == (u + (-self.delta) * v) {
+            // - because of delta
+            Ok(())
+        } else {
+            Err(Error::Other("checkMultiply fails".to_string()))
+        }
+    }
+}
+
+/// Authenticated values exported from an F_com instance.
+///
+/// They can be imported into any instance using the same MAC key as the
+/// exporting instance, that is the instance itself and its duplicates.
+#[derive(Clone, Debug)]
+pub struct Exported<M> {
+    key_id: Block,
+    macs: Vec<M>,
+}
+
+impl<M> Exported<M> {
+    /// Returns the number of exported values.
+    pub fn len(&self) -> usize {
+        self.macs.len()
+    }
+
+    /// Returns whether no values were exported.
+    pub fn is_empty(&self) -> bool {
+        self.macs.is_empty()
+    }
+}
+
+/// F_com protocol for the Prover, whose sVOLE base OTs are run with `B`.
+pub struct FComProver<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
+    svole_sender: Sender<FE, B>,
+    // background extension, used in place of `svole_sender` when set
+    pipeline: Option<PipelinedSender<FE>>,
     voles: Vec<(FE::PrimeField, FE)>,
+    pool_config: VolePoolConfig,
+    pool_stats: VolePoolStats,
+    // identifies the MAC key, shared with the duplicates of the instance
+    key_id: Block,
+    seq: Sequencer,
 }
 
 fn make_x_i<FE: FiniteField>(i: usize) -> FE {
@@ -63,21 +368,52 @@ fn make_x_i<FE: FiniteField>(i: usize) -> FE {
     FE::from_subfield(&v)
 }
 
-impl<FE: FiniteField> FComProver<FE> {
+impl<FE: FiniteField, B: BaseOt> FComProver<FE, B> {
     /// Initialize the functionality.
+    ///
+    /// Fails with `Error::InvalidLpnParams` if the LPN parameters are
+    /// inconsistent, see [`LpnParams::validate`], and with
+    /// `Error::LpnParamsMismatch` if the other party uses different ones.
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         rng: &mut RNG,
         lpn_setup: LpnParams,
         lpn_extend: LpnParams,
     ) -> Result<Self, Error> {
-        Ok(Self {
-            svole_sender: Sender::init(channel, rng, lpn_setup, lpn_extend)?,
+        let svole_sender = Sender::init(channel, rng, lpn_setup, lpn_extend)?;
+        Ok(Self::from_svole(svole_sender, rng))
+    }
+
+    /// Initialize the functionality from a base OT setup shared with other
+    /// instances, possibly over other fields. The verifier must use
+    /// [`FComVerifier::init_with_setup`] at the same point of the protocol.
+    pub fn init_with_setup<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        setup: &mut SetupSender<B>,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        let svole_sender = setup.derive(channel, rng, lpn_setup, lpn_extend)?;
+        Ok(Self::from_svole(svole_sender, rng))
+    }
+
+    fn from_svole<RNG: CryptoRng + Rng>(svole_sender: Sender<FE, B>, rng: &mut RNG) -> Self {
+        Self {
+            svole_sender,
+            pipeline: None,
             voles: Vec::new(),
-        })
+            pool_config: VolePoolConfig::default(),
+            pool_stats: VolePoolStats::default(),
+            key_id: rng.gen(),
+            seq: Sequencer::default(),
+        }
     }
 
     /// Duplicate the functionality.
+    ///
+    /// The duplicate uses the same MAC key, so that values can be moved
+    /// between the two instances with `export` and `import`.
     pub fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -85,10 +421,205 @@ impl<FE: FiniteField> FComProver<FE> {
     ) -> Result<Self, Error> {
         Ok(Self {
             svole_sender: self.svole_sender.duplicate(channel, rng)?,
+            pipeline: None,
             voles: Vec::new(),
+            pool_config: self.pool_config,
+            pool_stats: VolePoolStats::default(),
+            key_id: self.key_id,
+            seq: Sequencer::default(),
         })
     }
 
+    /// Returns whether the two instances use the same MAC key.
+    pub fn shares_key_with(&self, other: &Self) -> bool {
+        self.key_id == other.key_id
+    }
+
+    /// Export authenticated values, to be imported into an instance using
+    /// the same MAC key. This is purely local and must be matched by an
+    /// export of the corresponding values by the verifier.
+    pub fn export(&self, xs: &[MacProver<FE>]) -> Exported<MacProver<FE>> {
+        Exported {
+            key_id: self.key_id,
+            macs: xs.to_vec(),
+        }
+    }
+
+    /// Import authenticated values exported by an instance using the same
+    /// MAC key.
+    ///
+    /// Values authenticated under a different MAC key are rejected, they
+    /// must be transferred with [`FComProver::reauthenticate`] instead.
+    pub fn import(&self, exported: Exported<MacProver<FE>>) -> Result<Vec<MacProver<FE>>, Error> {
+        if exported.key_id != self.key_id {
+            return Err(Error::Other(
+                "cannot import values authenticated under a different MAC key".to_string(),
+            ));
+        }
+        Ok(exported.macs)
+    }
+
+    /// Set the refill policy of the pool of VOLEs. The verifier must use
+    /// the same policy.
+    pub fn set_pool_config(&mut self, config: VolePoolConfig) {
+        self.pool_config = config;
+    }
+
+    /// Returns the refill policy of the pool of VOLEs.
+    pub fn pool_config(&self) -> VolePoolConfig {
+        self.pool_config
+    }
+
+    /// Returns statistics about the pool of VOLEs.
+    pub fn pool_stats(&self) -> VolePoolStats {
+        self.pool_stats
+    }
+
+    /// Returns the number of random Macs that can be produced before the
+    /// pool of VOLEs needs to be extended.
+    ///
+    /// The pool is not shared with duplicates: `duplicate` returns an
+    /// instance with an empty pool.
+    pub fn available(&self) -> usize {
+        self.voles.len()
+    }
+
+    /// Extend the pool of VOLEs now, and returns the number of VOLEs added.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// verifier, using [`FComVerifier::extend`].
+    pub fn extend<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<usize, Error> {
+        self.extend_internal(channel, rng, None)
+    }
+
+    /// Extend the pool of VOLEs by at least `num` VOLEs, and returns the
+    /// exact number of VOLEs added. The extensions are sized according to
+    /// `num` rather than to the LPN parameters of the instance, see
+    /// [`Sender::send_at_least`].
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// verifier, using [`FComVerifier::extend_at_least`] with the same `num`.
+    pub fn extend_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<usize, Error> {
+        self.extend_internal(channel, rng, Some(num))
+    }
+
+    // Runs one extension, or extensions producing at least `num` VOLEs when
+    // `num` is set, and adds their output to the pool.
+    fn extend_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: Option<usize>,
+    ) -> Result<usize, Error> {
+        let mut voles = Vec::new();
+        let batch = match (&mut self.pipeline, num) {
+            (Some(pipeline), None) => pipeline.next_batch(),
+            (Some(pipeline), Some(num)) => pipeline.next_at_least(num),
+            (None, None) => self
+                .svole_sender
+                .send(channel, rng, &mut voles)
+                .map(|_| voles),
+            (None, Some(num)) => self
+                .svole_sender
+                .send_at_least(channel, rng, num, &mut voles)
+                .map(|_| voles),
+        };
+        let mut voles = match batch {
+            Ok(voles) => voles,
+            Err(e) => {
+                // The pools of the two parties are out of sync after an
+                // interrupted extension: the pool is emptied so that every
+                // later use goes through a failing extension.
+                if self.pipeline.is_some() || self.svole_sender.is_poisoned() {
+                    self.voles.clear();
+                }
+                return Err(e);
+            }
+        };
+        let num = voles.len();
+        // the remaining VOLEs are kept at the end so that they are used first
+        voles.append(&mut self.voles);
+        self.voles = voles;
+        self.pool_stats.extensions += 1;
+        Ok(num)
+    }
+
+    /// Set the progress observer and the cancellation token of the
+    /// extensions of the pool of VOLEs, see [`ExtendHooks`]. The hooks are
+    /// inherited by the duplicates and by the pipeline started afterwards.
+    ///
+    /// An extension cancelled before it starts leaves the functionality
+    /// untouched. Otherwise the functionality is poisoned: its pool is
+    /// emptied and every later extension fails with `Error::Poisoned`.
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.svole_sender.set_extend_hooks(hooks);
+    }
+
+    /// Run the extensions of the pool of VOLEs on a background thread over
+    /// `ext_channel`, keeping at most `depth` batches of VOLEs ahead, so
+    /// that `extend` does not stall the protocol. See
+    /// [`crate::svole::wykw::PipelinedSender`].
+    ///
+    /// The background extension uses a duplicate of the sVOLE, set up over
+    /// `channel`. `ext_channel` is dedicated to the extensions and must not
+    /// be used by anything else. This must be called at the same point of
+    /// the protocol by the verifier, using [`FComVerifier::start_pipeline`].
+    ///
+    /// This requires the `threads` feature.
+    #[cfg(feature = "threads")]
+    pub fn start_pipeline<
+        C: AbstractChannel,
+        E: AbstractChannel + Send + 'static,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        ext_channel: E,
+        depth: usize,
+    ) -> Result<(), Error> {
+        let svole = self.svole_sender.duplicate(channel, rng)?;
+        self.pipeline = Some(PipelinedSender::spawn(svole, ext_channel, depth));
+        Ok(())
+    }
+
+    /// Stop the background extensions started with `start_pipeline`, the
+    /// pool of VOLEs is then extended over the protocol channel again.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// verifier, using [`FComVerifier::stop_pipeline`].
+    pub fn stop_pipeline(&mut self) {
+        self.pipeline = None;
+    }
+
+    /// Refill the pool of VOLEs if it holds fewer VOLEs than the low
+    /// watermark of the pool configuration, and returns whether a refill
+    /// happened.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// verifier, using [`FComVerifier::refill_if_low`].
+    pub fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<bool, Error> {
+        if self.voles.len() >= self.pool_config.low_watermark {
+            return Ok(false);
+        }
+        self.extend_at_least(channel, rng, self.pool_config.refill_size.max(1))?;
+        self.pool_stats.refills += 1;
+        Ok(true)
+    }
+
     /// Returns a random mac.
     pub fn random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
@@ -100,7 +631,7 @@ impl<FE: FiniteField> FComProver<FE> {
                 return Ok(MacProver(e.0, e.1));
             }
             None => {
-                self.svole_sender.send(channel, rng, &mut self.voles)?;
+                self.extend(channel, rng)?;
                 match self.voles.pop() {
                     Some(e) => {
                         return Ok(MacProver(e.0, e.1));
@@ -113,7 +644,35 @@ impl<FE: FiniteField> FComProver<FE> {
         }
     }
 
-    /// Input a slice of values and returns a vector of its macs.
+    /// Returns `num` random macs, in the order of `num` calls to `random`,
+    /// running the extensions they need at once.
+    ///
+    /// The verifier calls [`FComVerifier::random_batch`] at the same point
+    /// of the protocol.
+    pub fn random_batch<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<MacProver<FE>>, Error> {
+        if self.voles.len() < num {
+            self.extend_at_least(channel, rng, num - self.voles.len())?;
+        }
+        if self.voles.len() < num {
+            return Err(Error::Other("svole failed for random".to_string()));
+        }
+        let start = self.voles.len() - num;
+        Ok(self
+            .voles
+            .drain(start..)
+            .rev()
+            .map(|e| MacProver(e.0, e.1))
+            .collect())
+    }
+
+    /// Input a slice of values and returns a vector of their macs.
+    ///
+    /// See [`FComProver::input_into`] for the flushing requirements.
     pub fn input<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -121,28 +680,53 @@ impl<FE: FiniteField> FComProver<FE> {
         x: &[FE::PrimeField],
     ) -> Result<Vec<FE>, Error> {
         let mut out = Vec::with_capacity(x.len());
-        self.input_low_level(channel, rng, x, &mut out)?;
+        self.input_into(channel, rng, x, &mut out)?;
         Ok(out)
     }
 
-    /// lower level implementation of `input` with pre-defined out vector.
-    pub fn input_low_level<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    /// Input a slice of values and append their macs to `out`, in the same
+    /// order as `x`.
+    ///
+    /// The masked values are written to `channel` but the channel is *not*
+    /// flushed, so that several inputs can be batched in a single message.
+    /// The caller must flush `channel` before reading from it, otherwise the
+    /// verifier may still be waiting on the buffered values. The verifier
+    /// must call [`FComVerifier::input_into`] with `x.len()` at the same
+    /// point of the protocol.
+    pub fn input_into<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
         rng: &mut RNG,
         x: &[FE::PrimeField],
         out: &mut Vec<FE>,
     ) -> Result<(), Error> {
-        for i in 0..x.len() {
+        out.reserve(x.len());
+        self.seq.send(channel, Op::Input)?;
+        let mut ys = Vec::with_capacity(x.len());
+        for x_i in x.iter() {
             let r = self.random(channel, rng)?;
-            let y = x[i] - r.0;
+            ys.push(*x_i - r.0);
             out.push(r.1);
-            channel.write_serializable::<FE::PrimeField>(&y)?;
         }
+        channel.write_serializable_slice::<FE::PrimeField>(&ys)?;
         Ok(())
     }
 
+    /// lower level implementation of `input` with pre-defined out vector.
+    #[deprecated(note = "use `input_into` instead")]
+    pub fn input_low_level<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        x: &[FE::PrimeField],
+        out: &mut Vec<FE>,
+    ) -> Result<(), Error> {
+        self.input_into(channel, rng, x, out)
+    }
+
     /// Input a single value and returns its mac.
+    ///
+    /// As with [`FComProver::input_into`], the channel is not flushed.
     pub fn input1<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -151,6 +735,7 @@ impl<FE: FiniteField> FComProver<FE> {
     ) -> Result<FE, Error> {
         let r = self.random(channel, rng)?;
         let y = x - r.0;
+        self.seq.send(channel, Op::Input)?;
         channel.write_serializable::<FE::PrimeField>(&y)?;
 
         Ok(r.1)
@@ -191,12 +776,55 @@ impl<FE: FiniteField> FComProver<FE> {
         return MacProver(a - b, a_mac - b_mac);
     }
 
+    /// Multiply by a constant a slice of Macs, appending the results to
+    /// `out`.
+    pub fn affine_mult_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacProver<FE>],
+        out: &mut Vec<MacProver<FE>>,
+    ) {
+        out.extend(
+            xs.iter()
+                .map(|MacProver(x, x_mac)| MacProver(cst * *x, cst * *x_mac)),
+        );
+    }
+
+    /// Add a constant to a slice of Macs, appending the results to `out`.
+    pub fn affine_add_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacProver<FE>],
+        out: &mut Vec<MacProver<FE>>,
+    ) {
+        out.extend(
+            xs.iter()
+                .map(|MacProver(x, x_mac)| MacProver(cst + *x, *x_mac)),
+        );
+    }
+
+    /// Multiply by a constant a slice of Macs and add the results to the
+    /// accumulators `acc`, i.e. `acc[i] += cst * xs[i]`.
+    pub fn fma_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacProver<FE>],
+        acc: &mut [MacProver<FE>],
+    ) {
+        assert_eq!(xs.len(), acc.len());
+        for (MacProver(a, a_mac), MacProver(x, x_mac)) in acc.iter_mut().zip(xs.iter()) {
+            *a += cst * *x;
+            *a_mac += cst * *x_mac;
+        }
+    }
+
     /// Check that a batch of Macs are zero.
     pub fn check_zero<C: AbstractChannel>(
         &mut self,
         channel: &mut C,
         x_mac_batch: &[MacProver<FE>],
     ) -> Result<(), Error> {
+        self.seq.recv(channel, Op::CheckZeroSeed)?;
         let seed = channel.read_block()?;
         let mut rng = AesRng::from_seed(seed);
 
@@ -207,6 +835,7 @@ impl<FE: FiniteField> FComProver<FE> {
             let chi = FE::random(&mut rng);
             m += chi * *x_mac;
         }
+        self.seq.send(channel, Op::CheckZero)?;
         channel.write_serializable::<FE>(&m)?;
         channel.flush()?;
 
@@ -217,15 +846,49 @@ impl<FE: FiniteField> FComProver<FE> {
         }
     }
 
+    /// Check that two batches of Macs are element-wise equal, without
+    /// opening them. The differences are checked with a single
+    /// `check_zero`.
+    pub fn assert_equal<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        xs: &[MacProver<FE>],
+        ys: &[MacProver<FE>],
+    ) -> Result<(), Error> {
+        if xs.len() != ys.len() {
+            return Err(Error::InvalidInputLength);
+        }
+        let diffs: Vec<MacProver<FE>> = xs
+            .iter()
+            .zip(ys.iter())
+            .map(|(x, y)| self.sub(*x, *y))
+            .collect();
+        self.check_zero(channel, &diffs)
+    }
+
     /// Open Macs.
     pub fn open<C: AbstractChannel>(
         &mut self,
         channel: &mut C,
         batch: &[MacProver<FE>],
     ) -> Result<(), Error> {
+        self.open_with(channel, batch, |channel, xs| {
+            channel.write_serializable_slice(xs)
+        })
+    }
+
+    // Open Macs, with the values written by `write_values`.
+    fn open_with<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        batch: &[MacProver<FE>],
+        write_values: impl FnOnce(&mut C, &[FE::PrimeField]) -> std::io::Result<()>,
+    ) -> Result<(), Error> {
+        self.seq.send(channel, Op::Open)?;
+        let xs: Vec<FE::PrimeField> = batch.iter().map(|MacProver(x, _)| *x).collect();
+        write_values(channel, &xs)?;
         let mut hasher = blake3::Hasher::new();
-        for MacProver(x, _) in batch.iter() {
-            channel.write_serializable::<FE::PrimeField>(x)?;
+        for x in xs.iter() {
             hasher.update(&x.to_bytes());
         }
 
@@ -243,54 +906,184 @@ impl<FE: FiniteField> FComProver<FE> {
         Ok(())
     }
 
-    /// Quicksilver multiplication check.
-    pub fn quicksilver_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
+    /// Re-authenticate values committed with `src` into commitments with
+    /// `dst`, without opening them. The two instances may use unrelated MAC
+    /// keys.
+    ///
+    /// Every value is masked with a random commitment obtained from `src`,
+    /// which is also input into `dst`. The consistency of the masks across
+    /// the two instances is checked on random linear combinations before
+    /// the masked values are opened.
+    pub fn reauthenticate<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         rng: &mut RNG,
-        triples: &[(MacProver<FE>, MacProver<FE>, MacProver<FE>)],
-    ) -> Result<(), Error> {
-        let mut sum_a0 = FE::ZERO;
-        let mut sum_a1 = FE::ZERO;
-
-        let chi = channel.read_serializable()?;
-        let mut chi_power = chi;
-
-        for (MacProver(x, x_mac), MacProver(y, y_mac), MacProver(_z, z_mac)) in
-            triples.iter().copied()
-        {
-            let a0 = x_mac * y_mac;
-            let a1 = y * x_mac + x * y_mac - z_mac;
+        src: &mut Self,
+        dst: &mut Self,
+        xs: &[MacProver<FE>],
+    ) -> Result<Vec<MacProver<FE>>, Error> {
+        let n = xs.len();
+        let num_checks = num_reauth_checks::<FE>();
+
+        let mut masks = Vec::with_capacity(n + num_checks);
+        for _ in 0..n + num_checks {
+            masks.push(src.random(channel, rng)?);
+        }
+        let values: Vec<FE::PrimeField> = masks.iter().map(|m| m.0).collect();
+        let macs = dst.input(channel, rng, &values)?;
+        let masks_dst: Vec<MacProver<FE>> = values
+            .iter()
+            .zip(macs)
+            .map(|(v, m)| MacProver(*v, m))
+            .collect();
+        channel.flush()?;
 
-            sum_a0 += a0 * chi_power;
-            sum_a1 += a1 * chi_power;
+        src.seq.recv(channel, Op::ReauthenticateSeed)?;
+        let seed = channel.read_block()?;
+        let mut chi_rng = AesRng::from_seed(seed);
+
+        let mut to_open_src = Vec::with_capacity(num_checks + n);
+        let mut to_open_dst = Vec::with_capacity(num_checks);
+        for k in 0..num_checks {
+            let mut c_src = masks[n + k];
+            let mut c_dst = masks_dst[n + k];
+            for i in 0..n {
+                let chi = FE::PrimeField::random(&mut chi_rng);
+                c_src = src.add(c_src, src.affine_mult_cst(chi, masks[i]));
+                c_dst = dst.add(c_dst, dst.affine_mult_cst(chi, masks_dst[i]));
+            }
+            to_open_src.push(c_src);
+            to_open_dst.push(c_dst);
+        }
+        for i in 0..n {
+            to_open_src.push(src.sub(xs[i], masks[i]));
+        }
+        src.open(channel, &to_open_src)?;
+        dst.open(channel, &to_open_dst)?;
 
-            chi_power *= chi;
+        let mut out = Vec::with_capacity(n);
+        for i in 0..n {
+            let d = to_open_src[num_checks + i].0;
+            out.push(dst.affine_add_cst(d, masks_dst[i]));
         }
+        Ok(out)
+    }
 
-        // The following block implements VOPE(1)
-        let mut mask = FE::ZERO;
-        let mut mask_mac = FE::ZERO;
+    /// Lift a bit committed in the binary field `TF` into a commitment of
+    /// the same bit in `FE`, consuming a dabit.
+    pub fn lift_bit<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComProver<TF, B>,
+        dabit: &DabitProver<FE, TF>,
+        bit: MacProver<TF>,
+    ) -> Result<MacProver<FE>, Error> {
+        let mut out = Vec::with_capacity(1);
+        self.lift_bits(
+            channel,
+            fcom_f2,
+            std::slice::from_ref(dabit),
+            &[bit],
+            &mut out,
+        )?;
+        Ok(out[0])
+    }
 
-        for i in 0..Degree::<FE>::USIZE {
-            let MacProver(u, u_mac) = self.random(channel, rng)?;
-            let x_i: FE = make_x_i(i);
-            mask += u * x_i;
-            mask_mac += u_mac * x_i;
+    /// Lift a batch of bits committed in the binary field `TF` into
+    /// commitments in `FE`, consuming one dabit per bit. The results are
+    /// appended to `out`.
+    pub fn lift_bits<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComProver<TF, B>,
+        dabits: &[DabitProver<FE, TF>],
+        bits: &[MacProver<TF>],
+        out: &mut Vec<MacProver<FE>>,
+    ) -> Result<(), Error> {
+        if dabits.len() != bits.len() {
+            return Err(Error::InvalidInputLength);
         }
 
-        let u = sum_a0 + mask_mac;
-        let v = sum_a1 + mask;
+        let mut c_batch = Vec::with_capacity(bits.len());
+        for (dabit, bit) in dabits.iter().zip(bits.iter()) {
+            c_batch.push(fcom_f2.add(dabit.bit, *bit));
+        }
+        fcom_f2.open(channel, &c_batch)?;
 
-        channel.write_serializable(&u)?;
-        channel.write_serializable(&v)?;
-        channel.flush()?;
+        for (dabit, MacProver(c, _)) in dabits.iter().zip(c_batch.iter()) {
+            let c_m = f2_to_fe::<FE::PrimeField>(*c);
 
+            let choice = c.ct_eq(&F2::ONE);
+            let beq = self.affine_add_cst(c_m, self.neg(dabit.value));
+            let bneq = self.affine_add_cst(c_m, dabit.value);
+            out.push(MacProver::conditional_select(&bneq, &beq, choice));
+        }
         Ok(())
     }
 
-    /// Wolverine multiplication check.
-    pub fn wolverine_check_multiply<C: AbstractChannel>(
+    /// Lift a batch of bits as with [`FComProver::lift_bits`], drawing
+    /// the dabits from `pool`.
+    pub fn lift_bits_from_pool<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComProver<TF, B>,
+        pool: &mut DabitPool<DabitProver<FE, TF>>,
+        bits: &[MacProver<TF>],
+        out: &mut Vec<MacProver<FE>>,
+    ) -> Result<(), Error> {
+        let dabits = pool.draw(bits.len())?;
+        self.lift_bits(channel, fcom_f2, &dabits, bits, out)
+    }
+
+    /// Quicksilver multiplication check.
+    pub fn quicksilver_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: &[(MacProver<FE>, MacProver<FE>, MacProver<FE>)],
+    ) -> Result<(), Error> {
+        self.quicksilver_check_multiply_iter(channel, rng, triples.iter().copied())
+    }
+
+    /// Quicksilver multiplication check on triples produced by an iterator,
+    /// which are consumed one at a time.
+    pub fn quicksilver_check_multiply_iter<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        I: IntoIterator<Item = (MacProver<FE>, MacProver<FE>, MacProver<FE>)>,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: I,
+    ) -> Result<(), Error> {
+        let mut check = self.quicksilver_check_start(channel)?;
+        for (x, y, z) in triples {
+            check.push_triple(x, y, z);
+        }
+        check.finish(channel, rng, self)
+    }
+
+    /// Start a Quicksilver multiplication check, the triples are then
+    /// pushed into the returned checker.
+    ///
+    /// This receives the challenge of the check: every value of the
+    /// triples must have been input, and the channel flushed, beforehand.
+    pub fn quicksilver_check_start<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+    ) -> Result<QuicksilverCheckProver<FE>, Error> {
+        self.seq.recv(channel, Op::QuicksilverChallenge)?;
+        let chi = channel.read_serializable()?;
+        Ok(QuicksilverCheckProver {
+            chi,
+            chi_power: chi,
+            sum_a0: FE::ZERO,
+            sum_a1: FE::ZERO,
+        })
+    }
+
+    /// Wolverine multiplication check.
+    pub fn wolverine_check_multiply<C: AbstractChannel>(
         &mut self,
         channel: &mut C,
         triples: &[(MacProver<FE>, MacProver<FE>, MacProver<FE>)],
@@ -341,15 +1134,26 @@ impl<FE: FiniteField> FComProver<FE> {
     }
 }
 
-/// F_com protocol for the Verififier
-pub struct FComVerifier<FE: FiniteField> {
+/// F_com protocol for the Verififier, whose sVOLE base OTs are run with `B`.
+pub struct FComVerifier<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
     delta: FE,
-    svole_receiver: Receiver<FE>,
+    svole_receiver: Receiver<FE, B>,
+    // background extension, used in place of `svole_receiver` when set
+    pipeline: Option<PipelinedReceiver<FE>>,
     voles: Vec<FE>,
+    pool_config: VolePoolConfig,
+    pool_stats: VolePoolStats,
+    // identifies the MAC key, shared with the duplicates of the instance
+    key_id: Block,
+    seq: Sequencer,
 }
 
-impl<FE: FiniteField> FComVerifier<FE> {
+impl<FE: FiniteField, B: BaseOt> FComVerifier<FE, B> {
     /// Initialize the functionality.
+    ///
+    /// Fails with `Error::InvalidLpnParams` if the LPN parameters are
+    /// inconsistent, see [`LpnParams::validate`], and with
+    /// `Error::LpnParamsMismatch` if the other party uses different ones.
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         rng: &mut RNG,
@@ -357,14 +1161,40 @@ impl<FE: FiniteField> FComVerifier<FE> {
         lpn_extend: LpnParams,
     ) -> Result<Self, Error> {
         let recv = Receiver::init(channel, rng, lpn_setup, lpn_extend)?;
-        Ok(Self {
+        Ok(Self::from_svole(recv, rng))
+    }
+
+    /// Initialize the functionality from a base OT setup shared with other
+    /// instances, see [`FComProver::init_with_setup`]. The delta is fresh,
+    /// the instance does not share its MAC key with any other.
+    pub fn init_with_setup<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        setup: &mut SetupReceiver<B>,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        let recv = setup.derive(channel, rng, lpn_setup, lpn_extend)?;
+        Ok(Self::from_svole(recv, rng))
+    }
+
+    fn from_svole<RNG: CryptoRng + Rng>(recv: Receiver<FE, B>, rng: &mut RNG) -> Self {
+        Self {
             delta: recv.delta(),
             svole_receiver: recv,
+            pipeline: None,
             voles: Vec::new(),
-        })
+            pool_config: VolePoolConfig::default(),
+            pool_stats: VolePoolStats::default(),
+            key_id: rng.gen(),
+            seq: Sequencer::default(),
+        }
     }
 
     /// Duplicate the functionality.
+    ///
+    /// The duplicate uses the same delta, so that values can be moved
+    /// between the two instances with `export` and `import`.
     pub fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -373,7 +1203,12 @@ impl<FE: FiniteField> FComVerifier<FE> {
         Ok(Self {
             delta: self.get_delta(),
             svole_receiver: self.svole_receiver.duplicate(channel, rng)?,
+            pipeline: None,
             voles: Vec::new(),
+            pool_config: self.pool_config,
+            pool_stats: VolePoolStats::default(),
+            key_id: self.key_id,
+            seq: Sequencer::default(),
         })
     }
 
@@ -383,6 +1218,194 @@ impl<FE: FiniteField> FComVerifier<FE> {
         self.delta
     }
 
+    /// Returns whether the two instances use the same delta.
+    pub fn shares_key_with(&self, other: &Self) -> bool {
+        self.key_id == other.key_id && self.delta == other.delta
+    }
+
+    /// Export authenticated values, to be imported into an instance using
+    /// the same delta. This is purely local and must be matched by an
+    /// export of the corresponding values by the prover.
+    pub fn export(&self, xs: &[MacVerifier<FE>]) -> Exported<MacVerifier<FE>> {
+        Exported {
+            key_id: self.key_id,
+            macs: xs.to_vec(),
+        }
+    }
+
+    /// Import authenticated values exported by an instance using the same
+    /// delta.
+    ///
+    /// Values authenticated under a different delta are rejected, they
+    /// must be transferred with [`FComVerifier::reauthenticate`] instead.
+    pub fn import(
+        &self,
+        exported: Exported<MacVerifier<FE>>,
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        if exported.key_id != self.key_id {
+            return Err(Error::Other(
+                "cannot import values authenticated under a different MAC key".to_string(),
+            ));
+        }
+        Ok(exported.macs)
+    }
+
+    /// Set the refill policy of the pool of VOLEs. The prover must use the
+    /// same policy.
+    pub fn set_pool_config(&mut self, config: VolePoolConfig) {
+        self.pool_config = config;
+    }
+
+    /// Returns the refill policy of the pool of VOLEs.
+    pub fn pool_config(&self) -> VolePoolConfig {
+        self.pool_config
+    }
+
+    /// Returns statistics about the pool of VOLEs.
+    pub fn pool_stats(&self) -> VolePoolStats {
+        self.pool_stats
+    }
+
+    /// Returns the number of random Macs that can be produced before the
+    /// pool of VOLEs needs to be extended.
+    ///
+    /// The pool is not shared with duplicates: `duplicate` returns an
+    /// instance with an empty pool.
+    pub fn available(&self) -> usize {
+        self.voles.len()
+    }
+
+    /// Extend the pool of VOLEs now, and returns the number of VOLEs added.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// prover, using [`FComProver::extend`].
+    pub fn extend<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<usize, Error> {
+        self.extend_internal(channel, rng, None)
+    }
+
+    /// Extend the pool of VOLEs by at least `num` VOLEs, and returns the
+    /// exact number of VOLEs added. The extensions are sized according to
+    /// `num` rather than to the LPN parameters of the instance, see
+    /// [`Receiver::receive_at_least`].
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// prover, using [`FComProver::extend_at_least`] with the same `num`.
+    pub fn extend_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<usize, Error> {
+        self.extend_internal(channel, rng, Some(num))
+    }
+
+    // Runs one extension, or extensions producing at least `num` VOLEs when
+    // `num` is set, and adds their output to the pool.
+    fn extend_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: Option<usize>,
+    ) -> Result<usize, Error> {
+        let mut voles = Vec::new();
+        let batch = match (&mut self.pipeline, num) {
+            (Some(pipeline), None) => pipeline.next_batch(),
+            (Some(pipeline), Some(num)) => pipeline.next_at_least(num),
+            (None, None) => self
+                .svole_receiver
+                .receive(channel, rng, &mut voles)
+                .map(|_| voles),
+            (None, Some(num)) => self
+                .svole_receiver
+                .receive_at_least(channel, rng, num, &mut voles)
+                .map(|_| voles),
+        };
+        let mut voles = match batch {
+            Ok(voles) => voles,
+            Err(e) => {
+                // The pools of the two parties are out of sync after an
+                // interrupted extension: the pool is emptied so that every
+                // later use goes through a failing extension.
+                if self.pipeline.is_some() || self.svole_receiver.is_poisoned() {
+                    self.voles.clear();
+                }
+                return Err(e);
+            }
+        };
+        let num = voles.len();
+        // the remaining VOLEs are kept at the end so that they are used first
+        voles.append(&mut self.voles);
+        self.voles = voles;
+        self.pool_stats.extensions += 1;
+        Ok(num)
+    }
+
+    /// Set the progress observer and the cancellation token of the
+    /// extensions of the pool of VOLEs, see [`FComProver::set_extend_hooks`].
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.svole_receiver.set_extend_hooks(hooks);
+    }
+
+    /// Run the extensions of the pool of VOLEs on a background thread over
+    /// `ext_channel`, keeping at most `depth` batches of VOLEs ahead, so
+    /// that `extend` does not stall the protocol. See
+    /// [`crate::svole::wykw::PipelinedReceiver`].
+    ///
+    /// The background extension uses a duplicate of the sVOLE, set up over
+    /// `channel`. `ext_channel` is dedicated to the extensions and must not
+    /// be used by anything else. This must be called at the same point of
+    /// the protocol by the prover, using [`FComProver::start_pipeline`].
+    ///
+    /// This requires the `threads` feature.
+    #[cfg(feature = "threads")]
+    pub fn start_pipeline<
+        C: AbstractChannel,
+        E: AbstractChannel + Send + 'static,
+        RNG: CryptoRng + Rng,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        ext_channel: E,
+        depth: usize,
+    ) -> Result<(), Error> {
+        let svole = self.svole_receiver.duplicate(channel, rng)?;
+        self.pipeline = Some(PipelinedReceiver::spawn(svole, ext_channel, depth));
+        Ok(())
+    }
+
+    /// Stop the background extensions started with `start_pipeline`, the
+    /// pool of VOLEs is then extended over the protocol channel again.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// prover, using [`FComProver::stop_pipeline`].
+    pub fn stop_pipeline(&mut self) {
+        self.pipeline = None;
+    }
+
+    /// Refill the pool of VOLEs if it holds fewer VOLEs than the low
+    /// watermark of the pool configuration, and returns whether a refill
+    /// happened.
+    ///
+    /// This must be called at the same point of the protocol by the
+    /// prover, using [`FComProver::refill_if_low`].
+    pub fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<bool, Error> {
+        if self.voles.len() >= self.pool_config.low_watermark {
+            return Ok(false);
+        }
+        self.extend_at_least(channel, rng, self.pool_config.refill_size.max(1))?;
+        self.pool_stats.refills += 1;
+        Ok(true)
+    }
+
     /// Returns a random mac.
     pub fn random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
@@ -394,9 +1417,7 @@ impl<FE: FiniteField> FComVerifier<FE> {
                 return Ok(MacVerifier(e));
             }
             None => {
-                let _start = Instant::now();
-                self.svole_receiver.receive(channel, rng, &mut self.voles)?;
-                println!("SVOLE<{:?}>", _start.elapsed());
+                self.extend(channel, rng)?;
                 match self.voles.pop() {
                     Some(e) => {
                         return Ok(MacVerifier(e));
@@ -409,7 +1430,27 @@ impl<FE: FiniteField> FComVerifier<FE> {
         }
     }
 
+    /// Returns `num` random macs with the prover, see
+    /// [`FComProver::random_batch`].
+    pub fn random_batch<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        if self.voles.len() < num {
+            self.extend_at_least(channel, rng, num - self.voles.len())?;
+        }
+        if self.voles.len() < num {
+            return Err(Error::Other("svole failed for random".to_string()));
+        }
+        let start = self.voles.len() - num;
+        Ok(self.voles.drain(start..).rev().map(MacVerifier).collect())
+    }
+
     /// Input a number of values and returns the associated macs.
+    ///
+    /// See [`FComVerifier::input_into`] for the flushing requirements.
     pub fn input<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -417,26 +1458,49 @@ impl<FE: FiniteField> FComVerifier<FE> {
         num: usize,
     ) -> Result<Vec<MacVerifier<FE>>, Error> {
         let mut out = Vec::with_capacity(num);
-        self.input_low_level(channel, rng, num, &mut out)?;
+        self.input_into(channel, rng, num, &mut out)?;
         Ok(out)
     }
 
-    /// lower level implementation of `input` for predefined  out vector.
-    pub fn input_low_level<C: AbstractChannel, RNG: CryptoRng + Rng>(
+    /// Input `num` values and append their macs to `out`, in the order in
+    /// which the prover input them.
+    ///
+    /// This matches a call to [`FComProver::input_into`] on `num` values.
+    /// It only reads from `channel`, and blocks until the prover has
+    /// flushed the corresponding values.
+    pub fn input_into<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
         rng: &mut RNG,
         num: usize,
         out: &mut Vec<MacVerifier<FE>>,
     ) -> Result<(), Error> {
+        out.reserve(num);
+        self.seq.recv(channel, Op::Input)?;
+        let mut rs = Vec::with_capacity(num);
         for _i in 0..num {
-            let r = self.random(channel, rng)?;
-            let y = channel.read_serializable::<FE::PrimeField>()?;
+            rs.push(self.random(channel, rng)?);
+        }
+        let mut ys = Vec::with_capacity(num);
+        channel.read_serializable_slice::<FE::PrimeField>(num, &mut ys)?;
+        for (r, y) in rs.into_iter().zip(ys) {
             out.push(MacVerifier(r.0 - y * self.delta));
         }
         Ok(())
     }
 
+    /// lower level implementation of `input` for predefined  out vector.
+    #[deprecated(note = "use `input_into` instead")]
+    pub fn input_low_level<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        self.input_into(channel, rng, num, out)
+    }
+
     /// Input a single value and returns its associated Mac.
     pub fn input1<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
@@ -444,6 +1508,7 @@ impl<FE: FiniteField> FComVerifier<FE> {
         rng: &mut RNG,
     ) -> Result<MacVerifier<FE>, Error> {
         let r = self.random(channel, rng)?;
+        self.seq.recv(channel, Op::Input)?;
         let y = channel.read_serializable::<FE::PrimeField>()?;
         let out = MacVerifier(r.0 - y * self.delta);
         Ok(out)
@@ -484,6 +1549,48 @@ impl<FE: FiniteField> FComVerifier<FE> {
         return MacVerifier(a_mac - b_mac);
     }
 
+    /// Multiply by a constant a slice of Macs, appending the results to
+    /// `out`.
+    pub fn affine_mult_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacVerifier<FE>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) {
+        out.extend(
+            xs.iter()
+                .map(|MacVerifier(x_mac)| MacVerifier(cst * *x_mac)),
+        );
+    }
+
+    /// Add a constant to a slice of Macs, appending the results to `out`.
+    pub fn affine_add_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacVerifier<FE>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) {
+        let cst_delta = cst * self.delta;
+        out.extend(
+            xs.iter()
+                .map(|MacVerifier(x_mac)| MacVerifier(*x_mac - cst_delta)),
+        );
+    }
+
+    /// Multiply by a constant a slice of Macs and add the results to the
+    /// accumulators `acc`, i.e. `acc[i] += cst * xs[i]`.
+    pub fn fma_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacVerifier<FE>],
+        acc: &mut [MacVerifier<FE>],
+    ) {
+        assert_eq!(xs.len(), acc.len());
+        for (MacVerifier(a_mac), MacVerifier(x_mac)) in acc.iter_mut().zip(xs.iter()) {
+            *a_mac += cst * *x_mac;
+        }
+    }
+
     /// Check that a batch of Macs are zero.
     pub fn check_zero<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
@@ -492,6 +1599,7 @@ impl<FE: FiniteField> FComVerifier<FE> {
         key_batch: &[MacVerifier<FE>],
     ) -> Result<(), Error> {
         let seed = rng.gen::<Block>();
+        self.seq.send(channel, Op::CheckZeroSeed)?;
         channel.write_block(&seed)?;
         channel.flush()?;
         let mut rng = AesRng::from_seed(seed);
@@ -501,6 +1609,7 @@ impl<FE: FiniteField> FComVerifier<FE> {
             let chi = FE::random(&mut rng);
             key_chi += chi * *key;
         }
+        self.seq.recv(channel, Op::CheckZero)?;
         let m = channel.read_serializable::<FE>()?;
 
         let b = key_chi == m;
@@ -512,6 +1621,27 @@ impl<FE: FiniteField> FComVerifier<FE> {
         }
     }
 
+    /// Check that two batches of Macs are element-wise equal, without
+    /// opening them. The differences are checked with a single
+    /// `check_zero`.
+    pub fn assert_equal<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        xs: &[MacVerifier<FE>],
+        ys: &[MacVerifier<FE>],
+    ) -> Result<(), Error> {
+        if xs.len() != ys.len() {
+            return Err(Error::InvalidInputLength);
+        }
+        let diffs: Vec<MacVerifier<FE>> = xs
+            .iter()
+            .zip(ys.iter())
+            .map(|(x, y)| self.sub(*x, *y))
+            .collect();
+        self.check_zero(channel, rng, &diffs)
+    }
+
     /// Open Macs.
     pub fn open<C: AbstractChannel>(
         &mut self,
@@ -519,11 +1649,24 @@ impl<FE: FiniteField> FComVerifier<FE> {
         keys: &[MacVerifier<FE>],
         out: &mut Vec<FE::PrimeField>,
     ) -> Result<(), Error> {
-        let mut hasher = blake3::Hasher::new();
+        self.open_with(channel, keys, out, |channel, num, out| {
+            channel.read_serializable_slice(num, out)
+        })
+    }
+
+    // Open Macs, with the values read by `read_values`.
+    fn open_with<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        keys: &[MacVerifier<FE>],
+        out: &mut Vec<FE::PrimeField>,
+        read_values: impl FnOnce(&mut C, usize, &mut Vec<FE::PrimeField>) -> std::io::Result<()>,
+    ) -> Result<(), Error> {
+        self.seq.recv(channel, Op::Open)?;
         out.clear();
-        for _ in 0..keys.len() {
-            let x = channel.read_serializable::<FE::PrimeField>()?;
-            out.push(x);
+        read_values(channel, keys.len(), out)?;
+        let mut hasher = blake3::Hasher::new();
+        for x in out.iter() {
             hasher.update(&x.to_bytes());
         }
         let seed = Block::try_from_slice(&hasher.finalize().as_bytes()[0..16]).unwrap();
@@ -549,6 +1692,159 @@ impl<FE: FiniteField> FComVerifier<FE> {
         }
     }
 
+    /// Open Macs and check that every opened value satisfies `pred`, which
+    /// is given the index of the value in the batch and the value itself.
+    ///
+    /// The prover side of this is a plain `open`. Returns the opened
+    /// values, or an `Error::OpenCheckFailed` naming the first value that
+    /// does not satisfy the predicate.
+    pub fn open_and_check<C: AbstractChannel, P: FnMut(usize, FE::PrimeField) -> bool>(
+        &mut self,
+        channel: &mut C,
+        keys: &[MacVerifier<FE>],
+        mut pred: P,
+    ) -> Result<Vec<FE::PrimeField>, Error> {
+        let mut out = Vec::with_capacity(keys.len());
+        self.open(channel, keys, &mut out)?;
+        for (i, x) in out.iter().enumerate() {
+            if !pred(i, *x) {
+                return Err(Error::OpenCheckFailed {
+                    index: i,
+                    value: format!("{:?}", x),
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    /// Re-authenticate values committed with `src` into commitments with
+    /// `dst`, without opening them. The two instances may use unrelated
+    /// deltas.
+    ///
+    /// See [`FComProver::reauthenticate`] for a description of the
+    /// protocol.
+    pub fn reauthenticate<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        src: &mut Self,
+        dst: &mut Self,
+        xs: &[MacVerifier<FE>],
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        let n = xs.len();
+        let num_checks = num_reauth_checks::<FE>();
+
+        let mut masks = Vec::with_capacity(n + num_checks);
+        for _ in 0..n + num_checks {
+            masks.push(src.random(channel, rng)?);
+        }
+        let masks_dst = dst.input(channel, rng, n + num_checks)?;
+
+        let seed = rng.gen::<Block>();
+        src.seq.send(channel, Op::ReauthenticateSeed)?;
+        channel.write_block(&seed)?;
+        channel.flush()?;
+        let mut chi_rng = AesRng::from_seed(seed);
+
+        let mut to_open_src = Vec::with_capacity(num_checks + n);
+        let mut to_open_dst = Vec::with_capacity(num_checks);
+        for k in 0..num_checks {
+            let mut c_src = masks[n + k];
+            let mut c_dst = masks_dst[n + k];
+            for i in 0..n {
+                let chi = FE::PrimeField::random(&mut chi_rng);
+                c_src = src.add(c_src, src.affine_mult_cst(chi, masks[i]));
+                c_dst = dst.add(c_dst, dst.affine_mult_cst(chi, masks_dst[i]));
+            }
+            to_open_src.push(c_src);
+            to_open_dst.push(c_dst);
+        }
+        for i in 0..n {
+            to_open_src.push(src.sub(xs[i], masks[i]));
+        }
+        let mut opened_src = Vec::with_capacity(num_checks + n);
+        src.open(channel, &to_open_src, &mut opened_src)?;
+        let mut opened_dst = Vec::with_capacity(num_checks);
+        dst.open(channel, &to_open_dst, &mut opened_dst)?;
+        if opened_src[..num_checks] != opened_dst[..] {
+            return Err(Error::Other(
+                "reauthentication consistency check failed".to_string(),
+            ));
+        }
+
+        let mut out = Vec::with_capacity(n);
+        for i in 0..n {
+            out.push(dst.affine_add_cst(opened_src[num_checks + i], masks_dst[i]));
+        }
+        Ok(out)
+    }
+
+    /// Lift a bit committed in the binary field `TF` into a commitment of
+    /// the same bit in `FE`, consuming a dabit.
+    pub fn lift_bit<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComVerifier<TF, B>,
+        dabit: &DabitVerifier<FE, TF>,
+        bit: MacVerifier<TF>,
+    ) -> Result<MacVerifier<FE>, Error> {
+        let mut out = Vec::with_capacity(1);
+        self.lift_bits(
+            channel,
+            fcom_f2,
+            std::slice::from_ref(dabit),
+            &[bit],
+            &mut out,
+        )?;
+        Ok(out[0])
+    }
+
+    /// Lift a batch of bits committed in the binary field `TF` into
+    /// commitments in `FE`, consuming one dabit per bit. The results are
+    /// appended to `out`.
+    pub fn lift_bits<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComVerifier<TF, B>,
+        dabits: &[DabitVerifier<FE, TF>],
+        bits: &[MacVerifier<TF>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        if dabits.len() != bits.len() {
+            return Err(Error::InvalidInputLength);
+        }
+
+        let mut c_mac_batch = Vec::with_capacity(bits.len());
+        for (dabit, bit) in dabits.iter().zip(bits.iter()) {
+            c_mac_batch.push(fcom_f2.add(dabit.bit, *bit));
+        }
+        let mut c_batch = Vec::with_capacity(bits.len());
+        fcom_f2.open(channel, &c_mac_batch, &mut c_batch)?;
+
+        for (dabit, c) in dabits.iter().zip(c_batch.iter()) {
+            let c_m = f2_to_fe::<FE::PrimeField>(*c);
+
+            let choice = c.ct_eq(&F2::ONE);
+            let beq = self.affine_add_cst(c_m, self.neg(dabit.value));
+            let bneq = self.affine_add_cst(c_m, dabit.value);
+            out.push(MacVerifier::conditional_select(&bneq, &beq, choice));
+        }
+        Ok(())
+    }
+
+    /// Lift a batch of bits as with [`FComVerifier::lift_bits`], drawing
+    /// the dabits from `pool`.
+    pub fn lift_bits_from_pool<C: AbstractChannel, TF: FiniteField<PrimeField = F2>>(
+        &mut self,
+        channel: &mut C,
+        fcom_f2: &mut FComVerifier<TF, B>,
+        pool: &mut DabitPool<DabitVerifier<FE, TF>>,
+        bits: &[MacVerifier<TF>],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        let dabits = pool.draw(bits.len())?;
+        self.lift_bits(channel, fcom_f2, &dabits, bits, out)
+    }
+
     /// Quicksilver multiplication check.
     pub fn quicksilver_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
@@ -556,212 +1852,1355 @@ impl<FE: FiniteField> FComVerifier<FE> {
         rng: &mut RNG,
         triples: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
     ) -> Result<(), Error> {
+        self.quicksilver_check_multiply_iter(channel, rng, triples.iter().copied())
+    }
+
+    /// Quicksilver multiplication check on triples produced by an iterator,
+    /// which are consumed one at a time.
+    pub fn quicksilver_check_multiply_iter<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        I: IntoIterator<Item = (MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)>,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: I,
+    ) -> Result<(), Error> {
+        let mut check = self.quicksilver_check_start(channel, rng)?;
+        for (x, y, z) in triples {
+            check.push_triple(x, y, z);
+        }
+        check.finish(channel, rng, self)
+    }
+
+    /// Start a Quicksilver multiplication check, the triples are then
+    /// pushed into the returned checker.
+    ///
+    /// This sends the challenge of the check, so every value of the
+    /// triples must have been input beforehand.
+    pub fn quicksilver_check_start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<QuicksilverCheckVerifier<FE>, Error> {
         let chi = FE::random(rng);
+        self.seq.send(channel, Op::QuicksilverChallenge)?;
         channel.write_serializable::<FE>(&chi)?;
         channel.flush()?;
+        Ok(QuicksilverCheckVerifier {
+            delta: self.delta,
+            chi,
+            power_chi: chi,
+            sum_b: FE::ZERO,
+        })
+    }
 
-        let mut sum_b = FE::ZERO;
-        let mut power_chi = chi;
+    /// Wolverine multiplication check
+    pub fn wolverine_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
+        aux: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
+    ) -> Result<(), Error> {
+        let n = triples.len();
+        let mut to_open = Vec::with_capacity(2 * n);
+
+        for i in 0..n {
+            let (a, b, _c) = triples[i];
+            let (x, y, _z) = aux[i];
 
-        for (MacVerifier(x_mac), MacVerifier(y_mac), MacVerifier(z_mac)) in triples.iter() {
-            //  should be `- (-delta)` with our conventions compared to
-            //  quicksilver but simplified out.
-            let b = (*x_mac) * (*y_mac) + self.delta * *z_mac;
+            let minus_x = self.affine_mult_cst(-FE::PrimeField::ONE, x);
+            let minus_y = self.affine_mult_cst(-FE::PrimeField::ONE, y);
+            let d = self.add(a, minus_x);
+            let e = self.add(b, minus_y);
 
-            sum_b += b * power_chi;
-            power_chi = power_chi * chi;
+            to_open.push(d);
+            to_open.push(e);
         }
+        let mut opened = Vec::with_capacity(2 * n);
+        self.open(channel, &to_open, &mut opened)?;
 
-        // The following block implements VOPE(1)
-        let mut mask_mac = FE::ZERO;
-        for i in 0..Degree::<FE>::USIZE {
-            let MacVerifier(v_m) = self.random(channel, rng)?;
-            let x_i: FE = make_x_i(i);
-            mask_mac += v_m * x_i;
+        let mut to_check = Vec::with_capacity(n);
+        for i in 0..n {
+            let (_a, _b, c) = triples[i];
+            let (x, y, z) = aux[i];
+            let d = opened[2 * i];
+            let e = opened[2 * i + 1];
+
+            let d_e = d * e;
+
+            let e_x = self.affine_mult_cst(e, x);
+            let d_y = self.affine_mult_cst(d, y);
+
+            let mut w: MacVerifier<FE> = z;
+            w = self.sub(w, c);
+            w = self.add(w, e_x);
+            w = self.add(w, d_y);
+            w = self.affine_add_cst(d_e, w);
+
+            to_check.push(w);
         }
+        self.check_zero(channel, rng, &to_check)?;
+        Ok(())
+    }
+}
+
+/// Operations of the F_com functionality shared by the prover and the
+/// verifier, so that protocols built on top of it can be written once for
+/// both parties.
+///
+/// Committed values are represented by [`FComParty::Mac`], that is
+/// `MacProver` for the prover and `MacVerifier` for the verifier. Only the
+/// prover knows the committed values, which [`FComParty::value`] returns.
+pub trait FComParty<FE: FiniteField> {
+    /// Representation of a committed value.
+    type Mac: Copy;
+
+    /// State of a Quicksilver multiplication check.
+    type QuicksilverCheck;
+
+    /// Whether this is the prover side of F_com.
+    const IS_PROVER: bool;
+
+    /// Returns the committed value, if known.
+    fn value(x: &Self::Mac) -> Option<FE::PrimeField>;
+
+    /// Returns a random commitment.
+    fn random<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self::Mac, Error>;
+
+    /// Input `num` values and append their commitments to `out`.
+    ///
+    /// The prover inputs the values `xs`, of length `num`, while the
+    /// verifier ignores `xs`. As with [`FComProver::input_into`], the
+    /// channel is not flushed.
+    fn input_into<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        xs: &[FE::PrimeField],
+        out: &mut Vec<Self::Mac>,
+    ) -> Result<(), Error>;
+
+    /// Add two commitments.
+    fn add(&self, a: Self::Mac, b: Self::Mac) -> Self::Mac;
+
+    /// Subtract two commitments.
+    fn sub(&self, a: Self::Mac, b: Self::Mac) -> Self::Mac;
+
+    /// Negate a commitment.
+    fn neg(&self, a: Self::Mac) -> Self::Mac;
+
+    /// Add a constant to a commitment.
+    fn affine_add_cst(&self, cst: FE::PrimeField, x: Self::Mac) -> Self::Mac;
+
+    /// Multiply a commitment by a constant.
+    fn affine_mult_cst(&self, cst: FE::PrimeField, x: Self::Mac) -> Self::Mac;
+
+    /// Multiply by a constant a slice of commitments and add the results to
+    /// the accumulators `acc`, see [`FComProver::fma_cst_slice`].
+    fn fma_cst_slice(&self, cst: FE::PrimeField, xs: &[Self::Mac], acc: &mut [Self::Mac]);
+
+    /// Returns the number of VOLEs remaining in the pool.
+    fn available(&self) -> usize;
+
+    /// Refill the pool of VOLEs if it is below its low watermark, see
+    /// [`FComProver::refill_if_low`].
+    fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<bool, Error>;
+
+    /// Extend the pool of VOLEs by at least `num` VOLEs, see
+    /// [`FComProver::extend_at_least`].
+    fn extend_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<usize, Error>;
+
+    /// Agree on a random seed chosen by the verifier. The prover flushes
+    /// the channel before waiting for it.
+    fn challenge<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Block, Error>;
+
+    /// Open commitments and return their values.
+    fn open<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        xs: &[Self::Mac],
+    ) -> Result<Vec<FE::PrimeField>, Error>;
+
+    /// Open commitments and return their values, which the verifier checks
+    /// with `pred`, see [`FComVerifier::open_and_check`]. The prover does not
+    /// check them.
+    fn open_and_check<C: AbstractChannel, P: FnMut(usize, FE::PrimeField) -> bool>(
+        &mut self,
+        channel: &mut C,
+        xs: &[Self::Mac],
+        pred: P,
+    ) -> Result<Vec<FE::PrimeField>, Error>;
+
+    /// Check that a batch of commitments are zero.
+    fn check_zero<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        xs: &[Self::Mac],
+    ) -> Result<(), Error>;
+
+    /// Start a Quicksilver multiplication check, see
+    /// [`FComProver::quicksilver_check_start`].
+    fn quicksilver_check_start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self::QuicksilverCheck, Error>;
+
+    /// Add a triple `(x, y, z)`, with `z = x * y`, to a Quicksilver check.
+    fn quicksilver_check_push(
+        check: &mut Self::QuicksilverCheck,
+        x: Self::Mac,
+        y: Self::Mac,
+        z: Self::Mac,
+    );
+
+    /// Finish a Quicksilver multiplication check.
+    fn quicksilver_check_finish<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        check: Self::QuicksilverCheck,
+    ) -> Result<(), Error>;
+
+    /// Quicksilver multiplication check on triples produced by an iterator.
+    fn quicksilver_check_multiply_iter<
+        C: AbstractChannel,
+        RNG: CryptoRng + Rng,
+        I: IntoIterator<Item = (Self::Mac, Self::Mac, Self::Mac)>,
+    >(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: I,
+    ) -> Result<(), Error> {
+        let mut check = self.quicksilver_check_start(channel, rng)?;
+        for (x, y, z) in triples {
+            Self::quicksilver_check_push(&mut check, x, y, z);
+        }
+        self.quicksilver_check_finish(channel, rng, check)
+    }
+
+    /// Wolverine multiplication check of `triples`, using the random
+    /// triples `aux`.
+    fn wolverine_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: &[(Self::Mac, Self::Mac, Self::Mac)],
+        aux: &[(Self::Mac, Self::Mac, Self::Mac)],
+    ) -> Result<(), Error>;
+}
+
+impl<FE: FiniteField<PrimeField = F2>, B: BaseOt> FComProver<FE, B> {
+    /// Open Macs of bits, sent packed eight per byte.
+    ///
+    /// The verifier must call [`FComVerifier::open_packed`].
+    pub fn open_packed<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        batch: &[MacProver<FE>],
+    ) -> Result<(), Error> {
+        self.open_with(channel, batch, |channel, xs| {
+            channel.write_f2_slice_packed(xs)
+        })
+    }
+}
+
+impl<FE: FiniteField<PrimeField = F2>, B: BaseOt> FComVerifier<FE, B> {
+    /// Open Macs of bits, sent packed eight per byte.
+    ///
+    /// This matches a call to [`FComProver::open_packed`].
+    pub fn open_packed<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        keys: &[MacVerifier<FE>],
+        out: &mut Vec<F2>,
+    ) -> Result<(), Error> {
+        self.open_with(channel, keys, out, |channel, num, out| {
+            channel.read_f2_slice_packed(num, out)
+        })
+    }
+}
+
+impl<FE: FiniteField, B: BaseOt> FComParty<FE> for FComProver<FE, B> {
+    type Mac = MacProver<FE>;
+    type QuicksilverCheck = QuicksilverCheckProver<FE>;
+    const IS_PROVER: bool = true;
+
+    #[inline]
+    fn value(x: &MacProver<FE>) -> Option<FE::PrimeField> {
+        Some(x.0)
+    }
+
+    fn random<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<MacProver<FE>, Error> {
+        FComProver::random(self, channel, rng)
+    }
+
+    fn input_into<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        xs: &[FE::PrimeField],
+        out: &mut Vec<MacProver<FE>>,
+    ) -> Result<(), Error> {
+        if xs.len() != num {
+            return Err(Error::InvalidInputLength);
+        }
+        let mut macs = Vec::with_capacity(num);
+        FComProver::input_into(self, channel, rng, xs, &mut macs)?;
+        out.extend(xs.iter().zip(macs).map(|(x, mac)| MacProver(*x, mac)));
+        Ok(())
+    }
+
+    #[inline]
+    fn add(&self, a: MacProver<FE>, b: MacProver<FE>) -> MacProver<FE> {
+        FComProver::add(self, a, b)
+    }
+
+    #[inline]
+    fn sub(&self, a: MacProver<FE>, b: MacProver<FE>) -> MacProver<FE> {
+        FComProver::sub(self, a, b)
+    }
+
+    #[inline]
+    fn neg(&self, a: MacProver<FE>) -> MacProver<FE> {
+        FComProver::neg(self, a)
+    }
+
+    #[inline]
+    fn affine_add_cst(&self, cst: FE::PrimeField, x: MacProver<FE>) -> MacProver<FE> {
+        FComProver::affine_add_cst(self, cst, x)
+    }
+
+    #[inline]
+    fn affine_mult_cst(&self, cst: FE::PrimeField, x: MacProver<FE>) -> MacProver<FE> {
+        FComProver::affine_mult_cst(self, cst, x)
+    }
+
+    fn fma_cst_slice(&self, cst: FE::PrimeField, xs: &[MacProver<FE>], acc: &mut [MacProver<FE>]) {
+        FComProver::fma_cst_slice(self, cst, xs, acc)
+    }
+
+    fn available(&self) -> usize {
+        FComProver::available(self)
+    }
+
+    fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<bool, Error> {
+        FComProver::refill_if_low(self, channel, rng)
+    }
+
+    fn extend_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<usize, Error> {
+        FComProver::extend_at_least(self, channel, rng, num)
+    }
+
+    fn challenge<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        _rng: &mut RNG,
+    ) -> Result<Block, Error> {
+        channel.flush()?;
+        self.seq.recv(channel, Op::Challenge)?;
+        Ok(channel.read_block()?)
+    }
+
+    fn open<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        xs: &[MacProver<FE>],
+    ) -> Result<Vec<FE::PrimeField>, Error> {
+        FComProver::open(self, channel, xs)?;
+        Ok(xs.iter().map(|x| x.0).collect())
+    }
+
+    fn open_and_check<C: AbstractChannel, P: FnMut(usize, FE::PrimeField) -> bool>(
+        &mut self,
+        channel: &mut C,
+        xs: &[MacProver<FE>],
+        _pred: P,
+    ) -> Result<Vec<FE::PrimeField>, Error> {
+        <Self as FComParty<FE>>::open(self, channel, xs)
+    }
+
+    fn check_zero<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        _rng: &mut RNG,
+        xs: &[MacProver<FE>],
+    ) -> Result<(), Error> {
+        FComProver::check_zero(self, channel, xs)
+    }
+
+    fn quicksilver_check_start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        _rng: &mut RNG,
+    ) -> Result<QuicksilverCheckProver<FE>, Error> {
+        FComProver::quicksilver_check_start(self, channel)
+    }
+
+    #[inline]
+    fn quicksilver_check_push(
+        check: &mut QuicksilverCheckProver<FE>,
+        x: MacProver<FE>,
+        y: MacProver<FE>,
+        z: MacProver<FE>,
+    ) {
+        check.push_triple(x, y, z)
+    }
+
+    fn quicksilver_check_finish<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        check: QuicksilverCheckProver<FE>,
+    ) -> Result<(), Error> {
+        check.finish(channel, rng, self)
+    }
+
+    fn wolverine_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        _rng: &mut RNG,
+        triples: &[(MacProver<FE>, MacProver<FE>, MacProver<FE>)],
+        aux: &[(MacProver<FE>, MacProver<FE>, MacProver<FE>)],
+    ) -> Result<(), Error> {
+        FComProver::wolverine_check_multiply(self, channel, triples, aux)
+    }
+}
+
+impl<FE: FiniteField, B: BaseOt> FComParty<FE> for FComVerifier<FE, B> {
+    type Mac = MacVerifier<FE>;
+    type QuicksilverCheck = QuicksilverCheckVerifier<FE>;
+    const IS_PROVER: bool = false;
+
+    #[inline]
+    fn value(_x: &MacVerifier<FE>) -> Option<FE::PrimeField> {
+        None
+    }
+
+    fn random<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<MacVerifier<FE>, Error> {
+        FComVerifier::random(self, channel, rng)
+    }
+
+    fn input_into<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        _xs: &[FE::PrimeField],
+        out: &mut Vec<MacVerifier<FE>>,
+    ) -> Result<(), Error> {
+        FComVerifier::input_into(self, channel, rng, num, out)
+    }
+
+    #[inline]
+    fn add(&self, a: MacVerifier<FE>, b: MacVerifier<FE>) -> MacVerifier<FE> {
+        FComVerifier::add(self, a, b)
+    }
+
+    #[inline]
+    fn sub(&self, a: MacVerifier<FE>, b: MacVerifier<FE>) -> MacVerifier<FE> {
+        FComVerifier::sub(self, a, b)
+    }
+
+    #[inline]
+    fn neg(&self, a: MacVerifier<FE>) -> MacVerifier<FE> {
+        FComVerifier::neg(self, a)
+    }
+
+    #[inline]
+    fn affine_add_cst(&self, cst: FE::PrimeField, x: MacVerifier<FE>) -> MacVerifier<FE> {
+        FComVerifier::affine_add_cst(self, cst, x)
+    }
+
+    #[inline]
+    fn affine_mult_cst(&self, cst: FE::PrimeField, x: MacVerifier<FE>) -> MacVerifier<FE> {
+        FComVerifier::affine_mult_cst(self, cst, x)
+    }
+
+    fn fma_cst_slice(
+        &self,
+        cst: FE::PrimeField,
+        xs: &[MacVerifier<FE>],
+        acc: &mut [MacVerifier<FE>],
+    ) {
+        FComVerifier::fma_cst_slice(self, cst, xs, acc)
+    }
+
+    fn available(&self) -> usize {
+        FComVerifier::available(self)
+    }
+
+    fn refill_if_low<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<bool, Error> {
+        FComVerifier::refill_if_low(self, channel, rng)
+    }
+
+    fn extend_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+    ) -> Result<usize, Error> {
+        FComVerifier::extend_at_least(self, channel, rng, num)
+    }
+
+    fn challenge<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Block, Error> {
+        let seed = rng.gen::<Block>();
+        self.seq.send(channel, Op::Challenge)?;
+        channel.write_block(&seed)?;
+        channel.flush()?;
+        Ok(seed)
+    }
+
+    fn open<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        xs: &[MacVerifier<FE>],
+    ) -> Result<Vec<FE::PrimeField>, Error> {
+        let mut out = Vec::with_capacity(xs.len());
+        FComVerifier::open(self, channel, xs, &mut out)?;
+        Ok(out)
+    }
+
+    fn open_and_check<C: AbstractChannel, P: FnMut(usize, FE::PrimeField) -> bool>(
+        &mut self,
+        channel: &mut C,
+        xs: &[MacVerifier<FE>],
+        pred: P,
+    ) -> Result<Vec<FE::PrimeField>, Error> {
+        FComVerifier::open_and_check(self, channel, xs, pred)
+    }
+
+    fn check_zero<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        xs: &[MacVerifier<FE>],
+    ) -> Result<(), Error> {
+        FComVerifier::check_zero(self, channel, rng, xs)
+    }
+
+    fn quicksilver_check_start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<QuicksilverCheckVerifier<FE>, Error> {
+        FComVerifier::quicksilver_check_start(self, channel, rng)
+    }
+
+    #[inline]
+    fn quicksilver_check_push(
+        check: &mut QuicksilverCheckVerifier<FE>,
+        x: MacVerifier<FE>,
+        y: MacVerifier<FE>,
+        z: MacVerifier<FE>,
+    ) {
+        check.push_triple(x, y, z)
+    }
+
+    fn quicksilver_check_finish<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        check: QuicksilverCheckVerifier<FE>,
+    ) -> Result<(), Error> {
+        check.finish(channel, rng, self)
+    }
+
+    fn wolverine_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        triples: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
+        aux: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
+    ) -> Result<(), Error> {
+        FComVerifier::wolverine_check_multiply(self, channel, rng, triples, aux)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::strict_channel::StrictChannel;
+    use super::{
+        f2_to_fe, num_reauth_checks, DabitPool, DabitProver, DabitVerifier, FComProver,
+        FComVerifier, MacProver, MacVerifier, VolePoolConfig, VolePoolStats,
+    };
+    use crate::errors::Error;
+    use crate::svole::wykw::{
+        CancellationToken, ExtendHooks, ExtendPhase, UnsafeInsecureTestOt, LPN_EXTEND_SMALL,
+        LPN_SETUP_SMALL,
+    };
+    use rand::SeedableRng;
+    use scuttlebutt::{
+        field::{F40b, F61p, FiniteField},
+        local_pair,
+        ring::FiniteRing,
+        AbstractChannel, AesRng,
+    };
+
+    fn test_fcom_random<FE: FiniteField>() -> () {
+        let count = 100;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut v = Vec::with_capacity(count);
+            for _ in 0..count {
+                v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            let _ = fcom.open(&mut channel, &v).unwrap();
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut v = Vec::with_capacity(count);
+        for _ in 0..count {
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &v, &mut r).unwrap();
+
+        let resprover = handle.join().unwrap();
+
+        for i in 0..count {
+            assert_eq!(r[i], resprover[i].0);
+        }
+    }
+
+    // Draw a few random macs one at a time, then a batch needing
+    // extensions, and open them.
+    fn test_fcom_random_batch<FE: FiniteField>() {
+        let (single, batch) = (5, 20_000);
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            let mut v = Vec::with_capacity(single + batch);
+            for _ in 0..single {
+                v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            v.extend(fcom.random_batch(&mut channel, &mut rng, batch).unwrap());
+            fcom.open(&mut channel, &v).unwrap();
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut v = Vec::with_capacity(single + batch);
+        for _ in 0..single {
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        v.extend(fcom.random_batch(&mut channel, &mut rng, batch).unwrap());
+        assert!(fcom
+            .random_batch(&mut channel, &mut rng, 0)
+            .unwrap()
+            .is_empty());
+
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &v, &mut r).unwrap();
+        let resprover = handle.join().unwrap();
+        assert_eq!(r.len(), single + batch);
+        for i in 0..single + batch {
+            assert_eq!(r[i], resprover[i].0);
+        }
+    }
+
+    fn test_fcom_affine() -> () {
+        let count = 200;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut v = Vec::new();
+            for _ in 0..count {
+                let MacProver(x, x_mac) = fcom.random(&mut channel, &mut rng).unwrap();
+                let cst = F61p::random(&mut rng);
+                channel.write_serializable::<F61p>(&cst).unwrap();
+                channel.flush().unwrap();
+                let m = fcom.affine_mult_cst(cst, MacProver(x, x_mac));
+                v.push(m);
+                let a = fcom.affine_add_cst(cst, MacProver(x, x_mac));
+                v.push(a);
+            }
+            let _ = fcom.open(&mut channel, &v).unwrap();
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+
+        let mut v = Vec::new();
+        for _ in 0..count {
+            let x_mac = fcom.random(&mut channel, &mut rng).unwrap();
+            let cst = channel.read_serializable().unwrap();
+            let m_mac = fcom.affine_mult_cst(cst, x_mac);
+            v.push(m_mac);
+            let a_mac = fcom.affine_add_cst(cst, x_mac);
+            v.push(a_mac);
+        }
+
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &v, &mut r).unwrap();
+
+        let batch_prover = handle.join().unwrap();
+
+        for i in 0..count {
+            assert_eq!(r[i], batch_prover[i].0);
+        }
+        ()
+    }
+
+    fn test_fcom_affine_slice<FE: FiniteField>() {
+        let count = 200;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut xs = Vec::with_capacity(count);
+            for _ in 0..count {
+                xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            let cst = channel.read_serializable::<FE::PrimeField>().unwrap();
+
+            let mut v = Vec::with_capacity(3 * count);
+            fcom.affine_mult_cst_slice(cst, &xs, &mut v);
+            fcom.affine_add_cst_slice(cst, &xs, &mut v);
+            let mut acc = xs.clone();
+            fcom.fma_cst_slice(cst, &xs, &mut acc);
+            for i in 0..count {
+                assert_eq!(v[i], fcom.affine_mult_cst(cst, xs[i]));
+                assert_eq!(v[count + i], fcom.affine_add_cst(cst, xs[i]));
+                assert_eq!(acc[i].0, xs[i].0 + cst * xs[i].0);
+            }
+            v.extend(acc);
+            fcom.open(&mut channel, &v).unwrap();
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+
+        let mut xs = Vec::with_capacity(count);
+        for _ in 0..count {
+            xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        let cst = FE::PrimeField::random(&mut rng);
+        channel.write_serializable::<FE::PrimeField>(&cst).unwrap();
+        channel.flush().unwrap();
+
+        let mut v = Vec::with_capacity(3 * count);
+        fcom.affine_mult_cst_slice(cst, &xs, &mut v);
+        fcom.affine_add_cst_slice(cst, &xs, &mut v);
+        let mut acc = xs.clone();
+        fcom.fma_cst_slice(cst, &xs, &mut acc);
+        v.extend(acc);
+
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &v, &mut r).unwrap();
+
+        let batch_prover = handle.join().unwrap();
+        for i in 0..3 * count {
+            assert_eq!(r[i], batch_prover[i].0);
+        }
+    }
+
+    fn test_fcom_refill<FE: FiniteField>() {
+        let count = 100;
+        let high = VolePoolConfig {
+            low_watermark: usize::MAX,
+            refill_size: 1,
+        };
+        let low = VolePoolConfig {
+            low_watermark: 1,
+            refill_size: 1,
+        };
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut v = Vec::with_capacity(2 * count);
+            for _ in 0..count {
+                v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            fcom.set_pool_config(high);
+            assert!(fcom.refill_if_low(&mut channel, &mut rng).unwrap());
+            fcom.set_pool_config(low);
+            assert!(!fcom.refill_if_low(&mut channel, &mut rng).unwrap());
+            for _ in 0..count {
+                v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            fcom.open(&mut channel, &v).unwrap();
+            assert_eq!(
+                fcom.pool_stats(),
+                VolePoolStats {
+                    extensions: 2,
+                    refills: 1
+                }
+            );
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+
+        let mut v = Vec::with_capacity(2 * count);
+        for _ in 0..count {
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        fcom.set_pool_config(high);
+        assert!(fcom.refill_if_low(&mut channel, &mut rng).unwrap());
+        fcom.set_pool_config(low);
+        assert!(!fcom.refill_if_low(&mut channel, &mut rng).unwrap());
+        for _ in 0..count {
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &v, &mut r).unwrap();
+        assert_eq!(
+            fcom.pool_stats(),
+            VolePoolStats {
+                extensions: 2,
+                refills: 1
+            }
+        );
+
+        let batch_prover = handle.join().unwrap();
+        for i in 0..2 * count {
+            assert_eq!(r[i], batch_prover[i].0);
+        }
+    }
+
+    fn test_fcom_export_import<FE: FiniteField>() {
+        let count = 100;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            let mut fcom_dup = fcom.duplicate(&mut channel, &mut rng).unwrap();
+            let fcom_other =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            assert!(fcom.shares_key_with(&fcom_dup));
+            assert!(!fcom.shares_key_with(&fcom_other));
+
+            let mut xs = Vec::with_capacity(count);
+            for _ in 0..count {
+                xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            assert!(fcom_other.import(fcom.export(&xs)).is_err());
+            let ys = fcom_dup.import(fcom.export(&xs)).unwrap();
+            fcom_dup.open(&mut channel, &ys).unwrap();
+            xs
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut fcom_dup = fcom.duplicate(&mut channel, &mut rng).unwrap();
+        let fcom_other =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        assert!(fcom.shares_key_with(&fcom_dup));
+        assert!(!fcom.shares_key_with(&fcom_other));
+
+        let mut xs: Vec<MacVerifier<FE>> = Vec::with_capacity(count);
+        for _ in 0..count {
+            xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        assert!(fcom_other.import(fcom.export(&xs)).is_err());
+        let ys = fcom_dup.import(fcom.export(&xs)).unwrap();
+        let mut r = Vec::new();
+        fcom_dup.open(&mut channel, &ys, &mut r).unwrap();
+
+        let xs_prover = handle.join().unwrap();
+        for i in 0..count {
+            assert_eq!(r[i], xs_prover[i].0);
+        }
+    }
+
+    fn test_fcom_reauthenticate<FE: FiniteField>() {
+        let count = 100;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut src =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            let mut dst =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut xs = Vec::with_capacity(count);
+            for _ in 0..count {
+                xs.push(src.random(&mut channel, &mut rng).unwrap());
+            }
+            let ys = FComProver::reauthenticate(&mut channel, &mut rng, &mut src, &mut dst, &xs)
+                .unwrap();
+            dst.open(&mut channel, &ys).unwrap();
+            xs
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut src =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut dst =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        assert!(!src.shares_key_with(&dst));
+
+        let mut xs = Vec::with_capacity(count);
+        for _ in 0..count {
+            xs.push(src.random(&mut channel, &mut rng).unwrap());
+        }
+        let ys =
+            FComVerifier::reauthenticate(&mut channel, &mut rng, &mut src, &mut dst, &xs).unwrap();
+        let mut r = Vec::new();
+        dst.open(&mut channel, &ys, &mut r).unwrap();
+
+        let xs_prover = handle.join().unwrap();
+        for i in 0..count {
+            assert_eq!(r[i], xs_prover[i].0);
+        }
+    }
+
+    // The prover changes the first value in transit by inputting a wrong
+    // mask into the destination instance.
+    fn test_fcom_reauthenticate_malicious<FE: FiniteField>() {
+        let count = 10;
+        let num_checks = num_reauth_checks::<FE>();
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = sender;
+            let mut src =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            let mut dst =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut xs = Vec::with_capacity(count);
+            for _ in 0..count {
+                xs.push(src.random(&mut channel, &mut rng).unwrap());
+            }
+
+            let mut masks = Vec::with_capacity(count + num_checks);
+            for _ in 0..count + num_checks {
+                masks.push(src.random(&mut channel, &mut rng).unwrap());
+            }
+            let mut values: Vec<FE::PrimeField> = masks.iter().map(|m| m.0).collect();
+            values[0] -= FE::PrimeField::ONE;
+            let macs = dst.input(&mut channel, &mut rng, &values).unwrap();
+            channel.flush().unwrap();
 
-        let u = channel.read_serializable::<FE>()?;
-        let v = channel.read_serializable::<FE>()?;
+            let seed = channel.read_block().unwrap();
+            let mut chi_rng = AesRng::from_seed(seed);
+            let mut to_open_src = Vec::new();
+            let mut to_open_dst = Vec::new();
+            for k in 0..num_checks {
+                let mut c_src = masks[count + k];
+                let mut c_dst = MacProver(values[count + k], macs[count + k]);
+                for i in 0..count {
+                    let chi = FE::PrimeField::random(&mut chi_rng);
+                    c_src = src.add(c_src, src.affine_mult_cst(chi, masks[i]));
+                    let mask_dst = MacProver(values[i], macs[i]);
+                    c_dst = dst.add(c_dst, dst.affine_mult_cst(chi, mask_dst));
+                }
+                to_open_src.push(c_src);
+                to_open_dst.push(c_dst);
+            }
+            for i in 0..count {
+                to_open_src.push(src.sub(xs[i], masks[i]));
+            }
+            let _ = src.open(&mut channel, &to_open_src);
+            let _ = dst.open(&mut channel, &to_open_dst);
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = receiver;
+        let mut src =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut dst =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
 
-        let b_plus = sum_b + mask_mac;
-        if b_plus == (u + (-self.delta) * v) {
-            // - because of delta
-            Ok(())
-        } else {
-            Err(Error::Other("checkMultiply fails".to_string()))
+        let mut xs = Vec::with_capacity(count);
+        for _ in 0..count {
+            xs.push(src.random(&mut channel, &mut rng).unwrap());
         }
+        let res = FComVerifier::reauthenticate(&mut channel, &mut rng, &mut src, &mut dst, &xs);
+        assert!(res.is_err());
+        handle.join().unwrap();
     }
 
-    /// Wolverine multiplication check
-    pub fn wolverine_check_multiply<C: AbstractChannel, RNG: CryptoRng + Rng>(
-        &mut self,
-        channel: &mut C,
-        rng: &mut RNG,
-        triples: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
-        aux: &[(MacVerifier<FE>, MacVerifier<FE>, MacVerifier<FE>)],
-    ) -> Result<(), Error> {
-        let n = triples.len();
-        let mut to_open = Vec::with_capacity(2 * n);
+    fn test_fcom_open_and_check<FE: FiniteField>() {
+        let count = 10;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
 
-        for i in 0..n {
-            let (a, b, _c) = triples[i];
-            let (x, y, _z) = aux[i];
+            let mut v = Vec::with_capacity(count);
+            for _ in 0..count {
+                v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            fcom.open(&mut channel, &v).unwrap();
+            fcom.open(&mut channel, &v).unwrap();
+            v
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
 
-            let minus_x = self.affine_mult_cst(-FE::PrimeField::ONE, x);
-            let minus_y = self.affine_mult_cst(-FE::PrimeField::ONE, y);
-            let d = self.add(a, minus_x);
-            let e = self.add(b, minus_y);
+        let mut v = Vec::with_capacity(count);
+        for _ in 0..count {
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        let r = fcom.open_and_check(&mut channel, &v, |_, _| true).unwrap();
+        match fcom.open_and_check(&mut channel, &v, |i, x| i != 3 || x != r[3]) {
+            Err(Error::OpenCheckFailed { index, .. }) => assert_eq!(index, 3),
+            _ => panic!("expected the check to fail at index 3"),
+        }
 
-            to_open.push(d);
-            to_open.push(e);
+        let batch_prover = handle.join().unwrap();
+        for i in 0..count {
+            assert_eq!(r[i], batch_prover[i].0);
         }
-        let mut opened = Vec::with_capacity(2 * n);
-        self.open(channel, &to_open, &mut opened)?;
+    }
 
-        let mut to_check = Vec::with_capacity(n);
-        for i in 0..n {
-            let (_a, _b, c) = triples[i];
-            let (x, y, z) = aux[i];
-            let d = opened[2 * i];
-            let e = opened[2 * i + 1];
+    fn test_fcom_assert_equal<FE: FiniteField>() {
+        let count = 50;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
 
-            let d_e = d * e;
+            let mut xs = Vec::with_capacity(count);
+            for _ in 0..count {
+                xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+            }
+            let mut values: Vec<FE::PrimeField> = xs.iter().map(|x| x.0).collect();
+            let macs = fcom.input(&mut channel, &mut rng, &values).unwrap();
+            let ys: Vec<MacProver<FE>> = values
+                .iter()
+                .zip(macs)
+                .map(|(v, m)| MacProver(*v, m))
+                .collect();
+            channel.flush().unwrap();
+            fcom.assert_equal(&mut channel, &xs, &ys).unwrap();
+
+            values[count / 2] += FE::PrimeField::ONE;
+            let macs = fcom.input(&mut channel, &mut rng, &values).unwrap();
+            let zs: Vec<MacProver<FE>> = values
+                .iter()
+                .zip(macs)
+                .map(|(v, m)| MacProver(*v, m))
+                .collect();
+            channel.flush().unwrap();
+            assert!(fcom.assert_equal(&mut channel, &xs, &zs).is_err());
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
 
-            let e_x = self.affine_mult_cst(e, x);
-            let d_y = self.affine_mult_cst(d, y);
+        let mut xs = Vec::with_capacity(count);
+        for _ in 0..count {
+            xs.push(fcom.random(&mut channel, &mut rng).unwrap());
+        }
+        let ys = fcom.input(&mut channel, &mut rng, count).unwrap();
+        fcom.assert_equal(&mut channel, &mut rng, &xs, &ys).unwrap();
 
-            let mut w: MacVerifier<FE> = z;
-            w = self.sub(w, c);
-            w = self.add(w, e_x);
-            w = self.add(w, d_y);
-            w = self.affine_add_cst(d_e, w);
+        let zs = fcom.input(&mut channel, &mut rng, count).unwrap();
+        assert!(fcom.assert_equal(&mut channel, &mut rng, &xs, &zs).is_err());
+        assert!(fcom
+            .assert_equal(&mut channel, &mut rng, &xs, &zs[1..])
+            .is_err());
 
-            to_check.push(w);
-        }
-        self.check_zero(channel, rng, &to_check)?;
-        Ok(())
+        handle.join().unwrap();
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{FComProver, FComVerifier, MacProver};
-    use crate::svole::wykw::{LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
-    use rand::SeedableRng;
-    use scuttlebutt::{
-        field::{F40b, F61p, FiniteField},
-        ring::FiniteRing,
-        AbstractChannel, AesRng, Channel,
-    };
-    use std::{
-        io::{BufReader, BufWriter},
-    };
-    use uds_windows::UnixStream;
 
-    fn test_fcom_random<FE: FiniteField>() -> () {
-        let count = 100;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+    fn test_fcom_extend<FE: FiniteField>() {
+        let count = 10;
+        let (sender, receiver) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::from_seed(Default::default());
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
+            let mut channel = StrictChannel::new(sender);
             let mut fcom =
                 FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                     .unwrap();
+            assert_eq!(fcom.available(), 0);
+            let num = fcom.extend(&mut channel, &mut rng).unwrap();
+            assert_eq!(fcom.available(), num);
 
             let mut v = Vec::with_capacity(count);
             for _ in 0..count {
                 v.push(fcom.random(&mut channel, &mut rng).unwrap());
             }
-            let _ = fcom.open(&mut channel, &v).unwrap();
+            assert_eq!(fcom.available(), num - count);
+            assert_eq!(fcom.pool_stats().extensions, 1);
+            fcom.open(&mut channel, &v).unwrap();
             v
         });
         let mut rng = AesRng::from_seed(Default::default());
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
+        let mut channel = StrictChannel::new(receiver);
         let mut fcom =
             FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                 .unwrap();
+        assert_eq!(fcom.available(), 0);
+        let num = fcom.extend(&mut channel, &mut rng).unwrap();
+        assert_eq!(fcom.available(), num);
+
         let mut v = Vec::with_capacity(count);
         for _ in 0..count {
             v.push(fcom.random(&mut channel, &mut rng).unwrap());
         }
-
+        assert_eq!(fcom.available(), num - count);
+        assert_eq!(fcom.pool_stats().extensions, 1);
         let mut r = Vec::new();
         fcom.open(&mut channel, &v, &mut r).unwrap();
 
-        let resprover = handle.join().unwrap();
-
+        let batch_prover = handle.join().unwrap();
         for i in 0..count {
-            assert_eq!(r[i], resprover[i].0);
+            assert_eq!(r[i], batch_prover[i].0);
         }
     }
 
-    fn test_fcom_affine() -> () {
-        let count = 200;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+    // Cancels the second extension of the prover before it starts when `at`
+    // is `None`, or in the middle of the phase `at`.
+    fn test_fcom_cancel_extend<FE: FiniteField>(at: Option<ExtendPhase>) {
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom = FComProver::<FE, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let num = fcom.extend(&mut channel, &mut rng).unwrap();
+
+            let token = CancellationToken::new();
+            let hooks = ExtendHooks::new().with_cancellation(token.clone());
+            let hooks = match at {
+                None => {
+                    token.cancel();
+                    hooks
+                }
+                Some(at) => hooks.with_observer(move |phase, fraction| {
+                    if phase == at && fraction >= 0.5 {
+                        token.cancel();
+                    }
+                }),
+            };
+            fcom.set_extend_hooks(hooks);
+            let result = fcom.extend(&mut channel, &mut rng);
+            assert!(matches!(result, Err(Error::Cancelled)));
+            fcom.set_extend_hooks(ExtendHooks::new());
+
+            if at.is_some() {
+                // poisoned
+                assert_eq!(fcom.available(), 0);
+                let result = fcom.extend(&mut channel, &mut rng);
+                assert!(matches!(result, Err(Error::Poisoned)));
+                let result = fcom.random(&mut channel, &mut rng);
+                assert!(matches!(result, Err(Error::Poisoned)));
+                return None;
+            }
+            // rolled back
+            assert_eq!(fcom.available(), num);
+            fcom.extend(&mut channel, &mut rng).unwrap();
+            let x = fcom.random(&mut channel, &mut rng).unwrap();
+            fcom.open(&mut channel, &[x]).unwrap();
+            Some(x)
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom = FComVerifier::<FE, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        fcom.extend(&mut channel, &mut rng).unwrap();
+        if at.is_some() {
+            // the prover stops after its cancelled extension, which may
+            // complete on this side
+            let _ = fcom.extend(&mut channel, &mut rng);
+            assert!(handle.join().unwrap().is_none());
+            return;
+        }
+        fcom.extend(&mut channel, &mut rng).unwrap();
+        let x = fcom.random(&mut channel, &mut rng).unwrap();
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &[x], &mut r).unwrap();
+        assert_eq!(r[0], handle.join().unwrap().unwrap().0);
+    }
+
+    #[cfg(feature = "threads")]
+    fn test_fcom_pipelined<FE: FiniteField>() {
+        let num_extensions = 3;
+        let (sender, receiver) = local_pair();
+        let (sender_ext, receiver_ext) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::from_seed(Default::default());
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
+            let mut channel = StrictChannel::new(sender);
             let mut fcom =
-                FComProver::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                     .unwrap();
-
+            let ext_channel = sender_ext;
+            fcom.start_pipeline(&mut channel, &mut rng, ext_channel, 2)
+                .unwrap();
             let mut v = Vec::new();
-            for _ in 0..count {
-                let MacProver(x, x_mac) = fcom.random(&mut channel, &mut rng).unwrap();
-                let cst = F61p::random(&mut rng);
-                channel.write_serializable::<F61p>(&cst).unwrap();
-                channel.flush().unwrap();
-                let m = fcom.affine_mult_cst(cst, MacProver(x, x_mac));
-                v.push(m);
-                let a = fcom.affine_add_cst(cst, MacProver(x, x_mac));
-                v.push(a);
+            for _ in 0..num_extensions {
+                fcom.extend(&mut channel, &mut rng).unwrap();
+                while fcom.available() > 0 {
+                    let x = fcom.random(&mut channel, &mut rng).unwrap();
+                    if fcom.available() % 1_000 == 0 {
+                        v.push(x);
+                    }
+                }
             }
-            let _ = fcom.open(&mut channel, &v).unwrap();
+            fcom.stop_pipeline();
+            v.push(fcom.random(&mut channel, &mut rng).unwrap());
+            assert_eq!(fcom.pool_stats().extensions, num_extensions + 1);
+            fcom.open(&mut channel, &v).unwrap();
             v
         });
         let mut rng = AesRng::from_seed(Default::default());
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
+        let mut channel = StrictChannel::new(receiver);
         let mut fcom =
-            FComVerifier::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                 .unwrap();
-
+        let ext_channel = receiver_ext;
+        fcom.start_pipeline(&mut channel, &mut rng, ext_channel, 2)
+            .unwrap();
         let mut v = Vec::new();
-        for _ in 0..count {
-            let x_mac = fcom.random(&mut channel, &mut rng).unwrap();
-            let cst = channel.read_serializable().unwrap();
-            let m_mac = fcom.affine_mult_cst(cst, x_mac);
-            v.push(m_mac);
-            let a_mac = fcom.affine_add_cst(cst, x_mac);
-            v.push(a_mac);
+        for _ in 0..num_extensions {
+            fcom.extend(&mut channel, &mut rng).unwrap();
+            while fcom.available() > 0 {
+                let x = fcom.random(&mut channel, &mut rng).unwrap();
+                if fcom.available() % 1_000 == 0 {
+                    v.push(x);
+                }
+            }
         }
-
+        fcom.stop_pipeline();
+        v.push(fcom.random(&mut channel, &mut rng).unwrap());
+        assert_eq!(fcom.pool_stats().extensions, num_extensions + 1);
         let mut r = Vec::new();
         fcom.open(&mut channel, &v, &mut r).unwrap();
 
         let batch_prover = handle.join().unwrap();
-
-        for i in 0..count {
+        assert_eq!(r.len(), batch_prover.len());
+        for i in 0..r.len() {
             assert_eq!(r[i], batch_prover[i].0);
         }
-        ()
     }
 
     fn test_fcom_multiplication<FE: FiniteField>() -> () {
         let count = 50;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+        let (sender, receiver) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::from_seed(Default::default());
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
+            let mut channel = StrictChannel::new(sender);
             let mut fcom =
                 FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                     .unwrap();
@@ -785,9 +3224,7 @@ mod tests {
             (v, b)
         });
         let mut rng = AesRng::from_seed(Default::default());
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
+        let mut channel = StrictChannel::new(receiver);
         let mut fcom =
             FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                 .unwrap();
@@ -809,12 +3246,10 @@ mod tests {
 
     fn test_fcom_wolverine<FE: FiniteField>() -> () {
         let count = 50;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+        let (sender, receiver) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::from_seed(Default::default());
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
+            let mut channel = StrictChannel::new(sender);
             let mut fcom =
                 FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                     .unwrap();
@@ -851,9 +3286,7 @@ mod tests {
             (v, b)
         });
         let mut rng = AesRng::from_seed(Default::default());
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
+        let mut channel = StrictChannel::new(receiver);
         let mut fcom =
             FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                 .unwrap();
@@ -882,12 +3315,10 @@ mod tests {
 
     fn test_fcom_check_zero<FE: FiniteField>() -> () {
         let count = 50;
-        let (sender, receiver) = UnixStream::pair().unwrap();
+        let (sender, receiver) = local_pair();
         let handle = std::thread::spawn(move || {
             let mut rng = AesRng::from_seed(Default::default());
-            let reader = BufReader::new(sender.try_clone().unwrap());
-            let writer = BufWriter::new(sender);
-            let mut channel = Channel::new(reader, writer);
+            let mut channel = StrictChannel::new(sender);
             let mut fcom =
                 FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                     .unwrap();
@@ -923,9 +3354,7 @@ mod tests {
             }
         });
         let mut rng = AesRng::from_seed(Default::default());
-        let reader = BufReader::new(receiver.try_clone().unwrap());
-        let writer = BufWriter::new(receiver);
-        let mut channel = Channel::new(reader, writer);
+        let mut channel = StrictChannel::new(receiver);
         let mut fcom =
             FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
                 .unwrap();
@@ -959,16 +3388,124 @@ mod tests {
         handle.join().unwrap();
     }
 
+    fn test_fcom_lift_bit<FE: FiniteField<PrimeField = FE>>() {
+        let count = 100;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::from_seed(Default::default());
+            let mut channel = StrictChannel::new(sender);
+            let mut fcom_f2 =
+                FComProver::<F40b>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+            let mut fcom =
+                FComProver::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                    .unwrap();
+
+            let mut dabits = Vec::with_capacity(count);
+            let mut bits = Vec::with_capacity(count);
+            for _ in 0..count {
+                let MacProver(rb, rb_mac) = fcom_f2.random(&mut channel, &mut rng).unwrap();
+                let rm = f2_to_fe(rb);
+                let rm_mac = fcom.input(&mut channel, &mut rng, &[rm]).unwrap()[0];
+                dabits.push(DabitProver {
+                    bit: MacProver(rb, rb_mac),
+                    value: MacProver(rm, rm_mac),
+                });
+                bits.push(fcom_f2.random(&mut channel, &mut rng).unwrap());
+            }
+            channel.flush().unwrap();
+
+            let mut pool = DabitPool::new(dabits.split_off(1));
+            let mut lifted = vec![fcom
+                .lift_bit(&mut channel, &mut fcom_f2, &dabits[0], bits[0])
+                .unwrap()];
+            fcom.lift_bits_from_pool(
+                &mut channel,
+                &mut fcom_f2,
+                &mut pool,
+                &bits[1..],
+                &mut lifted,
+            )
+            .unwrap();
+            assert!(pool.is_empty());
+
+            fcom.open(&mut channel, &lifted).unwrap();
+            for i in 0..count {
+                assert_eq!(f2_to_fe::<FE>(bits[i].0), lifted[i].0);
+            }
+            bits
+        });
+        let mut rng = AesRng::from_seed(Default::default());
+        let mut channel = StrictChannel::new(receiver);
+        let mut fcom_f2 =
+            FComVerifier::<F40b>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+        let mut fcom =
+            FComVerifier::<FE>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+
+        let mut dabits = Vec::with_capacity(count);
+        let mut bits = Vec::with_capacity(count);
+        for _ in 0..count {
+            let rb_mac = fcom_f2.random(&mut channel, &mut rng).unwrap();
+            let rm_mac = fcom.input(&mut channel, &mut rng, 1).unwrap()[0];
+            dabits.push(DabitVerifier {
+                bit: rb_mac,
+                value: rm_mac,
+            });
+            bits.push(fcom_f2.random(&mut channel, &mut rng).unwrap());
+        }
+
+        let mut pool = DabitPool::new(dabits.split_off(1));
+        let mut lifted = vec![fcom
+            .lift_bit(&mut channel, &mut fcom_f2, &dabits[0], bits[0])
+            .unwrap()];
+        fcom.lift_bits_from_pool(
+            &mut channel,
+            &mut fcom_f2,
+            &mut pool,
+            &bits[1..],
+            &mut lifted,
+        )
+        .unwrap();
+        assert!(pool.is_empty());
+        assert!(pool.draw(1).is_err());
+
+        let mut r = Vec::new();
+        fcom.open(&mut channel, &lifted, &mut r).unwrap();
+
+        let bits_prover = handle.join().unwrap();
+        for i in 0..count {
+            assert_eq!(f2_to_fe::<FE>(bits_prover[i].0), r[i]);
+        }
+        assert!(r.iter().all(|x| *x == FE::ZERO || *x == FE::ONE));
+    }
+
     #[test]
     fn test_fcom_random_f61p() {
         let _t = test_fcom_random::<F61p>();
     }
 
+    #[test]
+    fn test_fcom_random_batch_f61p() {
+        test_fcom_random_batch::<F61p>();
+    }
+
     #[test]
     fn test_fcom_affine_f61p() {
         let _t = test_fcom_affine();
     }
 
+    #[test]
+    fn test_fcom_affine_slice_f61p() {
+        test_fcom_affine_slice::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_affine_slice_gf40() {
+        test_fcom_affine_slice::<F40b>();
+    }
+
     #[test]
     fn test_fcom_multiplication_check_f61p() {
         let _t = test_fcom_multiplication::<F61p>();
@@ -988,4 +3525,80 @@ mod tests {
     fn test_fcom_check_zero_f61p() {
         let _t = test_fcom_check_zero::<F61p>();
     }
+
+    #[test]
+    fn test_fcom_lift_bit_f61p() {
+        test_fcom_lift_bit::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_refill_f61p() {
+        test_fcom_refill::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_export_import_f61p() {
+        test_fcom_export_import::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_reauthenticate_f61p() {
+        test_fcom_reauthenticate::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_reauthenticate_gf40() {
+        test_fcom_reauthenticate::<F40b>();
+    }
+
+    #[test]
+    fn test_fcom_reauthenticate_malicious_f61p() {
+        test_fcom_reauthenticate_malicious::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_reauthenticate_malicious_gf40() {
+        test_fcom_reauthenticate_malicious::<F40b>();
+    }
+
+    #[test]
+    fn test_fcom_open_and_check_f61p() {
+        test_fcom_open_and_check::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_assert_equal_f61p() {
+        test_fcom_assert_equal::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_assert_equal_gf40() {
+        test_fcom_assert_equal::<F40b>();
+    }
+
+    #[test]
+    fn test_fcom_extend_f61p() {
+        test_fcom_extend::<F61p>();
+    }
+
+    #[cfg(feature = "threads")]
+    #[test]
+    fn test_fcom_pipelined_f61p() {
+        test_fcom_pipelined::<F61p>();
+    }
+
+    #[test]
+    fn test_fcom_cancel_before_extend_f61p() {
+        test_fcom_cancel_extend::<F61p>(None);
+    }
+
+    #[test]
+    fn test_fcom_cancel_after_single_point_f61p() {
+        test_fcom_cancel_extend::<F61p>(Some(ExtendPhase::SinglePoint));
+    }
+
+    #[test]
+    fn test_fcom_cancel_during_lpn_f61p() {
+        test_fcom_cancel_extend::<F61p>(Some(ExtendPhase::Lpn));
+    }
 }
@@ -1,6 +1,22 @@
 //! This is a library implementing the field conversion using edabits
+#[cfg(feature = "tokio")]
+mod async_conv;
+mod checked;
+mod converter;
 mod edabits;
 mod homcom;
+mod mult_check;
+mod observer;
+mod serialization;
+mod spdz;
+#[cfg(test)]
+mod strict_channel;
+mod timer;
 
+pub use converter::*;
 pub use edabits::*;
 pub use homcom::*;
+pub use mult_check::*;
+pub use observer::*;
+pub use serialization::*;
+pub use spdz::*;
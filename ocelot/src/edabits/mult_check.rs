@@ -0,0 +1,168 @@
+//! Checks of the multiplications of the conversion, which can be supplied
+//! by other crates.
+
+use super::homcom::FComParty;
+use crate::errors::Error;
+use rand::{CryptoRng, Rng};
+use scuttlebutt::{field::FiniteField, AbstractChannel};
+use std::marker::PhantomData;
+
+/// Check of multiplication triples `(x, y, z)`, with `z = x * y`, committed
+/// with the F_com instance `P` over `FE`.
+///
+/// A check is started with `start` once all the triples to check are
+/// committed, the triples are then added with `push_triples`, and checked
+/// by `finalize`, which fails if one of them is not a multiplication
+/// triple. A checker may run several checks in a row. The prover and the
+/// verifier must use matching checkers.
+pub trait MultChecker<FE: FiniteField, P: FComParty<FE>> {
+    /// Start a check. This does nothing by default.
+    fn start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        _fcom: &mut P,
+        _channel: &mut C,
+        _rng: &mut RNG,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Add triples to the check.
+    fn push_triples(&mut self, triples: &[(P::Mac, P::Mac, P::Mac)]);
+
+    /// Check the triples added since the check started.
+    fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        fcom: &mut P,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<(), Error>;
+}
+
+/// The QuickSilver check: a single random linear combination of all the
+/// triples, accumulated as they are added.
+pub struct QuickSilverChecker<FE: FiniteField, P: FComParty<FE>> {
+    check: Option<P::QuicksilverCheck>,
+    phantom: PhantomData<FE>,
+}
+
+impl<FE: FiniteField, P: FComParty<FE>> QuickSilverChecker<FE, P> {
+    /// Return a new checker.
+    pub fn new() -> Self {
+        QuickSilverChecker {
+            check: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<FE: FiniteField, P: FComParty<FE>> Clone for QuickSilverChecker<FE, P>
+where
+    P::QuicksilverCheck: Clone,
+{
+    fn clone(&self) -> Self {
+        QuickSilverChecker {
+            check: self.check.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<FE: FiniteField, P: FComParty<FE>> Default for QuickSilverChecker<FE, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<FE: FiniteField, P: FComParty<FE>> MultChecker<FE, P> for QuickSilverChecker<FE, P> {
+    fn start<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        fcom: &mut P,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<(), Error> {
+        self.check = Some(fcom.quicksilver_check_start(channel, rng)?);
+        Ok(())
+    }
+
+    fn push_triples(&mut self, triples: &[(P::Mac, P::Mac, P::Mac)]) {
+        let check = self
+            .check
+            .as_mut()
+            .expect("the QuickSilver check is started before adding triples");
+        for (x, y, z) in triples.iter() {
+            P::quicksilver_check_push(check, *x, *y, *z);
+        }
+    }
+
+    fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        fcom: &mut P,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<(), Error> {
+        let check = self.check.take().ok_or_else(|| {
+            Error::Other("the QuickSilver check is finalized before it starts".to_string())
+        })?;
+        fcom.quicksilver_check_finish(channel, rng, check)
+    }
+}
+
+/// The Wolverine check: every triple is checked against one of the random
+/// triples `aux`, which must have been checked beforehand, e.g. by
+/// cut-and-choose. Each random triple is used once.
+///
+/// With buckets of random triples, every triple is checked against as many
+/// random triples, so that the check still holds when some of the random
+/// triples escaped the cut-and-choose.
+pub struct WolverineChecker<'a, M> {
+    aux: &'a [(M, M, M)],
+    bucket: usize,
+    triples: Vec<(M, M, M)>,
+}
+
+impl<'a, M> WolverineChecker<'a, M> {
+    /// Return a new checker using the random triples `aux`.
+    pub fn new(aux: &'a [(M, M, M)]) -> Self {
+        Self::with_bucket(aux, 1)
+    }
+
+    /// Return a new checker using the random triples `aux`, `bucket` of
+    /// them per triple checked.
+    pub fn with_bucket(aux: &'a [(M, M, M)], bucket: usize) -> Self {
+        WolverineChecker {
+            aux,
+            bucket,
+            triples: Vec::new(),
+        }
+    }
+}
+
+impl<'a, FE: FiniteField, P: FComParty<FE>> MultChecker<FE, P> for WolverineChecker<'a, P::Mac> {
+    fn push_triples(&mut self, triples: &[(P::Mac, P::Mac, P::Mac)]) {
+        self.triples.extend_from_slice(triples);
+    }
+
+    fn finalize<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        fcom: &mut P,
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<(), Error> {
+        let n = self.triples.len() * self.bucket;
+        if n > self.aux.len() {
+            return Err(Error::Other(format!(
+                "Wolverine check of {} triples with {} random triples",
+                n,
+                self.aux.len()
+            )));
+        }
+        let (aux, rest) = self.aux.split_at(n);
+        self.aux = rest;
+        // the copy `k` of the triples is checked against the random triples
+        // `k * len..(k + 1) * len`, all in a single check
+        let triples = self.triples.repeat(self.bucket);
+        let res = fcom.wolverine_check_multiply(channel, rng, &triples, aux);
+        self.triples.clear();
+        res
+    }
+}
@@ -0,0 +1,212 @@
+//! Observation of the phases of the conversion while it runs, e.g. to export
+//! live metrics of a long conversion.
+
+use super::edabits::ConvStats;
+use scuttlebutt::ScopeComm;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+// The label of a phase, behind an alias so that serde does not borrow it from
+// the input, which would only deserialize it from a `'static` one.
+type PhaseLabel = &'static str;
+
+/// Statistics of a finished phase of `conv`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseStats {
+    /// The phase, labelled with its `CONV_SCOPE_*` scope.
+    #[cfg_attr(feature = "serde", serde(with = "phase_label"))]
+    pub phase: PhaseLabel,
+    /// Number of values the phase worked on: VOLEs, edabits, dabits,
+    /// triples, or buckets depending on the phase.
+    pub size: usize,
+    /// Duration of the phase, with the `std-time` feature.
+    pub duration: Option<Duration>,
+    /// Communication of the phase on the channel given to `conv`, when the
+    /// channel keeps track of it.
+    pub comm: Option<ScopeComm>,
+    /// Number of flushes of the channel given to `conv` during the phase,
+    /// which bounds its number of rounds, when the channel keeps track of
+    /// its communication.
+    pub rounds: Option<usize>,
+}
+
+/// Durations of the steps of the check of the buckets, summed over the
+/// buckets. They are zero without the `std-time` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BucketSteps {
+    /// Bit additions of the edabits with the random edabits, including the
+    /// check of their multiplications.
+    pub add: Duration,
+    /// Conversions of the carries to the arithmetic field with the dabits.
+    pub a2b: Duration,
+    /// Openings of the bits of the sums.
+    pub open: Duration,
+    /// Checks that the sums match their opened bits.
+    pub check_zero: Duration,
+}
+
+impl std::ops::AddAssign for BucketSteps {
+    fn add_assign(&mut self, other: Self) {
+        self.add += other.add;
+        self.a2b += other.a2b;
+        self.open += other.open;
+        self.check_zero += other.check_zero;
+    }
+}
+
+// The phases are labelled with the `CONV_SCOPE_*` scopes, so that labels are
+// deserialized to one of them.
+#[cfg(feature = "serde")]
+mod phase_label {
+    use super::super::edabits::CONV_SCOPES;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        phase: &&'static str,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(phase)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<&'static str, D::Error> {
+        let label = String::deserialize(deserializer)?;
+        CONV_SCOPES
+            .iter()
+            .copied()
+            .find(|scope| *scope == label)
+            .ok_or_else(|| D::Error::custom(format!("unknown phase `{}`", label)))
+    }
+}
+
+/// Observer of a run of `conv`, set with `ConvConfig::observer`.
+///
+/// The methods are called from the thread running `conv`, except that
+/// `extension_triggered`, `bucket_checked` and `bucket_completed` are called
+/// from the bucket threads when the buckets are checked in parallel. All the methods do
+/// nothing by default.
+pub trait ConvObserver: Send + Sync {
+    /// The phase `phase`, labelled with its `CONV_SCOPE_*` scope, started on
+    /// `size` values.
+    fn phase_started(&self, _phase: &'static str, _size: usize) {}
+
+    /// A phase finished.
+    fn phase_finished(&self, _stats: &PhaseStats) {}
+
+    /// A pool of VOLEs was extended.
+    fn extension_triggered(&self) {}
+
+    /// The steps of the check of the bucket `bucket` took `steps`, reported
+    /// just before `bucket_completed`.
+    fn bucket_checked(&self, _bucket: usize, _steps: &BucketSteps) {}
+
+    /// The bucket `bucket` was checked.
+    fn bucket_completed(&self, _bucket: usize) {}
+}
+
+impl<T: ConvObserver + ?Sized> ConvObserver for Arc<T> {
+    fn phase_started(&self, phase: &'static str, size: usize) {
+        (**self).phase_started(phase, size);
+    }
+
+    fn phase_finished(&self, stats: &PhaseStats) {
+        (**self).phase_finished(stats);
+    }
+
+    fn extension_triggered(&self) {
+        (**self).extension_triggered();
+    }
+
+    fn bucket_checked(&self, bucket: usize, steps: &BucketSteps) {
+        (**self).bucket_checked(bucket, steps);
+    }
+
+    fn bucket_completed(&self, bucket: usize) {
+        (**self).bucket_completed(bucket);
+    }
+}
+
+/// Observer ignoring everything.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopObserver;
+
+impl ConvObserver for NoopObserver {}
+
+/// Observer aggregating the phases, the extensions, the checked buckets and
+/// the durations of their steps into a [`ConvStats`], which may be read while `conv` runs.
+#[derive(Debug, Default)]
+pub struct StatsObserver {
+    stats: Mutex<ConvStats>,
+}
+
+impl StatsObserver {
+    /// Return a new observer, with empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the statistics aggregated so far.
+    pub fn stats(&self) -> ConvStats {
+        self.lock().clone()
+    }
+
+    // A panic of another observer of the same run does not corrupt the
+    // statistics, so a poisoned lock is simply taken over.
+    fn lock(&self) -> std::sync::MutexGuard<'_, ConvStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ConvObserver for StatsObserver {
+    fn phase_finished(&self, stats: &PhaseStats) {
+        self.lock().phases.push(*stats);
+    }
+
+    fn extension_triggered(&self) {
+        self.lock().extensions += 1;
+    }
+
+    fn bucket_checked(&self, _bucket: usize, steps: &BucketSteps) {
+        self.lock().bucket_steps += *steps;
+    }
+
+    fn bucket_completed(&self, _bucket: usize) {
+        self.lock().buckets_completed += 1;
+    }
+}
+
+// Forwards everything to both observers, so that `conv` aggregates its
+// statistics while notifying the observer of the configuration.
+pub(crate) struct TeeObserver<A, B>(pub(crate) A, pub(crate) B);
+
+impl<A: ConvObserver, B: ConvObserver> ConvObserver for TeeObserver<A, B> {
+    fn phase_started(&self, phase: &'static str, size: usize) {
+        self.0.phase_started(phase, size);
+        self.1.phase_started(phase, size);
+    }
+
+    fn phase_finished(&self, stats: &PhaseStats) {
+        self.0.phase_finished(stats);
+        self.1.phase_finished(stats);
+    }
+
+    fn extension_triggered(&self) {
+        self.0.extension_triggered();
+        self.1.extension_triggered();
+    }
+
+    fn bucket_checked(&self, bucket: usize, steps: &BucketSteps) {
+        self.0.bucket_checked(bucket, steps);
+        self.1.bucket_checked(bucket, steps);
+    }
+
+    fn bucket_completed(&self, bucket: usize) {
+        self.0.bucket_completed(bucket);
+        self.1.bucket_completed(bucket);
+    }
+}
@@ -0,0 +1,364 @@
+//! Serialization of the conversion material, e.g. to generate random edabits
+//! and triples on one machine and to load them later for the online phase.
+//!
+//! The field elements are written with their canonical byte representations.
+//! Vectors are written with [`write_edabits`] and [`write_triples`], after a
+//! version byte and their length.
+
+use super::edabits::{check_nb_bits, EdabitsProver, EdabitsVerifier};
+use super::homcom::{MacProver, MacVerifier};
+use crate::errors::Error;
+use generic_array::GenericArray;
+use scuttlebutt::{field::FiniteField, serialization::CanonicalSerialize};
+use std::io::{ErrorKind, Read, Write};
+
+/// Version of the format of [`write_edabits`] and [`write_triples`].
+pub const MATERIAL_FORMAT_VERSION: u8 = 1;
+
+/// Values of the conversion which can be written to bytes and read back.
+pub trait ConvSerialize: Sized {
+    /// Write the value to `writer`.
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error>;
+
+    /// Read a value written with `write` from `reader`, failing on truncated
+    /// or invalid input.
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error>;
+
+    /// Serialize the value to bytes.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes)
+            .expect("writing to a vector does not fail");
+        bytes
+    }
+
+    /// Deserialize a value serialized with `to_bytes`, which must be all of
+    /// `bytes`.
+    fn from_bytes(mut bytes: &[u8]) -> Result<Self, Error> {
+        let value = Self::read(&mut bytes)?;
+        if !bytes.is_empty() {
+            return Err(Error::Other(format!(
+                "{} trailing bytes after the conversion material",
+                bytes.len()
+            )));
+        }
+        Ok(value)
+    }
+}
+
+// A truncated input is reported as such, rather than as a disconnected peer.
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), Error> {
+    reader.read_exact(buf).map_err(|e| {
+        if e.kind() == ErrorKind::UnexpectedEof {
+            Error::Other("truncated conversion material".to_string())
+        } else {
+            Error::from(e)
+        }
+    })
+}
+
+fn write_element<W: Write, F: CanonicalSerialize>(writer: &mut W, x: &F) -> Result<(), Error> {
+    writer.write_all(&x.to_bytes())?;
+    Ok(())
+}
+
+fn read_element<R: Read, F: CanonicalSerialize>(reader: &mut R) -> Result<F, Error> {
+    let mut bytes = GenericArray::<u8, F::ByteReprLen>::default();
+    read_exact(reader, &mut bytes)?;
+    F::from_bytes(&bytes).map_err(|e| {
+        Error::Other(format!(
+            "invalid element of {} in the conversion material: {}",
+            std::any::type_name::<F>(),
+            e
+        ))
+    })
+}
+
+fn write_len<W: Write>(writer: &mut W, len: usize) -> Result<(), Error> {
+    writer.write_all(&(len as u64).to_le_bytes())?;
+    Ok(())
+}
+
+fn read_len<R: Read>(reader: &mut R) -> Result<usize, Error> {
+    let mut bytes = [0; 8];
+    read_exact(reader, &mut bytes)?;
+    usize::try_from(u64::from_le_bytes(bytes))
+        .map_err(|_| Error::Other("invalid length in the conversion material".to_string()))
+}
+
+impl<FE: FiniteField> ConvSerialize for MacProver<FE> {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_element(writer, &self.0)?;
+        write_element(writer, &self.1)
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let value = read_element(reader)?;
+        let mac = read_element(reader)?;
+        Ok(MacProver(value, mac))
+    }
+}
+
+impl<FE: FiniteField> ConvSerialize for MacVerifier<FE> {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_element(writer, &self.0)
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Ok(MacVerifier(read_element(reader)?))
+    }
+}
+
+impl<M: ConvSerialize> ConvSerialize for (M, M, M) {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.write(writer)?;
+        self.1.write(writer)?;
+        self.2.write(writer)
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Ok((M::read(reader)?, M::read(reader)?, M::read(reader)?))
+    }
+}
+
+// The number of bits of an edabit, then its bits and its value.
+fn write_edabit<W: Write, B: ConvSerialize, V: ConvSerialize>(
+    writer: &mut W,
+    bits: &[B],
+    value: &V,
+) -> Result<(), Error> {
+    write_len(writer, bits.len())?;
+    for bit in bits {
+        bit.write(writer)?;
+    }
+    value.write(writer)
+}
+
+fn read_edabit<R: Read, FE: FiniteField, B: ConvSerialize, V: ConvSerialize>(
+    reader: &mut R,
+) -> Result<(Vec<B>, V), Error> {
+    let nb_bits = read_len(reader)?;
+    check_nb_bits::<FE>(nb_bits)?;
+    let bits = (0..nb_bits)
+        .map(|_| B::read(reader))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((bits, V::read(reader)?))
+}
+
+impl<FE: FiniteField, TF: FiniteField> ConvSerialize for EdabitsProver<FE, TF> {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_edabit(writer, self.bits(), &self.value())
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let (bits, value) = read_edabit::<_, FE, _, _>(reader)?;
+        Ok(EdabitsProver::new(bits, value))
+    }
+}
+
+impl<FE: FiniteField, TF: FiniteField> ConvSerialize for EdabitsVerifier<FE, TF> {
+    fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write_edabit(writer, self.bits(), &self.value())
+    }
+
+    fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let (bits, value) = read_edabit::<_, FE, _, _>(reader)?;
+        Ok(EdabitsVerifier::new(bits, value))
+    }
+}
+
+fn write_vec<W: Write, T: ConvSerialize>(writer: &mut W, values: &[T]) -> Result<(), Error> {
+    writer.write_all(&[MATERIAL_FORMAT_VERSION])?;
+    write_len(writer, values.len())?;
+    for value in values {
+        value.write(writer)?;
+    }
+    Ok(())
+}
+
+fn read_vec<R: Read, T: ConvSerialize>(reader: &mut R) -> Result<Vec<T>, Error> {
+    let mut version = [0];
+    read_exact(reader, &mut version)?;
+    if version[0] != MATERIAL_FORMAT_VERSION {
+        return Err(Error::Other(format!(
+            "conversion material of version {}, expected {}",
+            version[0], MATERIAL_FORMAT_VERSION
+        )));
+    }
+    let len = read_len(reader)?;
+    // the length is not trusted to allocate
+    let mut values = Vec::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        values.push(T::read(reader)?);
+    }
+    Ok(values)
+}
+
+/// Write `edabits`, of the prover or of the verifier, to `writer`.
+pub fn write_edabits<W: Write, E: ConvSerialize>(
+    writer: &mut W,
+    edabits: &[E],
+) -> Result<(), Error> {
+    write_vec(writer, edabits)
+}
+
+/// Read edabits written with [`write_edabits`] from `reader`.
+pub fn read_edabits<R: Read, E: ConvSerialize>(reader: &mut R) -> Result<Vec<E>, Error> {
+    read_vec(reader)
+}
+
+/// Write multiplication `triples`, of the prover or of the verifier, to
+/// `writer`.
+pub fn write_triples<W: Write, M: ConvSerialize>(
+    writer: &mut W,
+    triples: &[(M, M, M)],
+) -> Result<(), Error> {
+    write_vec(writer, triples)
+}
+
+/// Read multiplication triples written with [`write_triples`] from `reader`.
+pub fn read_triples<R: Read, M: ConvSerialize>(reader: &mut R) -> Result<Vec<(M, M, M)>, Error> {
+    read_vec(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_edabits, read_triples, write_edabits, write_triples, ConvSerialize,
+        MATERIAL_FORMAT_VERSION,
+    };
+    use crate::edabits::{
+        ConvConfig, EdabitsProver, EdabitsVerifier, MacProver, MacVerifier, ProverConv,
+        VerifierConv,
+    };
+    use crate::svole::wykw::{UnsafeInsecureTestOt, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use scuttlebutt::{
+        field::{F40b, F61p, F2},
+        local_pair,
+        ring::FiniteRing,
+        AesRng,
+    };
+
+    type Prover = ProverConv<F61p, F40b, UnsafeInsecureTestOt>;
+    type Verifier = VerifierConv<F61p, F40b, UnsafeInsecureTestOt>;
+
+    const NB_BITS: usize = 38;
+
+    // Persist random edabits of both parties and reload them, then check that
+    // they still pass `conv`. Return the persisted edabits.
+    fn persist_and_conv(
+        num: usize,
+    ) -> (
+        Vec<EdabitsProver<F61p, F40b>>,
+        Vec<EdabitsVerifier<F61p, F40b>>,
+    ) {
+        let (mut sender, mut receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut fconv =
+                Prover::init(&mut sender, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL).unwrap();
+            let edabits = fconv
+                .random_edabits(&mut sender, &mut rng, NB_BITS, num)
+                .unwrap();
+            let mut bytes = Vec::new();
+            write_edabits(&mut bytes, &edabits).unwrap();
+            let reloaded: Vec<EdabitsProver<F61p, F40b>> = read_edabits(&mut &bytes[..]).unwrap();
+            fconv
+                .conv(&mut sender, &mut rng, &reloaded, &ConvConfig::default())
+                .unwrap();
+            reloaded
+        });
+        let mut rng = AesRng::new();
+        let mut fconv =
+            Verifier::init(&mut receiver, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL).unwrap();
+        let edabits = fconv
+            .random_edabits(&mut receiver, &mut rng, NB_BITS, num)
+            .unwrap();
+        let mut bytes = Vec::new();
+        write_edabits(&mut bytes, &edabits).unwrap();
+        let reloaded: Vec<EdabitsVerifier<F61p, F40b>> = read_edabits(&mut &bytes[..]).unwrap();
+        fconv
+            .conv(&mut receiver, &mut rng, &reloaded, &ConvConfig::default())
+            .unwrap();
+        (handle.join().unwrap(), reloaded)
+    }
+
+    #[test]
+    fn test_persisted_edabits_conv() {
+        let (prover, verifier) = persist_and_conv(10);
+        assert_eq!(prover.len(), 10);
+        assert_eq!(verifier.len(), 10);
+        for (e, f) in prover.iter().zip(verifier.iter()) {
+            assert_eq!(e.bits().len(), NB_BITS);
+            assert_eq!(f.bits().len(), NB_BITS);
+            // the persisted edabits keep their values
+            let value = EdabitsProver::<F61p, F40b>::from_bytes(&e.to_bytes()).unwrap();
+            assert_eq!(value.value(), e.value());
+            assert_eq!(value.bits(), e.bits());
+        }
+    }
+
+    #[test]
+    fn test_triples_round_trip() {
+        let mut rng = AesRng::new();
+        let triples: Vec<_> = (0..20)
+            .map(|_| {
+                (
+                    MacProver(F2::random(&mut rng), F40b::random(&mut rng)),
+                    MacProver(F2::random(&mut rng), F40b::random(&mut rng)),
+                    MacProver(F2::random(&mut rng), F40b::random(&mut rng)),
+                )
+            })
+            .collect::<Vec<(MacProver<F40b>, _, _)>>();
+        let mut bytes = Vec::new();
+        write_triples(&mut bytes, &triples).unwrap();
+        assert_eq!(read_triples(&mut &bytes[..]).unwrap(), triples);
+
+        let keys: Vec<_> = (0..20)
+            .map(|_| {
+                (
+                    MacVerifier(F40b::random(&mut rng)),
+                    MacVerifier(F40b::random(&mut rng)),
+                    MacVerifier(F40b::random(&mut rng)),
+                )
+            })
+            .collect::<Vec<(MacVerifier<F40b>, _, _)>>();
+        let mut bytes = Vec::new();
+        write_triples(&mut bytes, &keys).unwrap();
+        assert_eq!(read_triples(&mut &bytes[..]).unwrap(), keys);
+    }
+
+    #[test]
+    fn test_invalid_material() {
+        let mut rng = AesRng::new();
+        let edabits: Vec<_> = (0..3)
+            .map(|_| {
+                let bits = (0..NB_BITS)
+                    .map(|_| MacVerifier(F40b::random(&mut rng)))
+                    .collect();
+                EdabitsVerifier::<F61p, F40b>::new(bits, MacVerifier(F61p::random(&mut rng)))
+            })
+            .collect();
+        let mut bytes = Vec::new();
+        write_edabits(&mut bytes, &edabits).unwrap();
+        assert_eq!(bytes[0], MATERIAL_FORMAT_VERSION);
+
+        // every truncation is rejected
+        for len in 0..bytes.len() {
+            let res: Result<Vec<EdabitsVerifier<F61p, F40b>>, _> = read_edabits(&mut &bytes[..len]);
+            assert!(res.is_err(), "{} bytes", len);
+        }
+        let mut other_version = bytes.clone();
+        other_version[0] += 1;
+        assert!(read_edabits::<_, EdabitsVerifier<F61p, F40b>>(&mut &other_version[..]).is_err());
+        // an edabit of more bits than the field holds
+        let mut too_many_bits = bytes.clone();
+        too_many_bits[9..17].copy_from_slice(&61u64.to_le_bytes());
+        assert!(read_edabits::<_, EdabitsVerifier<F61p, F40b>>(&mut &too_many_bits[..]).is_err());
+        // trailing bytes
+        let mut bytes = edabits[0].to_bytes();
+        assert!(EdabitsVerifier::<F61p, F40b>::from_bytes(&bytes).is_ok());
+        bytes.push(0);
+        assert!(EdabitsVerifier::<F61p, F40b>::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,451 @@
+//! Hand-off of verified edabits to MP-SPDZ, as authenticated shares in its
+//! persistence format, and back.
+//!
+//! The arithmetic value `x` of an edabit is authenticated in the
+//! designated-verifier model: the prover holds `x` and a MAC `M`, the
+//! verifier holds a key `K` and the global key `Δ`, with `M = K + x·Δ`.
+//! This is a two-party SPDZ sharing of `x` under the MAC key `α = Δ`:
+//!
+//! | party        | value share | MAC share | MAC key share |
+//! |--------------|-------------|-----------|---------------|
+//! | 0 (prover)   | `x`         | `M`       | `0`           |
+//! | 1 (verifier) | `0`         | `-K`      | `Δ`           |
+//!
+//! The trust models only partially align:
+//! - the values stay hidden from the verifier, but not from the prover,
+//!   which knows them anyway;
+//! - the MACs remain sound against the prover only: the verifier knows the
+//!   whole MAC key, so the MAC checks of MP-SPDZ catch a cheating prover but
+//!   not a cheating verifier. Outputs of the downstream computation must be
+//!   trusted by the verifier only, as the conversion itself;
+//! - the bit decompositions are not transferable. Their MACs live in the
+//!   binary field of the conversion, e.g. `F40b` reduced over
+//!   `x^40 + x^5 + x^4 + x^3 + 1`, while the `gf2n` shares of MP-SPDZ are
+//!   reduced over `x^40 + x^20 + x^15 + x^10 + 1` under an independent key.
+//!   The bits must be recomputed by MP-SPDZ from the exported values;
+//! - only shares of the above form are imported, under the MAC key of the
+//!   verifier they were exported from. Shares produced by MP-SPDZ itself,
+//!   where both parties hold part of the values or of the key, are rejected.
+//!
+//! The files follow the layout of MP-SPDZ for `Share<gfp>`:
+//! - `Player-MAC-Keys-p-P<party>`: the number of players and the MAC key
+//!   share, in decimal, separated by a space;
+//! - `Persistence/Transactions-P<party>.data`: a signature made of its
+//!   length as 8 little-endian bytes, the type string [`SPDZ_GFP_TYPE`], and
+//!   the prime as a sign byte, its length as 8 little-endian bytes and its
+//!   big-endian bytes; then for every share, the value share and the MAC
+//!   share, each in Montgomery form as [`SPDZ_GFP_LIMBS`] little-endian
+//!   64-bit limbs.
+
+use super::edabits::{EdabitsProver, EdabitsVerifier, VerifierConv};
+use super::homcom::{MacProver, MacVerifier};
+use crate::{errors::Error, svole::wykw::BaseOt};
+use generic_array::{typenum::Unsigned, GenericArray};
+use scuttlebutt::{field::FiniteField, ring::FiniteRing};
+use std::io::{Read, Write};
+
+/// Number of 64-bit limbs of the `gfp` elements of MP-SPDZ, as set by its
+/// default `GFP_MOD_SZ`.
+pub const SPDZ_GFP_LIMBS: usize = 2;
+
+/// Type string of the shares over a prime field in MP-SPDZ.
+pub const SPDZ_GFP_TYPE: &str = "SPDZ gfp";
+
+// The parties exchange shares in the two-party setting only.
+const SPDZ_NUM_PLAYERS: usize = 2;
+
+/// An authenticated share of MP-SPDZ: a share of a value and a share of its
+/// MAC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpdzShare<FE> {
+    /// The share of the value.
+    pub value: FE,
+    /// The share of the MAC of the value.
+    pub mac: FE,
+}
+
+/// The shares of one party of MP-SPDZ, with its share of the MAC key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpdzShares<FE> {
+    /// The party, 0 for the prover and 1 for the verifier.
+    pub party: usize,
+    /// The share of the MAC key.
+    pub mac_key: FE,
+    /// The shares, in the order of the edabits.
+    pub shares: Vec<SpdzShare<FE>>,
+}
+
+impl<FE: FiniteField<PrimeField = FE>> SpdzShares<FE> {
+    /// Return the shares of the prover for the values of `edabits`.
+    pub fn from_prover<TF: FiniteField>(edabits: &[EdabitsProver<FE, TF>]) -> Self {
+        let shares = edabits
+            .iter()
+            .map(|edabit| SpdzShare {
+                value: edabit.value.0,
+                mac: edabit.value.1,
+            })
+            .collect();
+        Self {
+            party: 0,
+            mac_key: FE::ZERO,
+            shares,
+        }
+    }
+
+    /// Return the shares of the verifier for the values of `edabits`, which
+    /// were checked with `conv`.
+    pub fn from_verifier<TF: FiniteField, B: BaseOt>(
+        conv: &VerifierConv<FE, TF, B>,
+        edabits: &[EdabitsVerifier<FE, TF>],
+    ) -> Self {
+        let shares = edabits
+            .iter()
+            .map(|edabit| SpdzShare {
+                value: FE::ZERO,
+                mac: -edabit.value.0,
+            })
+            .collect();
+        Self {
+            party: 1,
+            mac_key: conv.fcom.get_delta(),
+            shares,
+        }
+    }
+
+    /// Return the values authenticated by the shares of the prover.
+    pub fn into_prover(self) -> Result<Vec<MacProver<FE>>, Error> {
+        if self.party != 0 || self.mac_key != FE::ZERO {
+            return Err(Error::Other(
+                "the shares are not the shares of the prover".to_string(),
+            ));
+        }
+        Ok(self
+            .shares
+            .into_iter()
+            .map(|share| MacProver(share.value, share.mac))
+            .collect())
+    }
+
+    /// Return the keys of the values authenticated by the shares of the
+    /// verifier, which must have been exported from `conv`.
+    pub fn into_verifier<TF: FiniteField, B: BaseOt>(
+        self,
+        conv: &VerifierConv<FE, TF, B>,
+    ) -> Result<Vec<MacVerifier<FE>>, Error> {
+        if self.party != 1 || self.shares.iter().any(|share| share.value != FE::ZERO) {
+            return Err(Error::Other(
+                "the shares are not the shares of the verifier".to_string(),
+            ));
+        }
+        if self.mac_key != conv.fcom.get_delta() {
+            return Err(Error::Other(
+                "the shares are authenticated under a different MAC key".to_string(),
+            ));
+        }
+        Ok(self
+            .shares
+            .into_iter()
+            .map(|share| MacVerifier(-share.mac))
+            .collect())
+    }
+
+    /// Write the share of the MAC key, as the `Player-MAC-Keys-p-P<party>`
+    /// file of MP-SPDZ.
+    pub fn write_mac_key<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        write!(
+            writer,
+            "{} {}",
+            SPDZ_NUM_PLAYERS,
+            to_decimal(&self.mac_key.to_bytes())
+        )?;
+        Ok(())
+    }
+
+    /// Write the shares, as the `Persistence/Transactions-P<party>.data`
+    /// file of MP-SPDZ.
+    pub fn write_shares<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        check_field::<FE>()?;
+        let signature = signature::<FE>();
+        writer.write_all(&(signature.len() as u64).to_le_bytes())?;
+        writer.write_all(&signature)?;
+        let radix = montgomery_radix::<FE>();
+        for share in self.shares.iter() {
+            write_gfp(&mut writer, share.value * radix)?;
+            write_gfp(&mut writer, share.mac * radix)?;
+        }
+        Ok(())
+    }
+
+    /// Read the shares of `party` from the files written by
+    /// [`SpdzShares::write_mac_key`] and [`SpdzShares::write_shares`].
+    pub fn read<R1: Read, R2: Read>(
+        party: usize,
+        mut mac_key: R1,
+        mut shares: R2,
+    ) -> Result<Self, Error> {
+        check_field::<FE>()?;
+
+        let mut key_file = String::new();
+        mac_key.read_to_string(&mut key_file)?;
+        let mut words = key_file.split_whitespace();
+        if words.next() != Some(SPDZ_NUM_PLAYERS.to_string().as_str()) {
+            return Err(Error::Other(format!(
+                "the MAC key is not for {} players",
+                SPDZ_NUM_PLAYERS
+            )));
+        }
+        let mac_key = words
+            .next()
+            .and_then(|key| from_decimal(key, FE::ByteReprLen::USIZE))
+            .and_then(|bytes| FE::from_bytes(GenericArray::from_slice(&bytes)).ok())
+            .ok_or_else(|| Error::Other("invalid MAC key".to_string()))?;
+
+        let mut len = [0u8; 8];
+        shares.read_exact(&mut len)?;
+        let mut signature = vec![0u8; u64::from_le_bytes(len) as usize];
+        shares.read_exact(&mut signature)?;
+        if signature != self::signature::<FE>() {
+            return Err(Error::Other(
+                "the shares are not SPDZ shares over this field".to_string(),
+            ));
+        }
+
+        let inv_radix = montgomery_radix::<FE>().inverse();
+        let mut out = Vec::new();
+        while let Some(value) = read_gfp::<FE, _>(&mut shares)? {
+            let mac = read_gfp::<FE, _>(&mut shares)?
+                .ok_or_else(|| Error::Other("truncated shares".to_string()))?;
+            out.push(SpdzShare {
+                value: value * inv_radix,
+                mac: mac * inv_radix,
+            });
+        }
+        Ok(Self {
+            party,
+            mac_key,
+            shares: out,
+        })
+    }
+}
+
+fn check_field<FE: FiniteField>() -> Result<(), Error> {
+    if FE::ByteReprLen::USIZE > 8 * SPDZ_GFP_LIMBS {
+        return Err(Error::Other(format!(
+            "the field does not fit in {} limbs",
+            SPDZ_GFP_LIMBS
+        )));
+    }
+    Ok(())
+}
+
+// The signature of the files of shares over `FE`.
+fn signature<FE: FiniteField<PrimeField = FE>>() -> Vec<u8> {
+    // The modulus is one more than the largest element.
+    let mut modulus = (-FE::ONE).to_bytes().to_vec();
+    let mut carry = true;
+    for byte in modulus.iter_mut() {
+        (*byte, carry) = byte.overflowing_add(u8::from(carry));
+    }
+    if carry {
+        modulus.push(1);
+    }
+    while modulus.last() == Some(&0) {
+        modulus.pop();
+    }
+    modulus.reverse();
+
+    let mut signature = SPDZ_GFP_TYPE.as_bytes().to_vec();
+    signature.push(0);
+    signature.extend_from_slice(&(modulus.len() as u64).to_le_bytes());
+    signature.extend_from_slice(&modulus);
+    signature
+}
+
+// MP-SPDZ keeps `gfp` elements in Montgomery form, `x·R` with
+// `R = 2^(64·SPDZ_GFP_LIMBS)`.
+fn montgomery_radix<FE: FiniteRing>() -> FE {
+    (0..64 * SPDZ_GFP_LIMBS).fold(FE::ONE, |r, _| r + r)
+}
+
+fn write_gfp<FE: FiniteField, W: Write>(writer: &mut W, x: FE) -> Result<(), Error> {
+    let mut limbs = [0u8; 8 * SPDZ_GFP_LIMBS];
+    let bytes = x.to_bytes();
+    limbs[..bytes.len()].copy_from_slice(&bytes);
+    writer.write_all(&limbs)?;
+    Ok(())
+}
+
+// Returns `None` at the end of the file.
+fn read_gfp<FE: FiniteField, R: Read>(reader: &mut R) -> Result<Option<FE>, Error> {
+    let mut limbs = [0u8; 8 * SPDZ_GFP_LIMBS];
+    let mut read = 0;
+    while read < limbs.len() {
+        match reader.read(&mut limbs[read..])? {
+            0 if read == 0 => return Ok(None),
+            0 => return Err(Error::Other("truncated shares".to_string())),
+            n => read += n,
+        }
+    }
+    let (bytes, padding) = limbs.split_at(FE::ByteReprLen::USIZE);
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(Error::Other("share bigger than the modulus".to_string()));
+    }
+    FE::from_bytes(GenericArray::from_slice(bytes))
+        .map(Some)
+        .map_err(|_| Error::Other("share bigger than the modulus".to_string()))
+}
+
+// The decimal digits of the little-endian integer `bytes`.
+fn to_decimal(bytes: &[u8]) -> String {
+    let mut n = bytes.to_vec();
+    let mut digits = Vec::new();
+    loop {
+        let mut rem = 0u32;
+        for byte in n.iter_mut().rev() {
+            let cur = (rem << 8) | u32::from(*byte);
+            *byte = (cur / 10) as u8;
+            rem = cur % 10;
+        }
+        digits.push(char::from(b'0' + rem as u8));
+        if n.iter().all(|byte| *byte == 0) {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+// The `len` little-endian bytes of the decimal integer `s`, if it fits.
+fn from_decimal(s: &str, len: usize) -> Option<Vec<u8>> {
+    if s.is_empty() {
+        return None;
+    }
+    let mut n = vec![0u8; len];
+    for c in s.chars() {
+        let mut carry = c.to_digit(10)?;
+        for byte in n.iter_mut() {
+            let cur = u32::from(*byte) * 10 + carry;
+            *byte = cur as u8;
+            carry = cur >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_decimal, to_decimal, SpdzShares, SPDZ_GFP_LIMBS, SPDZ_GFP_TYPE};
+    use crate::edabits::{ProverConv, VerifierConv};
+    use crate::svole::wykw::{UnsafeInsecureTestOt, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use scuttlebutt::{
+        field::{F127p, F40b, F61p},
+        local_pair,
+        ring::FiniteRing,
+        AesRng,
+    };
+
+    const NB_BITS: usize = 38;
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(to_decimal(&[0, 0]), "0");
+        assert_eq!(to_decimal(&1234567u64.to_le_bytes()), "1234567");
+        assert_eq!(
+            from_decimal("1234567", 8),
+            Some(1234567u64.to_le_bytes().to_vec())
+        );
+        assert_eq!(from_decimal("256", 1), None);
+        assert_eq!(from_decimal("12a", 8), None);
+    }
+
+    #[test]
+    fn test_spdz_round_trip_f61p() {
+        let count = 300;
+        let (sender, receiver) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let mut channel = sender;
+            let mut fconv = ProverConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let edabits = fconv
+                .random_edabits(&mut channel, &mut rng, NB_BITS, count)
+                .unwrap();
+            SpdzShares::from_prover(&edabits)
+        });
+        let mut rng = AesRng::new();
+        let mut channel = receiver;
+        let mut fconv = VerifierConv::<F61p, F40b, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let edabits = fconv
+            .random_edabits(&mut channel, &mut rng, NB_BITS, count)
+            .unwrap();
+        let verifier = SpdzShares::from_verifier(&fconv, &edabits);
+        let prover = handle.join().unwrap();
+
+        // The shares are SPDZ shares: the MAC of the value is its product
+        // with the MAC key.
+        let mac_key = prover.mac_key + verifier.mac_key;
+        for (p, v) in prover.shares.iter().zip(verifier.shares.iter()) {
+            assert_eq!((p.value + v.value) * mac_key, p.mac + v.mac);
+        }
+
+        for shares in [&prover, &verifier] {
+            let mut key_file = Vec::new();
+            let mut shares_file = Vec::new();
+            shares.write_mac_key(&mut key_file).unwrap();
+            shares.write_shares(&mut shares_file).unwrap();
+
+            assert!(String::from_utf8(key_file.clone())
+                .unwrap()
+                .starts_with("2 "));
+            let signature_len = u64::from_le_bytes(shares_file[..8].try_into().unwrap()) as usize;
+            assert!(shares_file[8..].starts_with(SPDZ_GFP_TYPE.as_bytes()));
+            assert!(shares_file[8..8 + signature_len].ends_with(&((1u64 << 61) - 1).to_be_bytes()));
+            assert_eq!(
+                shares_file.len(),
+                8 + signature_len + count * 2 * 8 * SPDZ_GFP_LIMBS
+            );
+
+            let read =
+                SpdzShares::<F61p>::read(shares.party, &key_file[..], &shares_file[..]).unwrap();
+            assert_eq!(&read, shares);
+
+            // Files of another field are rejected.
+            assert!(
+                SpdzShares::<F127p>::read(shares.party, &key_file[..], &shares_file[..]).is_err()
+            );
+            // So are truncated files.
+            let truncated = &shares_file[..shares_file.len() - 1];
+            assert!(SpdzShares::<F61p>::read(shares.party, &key_file[..], truncated).is_err());
+        }
+
+        let keys = verifier.clone().into_verifier(&fconv).unwrap();
+        for (key, edabit) in keys.iter().zip(edabits.iter()) {
+            assert_eq!(*key, edabit.value);
+        }
+        let macs = prover.clone().into_prover().unwrap();
+        assert_eq!(macs.len(), count);
+        assert!(prover.into_verifier(&fconv).is_err());
+        assert!(verifier.into_prover().is_err());
+
+        // Shares under another MAC key are rejected.
+        let other = SpdzShares {
+            party: 1,
+            mac_key: F61p::ONE,
+            shares: Vec::new(),
+        };
+        assert!(other.into_verifier(&fconv).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! A channel wrapper enforcing the flushing contract of the F_com
+//! functionality in tests: a party must flush its pending writes before
+//! reading from the channel, otherwise both parties may wait on each other
+//! when the underlying transport is buffered.
+use scuttlebutt::{AbstractChannel, CommBreakdown};
+use std::{cell::Cell, io::Result, rc::Rc};
+
+/// Channel panicking when reading while some writes have not been flushed.
+pub(crate) struct StrictChannel<C> {
+    channel: C,
+    pending: Rc<Cell<bool>>,
+}
+
+impl<C: AbstractChannel> StrictChannel<C> {
+    /// Wrap `channel`.
+    pub(crate) fn new(channel: C) -> Self {
+        Self {
+            channel,
+            pending: Rc::new(Cell::new(false)),
+        }
+    }
+}
+
+impl<C: AbstractChannel> AbstractChannel for StrictChannel<C> {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        assert!(
+            !self.pending.get(),
+            "reading from a channel with unflushed writes"
+        );
+        self.channel.read_bytes(bytes)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.pending.set(true);
+        self.channel.write_bytes(bytes)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.pending.set(false);
+        self.channel.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self {
+            channel: self.channel.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    fn scope(&mut self, label: &'static str) -> Option<&'static str> {
+        self.channel.scope(label)
+    }
+
+    fn comm_breakdown(&self) -> Option<CommBreakdown> {
+        self.channel.comm_breakdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StrictChannel;
+    use scuttlebutt::{AbstractChannel, Channel};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_strict_channel_flushed() {
+        let mut channel = StrictChannel::new(Channel::new(Cursor::new(vec![1u8]), Vec::new()));
+        channel.write_u8(0).unwrap();
+        channel.flush().unwrap();
+        assert_eq!(channel.read_u8().unwrap(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_strict_channel_unflushed() {
+        let mut channel = StrictChannel::new(Channel::new(Cursor::new(vec![1u8]), Vec::new()));
+        channel.write_u8(0).unwrap();
+        let _ = channel.read_u8();
+    }
+}
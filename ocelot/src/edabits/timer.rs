@@ -0,0 +1,35 @@
+//! Timings of the phases of the conversion, reported in its statistics.
+//!
+//! `Instant::now` panics on `wasm32-unknown-unknown`, so the phases are only
+//! timed with the `std-time` feature.
+
+use std::time::Duration;
+#[cfg(feature = "std-time")]
+use std::time::Instant;
+
+/// Start of a timed phase.
+#[derive(Clone, Copy)]
+pub(crate) struct Timer {
+    #[cfg(feature = "std-time")]
+    start: Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(feature = "std-time")]
+            start: Instant::now(),
+        }
+    }
+
+    /// Return the time elapsed since the start, with the `std-time` feature.
+    #[cfg(feature = "std-time")]
+    pub(crate) fn elapsed(&self) -> Option<Duration> {
+        Some(self.start.elapsed())
+    }
+
+    #[cfg(not(feature = "std-time"))]
+    pub(crate) fn elapsed(&self) -> Option<Duration> {
+        None
+    }
+}
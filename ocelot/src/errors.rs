@@ -15,13 +15,91 @@ pub enum Error {
     EqCheckFailed,
     /// Commitment opening failed.
     InvalidOpening,
+    /// An opened value does not satisfy the expected predicate.
+    OpenCheckFailed {
+        /// Index of the failing value in the opened batch.
+        index: usize,
+        /// The failing opened value, formatted with `Debug`.
+        value: String,
+    },
+    /// An LPN parameter violates a structural constraint.
+    InvalidLpnParams {
+        /// Name of the offending parameter.
+        field: String,
+        /// The violated constraint.
+        constraint: String,
+    },
+    /// The two parties use different LPN parameters.
+    LpnParamsMismatch,
+    /// The operation was cancelled with a cancellation token.
+    Cancelled,
+    /// The instance was left inconsistent by an interrupted operation and
+    /// cannot be used anymore.
+    Poisoned,
+    /// The peer stopped responding, as detected by a keep-alive channel.
+    PeerDisconnected,
+    /// The check that the dabits have equal bits in both fields failed.
+    FdabitCheckFailed,
+    /// A random edabit opened during the cut-and-choose does not match its
+    /// bits.
+    EdabitOpenMismatch {
+        /// Index of the failing edabit in the opened batch.
+        index: usize,
+    },
+    /// Two batches processed together have different lengths.
+    BatchLengthMismatch {
+        /// Length of the first batch.
+        left: usize,
+        /// Length of the second batch.
+        right: usize,
+    },
+    /// The edabits of a batch have different numbers of bits.
+    EdabitWidthMismatch {
+        /// Index of the first edabit whose number of bits differs.
+        index: usize,
+        /// Number of bits of that edabit.
+        nb_bits: usize,
+        /// Number of bits of the first edabit of the batch.
+        expected: usize,
+    },
+    /// The field cannot hold the values of edabits of `nb_bits` bits.
+    InvalidNbBits {
+        /// The requested number of bits.
+        nb_bits: usize,
+        /// Name of the field.
+        field: String,
+    },
+    /// The field is too small to check `n` dabits at once with random
+    /// values of `gamma` bits.
+    InvalidFdabitParameters {
+        /// Number of dabits checked at once.
+        n: usize,
+        /// Number of bits of the random values.
+        gamma: usize,
+        /// Name of the field.
+        field: String,
+    },
+    /// The two parties run the conversion with different parameters, as
+    /// found by comparing the headers they exchange beforehand.
+    ConvParamsMismatch {
+        /// Name of the first parameter which differs.
+        param: String,
+        /// The value of this party.
+        local: u64,
+        /// The value of the other party.
+        remote: u64,
+    },
 }
 
 impl std::error::Error for Error {}
 
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Error {
-        Error::IoError(e)
+        if scuttlebutt::channel::PeerDisconnected::is(&e) {
+            Error::PeerDisconnected
+        } else {
+            Error::IoError(e)
+        }
     }
 }
 
@@ -41,6 +119,51 @@ impl std::fmt::Display for Error {
             Error::CorrelationCheckFailed => "Correlation check failed!, i.e, w != u'Δ + v".fmt(f),
             Error::EqCheckFailed => "EQ check failed!".fmt(f),
             Error::InvalidOpening => "Invalid commitment opening!".fmt(f),
+            Error::OpenCheckFailed { index, value } => write!(
+                f,
+                "check failed on opened value {} at index {}",
+                value, index
+            ),
+            Error::InvalidLpnParams { field, constraint } => {
+                write!(f, "invalid LPN parameter `{}`: {}", field, constraint)
+            }
+            Error::LpnParamsMismatch => "LPN parameters differ between the two parties".fmt(f),
+            Error::Cancelled => "operation cancelled".fmt(f),
+            Error::Poisoned => "instance poisoned by an interrupted operation".fmt(f),
+            Error::PeerDisconnected => "peer disconnected".fmt(f),
+            Error::FdabitCheckFailed => "fdabit check failed".fmt(f),
+            Error::EdabitOpenMismatch { index } => {
+                write!(f, "opened edabit {} does not match its bits", index)
+            }
+            Error::BatchLengthMismatch { left, right } => {
+                write!(f, "batches of different lengths: {} and {}", left, right)
+            }
+            Error::EdabitWidthMismatch {
+                index,
+                nb_bits,
+                expected,
+            } => write!(
+                f,
+                "edabit {} has {} bits, edabit 0 has {}",
+                index, nb_bits, expected
+            ),
+            Error::InvalidNbBits { nb_bits, field } => {
+                write!(f, "edabits of {} bits do not fit in {}", nb_bits, field)
+            }
+            Error::InvalidFdabitParameters { n, gamma, field } => write!(
+                f,
+                "fdabit cannot check {} dabits with gamma={} in {}",
+                n, gamma, field
+            ),
+            Error::ConvParamsMismatch {
+                param,
+                local,
+                remote,
+            } => write!(
+                f,
+                "conversion parameter `{}` differs between the two parties: {} here, {} for the other party",
+                param, local, remote
+            ),
         }
     }
 }
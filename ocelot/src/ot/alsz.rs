@@ -19,7 +19,7 @@ use scuttlebutt::{
 use std::marker::PhantomData;
 
 /// Oblivious transfer sender.
-pub struct Sender<OT: OtReceiver<Msg = Block> + SemiHonest> {
+pub struct Sender<OT: OtReceiver<Msg = Block>> {
     _ot: PhantomData<OT>,
     pub(super) hash: AesHash,
     s: Vec<bool>,
@@ -27,13 +27,13 @@ pub struct Sender<OT: OtReceiver<Msg = Block> + SemiHonest> {
     rngs: Vec<AesRng>,
 }
 /// Oblivious transfer receiver.
-pub struct Receiver<OT: OtSender<Msg = Block> + SemiHonest> {
+pub struct Receiver<OT: OtSender<Msg = Block>> {
     _ot: PhantomData<OT>,
     pub(super) hash: AesHash,
     rngs: Vec<(AesRng, AesRng)>,
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> FixedKeyInitializer for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> FixedKeyInitializer for Sender<OT> {
     fn init_fixed_key<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         s_: [u8; 16],
@@ -56,7 +56,20 @@ impl<OT: OtReceiver<Msg = Block> + SemiHonest> FixedKeyInitializer for Sender<OT
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> Sender<OT> {
+    // Derives a new instance from the base OTs of this one, without any
+    // communication. Each seeded RNG is forked, which the receiver mirrors
+    // in `Receiver::fork`, so that the base OT correlation is preserved.
+    pub(super) fn fork(&mut self) -> Self {
+        Self {
+            _ot: PhantomData::<OT>,
+            hash: AES_HASH,
+            s: self.s.clone(),
+            s_: self.s_,
+            rngs: self.rngs.iter_mut().map(|rng| rng.fork()).collect(),
+        }
+    }
+
     pub(super) fn send_setup<C: AbstractChannel>(
         &mut self,
         channel: &mut C,
@@ -78,7 +91,7 @@ impl<OT: OtReceiver<Msg = Block> + SemiHonest> Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> OtSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> OtSender for Sender<OT> {
     type Msg = Block;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -113,13 +126,13 @@ impl<OT: OtReceiver<Msg = Block> + SemiHonest> OtSender for Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> std::fmt::Display for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> std::fmt::Display for Sender<OT> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "ALSZ Sender")
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> CorrelatedSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> CorrelatedSender for Sender<OT> {
     fn send_correlated<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -145,7 +158,7 @@ impl<OT: OtReceiver<Msg = Block> + SemiHonest> CorrelatedSender for Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + SemiHonest> RandomSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> RandomSender for Sender<OT> {
     fn send_random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -167,7 +180,20 @@ impl<OT: OtReceiver<Msg = Block> + SemiHonest> RandomSender for Sender<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + SemiHonest> Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> Receiver<OT> {
+    // Counterpart of `Sender::fork`.
+    pub(super) fn fork(&mut self) -> Self {
+        Self {
+            _ot: PhantomData::<OT>,
+            hash: AES_HASH,
+            rngs: self
+                .rngs
+                .iter_mut()
+                .map(|(rng0, rng1)| (rng0.fork(), rng1.fork()))
+                .collect(),
+        }
+    }
+
     pub(super) fn receive_setup<C: AbstractChannel>(
         &mut self,
         channel: &mut C,
@@ -192,7 +218,7 @@ impl<OT: OtSender<Msg = Block> + SemiHonest> Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + SemiHonest> OtReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> OtReceiver for Receiver<OT> {
     type Msg = Block;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -242,7 +268,7 @@ impl<OT: OtSender<Msg = Block> + SemiHonest> OtReceiver for Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + SemiHonest> CorrelatedReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> CorrelatedReceiver for Receiver<OT> {
     fn receive_correlated<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -264,7 +290,7 @@ impl<OT: OtSender<Msg = Block> + SemiHonest> CorrelatedReceiver for Receiver<OT>
     }
 }
 
-impl<OT: OtSender<Msg = Block> + SemiHonest> RandomReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> RandomReceiver for Receiver<OT> {
     fn receive_random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -284,7 +310,7 @@ impl<OT: OtSender<Msg = Block> + SemiHonest> RandomReceiver for Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + SemiHonest> std::fmt::Display for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> std::fmt::Display for Receiver<OT> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "ALSZ Receiver")
     }
@@ -19,16 +19,28 @@ use std::io::ErrorKind;
 const SSP: usize = 40;
 
 /// Oblivious transfer extension sender.
-pub struct Sender<OT: OtReceiver<Msg = Block> + Malicious> {
+pub struct Sender<OT: OtReceiver<Msg = Block>> {
     pub(super) ot: AlszSender<OT>,
 }
 
 /// Oblivious transfer extension receiver.
-pub struct Receiver<OT: OtSender<Msg = Block> + Malicious> {
+pub struct Receiver<OT: OtSender<Msg = Block>> {
     ot: AlszReceiver<OT>,
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> Sender<OT> {
+    /// Derives a new, independent, OT extension instance from the base OTs
+    /// of this one, without any communication. The receiver must derive its
+    /// instance with [`Receiver::fork`] at the same point of the protocol.
+    ///
+    /// The seeds of the forked base OT RNGs are taken from the outputs of
+    /// the current ones, so by PRG security the extended OTs of the two
+    /// instances are independent. The global key `s` is shared, exactly as
+    /// it is across the successive extensions of a single instance.
+    pub fn fork(&mut self) -> Self {
+        Self { ot: self.ot.fork() }
+    }
+
     pub(super) fn send_setup<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -68,7 +80,7 @@ impl<OT: OtReceiver<Msg = Block> + Malicious> Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> FixedKeyInitializer for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> FixedKeyInitializer for Sender<OT> {
     fn init_fixed_key<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         s_: [u8; 16],
@@ -79,7 +91,7 @@ impl<OT: OtReceiver<Msg = Block> + Malicious> FixedKeyInitializer for Sender<OT>
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> OtSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> OtSender for Sender<OT> {
     type Msg = Block;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -114,7 +126,7 @@ impl<OT: OtReceiver<Msg = Block> + Malicious> OtSender for Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> CorrelatedSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> CorrelatedSender for Sender<OT> {
     fn send_correlated<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -140,7 +152,7 @@ impl<OT: OtReceiver<Msg = Block> + Malicious> CorrelatedSender for Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> RandomSender for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> RandomSender for Sender<OT> {
     fn send_random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -162,13 +174,20 @@ impl<OT: OtReceiver<Msg = Block> + Malicious> RandomSender for Sender<OT> {
     }
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious> std::fmt::Display for Sender<OT> {
+impl<OT: OtReceiver<Msg = Block>> std::fmt::Display for Sender<OT> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "KOS Sender")
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious> Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> Receiver<OT> {
+    /// Derives a new, independent, OT extension instance from the base OTs
+    /// of this one, without any communication. This is the counterpart of
+    /// [`Sender::fork`].
+    pub fn fork(&mut self) -> Self {
+        Self { ot: self.ot.fork() }
+    }
+
     pub(super) fn receive_setup<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -179,7 +198,7 @@ impl<OT: OtSender<Msg = Block> + Malicious> Receiver<OT> {
         let m = if m % 8 != 0 { m + (8 - m % 8) } else { m };
         let m_ = m + 128 + SSP;
         let mut r = utils::boolvec_to_u8vec(inputs);
-        r.extend((0..(m_ - m) / 8).map(|_| rand::random::<u8>()));
+        r.extend((0..(m_ - m) / 8).map(|_| rng.gen::<u8>()));
         let ts = self.ot.receive_setup(channel, &r, m_)?;
         // Check correlation
         let mut seed = Block::default();
@@ -207,7 +226,7 @@ impl<OT: OtSender<Msg = Block> + Malicious> Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious> OtReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> OtReceiver for Receiver<OT> {
     type Msg = Block;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -243,7 +262,7 @@ impl<OT: OtSender<Msg = Block> + Malicious> OtReceiver for Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious> CorrelatedReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> CorrelatedReceiver for Receiver<OT> {
     fn receive_correlated<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -267,7 +286,7 @@ impl<OT: OtSender<Msg = Block> + Malicious> CorrelatedReceiver for Receiver<OT>
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious> RandomReceiver for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> RandomReceiver for Receiver<OT> {
     fn receive_random<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -289,7 +308,7 @@ impl<OT: OtSender<Msg = Block> + Malicious> RandomReceiver for Receiver<OT> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious> std::fmt::Display for Receiver<OT> {
+impl<OT: OtSender<Msg = Block>> std::fmt::Display for Receiver<OT> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "KOS Receiver")
     }
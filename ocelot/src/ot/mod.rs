@@ -8,6 +8,8 @@
 //! * `chou_orlandi`: Chou-Orlandi malicious OT.
 //! * `alsz`: Asharov-Lindell-Schneider-Zohner semi-honest OT extension (+ correlated and random OT).
 //! * `kos`: Keller-Orsini-Scholl malicious OT extension (+ correlated and random OT).
+//! * `unsafe_insecure_test_ot`: the dummy OT, usable as the base OT of the
+//!   OT extensions in tests, with the `test-utils` feature.
 //!
 
 pub mod alsz;
@@ -16,6 +18,8 @@ pub mod dummy;
 pub mod kos;
 pub mod kos_delta;
 pub mod naor_pinkas;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod unsafe_insecure_test_ot;
 
 use crate::errors::Error;
 use rand::{CryptoRng, Rng};
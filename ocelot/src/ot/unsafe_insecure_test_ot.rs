@@ -0,0 +1,71 @@
+//! An **entirely insecure** base OT, to plug into the OT extensions of the
+//! maliciously secure protocols in tests. It is neither `SemiHonest` nor
+//! `Malicious`, so neither are the protocols built on it.
+//!
+//! The choice bits and the chosen messages are sent in the clear, see
+//! `dummy`. It only exists to make the tests of higher-level protocols fast
+//! and deterministic, and must never be used outside of tests.
+
+use crate::{
+    errors::Error,
+    ot::{dummy, Receiver as OtReceiver, Sender as OtSender},
+};
+use rand::{CryptoRng, Rng};
+use scuttlebutt::{AbstractChannel, Block};
+
+/// Insecure oblivious transfer sender, for testing only.
+pub struct Sender(dummy::Sender);
+/// Insecure oblivious transfer receiver, for testing only.
+pub struct Receiver(dummy::Receiver);
+
+impl OtSender for Sender {
+    type Msg = Block;
+
+    fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        Ok(Self(dummy::Sender::init(channel, rng)?))
+    }
+
+    fn send<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        inputs: &[(Block, Block)],
+        rng: &mut RNG,
+    ) -> Result<(), Error> {
+        self.0.send(channel, inputs, rng)
+    }
+}
+
+impl std::fmt::Display for Sender {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unsafe Insecure Test Sender")
+    }
+}
+
+impl OtReceiver for Receiver {
+    type Msg = Block;
+
+    fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        Ok(Self(dummy::Receiver::init(channel, rng)?))
+    }
+
+    fn receive<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        inputs: &[bool],
+        rng: &mut RNG,
+    ) -> Result<Vec<Block>, Error> {
+        self.0.receive(channel, inputs, rng)
+    }
+}
+
+impl std::fmt::Display for Receiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unsafe Insecure Test Receiver")
+    }
+}
@@ -1,14 +1,28 @@
 //! Implementation of the Weng-Yang-Katz-Wang random subfield VOLE protocol (cf.
 //! <https://eprint.iacr.org/2020/925>).
 
+mod base_ot;
 mod base_svole;
 mod copee;
+#[cfg(test)]
+pub(crate) mod deterministic;
 mod ggm_utils;
+mod hooks;
+mod pipelined;
+mod ring;
+mod setup;
 mod specialization;
 mod spsvole;
 mod svole;
 mod utils;
 
+pub use base_ot::{BaseOt, ChouOrlandiBaseOt};
+#[cfg(any(test, feature = "test-utils"))]
+pub use base_ot::UnsafeInsecureTestOt;
+pub use hooks::{CancellationToken, ExtendHooks, ExtendObserver, ExtendPhase};
+pub use pipelined::{PipelinedReceiver, PipelinedSender};
+pub use ring::{RingParams, RingReceiver, RingSender};
+pub use setup::{SetupReceiver, SetupSender};
 pub use svole::{
     LpnParams, Receiver, Sender, LPN_EXTEND_LARGE, LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL,
     LPN_SETUP_LARGE, LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
@@ -0,0 +1,43 @@
+//! Choice of the base OT run by the OT extensions of the sVOLE setup.
+
+#[cfg(any(test, feature = "test-utils"))]
+use crate::ot::unsafe_insecure_test_ot;
+use crate::ot::{
+    kos, ChouOrlandiReceiver, ChouOrlandiSender, Receiver as OtReceiver, Sender as OtSender,
+};
+use scuttlebutt::Block;
+
+/// A base OT protocol, given by its sender and receiver.
+///
+/// The sVOLE setup extends OTs in both directions, so both roles are needed
+/// by each party. The sVOLE is only `Malicious` when both roles are.
+pub trait BaseOt: 'static {
+    /// Base OT sender.
+    type Sender: OtSender<Msg = Block> + Send + Sync;
+    /// Base OT receiver.
+    type Receiver: OtReceiver<Msg = Block> + Send + Sync;
+}
+
+/// The Chou-Orlandi OT, the default base OT.
+pub struct ChouOrlandiBaseOt;
+
+impl BaseOt for ChouOrlandiBaseOt {
+    type Sender = ChouOrlandiSender;
+    type Receiver = ChouOrlandiReceiver;
+}
+
+/// The **entirely insecure** OT of `ocelot::ot::unsafe_insecure_test_ot`,
+/// which makes the setup fast and deterministic. For tests only, so the
+/// sVOLE using it is neither `SemiHonest` nor `Malicious`.
+#[cfg(any(test, feature = "test-utils"))]
+pub struct UnsafeInsecureTestOt;
+
+#[cfg(any(test, feature = "test-utils"))]
+impl BaseOt for UnsafeInsecureTestOt {
+    type Sender = unsafe_insecure_test_ot::Sender;
+    type Receiver = unsafe_insecure_test_ot::Receiver;
+}
+
+// KOS OT extensions using the base OT `B`.
+pub(super) type OtExtSender<B> = kos::Sender<<B as BaseOt>::Receiver>;
+pub(super) type OtExtReceiver<B> = kos::Receiver<<B as BaseOt>::Sender>;
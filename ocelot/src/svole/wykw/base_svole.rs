@@ -2,6 +2,7 @@
 //! <https://eprint.iacr.org/2020/925>, Figure 5).
 
 use super::{
+    base_ot::{BaseOt, ChouOrlandiBaseOt, OtExtReceiver, OtExtSender},
     copee::{CopeeReceiver, CopeeSender},
     utils::Powers,
 };
@@ -16,24 +17,38 @@ use scuttlebutt::{
 };
 use std::marker::PhantomData;
 
-pub(super) struct Sender<FE: FF, S: FiniteFieldSpecialization<FE>> {
-    copee: CopeeSender<FE>,
+pub(super) struct Sender<FE: FF, S: FiniteFieldSpecialization<FE>, B: BaseOt = ChouOrlandiBaseOt> {
+    copee: CopeeSender<FE, B>,
     pows: Powers<FE>,
     phantom: PhantomData<S>,
 }
 
-pub struct Receiver<FE: FF> {
-    copee: CopeeReceiver<FE>,
+pub struct Receiver<FE: FF, B: BaseOt = ChouOrlandiBaseOt> {
+    copee: CopeeReceiver<FE, B>,
     pows: Powers<FE>,
 }
 
-impl<FE: FF, S: FiniteFieldSpecialization<FE>> Sender<FE, S> {
+impl<FE: FF, S: FiniteFieldSpecialization<FE>, B: BaseOt> Sender<FE, S, B> {
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
         rng: &mut RNG,
     ) -> Result<Self, Error> {
-        let copee = CopeeSender::<FE>::init(channel, pows.clone(), rng)?;
+        let copee = CopeeSender::<FE, B>::init(channel, pows.clone(), rng)?;
+        Ok(Self {
+            copee,
+            pows,
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        ot: &mut OtExtSender<B>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let copee = CopeeSender::<FE, B>::init_with_ot(channel, pows.clone(), ot, rng)?;
         Ok(Self {
             copee,
             pows,
@@ -75,13 +90,22 @@ impl<FE: FF, S: FiniteFieldSpecialization<FE>> Sender<FE, S> {
     }
 }
 
-impl<FE: FF> Receiver<FE> {
+impl<FE: FF, B: BaseOt> Receiver<FE, B> {
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
         rng: &mut RNG,
     ) -> Result<Self, Error> {
-        let cp = CopeeReceiver::<FE>::init(channel, pows.clone(), rng)?;
+        let cp = CopeeReceiver::<FE, B>::init(channel, pows.clone(), rng)?;
+        Ok(Self { copee: cp, pows })
+    }
+    pub fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        ot: &mut OtExtReceiver<B>,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let cp = CopeeReceiver::<FE, B>::init_with_ot(channel, pows.clone(), ot, rng)?;
         Ok(Self { copee: cp, pows })
     }
     pub fn delta(&self) -> FE {
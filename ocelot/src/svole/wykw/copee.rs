@@ -1,22 +1,25 @@
 //! Implementation of the Weng-Yang-Katz-Wang COPEe protocol (cf.
 //! <https://eprint.iacr.org/2020/925>, Figure 15).
 
-use super::utils::Powers;
+use super::{
+    base_ot::{ChouOrlandiBaseOt, OtExtReceiver, OtExtSender},
+    utils::Powers,
+};
 use crate::{
     errors::Error,
-    ot::{KosReceiver, KosSender, RandomReceiver as ROTReceiver, RandomSender as ROTSender},
+    ot::{RandomReceiver as ROTReceiver, RandomSender as ROTSender},
 };
 use generic_array::{typenum::Unsigned, GenericArray};
 use rand::{CryptoRng, Rng};
 use scuttlebutt::{
     field::{Degree, FiniteField as FF},
     ring::FiniteRing,
-    AbstractChannel, Aes128, Block, Malicious,
+    AbstractChannel, Aes128, Block,
 };
 use std::marker::PhantomData;
 use subtle::{Choice, ConditionallySelectable};
 
-pub struct Sender<ROT: ROTSender + Malicious, FE: FF> {
+pub struct Sender<ROT: ROTSender, FE: FF> {
     _ot: PhantomData<ROT>,
     aes_objs: Vec<(Aes128, Aes128)>,
     pows: Powers<FE>,
@@ -25,7 +28,7 @@ pub struct Sender<ROT: ROTSender + Malicious, FE: FF> {
     counter: u64,
 }
 
-pub struct Receiver<ROT: ROTReceiver + Malicious, FE: FF> {
+pub struct Receiver<ROT: ROTReceiver, FE: FF> {
     _ot: PhantomData<ROT>,
     delta: FE,
     choices: GenericArray<bool, FE::NumberOfBitsInBitDecomposition>,
@@ -36,8 +39,8 @@ pub struct Receiver<ROT: ROTReceiver + Malicious, FE: FF> {
     counter: u64,
 }
 
-pub type CopeeSender<FE> = Sender<KosSender, FE>;
-pub type CopeeReceiver<FE> = Receiver<KosReceiver, FE>;
+pub(super) type CopeeSender<FE, B = ChouOrlandiBaseOt> = Sender<OtExtSender<B>, FE>;
+pub(super) type CopeeReceiver<FE, B = ChouOrlandiBaseOt> = Receiver<OtExtReceiver<B>, FE>;
 
 // Uses `Aes128` as a pseudo-random function.
 fn prf<FE: FF>(aes: &Aes128, pt: Block) -> FE::PrimeField {
@@ -45,14 +48,25 @@ fn prf<FE: FF>(aes: &Aes128, pt: Block) -> FE::PrimeField {
     FE::PrimeField::from_uniform_bytes(&<[u8; 16]>::from(seed))
 }
 
-impl<ROT: ROTSender<Msg = Block> + Malicious, FE: FF> Sender<ROT, FE> {
+impl<ROT: ROTSender<Msg = Block>, FE: FF> Sender<ROT, FE> {
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
         mut rng: &mut RNG,
     ) -> Result<Self, Error> {
         let mut ot = ROT::init(channel, &mut rng)?;
-        let nbits = <FE::PrimeField as FF>::NumberOfBitsInBitDecomposition::USIZE;
+        Self::init_with_ot(channel, pows, &mut ot, rng)
+    }
+
+    // Runs the initialization using random OTs extended from `ot`, which
+    // can be shared by several instances.
+    pub fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        ot: &mut ROT,
+        mut rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let nbits = FE::PrimeField::bit_decomposition_len();
         let r = Degree::<FE>::USIZE;
         let keys = ot.send_random(channel, nbits * r, &mut rng)?;
         let aes_objs: Vec<(Aes128, Aes128)> = keys
@@ -99,14 +113,24 @@ impl<ROT: ROTSender<Msg = Block> + Malicious, FE: FF> Sender<ROT, FE> {
     }
 }
 
-impl<ROT: ROTReceiver<Msg = Block> + Malicious, FE: FF> Receiver<ROT, FE> {
+impl<ROT: ROTReceiver<Msg = Block>, FE: FF> Receiver<ROT, FE> {
     pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
         mut rng: &mut RNG,
     ) -> Result<Self, Error> {
-        let nbits = <FE::PrimeField as FF>::NumberOfBitsInBitDecomposition::USIZE;
         let mut ot = ROT::init(channel, &mut rng)?;
+        Self::init_with_ot(channel, pows, &mut ot, rng)
+    }
+
+    // Counterpart of `Sender::init_with_ot`, a fresh `delta` is sampled.
+    pub fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        ot: &mut ROT,
+        mut rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let nbits = FE::PrimeField::bit_decomposition_len();
         let delta = FE::random(&mut rng);
         let choices = delta.bit_decomposition();
         let mut acc = FE::ONE;
@@ -116,7 +140,9 @@ impl<ROT: ROTReceiver<Msg = Block> + Malicious, FE: FF> Receiver<ROT, FE> {
             *item = acc;
             acc *= two;
         }
-        let keys = ot.receive_random(channel, &choices, &mut rng)?;
+        // Only the bits which may be non-zero are sent by OT.
+        let num_choices = nbits * Degree::<FE>::USIZE;
+        let keys = ot.receive_random(channel, &choices[..num_choices], &mut rng)?;
         let aes_objs = keys.iter().map(|k| Aes128::new(*k)).collect();
         Ok(Self {
             _ot: PhantomData::<ROT>,
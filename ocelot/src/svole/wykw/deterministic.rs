@@ -0,0 +1,215 @@
+//! Deterministic sVOLE, for tests.
+//!
+//! The sVOLE draws all its randomness, including the one of the base OTs,
+//! of the OT extensions and of the coin tossings, from the RNGs given to its
+//! initialization and extensions. [`SeededSender`] and [`SeededReceiver`]
+//! own an RNG seeded by the caller, so that two runs with the same seeds
+//! produce the same correlations and the same transcript. Both seeds are
+//! needed: the coin tossings mix the randomness of the two parties.
+//!
+//! Layers above the sVOLE become deterministic in the same way when both
+//! parties use seeded RNGs, as long as the extensions are not pipelined.
+
+use super::{BaseOt, ChouOrlandiBaseOt, LpnParams, Receiver, Sender};
+use crate::{
+    errors::Error,
+    svole::{SVoleReceiver, SVoleSender},
+};
+use rand::SeedableRng;
+use scuttlebutt::{field::FiniteField, AbstractChannel, AesRng, Block};
+
+/// sVOLE sender drawing its randomness from a seeded RNG.
+pub(crate) struct SeededSender<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
+    sender: Sender<FE, B>,
+    rng: AesRng,
+}
+
+impl<FE: FiniteField, B: BaseOt> SeededSender<FE, B> {
+    /// Initializes the sender with the RNG seeded by `seed`.
+    pub(crate) fn init<C: AbstractChannel>(
+        channel: &mut C,
+        seed: Block,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        let mut rng = AesRng::from_seed(seed);
+        let sender = Sender::init(channel, &mut rng, lpn_setup, lpn_extend)?;
+        Ok(Self { sender, rng })
+    }
+
+    /// Runs an extension.
+    pub(crate) fn send<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        output: &mut Vec<(FE::PrimeField, FE)>,
+    ) -> Result<(), Error> {
+        self.sender.send(channel, &mut self.rng, output)
+    }
+}
+
+/// sVOLE receiver drawing its randomness from a seeded RNG.
+pub(crate) struct SeededReceiver<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
+    receiver: Receiver<FE, B>,
+    rng: AesRng,
+}
+
+impl<FE: FiniteField, B: BaseOt> SeededReceiver<FE, B> {
+    /// Initializes the receiver with the RNG seeded by `seed`.
+    pub(crate) fn init<C: AbstractChannel>(
+        channel: &mut C,
+        seed: Block,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Self, Error> {
+        let mut rng = AesRng::from_seed(seed);
+        let receiver = Receiver::init(channel, &mut rng, lpn_setup, lpn_extend)?;
+        Ok(Self { receiver, rng })
+    }
+
+    /// Returns delta.
+    pub(crate) fn delta(&self) -> FE {
+        self.receiver.delta()
+    }
+
+    /// Runs an extension.
+    pub(crate) fn receive<C: AbstractChannel>(
+        &mut self,
+        channel: &mut C,
+        output: &mut Vec<FE>,
+    ) -> Result<(), Error> {
+        self.receiver.receive(channel, &mut self.rng, output)
+    }
+}
+
+mod tests {
+    use super::{SeededReceiver, SeededSender};
+    use crate::svole::wykw::{UnsafeInsecureTestOt, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use scuttlebutt::{
+        field::{F40b, F61p, FiniteField as FF},
+        serialization::CanonicalSerialize,
+        Block, HashChannel,
+    };
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    struct Run<FE: FF> {
+        delta: FE,
+        uws: Vec<(FE::PrimeField, FE)>,
+        vs: Vec<FE>,
+        transcript: [u8; 32],
+    }
+
+    // Runs the initialization and two extensions with the given seeds.
+    fn run<FE: FF>(sender_seed: Block, receiver_seed: Block) -> Run<FE> {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = HashChannel::new(reader, writer);
+            let mut vole = SeededSender::<FE, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                sender_seed,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let mut uws = Vec::new();
+            let mut out = Vec::new();
+            for _ in 0..2 {
+                vole.send(&mut channel, &mut out).unwrap();
+                uws.append(&mut out);
+            }
+            uws
+        });
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = HashChannel::new(reader, writer);
+        let mut vole = SeededReceiver::<FE, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            receiver_seed,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut vs = Vec::new();
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            vole.receive(&mut channel, &mut out).unwrap();
+            vs.append(&mut out);
+        }
+        let uws = handle.join().unwrap();
+        for ((u, w), v) in uws.iter().zip(vs.iter()) {
+            assert_eq!(*w, *u * vole.delta() + *v);
+        }
+        Run {
+            delta: vole.delta(),
+            uws,
+            vs,
+            transcript: channel.finish(),
+        }
+    }
+
+    fn test_deterministic_svole<FE: FF>() {
+        let seed0 = Block::from(1u128);
+        let seed1 = Block::from(2u128);
+        let a = run::<FE>(seed0, seed1);
+        let b = run::<FE>(seed0, seed1);
+        assert_eq!(a.delta, b.delta);
+        assert_eq!(a.uws, b.uws);
+        assert_eq!(a.vs, b.vs);
+        assert_eq!(a.transcript, b.transcript);
+
+        // each seed changes the correlations
+        let c = run::<FE>(seed1, seed1);
+        assert_ne!(a.vs, c.vs);
+        assert_ne!(a.transcript, c.transcript);
+        let d = run::<FE>(seed0, seed0);
+        assert_ne!(a.delta, d.delta);
+        assert_ne!(a.transcript, d.transcript);
+    }
+
+    // Checks that the runs with the seeds `1` and `2` give the delta, the
+    // first correlation and the transcript digest below, in hex. They only
+    // change along with the protocol or its wire format.
+    fn test_known_answer_svole<FE: FF>(delta: &str, uw: (&str, &str), v: &str, transcript: &str) {
+        let run = run::<FE>(Block::from(1u128), Block::from(2u128));
+        assert_eq!(hex::encode(run.delta.to_bytes()), delta);
+        let (u, w) = run.uws[0];
+        assert_eq!(
+            (hex::encode(u.to_bytes()), hex::encode(w.to_bytes())),
+            (uw.0.to_string(), uw.1.to_string())
+        );
+        assert_eq!(hex::encode(run.vs[0].to_bytes()), v);
+        assert_eq!(hex::encode(run.transcript), transcript);
+    }
+
+    #[test]
+    fn test_deterministic_svole_f61p() {
+        test_deterministic_svole::<F61p>();
+    }
+
+    #[test]
+    fn test_deterministic_svole_f40b() {
+        test_deterministic_svole::<F40b>();
+    }
+
+    #[test]
+    fn test_known_answer_svole_f61p() {
+        test_known_answer_svole::<F61p>(
+            "e6dd24e218f46b0d",
+            ("a4cf1b43730cee1e", "424f33f2cb625b13"),
+            "346c2d09bfe03f10",
+            "c20fc4c311e59202da4800337cf5a6a4308cdf307882d0e9c2b1e0650a9de435",
+        );
+    }
+
+    #[test]
+    fn test_known_answer_svole_f40b() {
+        test_known_answer_svole::<F40b>(
+            "e0dd24e218",
+            ("00", "58e40b6b07"),
+            "58e40b6b07",
+            "a6024ce227ad399864787e01eddf601081c2fa34ee410217e85cf7cfb549ee31",
+        );
+    }
+}
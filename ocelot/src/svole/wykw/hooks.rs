@@ -0,0 +1,101 @@
+//! Progress reporting and cancellation of the sVOLE extensions.
+//!
+//! An extension checks its cancellation token before it starts, in which
+//! case nothing has been sent and the instance is left untouched, and then
+//! at every progress report. Once it has started, an interrupted extension
+//! leaves the two parties out of sync: the instance is poisoned and every
+//! later use fails with `Error::Poisoned`.
+
+use crate::errors::Error;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Phase of an sVOLE extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtendPhase {
+    /// The single-point sVOLEs, which carry all the communication of the
+    /// extension.
+    SinglePoint,
+    /// The local multiplication by the LPN matrix.
+    Lpn,
+}
+
+/// Token cancelling the extensions it is attached to. Its clones share the
+/// cancellation.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token, not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the extensions using the token. This cannot be undone.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Observer of the progress of the extensions, called with the current
+/// phase and the fraction of this phase completed, in `[0, 1]`.
+pub type ExtendObserver = Arc<dyn Fn(ExtendPhase, f64) + Send + Sync>;
+
+/// Hooks attached to the extensions of an sVOLE instance, none by default.
+///
+/// The hooks are inherited by the duplicates of the instance.
+#[derive(Clone, Default)]
+pub struct ExtendHooks {
+    observer: Option<ExtendObserver>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl ExtendHooks {
+    /// Creates hooks without observer nor cancellation token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the progress observer.
+    pub fn with_observer<F: Fn(ExtendPhase, f64) + Send + Sync + 'static>(
+        mut self,
+        observer: F,
+    ) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Sets the cancellation token.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    // Fails with `Error::Cancelled` if the token was cancelled.
+    pub(super) fn check_cancelled(&self) -> Result<(), Error> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(Error::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    // Reports the progress to the observer.
+    pub(super) fn notify(&self, phase: ExtendPhase, fraction: f64) {
+        if let Some(observer) = &self.observer {
+            observer(phase, fraction);
+        }
+    }
+
+    // Reports the progress to the observer, and then checks the token.
+    pub(super) fn report(&self, phase: ExtendPhase, fraction: f64) -> Result<(), Error> {
+        self.notify(phase, fraction);
+        self.check_cancelled()
+    }
+}
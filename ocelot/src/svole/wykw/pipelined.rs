@@ -0,0 +1,232 @@
+//! Pipelined sVOLE extension.
+//!
+//! The extension runs on a dedicated thread, over a dedicated channel, and
+//! the batches of VOLEs it produces are handed to the consumer through a
+//! bounded queue. The next batches are thus produced while the current one
+//! is consumed, instead of stalling the protocol when it runs out of VOLEs.
+//!
+//! The dedicated channel must not be used by anything else: its traffic
+//! would otherwise interleave with the extension messages. The sender and
+//! the receiver produce their batches in the same order, so that the `i`-th
+//! batch of the sender matches the `i`-th batch of the receiver.
+//!
+//! When an extension fails, the error is handed to the consumer in place of
+//! the batch and the thread stops, which closes the dedicated channel and
+//! stops the thread of the other party in turn. Dropping the pipeline stops
+//! the thread after its current extension, without waiting for it.
+
+use super::{BaseOt, Receiver, Sender};
+use crate::errors::Error;
+use crate::svole::{SVoleReceiver, SVoleSender};
+use scuttlebutt::{field::FiniteField, AbstractChannel, AesRng};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{sync_channel, Receiver as QueueReceiver},
+    Arc,
+};
+
+// Bounded queue of batches filled by a background thread.
+struct Pipeline<T> {
+    queue: Option<QueueReceiver<Result<T, Error>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    fn spawn<C, F>(mut channel: C, depth: usize, mut extend: F) -> Self
+    where
+        C: AbstractChannel + Send + 'static,
+        F: FnMut(&mut C, &mut AesRng) -> Result<T, Error> + Send + 'static,
+    {
+        let (tx, rx) = sync_channel(depth);
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = stop.clone();
+        std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            while !stop_thread.load(Ordering::Relaxed) {
+                let batch = extend(&mut channel, &mut rng);
+                let failed = batch.is_err();
+                // the consumer is gone when the queue is disconnected
+                if tx.send(batch).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self {
+            queue: Some(rx),
+            stop,
+        }
+    }
+
+    fn next(&mut self) -> Result<T, Error> {
+        match self.queue.as_ref().map(|queue| queue.recv()) {
+            Some(Ok(batch)) => batch,
+            _ => Err(Error::Other(
+                "pipelined sVOLE extension stopped".to_string(),
+            )),
+        }
+    }
+}
+
+impl<E: Send + 'static> Pipeline<Vec<E>> {
+    // Concatenates the next batches until they hold at least `num` elements.
+    fn next_at_least(&mut self, num: usize) -> Result<Vec<E>, Error> {
+        let mut out = Vec::new();
+        while out.len() < num {
+            out.append(&mut self.next()?);
+        }
+        Ok(out)
+    }
+}
+
+impl<T> Drop for Pipeline<T> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // unblocks the thread if it is waiting on a full queue
+        self.queue = None;
+    }
+}
+
+/// sVOLE sender extending in the background, see the module documentation.
+pub struct PipelinedSender<FE: FiniteField> {
+    pipeline: Pipeline<Vec<(FE::PrimeField, FE)>>,
+}
+
+impl<FE: FiniteField> PipelinedSender<FE> {
+    /// Run the extensions of `sender` on a background thread over
+    /// `channel`, keeping at most `depth` batches ahead of the consumer.
+    ///
+    /// The receiver must be spawned with [`PipelinedReceiver::spawn`] on
+    /// the other end of `channel`.
+    pub fn spawn<B: BaseOt, C: AbstractChannel + Send + 'static>(
+        mut sender: Sender<FE, B>,
+        channel: C,
+        depth: usize,
+    ) -> Self {
+        let pipeline = Pipeline::spawn(channel, depth, move |channel, rng| {
+            let mut out = Vec::new();
+            sender.send(channel, rng, &mut out)?;
+            Ok(out)
+        });
+        Self { pipeline }
+    }
+
+    /// Returns the next batch of VOLEs, waiting for it if it is not ready.
+    pub fn next_batch(&mut self) -> Result<Vec<(FE::PrimeField, FE)>, Error> {
+        self.pipeline.next()
+    }
+
+    /// Returns the next batches of VOLEs, at least `num` VOLEs in total.
+    pub fn next_at_least(&mut self, num: usize) -> Result<Vec<(FE::PrimeField, FE)>, Error> {
+        self.pipeline.next_at_least(num)
+    }
+}
+
+/// sVOLE receiver extending in the background, see the module
+/// documentation.
+pub struct PipelinedReceiver<FE: FiniteField> {
+    delta: FE,
+    pipeline: Pipeline<Vec<FE>>,
+}
+
+impl<FE: FiniteField> PipelinedReceiver<FE> {
+    /// Run the extensions of `receiver` on a background thread over
+    /// `channel`, keeping at most `depth` batches ahead of the consumer.
+    ///
+    /// The sender must be spawned with [`PipelinedSender::spawn`] on the
+    /// other end of `channel`.
+    pub fn spawn<B: BaseOt, C: AbstractChannel + Send + 'static>(
+        mut receiver: Receiver<FE, B>,
+        channel: C,
+        depth: usize,
+    ) -> Self {
+        let delta = receiver.delta();
+        let pipeline = Pipeline::spawn(channel, depth, move |channel, rng| {
+            let mut out = Vec::new();
+            receiver.receive(channel, rng, &mut out)?;
+            Ok(out)
+        });
+        Self { delta, pipeline }
+    }
+
+    /// Returns delta.
+    pub fn delta(&self) -> FE {
+        self.delta
+    }
+
+    /// Returns the next batch of VOLEs, waiting for it if it is not ready.
+    pub fn next_batch(&mut self) -> Result<Vec<FE>, Error> {
+        self.pipeline.next()
+    }
+
+    /// Returns the next batches of VOLEs, at least `num` VOLEs in total.
+    pub fn next_at_least(&mut self, num: usize) -> Result<Vec<FE>, Error> {
+        self.pipeline.next_at_least(num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PipelinedReceiver, PipelinedSender};
+    use crate::svole::wykw::{Receiver, Sender, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use crate::svole::{SVoleReceiver, SVoleSender};
+    use scuttlebutt::{
+        field::{F40b, F61p, FiniteField as FF},
+        AesRng, Channel, SyncChannel,
+    };
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    fn test_pipelined_svole<FE: FF>() {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let (sender_ext, receiver_ext) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = Channel::new(reader, writer);
+            let vole: Sender<FE> =
+                Sender::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL).unwrap();
+            let channel_ext = SyncChannel::new(
+                BufReader::new(sender_ext.try_clone().unwrap()),
+                BufWriter::new(sender_ext),
+            );
+            let mut pipeline = PipelinedSender::spawn(vole, channel_ext, 2);
+            let mut uws = Vec::new();
+            for _ in 0..3 {
+                uws.extend(pipeline.next_batch().unwrap());
+            }
+            uws
+        });
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = Channel::new(reader, writer);
+        let vole: Receiver<FE> =
+            Receiver::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL).unwrap();
+        let channel_ext = SyncChannel::new(
+            BufReader::new(receiver_ext.try_clone().unwrap()),
+            BufWriter::new(receiver_ext),
+        );
+        let mut pipeline = PipelinedReceiver::spawn(vole, channel_ext, 2);
+        let mut vs = Vec::new();
+        for _ in 0..3 {
+            vs.extend(pipeline.next_batch().unwrap());
+        }
+        let uws = handle.join().unwrap();
+        assert_eq!(uws.len(), vs.len());
+        for i in 0..uws.len() {
+            let right = uws[i].0 * pipeline.delta() + vs[i];
+            assert_eq!(uws[i].1, right);
+        }
+    }
+
+    #[test]
+    fn test_pipelined_svole_f61p() {
+        test_pipelined_svole::<F61p>();
+    }
+
+    #[test]
+    fn test_pipelined_svole_f40b() {
+        test_pipelined_svole::<F40b>();
+    }
+}
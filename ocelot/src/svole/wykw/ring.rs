@@ -0,0 +1,368 @@
+//! Random VOLE over the ring `Z_{2^{k+s}}`, as needed by SPDZ2k-style
+//! authenticated sharings (cf. <https://eprint.iacr.org/2018/482>).
+//!
+//! The LPN-based extension of the field sVOLE relies on field arithmetic in
+//! its consistency checks, so it does not port directly to rings. This
+//! variant instead runs the COPEe protocol (cf.
+//! <https://eprint.iacr.org/2020/925>, Figure 15) over `Z_{2^{k+s}}` for
+//! every VOLE, with a MAC key `Δ ∈ Z_{2^s}`, and checks each batch with a
+//! random linear combination with coefficients in `Z_{2^s}` as in the base
+//! sVOLE. Following the analysis of the SPDZ2k MAC check, a cheating sender
+//! passes the check with probability about `2^{-s + log s}`, and the
+//! correlation `w = u Δ + v` is then only guaranteed modulo `2^k`.
+//!
+//! Elements of `Z_{2^{k+s}}` are represented as `u128` reduced modulo
+//! `2^{k+s}`, which restricts this first version to `k + s <= 128`.
+
+use crate::{
+    errors::Error,
+    ot::{
+        KosReceiver, KosSender, RandomReceiver as ROTReceiver, RandomSender as ROTSender,
+        Receiver as OtReceiver, Sender as OtSender,
+    },
+};
+use rand::{CryptoRng, Rng, SeedableRng};
+use scuttlebutt::{AbstractChannel, Aes128, AesRng, Block};
+
+/// Parameters of a VOLE over `Z_{2^{k+s}}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RingParams {
+    /// Number of bits `k` of the ring the correlation is guaranteed over.
+    k: usize,
+    /// Statistical security parameter `s`, also the size of `Δ` in bits.
+    s: usize,
+    /// Number of VOLEs produced by an extension.
+    batch: usize,
+}
+
+impl RingParams {
+    /// Creates parameters for VOLEs over `Z_{2^{k+s}}`, produced `batch` at
+    /// a time. Returns an error if `k` or `s` is zero, if `k + s > 128`, or if
+    /// `batch` is zero.
+    pub fn new(k: usize, s: usize, batch: usize) -> Result<Self, Error> {
+        if k == 0 || s == 0 {
+            return Err(Error::Other(format!(
+                "invalid ring VOLE parameters: k = {} and s = {} must be positive",
+                k, s
+            )));
+        }
+        if k + s > 128 {
+            return Err(Error::Other(format!(
+                "invalid ring VOLE parameters: k + s = {} exceeds 128 bits",
+                k + s
+            )));
+        }
+        if batch == 0 {
+            return Err(Error::Other(
+                "invalid ring VOLE parameters: the batch size must be positive".to_string(),
+            ));
+        }
+        Ok(Self { k, s, batch })
+    }
+
+    /// Returns `k`.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns `s`.
+    pub fn s(&self) -> usize {
+        self.s
+    }
+
+    /// Returns the number of VOLEs produced by an extension.
+    pub fn batch(&self) -> usize {
+        self.batch
+    }
+
+    /// Returns the mask reducing a `u128` modulo `2^{k+s}`.
+    pub fn modulus_mask(&self) -> u128 {
+        bit_mask(self.k + self.s)
+    }
+
+    fn nbytes(&self) -> usize {
+        (self.k + self.s + 7) / 8
+    }
+}
+
+fn bit_mask(nbits: usize) -> u128 {
+    if nbits == 128 {
+        u128::MAX
+    } else {
+        (1u128 << nbits) - 1
+    }
+}
+
+// Uses `Aes128` as a pseudo-random function.
+fn prf(aes: &Aes128, counter: u64, mask: u128) -> u128 {
+    u128::from(aes.encrypt(Block::from(counter as u128))) & mask
+}
+
+fn write_elem<C: AbstractChannel>(
+    channel: &mut C,
+    params: &RingParams,
+    x: u128,
+) -> Result<(), Error> {
+    channel.write_bytes(&x.to_le_bytes()[..params.nbytes()])?;
+    Ok(())
+}
+
+fn read_elem<C: AbstractChannel>(channel: &mut C, params: &RingParams) -> Result<u128, Error> {
+    let mut bytes = [0u8; 16];
+    channel.read_bytes(&mut bytes[..params.nbytes()])?;
+    Ok(u128::from_le_bytes(bytes) & params.modulus_mask())
+}
+
+/// VOLE sender over `Z_{2^{k+s}}`, producing pairs `(u, w)`.
+pub struct RingSender {
+    params: RingParams,
+    aes_objs: Vec<(Aes128, Aes128)>,
+    counter: u64,
+}
+
+impl RingSender {
+    /// Runs the one-time initialization.
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        params: RingParams,
+    ) -> Result<Self, Error> {
+        let mut ot = KosSender::init(channel, rng)?;
+        let keys = ot.send_random(channel, params.s, rng)?;
+        let aes_objs = keys
+            .iter()
+            .map(|(k0, k1)| (Aes128::new(*k0), Aes128::new(*k1)))
+            .collect();
+        Ok(Self {
+            params,
+            aes_objs,
+            counter: 0,
+        })
+    }
+
+    /// Returns the parameters of the instance.
+    pub fn params(&self) -> RingParams {
+        self.params
+    }
+
+    fn cope<C: AbstractChannel>(&mut self, channel: &mut C, u: u128) -> Result<u128, Error> {
+        let mask = self.params.modulus_mask();
+        let mut w = 0u128;
+        for (i, (aes0, aes1)) in self.aes_objs.iter().enumerate() {
+            let a0 = prf(aes0, self.counter, mask);
+            let a1 = prf(aes1, self.counter, mask);
+            write_elem(
+                channel,
+                &self.params,
+                a0.wrapping_sub(a1).wrapping_sub(u) & mask,
+            )?;
+            w = w.wrapping_add(a0 << i);
+        }
+        self.counter += 1;
+        Ok(w & mask)
+    }
+
+    /// Generates `params.batch()` VOLEs, with `u` and `w` reduced modulo
+    /// `2^{k+s}`.
+    pub fn send<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        out: &mut Vec<(u128, u128)>,
+    ) -> Result<(), Error> {
+        let mask = self.params.modulus_mask();
+        out.clear();
+        out.reserve(self.params.batch);
+        for _ in 0..self.params.batch {
+            let u = rng.gen::<u128>() & mask;
+            let w = self.cope(channel, u)?;
+            out.push((u, w));
+        }
+        // The extra VOLE masks the linear combination of the check.
+        let mut x = rng.gen::<u128>() & mask;
+        let mut z = self.cope(channel, x)?;
+        channel.flush()?;
+        let seed = channel.read_block()?;
+        let mut rng_chi = AesRng::from_seed(seed);
+        let chi_mask = bit_mask(self.params.s);
+        for (u, w) in out.iter() {
+            let chi = rng_chi.gen::<u128>() & chi_mask;
+            x = x.wrapping_add(chi.wrapping_mul(*u));
+            z = z.wrapping_add(chi.wrapping_mul(*w));
+        }
+        write_elem(channel, &self.params, x & mask)?;
+        write_elem(channel, &self.params, z & mask)?;
+        channel.flush()?;
+        Ok(())
+    }
+}
+
+/// VOLE receiver over `Z_{2^{k+s}}`, holding `Δ` and producing `v`.
+pub struct RingReceiver {
+    params: RingParams,
+    delta: u128,
+    choices: Vec<bool>,
+    aes_objs: Vec<Aes128>,
+    counter: u64,
+}
+
+impl RingReceiver {
+    /// Runs the one-time initialization, sampling `Δ`.
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        params: RingParams,
+    ) -> Result<Self, Error> {
+        let mut ot = KosReceiver::init(channel, rng)?;
+        let delta = rng.gen::<u128>() & bit_mask(params.s);
+        let choices: Vec<bool> = (0..params.s).map(|i| (delta >> i) & 1 == 1).collect();
+        let keys = ot.receive_random(channel, &choices, rng)?;
+        let aes_objs = keys.iter().map(|k| Aes128::new(*k)).collect();
+        Ok(Self {
+            params,
+            delta,
+            choices,
+            aes_objs,
+            counter: 0,
+        })
+    }
+
+    /// Returns the parameters of the instance.
+    pub fn params(&self) -> RingParams {
+        self.params
+    }
+
+    /// Returns `Δ`, an element of `Z_{2^s}`.
+    pub fn delta(&self) -> u128 {
+        self.delta
+    }
+
+    fn cope<C: AbstractChannel>(&mut self, channel: &mut C) -> Result<u128, Error> {
+        let mask = self.params.modulus_mask();
+        let mut v = 0u128;
+        for (i, (aes, choice)) in self.aes_objs.iter().zip(self.choices.iter()).enumerate() {
+            let d = read_elem(channel, &self.params)?;
+            let mut q = prf(aes, self.counter, mask);
+            if *choice {
+                q = q.wrapping_add(d);
+            }
+            v = v.wrapping_add(q << i);
+        }
+        self.counter += 1;
+        Ok(v & mask)
+    }
+
+    /// Generates `params.batch()` VOLEs, with `v` reduced modulo `2^{k+s}`.
+    pub fn receive<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        out: &mut Vec<u128>,
+    ) -> Result<(), Error> {
+        let mask = self.params.modulus_mask();
+        out.clear();
+        out.reserve(self.params.batch);
+        for _ in 0..self.params.batch {
+            let v = self.cope(channel)?;
+            out.push(v);
+        }
+        let mut y = self.cope(channel)?;
+        let seed = rng.gen::<Block>();
+        channel.write_block(&seed)?;
+        channel.flush()?;
+        let mut rng_chi = AesRng::from_seed(seed);
+        let chi_mask = bit_mask(self.params.s);
+        for v in out.iter() {
+            let chi = rng_chi.gen::<u128>() & chi_mask;
+            y = y.wrapping_add(chi.wrapping_mul(*v));
+        }
+        let x = read_elem(channel, &self.params)?;
+        let z = read_elem(channel, &self.params)?;
+        if z == x.wrapping_mul(self.delta).wrapping_add(y) & mask {
+            Ok(())
+        } else {
+            Err(Error::CorrelationCheckFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RingParams, RingReceiver, RingSender};
+    use scuttlebutt::{AesRng, Channel};
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    fn test_ring_vole_(params: RingParams) {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = Channel::new(reader, writer);
+            let mut vole = RingSender::init(&mut channel, &mut rng, params).unwrap();
+            let mut uws = Vec::new();
+            let mut out = Vec::new();
+            for _ in 0..2 {
+                vole.send(&mut channel, &mut rng, &mut out).unwrap();
+                uws.extend(out.iter().copied());
+            }
+            uws
+        });
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = Channel::new(reader, writer);
+        let mut vole = RingReceiver::init(&mut channel, &mut rng, params).unwrap();
+        let mut vs = Vec::new();
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            vole.receive(&mut channel, &mut rng, &mut out).unwrap();
+            vs.extend(out.iter().copied());
+        }
+        let uws = handle.join().unwrap();
+        assert_eq!(uws.len(), 2 * params.batch());
+        assert_eq!(vs.len(), 2 * params.batch());
+
+        let mask = params.modulus_mask();
+        assert!(vole.delta() < 1u128 << params.s());
+        for ((u, w), v) in uws.iter().zip(vs.iter()) {
+            assert_eq!(*u & mask, *u);
+            assert_eq!(*w & mask, *w);
+            assert_eq!(*v & mask, *v);
+            assert_eq!(*w, u.wrapping_mul(vole.delta()).wrapping_add(*v) & mask);
+        }
+    }
+
+    #[test]
+    fn test_ring_vole_z2_128() {
+        test_ring_vole_(RingParams::new(64, 64, 1_000).unwrap());
+    }
+
+    #[test]
+    fn test_ring_vole_z2_64() {
+        test_ring_vole_(RingParams::new(32, 32, 1_000).unwrap());
+    }
+
+    #[test]
+    fn test_ring_vole_unaligned() {
+        test_ring_vole_(RingParams::new(61, 40, 500).unwrap());
+    }
+
+    #[test]
+    fn test_ring_params_validation() {
+        assert!(RingParams::new(64, 64, 1).is_ok());
+        assert!(RingParams::new(0, 64, 1).is_err());
+        assert!(RingParams::new(64, 0, 1).is_err());
+        assert!(RingParams::new(64, 65, 1).is_err());
+        assert!(RingParams::new(64, 40, 0).is_err());
+        assert_eq!(
+            RingParams::new(64, 64, 1).unwrap().modulus_mask(),
+            u128::MAX
+        );
+        assert_eq!(
+            RingParams::new(32, 32, 1).unwrap().modulus_mask(),
+            u64::MAX as u128
+        );
+    }
+}
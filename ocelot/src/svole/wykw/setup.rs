@@ -0,0 +1,281 @@
+//! Base OT setup shared by several sVOLE instances.
+//!
+//! The initialization of an sVOLE runs two OT extension setups, and thus two
+//! sets of 128 base OTs: one for the random OTs consumed by the COPEe
+//! protocol of the base sVOLE, where the sVOLE sender is the OT sender, and
+//! one owned by the single-point sVOLE, where the sVOLE sender is the OT
+//! receiver. [`SetupSender`] and [`SetupReceiver`] run these two setups once
+//! and derive any number of sVOLE instances from them.
+//!
+//! Each derived instance is independent of the others:
+//!   * the COPEe random OTs are extended from the shared instance, the
+//!     extended OTs of successive calls are independent as in any use of an
+//!     OT extension, so each instance gets a fresh `delta` and fresh base
+//!     sVOLEs;
+//!   * the OT extension of the single-point sVOLE is forked from the shared
+//!     one (see `ocelot::ot::kos::Sender::fork`), the seeds of the forked base OT RNGs being
+//!     outputs of the parent ones, so by PRG security the OTs extended by
+//!     two instances are independent;
+//!   * the GGM keys and the seed of the LPN matrix are drawn with fresh coin
+//!     tossings.
+//!
+//! Nothing in the setup depends on the field, so instances over different
+//! fields can be derived from the same setup.
+
+use super::{
+    base_ot::{BaseOt, ChouOrlandiBaseOt, OtExtReceiver, OtExtSender},
+    base_svole::{Receiver as BaseReceiver, Sender as BaseSender},
+    hooks::ExtendHooks,
+    specialization::NoSpecialization,
+    spsvole::{SpsReceiver, SpsSender},
+    svole::{check_lpn_params, LpnParams, Receiver, Sender},
+    utils::Powers,
+};
+use crate::{
+    errors::Error,
+    ot::{Receiver as OtReceiver, Sender as OtSender},
+};
+use rand::{CryptoRng, Rng};
+use scuttlebutt::{field::FiniteField, AbstractChannel};
+
+/// Base OT setup of the sVOLE sender, from which sVOLE senders are derived.
+/// The base OTs are run with `B`.
+pub struct SetupSender<B: BaseOt = ChouOrlandiBaseOt> {
+    // extends the random OTs of the COPEe protocols
+    copee_ot: OtExtSender<B>,
+    // forked for every single-point sVOLE
+    sps_ot: OtExtReceiver<B>,
+    hooks: ExtendHooks,
+}
+
+impl<B: BaseOt> SetupSender<B> {
+    /// Runs the base OT setup.
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let copee_ot = OtExtSender::<B>::init(channel, rng)?;
+        let sps_ot = OtExtReceiver::<B>::init(channel, rng)?;
+        Ok(Self {
+            copee_ot,
+            sps_ot,
+            hooks: ExtendHooks::default(),
+        })
+    }
+
+    /// Sets the hooks of the extensions of the instances derived from now
+    /// on, including the extension run by their initialization.
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.hooks = hooks;
+    }
+
+    /// Derives a new sVOLE sender over `FE`. The receiver must derive its
+    /// instance with [`SetupReceiver::derive`] at the same point of the
+    /// protocol.
+    pub fn derive<FE: FiniteField, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Sender<FE, B>, Error> {
+        check_lpn_params::<FE, C>(channel, lpn_setup, lpn_extend)?;
+        let pows: Powers<FE> = Default::default();
+        let base_sender = BaseSender::<FE, NoSpecialization, B>::init_with_ot(
+            channel,
+            pows.clone(),
+            &mut self.copee_ot,
+            rng,
+        )?;
+        let spsvole = SpsSender::<FE, B>::init_with_ot(channel, pows, self.sps_ot.fork(), rng)?;
+        Sender::init_internal(
+            channel,
+            rng,
+            base_sender,
+            spsvole,
+            lpn_setup,
+            lpn_extend,
+            self.hooks.clone(),
+        )
+    }
+
+    /// Derives `k` sVOLE senders over `FE`.
+    pub fn derive_many<FE: FiniteField, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        k: usize,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Vec<Sender<FE, B>>, Error> {
+        (0..k)
+            .map(|_| self.derive(channel, rng, lpn_setup, lpn_extend))
+            .collect()
+    }
+}
+
+/// Base OT setup of the sVOLE receiver, from which sVOLE receivers are
+/// derived. The base OTs are run with `B`.
+pub struct SetupReceiver<B: BaseOt = ChouOrlandiBaseOt> {
+    copee_ot: OtExtReceiver<B>,
+    sps_ot: OtExtSender<B>,
+    hooks: ExtendHooks,
+}
+
+impl<B: BaseOt> SetupReceiver<B> {
+    /// Runs the base OT setup.
+    pub fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
+        let copee_ot = OtExtReceiver::<B>::init(channel, rng)?;
+        let sps_ot = OtExtSender::<B>::init(channel, rng)?;
+        Ok(Self {
+            copee_ot,
+            sps_ot,
+            hooks: ExtendHooks::default(),
+        })
+    }
+
+    /// Sets the hooks of the extensions of the instances derived from now
+    /// on, including the extension run by their initialization.
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.hooks = hooks;
+    }
+
+    /// Derives a new sVOLE receiver over `FE`, with a fresh `delta`.
+    pub fn derive<FE: FiniteField, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Receiver<FE, B>, Error> {
+        check_lpn_params::<FE, C>(channel, lpn_setup, lpn_extend)?;
+        let pows: Powers<FE> = Default::default();
+        let base_receiver =
+            BaseReceiver::<FE, B>::init_with_ot(channel, pows.clone(), &mut self.copee_ot, rng)?;
+        let delta = base_receiver.delta();
+        let spsvole =
+            SpsReceiver::<FE, B>::init_with_ot(channel, pows, delta, self.sps_ot.fork(), rng)?;
+        Receiver::init_internal(
+            channel,
+            rng,
+            base_receiver,
+            spsvole,
+            lpn_setup,
+            lpn_extend,
+            self.hooks.clone(),
+        )
+    }
+
+    /// Derives `k` sVOLE receivers over `FE`.
+    pub fn derive_many<FE: FiniteField, C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        k: usize,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+    ) -> Result<Vec<Receiver<FE, B>>, Error> {
+        (0..k)
+            .map(|_| self.derive(channel, rng, lpn_setup, lpn_extend))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SetupReceiver, SetupSender};
+    use crate::svole::{
+        wykw::{LPN_EXTEND_SMALL, LPN_SETUP_SMALL},
+        SVoleReceiver, SVoleSender,
+    };
+    use scuttlebutt::{
+        field::{F40b, F61p},
+        AesRng, Channel,
+    };
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    #[test]
+    fn test_derived_svoles_are_independent() {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = Channel::new(reader, writer);
+            let mut setup: SetupSender = SetupSender::init(&mut channel, &mut rng).unwrap();
+            let voles = setup
+                .derive_many::<F61p, _, _>(
+                    &mut channel,
+                    &mut rng,
+                    2,
+                    LPN_SETUP_SMALL,
+                    LPN_EXTEND_SMALL,
+                )
+                .unwrap();
+            let mut outs = Vec::new();
+            for mut vole in voles {
+                let mut out = Vec::new();
+                vole.send(&mut channel, &mut rng, &mut out).unwrap();
+                outs.push(out);
+            }
+            let mut vole = setup
+                .derive::<F40b, _, _>(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+                .unwrap();
+            let mut out_f40b = Vec::new();
+            vole.send(&mut channel, &mut rng, &mut out_f40b).unwrap();
+            (outs, out_f40b)
+        });
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = Channel::new(reader, writer);
+        let mut setup: SetupReceiver = SetupReceiver::init(&mut channel, &mut rng).unwrap();
+        let mut voles = setup
+            .derive_many::<F61p, _, _>(&mut channel, &mut rng, 2, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+            .unwrap();
+        let mut vs = Vec::new();
+        for vole in voles.iter_mut() {
+            let mut out = Vec::new();
+            vole.receive(&mut channel, &mut rng, &mut out).unwrap();
+            vs.push(out);
+        }
+        let mut vole_f40b = setup
+            .derive::<F40b, _, _>(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL)
+            .unwrap();
+        let mut vs_f40b = Vec::new();
+        vole_f40b
+            .receive(&mut channel, &mut rng, &mut vs_f40b)
+            .unwrap();
+        let (uws, uws_f40b) = handle.join().unwrap();
+
+        // every instance is a correct sVOLE under its own delta
+        for (vole, (uws, vs)) in voles.iter().zip(uws.iter().zip(vs.iter())) {
+            for ((u, w), v) in uws.iter().zip(vs.iter()) {
+                assert_eq!(*w, *u * vole.delta() + *v);
+            }
+        }
+        for ((u, w), v) in uws_f40b.iter().zip(vs_f40b.iter()) {
+            assert_eq!(*w, *u * vole_f40b.delta() + *v);
+        }
+
+        // the instances share neither delta nor outputs
+        assert_ne!(voles[0].delta(), voles[1].delta());
+        let same_u = uws[0]
+            .iter()
+            .zip(uws[1].iter())
+            .filter(|((u0, _), (u1, _))| u0 == u1)
+            .count();
+        let same_v = vs[0]
+            .iter()
+            .zip(vs[1].iter())
+            .filter(|(v0, v1)| v0 == v1)
+            .count();
+        // collisions of random elements of a 61-bit field
+        assert!(same_u < 4, "{} equal u out of {}", same_u, uws[0].len());
+        assert!(same_v < 4, "{} equal v out of {}", same_v, vs[0].len());
+    }
+}
@@ -1,13 +1,14 @@
 //! Implementation of single-point sVOLE.
 
 use super::{
+    base_ot::{ChouOrlandiBaseOt, OtExtReceiver, OtExtSender},
     ggm_utils::{ggm, ggm_prime, GgmTemporaryStorage},
     specialization::NoSpecialization,
     utils::Powers,
 };
 use crate::{
     errors::Error,
-    ot::{KosReceiver, KosSender, Receiver as OtReceiver, Sender as OtSender},
+    ot::{Receiver as OtReceiver, Sender as OtSender},
 };
 use generic_array::typenum::Unsigned;
 use rand::{
@@ -19,17 +20,17 @@ use scuttlebutt::{
     field::{Degree, FiniteField as FF},
     ring::FiniteRing,
     utils::unpack_bits,
-    AbstractChannel, AesRng, Block, Malicious,
+    AbstractChannel, AesRng, Block,
 };
 use vectoreyes::{Aes128EncryptOnly, AesBlockCipher};
 
-pub(super) struct Sender<OT: OtReceiver + Malicious, FE: FF> {
+pub(super) struct Sender<OT: OtReceiver, FE: FF> {
     ot: OT,
     pows: Powers<FE>,
     ggm_seeds: (Aes128EncryptOnly, Aes128EncryptOnly),
 }
 
-pub(super) struct Receiver<OT: OtSender + Malicious, FE: FF> {
+pub(super) struct Receiver<OT: OtSender, FE: FF> {
     ot: OT,
     delta: FE,
     pows: Powers<FE>,
@@ -37,8 +38,8 @@ pub(super) struct Receiver<OT: OtSender + Malicious, FE: FF> {
     ggm_temporary_storage: GgmTemporaryStorage,
 }
 
-pub(super) type SpsSender<FE> = Sender<KosReceiver, FE>;
-pub(super) type SpsReceiver<FE> = Receiver<KosSender, FE>;
+pub(super) type SpsSender<FE, B = ChouOrlandiBaseOt> = Sender<OtExtReceiver<B>, FE>;
+pub(super) type SpsReceiver<FE, B = ChouOrlandiBaseOt> = Receiver<OtExtSender<B>, FE>;
 
 // Implementation of the EQ protocol functionality described in
 // <https://eprint.iacr.org/2020/925.pdf>, Page 30.
@@ -89,13 +90,23 @@ fn eq_receive<C: AbstractChannel, RNG: CryptoRng + Rng, FE: FF>(
     Ok(x == y)
 }
 
-impl<OT: OtReceiver<Msg = Block> + Malicious, FE: FF> Sender<OT, FE> {
+impl<OT: OtReceiver<Msg = Block>, FE: FF> Sender<OT, FE> {
     pub(super) fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
         rng: &mut RNG,
     ) -> Result<Self, Error> {
         let ot = OT::init(channel, rng)?;
+        Self::init_with_ot(channel, pows, ot, rng)
+    }
+
+    // Runs the initialization using the OT extension instance `ot`.
+    pub(super) fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        ot: OT,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
         let seed0 = rng.gen::<Block>();
         let seed1 = rng.gen::<Block>();
         let seeds = scuttlebutt::cointoss::send(channel, &[seed0, seed1])?;
@@ -238,7 +249,7 @@ impl<OT: OtReceiver<Msg = Block> + Malicious, FE: FF> Sender<OT, FE> {
     }
 }
 
-impl<OT: OtSender<Msg = Block> + Malicious, FE: FF> Receiver<OT, FE> {
+impl<OT: OtSender<Msg = Block>, FE: FF> Receiver<OT, FE> {
     pub(super) fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
         channel: &mut C,
         pows: Powers<FE>,
@@ -246,6 +257,17 @@ impl<OT: OtSender<Msg = Block> + Malicious, FE: FF> Receiver<OT, FE> {
         mut rng: &mut RNG,
     ) -> Result<Self, Error> {
         let ot = OT::init(channel, &mut rng)?;
+        Self::init_with_ot(channel, pows, delta, ot, rng)
+    }
+
+    // Counterpart of `Sender::init_with_ot`.
+    pub(super) fn init_with_ot<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        pows: Powers<FE>,
+        delta: FE,
+        ot: OT,
+        rng: &mut RNG,
+    ) -> Result<Self, Error> {
         let seed0 = rng.gen::<Block>();
         let seed1 = rng.gen::<Block>();
         let seeds = scuttlebutt::cointoss::receive(channel, &[seed0, seed1])?;
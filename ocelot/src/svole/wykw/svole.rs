@@ -1,5 +1,7 @@
 use super::{
+    base_ot::{BaseOt, ChouOrlandiBaseOt},
     base_svole::{Receiver as BaseReceiver, Sender as BaseSender},
+    hooks::{ExtendHooks, ExtendPhase},
     spsvole::{SpsReceiver, SpsSender},
     utils::Powers,
 };
@@ -27,7 +29,7 @@ use scuttlebutt::{
 /// Type for LPN parameters used internally in the setup phase and the extend phase of the
 /// protocol. LPN parameters are provided during the initialization of the protocol so that
 /// the extension produces small, medium or large number of values.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct LpnParams {
     /// Hamming weight `t` of the error vector `e` used in the LPN assumption.
     weight: usize,
@@ -58,12 +60,18 @@ pub const LPN_EXTEND_SMALL: LpnParams = LpnParams {
 };
 
 /// Medium LPN parameters for setup phase.
+///
+/// These are the small extend parameters, which produce enough VOLEs to
+/// bootstrap [`LPN_EXTEND_MEDIUM`].
 pub const LPN_SETUP_MEDIUM: LpnParams = LpnParams {
     weight: 2_600,
     cols: 166_400, // cols / weight = 64
     rows: 5_060,
 };
 /// Medium LPN parameters for extend phase.
+///
+/// Every extension produces about 10 million VOLEs, which suits workloads of
+/// 10⁵ to 10⁶ conversions without the setup cost of the large parameters.
 pub const LPN_EXTEND_MEDIUM: LpnParams = LpnParams {
     weight: 4_965,
     cols: 10_168_320, // cols / weight = 2_048
@@ -83,6 +91,158 @@ pub const LPN_EXTEND_LARGE: LpnParams = LpnParams {
     weight: 1_319,
 };
 
+// Vetted pairs of setup and extend parameters, together with the bits of
+// computational security they were chosen for, ordered by increasing size.
+const LPN_PRESETS: [(LpnParams, LpnParams, usize); 3] = [
+    (LPN_SETUP_SMALL, LPN_EXTEND_SMALL, 128),
+    (LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM, 128),
+    (LPN_SETUP_LARGE, LPN_EXTEND_LARGE, 128),
+];
+
+// Bits of computational security of the presets `send_at_least` and
+// `receive_at_least` may select.
+const LPN_MIN_SECURITY_BITS: usize = 128;
+
+impl LpnParams {
+    /// Number of VOLEs returned by an extension with these parameters. The
+    /// actual number is smaller by the degree of the field, whose VOLEs
+    /// are also kept for the next extension.
+    pub fn output_size(&self) -> usize {
+        self.cols - self.rows - self.weight
+    }
+
+    /// Returns the extend parameters best suited to produce `target_voles`
+    /// VOLEs with `security_bits` bits of computational security.
+    ///
+    /// The parameters are selected among the vetted presets: the smallest
+    /// preset producing at least `target_voles` in a single extension is
+    /// chosen, and the largest one when none does. An error is returned if
+    /// no preset provides `security_bits`, parameters are never derived
+    /// outside of the analyzed sets. The matching setup parameters are
+    /// given by [`LpnParams::setup_params`].
+    pub fn for_output(target_voles: usize, security_bits: usize) -> Result<LpnParams, Error> {
+        let mut presets = LPN_PRESETS
+            .iter()
+            .filter(|(_, _, security)| *security >= security_bits)
+            .map(|(_, extend, _)| *extend)
+            .peekable();
+        let mut params = match presets.peek() {
+            Some(extend) => *extend,
+            None => {
+                return Err(Error::Other(format!(
+                    "no vetted LPN parameters provide {} bits of security",
+                    security_bits
+                )))
+            }
+        };
+        for extend in presets {
+            params = extend;
+            if extend.output_size() >= target_voles {
+                break;
+            }
+        }
+        Ok(params)
+    }
+
+    /// Checks that the parameters are structurally consistent, i.e., that
+    /// they describe an LPN matrix and an error vector the extension can
+    /// use. Returns [`Error::InvalidLpnParams`] naming the first offending
+    /// parameter.
+    ///
+    /// This says nothing about the security of the parameters.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |field: &str, constraint: String| {
+            Err(Error::InvalidLpnParams {
+                field: field.to_string(),
+                constraint,
+            })
+        };
+        if self.weight == 0 {
+            return invalid("weight", "must be positive".to_string());
+        }
+        if self.cols < self.weight {
+            return invalid("weight", format!("must not exceed cols ({})", self.cols));
+        }
+        // The single-point sVOLEs use GGM trees with `cols / weight` leaves.
+        if self.cols % self.weight != 0 || !(self.cols / self.weight).is_power_of_two() {
+            return invalid(
+                "cols",
+                format!(
+                    "must be a power of two multiple of weight ({})",
+                    self.weight
+                ),
+            );
+        }
+        // Each column of the LPN matrix has `LPN_PARAMS_D` distinct non-zero
+        // entries, whose indices are sampled as `u32`.
+        if self.rows < LPN_PARAMS_D {
+            return invalid("rows", format!("must be at least {}", LPN_PARAMS_D));
+        }
+        if self.rows > u32::MAX as usize {
+            return invalid("rows", format!("must be at most {}", u32::MAX));
+        }
+        if self.cols <= self.rows + self.weight {
+            return invalid(
+                "cols",
+                format!("must exceed rows + weight ({})", self.rows + self.weight),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the setup parameters vetted together with these extend
+    /// parameters.
+    pub fn setup_params(&self) -> Result<LpnParams, Error> {
+        LPN_PRESETS
+            .iter()
+            .find(|(_, extend, _)| extend == self)
+            .map(|(setup, _, _)| *setup)
+            .ok_or_else(|| {
+                Error::Other("no vetted setup parameters for these LPN parameters".to_string())
+            })
+    }
+
+    // The number of rows, the number of columns and the weight, which the
+    // conversion compares when both parties initialize it.
+    pub(crate) fn dimensions(&self) -> (usize, usize, usize) {
+        (self.rows, self.cols, self.weight)
+    }
+}
+
+// Selects the extend parameters of an extension meant to produce `num`
+// VOLEs with `num_base_voles` saved base VOLEs. An extension leaves the
+// number of saved base VOLEs unchanged, as it saves as many VOLEs as it
+// consumes, so the selection is the same on both sides.
+fn select_extend_params<FE: FiniteField>(
+    lpn_extend: LpnParams,
+    num_base_voles: usize,
+    num: usize,
+) -> LpnParams {
+    let produced = |params: &LpnParams| params.cols - compute_num_saved::<FE>(*params);
+    let mut candidates: Vec<LpnParams> = LPN_PRESETS
+        .iter()
+        .filter(|(_, _, security)| *security >= LPN_MIN_SECURITY_BITS)
+        .map(|(_, extend, _)| *extend)
+        .filter(|params| {
+            let num_saved = compute_num_saved::<FE>(*params);
+            num_saved <= num_base_voles && num_saved < params.cols
+        })
+        .chain(std::iter::once(lpn_extend))
+        .collect();
+    candidates.sort_by_key(produced);
+    candidates
+        .iter()
+        .find(|params| produced(params) >= num)
+        .or_else(|| candidates.last())
+        .copied()
+        .unwrap_or(lpn_extend)
+}
+
+// Number of LPN matrix columns between two progress reports.
+fn progress_step(cols: usize) -> usize {
+    std::cmp::max(cols / 16, 1)
+}
+
 // Constant `d` representing a `d`-local linear code, meaning that each column
 // of the LPN matrix contains exactly `d` non-zero entries.
 const LPN_PARAMS_D: usize = 10;
@@ -92,6 +252,66 @@ fn compute_num_saved<FE: FiniteField>(params: LpnParams) -> usize {
     params.rows + params.weight + Degree::<FE>::USIZE
 }
 
+// Validates the parameters of an sVOLE over `FE`, and checks that the other
+// party uses the same ones by exchanging hashes of them. Both parties call it
+// before anything else is sent.
+pub(super) fn check_lpn_params<FE: FiniteField, C: AbstractChannel>(
+    channel: &mut C,
+    lpn_setup: LpnParams,
+    lpn_extend: LpnParams,
+) -> Result<(), Error> {
+    let prefixed = |name: &str, e: Error| match e {
+        Error::InvalidLpnParams { field, constraint } => Error::InvalidLpnParams {
+            field: format!("{}.{}", name, field),
+            constraint,
+        },
+        e => e,
+    };
+    lpn_setup.validate().map_err(|e| prefixed("lpn_setup", e))?;
+    lpn_extend
+        .validate()
+        .map_err(|e| prefixed("lpn_extend", e))?;
+    // The setup produces the VOLEs consumed by the first extension, which
+    // in turn must produce more VOLEs than it saves for the next one.
+    let num_saved = compute_num_saved::<FE>(lpn_extend);
+    if lpn_setup.cols < num_saved {
+        return Err(Error::InvalidLpnParams {
+            field: "lpn_setup.cols".to_string(),
+            constraint: format!(
+                "must be at least lpn_extend.rows + lpn_extend.weight + {} ({})",
+                Degree::<FE>::USIZE,
+                num_saved
+            ),
+        });
+    }
+    if lpn_extend.cols <= num_saved {
+        return Err(Error::InvalidLpnParams {
+            field: "lpn_extend.cols".to_string(),
+            constraint: format!(
+                "must exceed lpn_extend.rows + lpn_extend.weight + {} ({})",
+                Degree::<FE>::USIZE,
+                num_saved
+            ),
+        });
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    for params in [lpn_setup, lpn_extend] {
+        for x in [params.weight, params.cols, params.rows] {
+            hasher.update(&(x as u64).to_le_bytes());
+        }
+    }
+    let hash = hasher.finalize();
+    channel.write_bytes(hash.as_bytes())?;
+    channel.flush()?;
+    let mut other = [0u8; 32];
+    channel.read_bytes(&mut other)?;
+    if &other != hash.as_bytes() {
+        return Err(Error::LpnParamsMismatch);
+    }
+    Ok(())
+}
+
 fn lpn_mtx_indices<FE: FiniteField>(
     distribution: &Uniform<u32>,
     mut rng: &mut AesRng,
@@ -113,16 +333,121 @@ fn lpn_mtx_indices<FE: FiniteField>(
 }
 
 /// Subfield VOLE sender.
-pub struct Sender<FE: FiniteField> {
+///
+/// The base OTs of the setup are run with `B`.
+pub struct Sender<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
     lpn_setup: LpnParams,
     lpn_extend: LpnParams,
-    spsvole: SpsSender<FE>,
+    spsvole: SpsSender<FE, B>,
     base_voles: Vec<(FE::PrimeField, FE)>,
     // Shared RNG with the receiver for generating the LPN matrix.
     lpn_rng: AesRng,
+    hooks: ExtendHooks,
+    // set when an extension fails after it started
+    poisoned: bool,
 }
 
-impl<FE: FiniteField> Sender<FE> {
+impl<FE: FiniteField, B: BaseOt> Sender<FE, B> {
+    // Completes the initialization from a base sVOLE and a single-point
+    // sVOLE, the OT extension instances of which may be derived from a shared
+    // base OT setup, see `super::setup::SetupSender`.
+    pub(super) fn init_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        mut base_sender: BaseSender<FE, NoSpecialization, B>,
+        spsvole: SpsSender<FE, B>,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+        hooks: ExtendHooks,
+    ) -> Result<Self, Error> {
+        hooks.check_cancelled()?;
+        let base_voles_setup =
+            base_sender.send(channel, compute_num_saved::<FE>(lpn_setup), rng)?;
+        // the check of the base VOLEs is read by the receiver before the
+        // coin toss
+        channel.flush()?;
+        let seed = rng.gen::<Block>();
+        let seed = scuttlebutt::cointoss::receive(channel, &[seed])?[0];
+        let lpn_rng = AesRng::from_seed(seed);
+        let mut sender = Self {
+            lpn_setup,
+            lpn_extend,
+            spsvole,
+            base_voles: base_voles_setup,
+            lpn_rng,
+            hooks,
+            poisoned: false,
+        };
+
+        let mut base_voles_setup = Vec::new();
+        sender.send_internal(channel, sender.lpn_setup, 0, rng, &mut base_voles_setup)?;
+        sender.base_voles = base_voles_setup;
+        // let mut base_voles_extend = Vec::new();
+        // sender.send_internal(channel, LPN_SETUP_PARAMS, 0, rng, &mut base_voles_extend)?;
+        // sender.base_voles = base_voles_extend;
+        Ok(sender)
+    }
+
+    /// Sets the hooks of the extensions, see [`ExtendHooks`].
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.hooks = hooks;
+    }
+
+    /// Returns whether an interrupted extension left the instance unusable.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    // Runs `f` unless the instance is poisoned or the extensions are
+    // cancelled. The instance is poisoned if `f` fails, as it may be left
+    // half-extended.
+    fn guarded<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        if self.poisoned {
+            return Err(Error::Poisoned);
+        }
+        self.hooks.check_cancelled()?;
+        let result = f(self);
+        self.poisoned = result.is_err();
+        result
+    }
+
+    /// Runs extensions until at least `num` VOLEs are written to `output`,
+    /// and returns how many were produced.
+    ///
+    /// Each extension uses the smallest vetted extend parameters producing
+    /// the remaining VOLEs, or the largest when none does, among those
+    /// which the saved base VOLEs allow, see [`LpnParams::for_output`]. The
+    /// extend parameters of the instance are always allowed. The receiver
+    /// must call [`Receiver::receive_at_least`] with the same `num`.
+    pub fn send_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        output: &mut Vec<(FE::PrimeField, FE)>,
+    ) -> Result<usize, Error> {
+        output.clear();
+        let mut batch = Vec::new();
+        while output.len() < num {
+            let params = select_extend_params::<FE>(
+                self.lpn_extend,
+                self.base_voles.len(),
+                num - output.len(),
+            );
+            self.guarded(|sender| {
+                sender.send_internal(
+                    channel,
+                    params,
+                    compute_num_saved::<FE>(params),
+                    rng,
+                    &mut batch,
+                )
+            })?;
+            output.append(&mut batch);
+        }
+        Ok(output.len())
+    }
+
     fn send_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -148,10 +473,12 @@ impl<FE: FiniteField> Sender<FE> {
             r
         );
 
+        self.hooks.report(ExtendPhase::SinglePoint, 0.0)?;
         let uws = self
             .spsvole
             .send(channel, m, &self.base_voles[rows..rows + weight + r], rng)?;
         debug_assert_eq!(uws.len(), cols);
+        self.hooks.report(ExtendPhase::SinglePoint, 1.0)?;
 
         let leftover = self.base_voles.len() - used;
 
@@ -163,7 +490,12 @@ impl<FE: FiniteField> Sender<FE> {
         output.reserve(out_len);
         assert!(rows <= 4_294_967_295); // 2^32 -1
         let distribution = Uniform::<u32>::from(0..rows.try_into().unwrap());
+        let step = progress_step(cols);
         for (i, (e, c)) in uws.into_iter().enumerate() {
+            if i % step == 0 {
+                self.hooks
+                    .report(ExtendPhase::Lpn, i as f64 / cols as f64)?;
+            }
             let indices = lpn_mtx_indices::<FE>(&distribution, &mut self.lpn_rng);
             // Compute `x := u A + e` and `z := w A + c`, where `A` is the LPN matrix.
             let mut x = e;
@@ -183,11 +515,12 @@ impl<FE: FiniteField> Sender<FE> {
         self.base_voles = base_voles;
         debug_assert_eq!(self.base_voles.len(), num_saved + leftover);
         debug_assert_eq!(output.len(), cols - num_saved);
+        self.hooks.notify(ExtendPhase::Lpn, 1.0);
         Ok(())
     }
 }
 
-impl<FE: FiniteField> SVoleSender for Sender<FE> {
+impl<FE: FiniteField, B: BaseOt> SVoleSender for Sender<FE, B> {
     type Msg = FE;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -196,29 +529,19 @@ impl<FE: FiniteField> SVoleSender for Sender<FE> {
         lpn_setup: LpnParams,
         lpn_extend: LpnParams,
     ) -> Result<Self, Error> {
+        check_lpn_params::<FE, C>(channel, lpn_setup, lpn_extend)?;
         let pows: Powers<FE> = Default::default();
-        let mut base_sender = BaseSender::<FE, NoSpecialization>::init(channel, pows.clone(), rng)?;
-        let base_voles_setup =
-            base_sender.send(channel, compute_num_saved::<FE>(lpn_setup), rng)?;
-        let spsvole = SpsSender::<FE>::init(channel, pows, rng)?;
-        let seed = rng.gen::<Block>();
-        let seed = scuttlebutt::cointoss::receive(channel, &[seed])?[0];
-        let lpn_rng = AesRng::from_seed(seed);
-        let mut sender = Self {
+        let base_sender = BaseSender::<FE, NoSpecialization, B>::init(channel, pows.clone(), rng)?;
+        let spsvole = SpsSender::<FE, B>::init(channel, pows, rng)?;
+        Self::init_internal(
+            channel,
+            rng,
+            base_sender,
+            spsvole,
             lpn_setup,
             lpn_extend,
-            spsvole,
-            base_voles: base_voles_setup,
-            lpn_rng,
-        };
-
-        let mut base_voles_setup = Vec::new();
-        sender.send_internal(channel, sender.lpn_setup, 0, rng, &mut base_voles_setup)?;
-        sender.base_voles = base_voles_setup;
-        // let mut base_voles_extend = Vec::new();
-        // sender.send_internal(channel, LPN_SETUP_PARAMS, 0, rng, &mut base_voles_extend)?;
-        // sender.base_voles = base_voles_extend;
-        Ok(sender)
+            ExtendHooks::default(),
+        )
     }
 
     fn send<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -227,13 +550,15 @@ impl<FE: FiniteField> SVoleSender for Sender<FE> {
         rng: &mut RNG,
         output: &mut Vec<(FE::PrimeField, FE)>,
     ) -> Result<(), Error> {
-        self.send_internal(
-            channel,
-            self.lpn_extend,
-            compute_num_saved::<FE>(self.lpn_extend),
-            rng,
-            output,
-        )
+        self.guarded(|sender| {
+            sender.send_internal(
+                channel,
+                sender.lpn_extend,
+                compute_num_saved::<FE>(sender.lpn_extend),
+                rng,
+                output,
+            )
+        })
     }
 
     fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -241,51 +566,143 @@ impl<FE: FiniteField> SVoleSender for Sender<FE> {
         channel: &mut C,
         rng: &mut RNG,
     ) -> Result<Self, Error> {
-        let mut base_voles = Vec::new();
-        self.send_internal(
-            channel,
-            self.lpn_setup,
-            compute_num_saved::<FE>(self.lpn_setup),
-            rng,
-            &mut base_voles,
-        )?;
-        // let mut extras = Vec::new();
-        // self.send_internal(
-        //     channel,
-        //     LPN_SETUP_PARAMS,
-        //     compute_num_saved::<FE>(LPN_SETUP_PARAMS),
-        //     rng,
-        //     &mut extras,
-        // )?;
-        // base_voles.extend(extras.into_iter());
-
-        debug_assert!(base_voles.len() >= compute_num_saved::<FE>(self.lpn_extend));
-        debug_assert!(self.base_voles.len() >= compute_num_saved::<FE>(self.lpn_extend));
-
-        let spsvole = self.spsvole.duplicate(channel, rng)?;
-        let lpn_rng = self.lpn_rng.fork();
-        Ok(Self {
-            lpn_setup: self.lpn_setup,
-            lpn_extend: self.lpn_extend,
-            spsvole,
-            base_voles,
-            lpn_rng,
+        self.guarded(|sender| {
+            // A single extension with the setup parameters may not give
+            // enough base VOLEs to the extensions of the duplicate.
+            let mut base_voles = Vec::new();
+            let mut extras = Vec::new();
+            while base_voles.len() < compute_num_saved::<FE>(sender.lpn_extend) {
+                sender.send_internal(
+                    channel,
+                    sender.lpn_setup,
+                    compute_num_saved::<FE>(sender.lpn_setup),
+                    rng,
+                    &mut extras,
+                )?;
+                base_voles.append(&mut extras);
+            }
+
+            debug_assert!(sender.base_voles.len() >= compute_num_saved::<FE>(sender.lpn_extend));
+
+            let spsvole = sender.spsvole.duplicate(channel, rng)?;
+            let lpn_rng = sender.lpn_rng.fork();
+            Ok(Self {
+                lpn_setup: sender.lpn_setup,
+                lpn_extend: sender.lpn_extend,
+                spsvole,
+                base_voles,
+                lpn_rng,
+                hooks: sender.hooks.clone(),
+                poisoned: false,
+            })
         })
     }
 }
 
 /// Subfield VOLE receiver.
-pub struct Receiver<FE: FiniteField> {
+///
+/// The base OTs of the setup are run with `B`.
+pub struct Receiver<FE: FiniteField, B: BaseOt = ChouOrlandiBaseOt> {
     lpn_setup: LpnParams,
     lpn_extend: LpnParams,
-    spsvole: SpsReceiver<FE>,
+    spsvole: SpsReceiver<FE, B>,
     delta: FE,
     base_voles: Vec<FE>,
     // Shared RNG with the sender for generating the LPN matrix.
     lpn_rng: AesRng,
+    hooks: ExtendHooks,
+    // set when an extension fails after it started
+    poisoned: bool,
 }
 
-impl<FE: FiniteField> Receiver<FE> {
+impl<FE: FiniteField, B: BaseOt> Receiver<FE, B> {
+    // Counterpart of `Sender::init_internal`.
+    pub(super) fn init_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        channel: &mut C,
+        rng: &mut RNG,
+        mut base_receiver: BaseReceiver<FE, B>,
+        spsvole: SpsReceiver<FE, B>,
+        lpn_setup: LpnParams,
+        lpn_extend: LpnParams,
+        hooks: ExtendHooks,
+    ) -> Result<Self, Error> {
+        hooks.check_cancelled()?;
+        let base_voles_setup =
+            base_receiver.receive(channel, compute_num_saved::<FE>(lpn_setup), rng)?;
+        let delta = base_receiver.delta();
+        let seed = rng.gen::<Block>();
+        let seed = scuttlebutt::cointoss::send(channel, &[seed])?[0];
+        let lpn_rng = AesRng::from_seed(seed);
+        let mut receiver = Self {
+            lpn_setup,
+            lpn_extend,
+            spsvole,
+            delta,
+            base_voles: base_voles_setup,
+            lpn_rng,
+            hooks,
+            poisoned: false,
+        };
+        let mut base_voles_setup = Vec::new();
+        receiver.receive_internal(channel, lpn_setup, 0, rng, &mut base_voles_setup)?;
+        receiver.base_voles = base_voles_setup;
+        // let mut base_voles_extend = Vec::new();
+        // receiver.receive_internal(channel, LPN_SETUP_PARAMS, 0, rng, &mut base_voles_extend)?;
+        // receiver.base_voles = base_voles_extend;
+        Ok(receiver)
+    }
+
+    /// Sets the hooks of the extensions, see [`ExtendHooks`].
+    pub fn set_extend_hooks(&mut self, hooks: ExtendHooks) {
+        self.hooks = hooks;
+    }
+
+    /// Returns whether an interrupted extension left the instance unusable.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    // Same as `Sender::guarded`.
+    fn guarded<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        if self.poisoned {
+            return Err(Error::Poisoned);
+        }
+        self.hooks.check_cancelled()?;
+        let result = f(self);
+        self.poisoned = result.is_err();
+        result
+    }
+
+    /// Counterpart of [`Sender::send_at_least`].
+    pub fn receive_at_least<C: AbstractChannel, RNG: CryptoRng + Rng>(
+        &mut self,
+        channel: &mut C,
+        rng: &mut RNG,
+        num: usize,
+        output: &mut Vec<FE>,
+    ) -> Result<usize, Error> {
+        output.clear();
+        let mut batch = Vec::new();
+        while output.len() < num {
+            let params = select_extend_params::<FE>(
+                self.lpn_extend,
+                self.base_voles.len(),
+                num - output.len(),
+            );
+            self.guarded(|receiver| {
+                receiver.receive_internal(
+                    channel,
+                    params,
+                    compute_num_saved::<FE>(params),
+                    rng,
+                    &mut batch,
+                )
+            })?;
+            output.append(&mut batch);
+        }
+        Ok(output.len())
+    }
+
     fn receive_internal<C: AbstractChannel, RNG: CryptoRng + Rng>(
         &mut self,
         channel: &mut C,
@@ -313,16 +730,23 @@ impl<FE: FiniteField> Receiver<FE> {
 
         let leftover = self.base_voles.len() - used;
 
+        self.hooks.report(ExtendPhase::SinglePoint, 0.0)?;
         let vs =
             self.spsvole
                 .receive(channel, m, &self.base_voles[rows..rows + weight + r], rng)?;
         debug_assert!(vs.len() == cols);
+        self.hooks.report(ExtendPhase::SinglePoint, 1.0)?;
         let mut base_voles = Vec::with_capacity(num_saved + leftover);
         output.clear();
         output.reserve(cols - num_saved);
         assert!(rows <= 4_294_967_295); // 2^32 -1
         let distribution = Uniform::<u32>::from(0..rows.try_into().unwrap());
+        let step = progress_step(cols);
         for (i, b) in vs.into_iter().enumerate() {
+            if i % step == 0 {
+                self.hooks
+                    .report(ExtendPhase::Lpn, i as f64 / cols as f64)?;
+            }
             let indices = lpn_mtx_indices::<FE>(&distribution, &mut self.lpn_rng);
             let mut y = b;
 
@@ -337,11 +761,12 @@ impl<FE: FiniteField> Receiver<FE> {
         base_voles.extend(self.base_voles[used..].iter());
         self.base_voles = base_voles;
         debug_assert_eq!(output.len(), cols - num_saved);
+        self.hooks.notify(ExtendPhase::Lpn, 1.0);
         Ok(())
     }
 }
 
-impl<FE: FiniteField> SVoleReceiver for Receiver<FE> {
+impl<FE: FiniteField, B: BaseOt> SVoleReceiver for Receiver<FE, B> {
     type Msg = FE;
 
     fn init<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -350,30 +775,19 @@ impl<FE: FiniteField> SVoleReceiver for Receiver<FE> {
         lpn_setup: LpnParams,
         lpn_extend: LpnParams,
     ) -> Result<Self, Error> {
+        check_lpn_params::<FE, C>(channel, lpn_setup, lpn_extend)?;
         let pows: Powers<FE> = Default::default();
-        let mut base_receiver = BaseReceiver::<FE>::init(channel, pows.clone(), rng)?;
-        let base_voles_setup =
-            base_receiver.receive(channel, compute_num_saved::<FE>(lpn_setup), rng)?;
-        let delta = base_receiver.delta();
-        let spsvole = SpsReceiver::<FE>::init(channel, pows, delta, rng)?;
-        let seed = rng.gen::<Block>();
-        let seed = scuttlebutt::cointoss::send(channel, &[seed])?[0];
-        let lpn_rng = AesRng::from_seed(seed);
-        let mut receiver = Self {
+        let base_receiver = BaseReceiver::<FE, B>::init(channel, pows.clone(), rng)?;
+        let spsvole = SpsReceiver::<FE, B>::init(channel, pows, base_receiver.delta(), rng)?;
+        Self::init_internal(
+            channel,
+            rng,
+            base_receiver,
+            spsvole,
             lpn_setup,
             lpn_extend,
-            spsvole,
-            delta,
-            base_voles: base_voles_setup,
-            lpn_rng,
-        };
-        let mut base_voles_setup = Vec::new();
-        receiver.receive_internal(channel, lpn_setup, 0, rng, &mut base_voles_setup)?;
-        receiver.base_voles = base_voles_setup;
-        // let mut base_voles_extend = Vec::new();
-        // receiver.receive_internal(channel, LPN_SETUP_PARAMS, 0, rng, &mut base_voles_extend)?;
-        // receiver.base_voles = base_voles_extend;
-        Ok(receiver)
+            ExtendHooks::default(),
+        )
     }
 
     fn delta(&self) -> FE {
@@ -386,13 +800,15 @@ impl<FE: FiniteField> SVoleReceiver for Receiver<FE> {
         rng: &mut RNG,
         output: &mut Vec<FE>,
     ) -> Result<(), Error> {
-        self.receive_internal(
-            channel,
-            self.lpn_extend,
-            compute_num_saved::<FE>(self.lpn_extend),
-            rng,
-            output,
-        )
+        self.guarded(|receiver| {
+            receiver.receive_internal(
+                channel,
+                receiver.lpn_extend,
+                compute_num_saved::<FE>(receiver.lpn_extend),
+                rng,
+                output,
+            )
+        })
     }
 
     fn duplicate<C: AbstractChannel, RNG: CryptoRng + Rng>(
@@ -400,51 +816,79 @@ impl<FE: FiniteField> SVoleReceiver for Receiver<FE> {
         channel: &mut C,
         rng: &mut RNG,
     ) -> Result<Self, Error> {
-        let mut base_voles = Vec::new();
-        self.receive_internal(
-            channel,
-            self.lpn_setup,
-            compute_num_saved::<FE>(self.lpn_setup),
-            rng,
-            &mut base_voles,
-        )?;
-        // let mut extras = Vec::new();
-        // self.receive_internal(
-        //     channel,
-        //     LPN_SETUP_PARAMS,
-        //     compute_num_saved::<FE>(LPN_SETUP_PARAMS),
-        //     rng,
-        //     &mut extras,
-        // )?;
-        // base_voles.extend(extras.into_iter());
-
-        debug_assert!(base_voles.len() >= compute_num_saved::<FE>(self.lpn_extend));
-        debug_assert!(self.base_voles.len() >= compute_num_saved::<FE>(self.lpn_extend));
-
-        let spsvole = self.spsvole.duplicate(channel, rng)?;
-        let lpn_rng = self.lpn_rng.fork();
-        Ok(Self {
-            lpn_setup: self.lpn_setup,
-            lpn_extend: self.lpn_extend,
-            spsvole,
-            delta: self.delta,
-            base_voles,
-            lpn_rng,
+        self.guarded(|receiver| {
+            // A single extension with the setup parameters may not give
+            // enough base VOLEs to the extensions of the duplicate.
+            let mut base_voles = Vec::new();
+            let mut extras = Vec::new();
+            while base_voles.len() < compute_num_saved::<FE>(receiver.lpn_extend) {
+                receiver.receive_internal(
+                    channel,
+                    receiver.lpn_setup,
+                    compute_num_saved::<FE>(receiver.lpn_setup),
+                    rng,
+                    &mut extras,
+                )?;
+                base_voles.append(&mut extras);
+            }
+
+            debug_assert!(
+                receiver.base_voles.len() >= compute_num_saved::<FE>(receiver.lpn_extend)
+            );
+
+            let spsvole = receiver.spsvole.duplicate(channel, rng)?;
+            let lpn_rng = receiver.lpn_rng.fork();
+            Ok(Self {
+                lpn_setup: receiver.lpn_setup,
+                lpn_extend: receiver.lpn_extend,
+                spsvole,
+                delta: receiver.delta,
+                base_voles,
+                lpn_rng,
+                hooks: receiver.hooks.clone(),
+                poisoned: false,
+            })
         })
     }
 }
 
-impl<FF: FiniteField> SemiHonest for Sender<FF> {}
-impl<FF: FiniteField> SemiHonest for Receiver<FF> {}
-impl<FF: FiniteField> Malicious for Sender<FF> {}
-impl<FF: FiniteField> Malicious for Receiver<FF> {}
+// The sVOLE is as secure as the base OTs of its OT extensions.
+impl<FF: FiniteField, B: BaseOt> SemiHonest for Sender<FF, B>
+where
+    B::Sender: Malicious,
+    B::Receiver: Malicious,
+{
+}
+impl<FF: FiniteField, B: BaseOt> SemiHonest for Receiver<FF, B>
+where
+    B::Sender: Malicious,
+    B::Receiver: Malicious,
+{
+}
+impl<FF: FiniteField, B: BaseOt> Malicious for Sender<FF, B>
+where
+    B::Sender: Malicious,
+    B::Receiver: Malicious,
+{
+}
+impl<FF: FiniteField, B: BaseOt> Malicious for Receiver<FF, B>
+where
+    B::Sender: Malicious,
+    B::Receiver: Malicious,
+{
+}
 
 #[cfg(test)]
 mod tests {
-    use super::{Receiver, SVoleReceiver, SVoleSender, Sender, LPN_EXTEND_SMALL, LPN_SETUP_SMALL};
+    use super::{
+        super::UnsafeInsecureTestOt, check_lpn_params, compute_num_saved, select_extend_params,
+        LpnParams, Receiver, SVoleReceiver, SVoleSender, Sender, LPN_EXTEND_LARGE,
+        LPN_EXTEND_MEDIUM, LPN_EXTEND_SMALL, LPN_PRESETS, LPN_SETUP_MEDIUM, LPN_SETUP_SMALL,
+    };
+    use crate::errors::Error;
     use scuttlebutt::{
         field::{F128b, F40b, F61p, FiniteField as FF},
-        AesRng, Channel,
+        AesRng, Channel, Malicious,
     };
     use std::{
         io::{BufReader, BufWriter},
@@ -543,6 +987,15 @@ mod tests {
         test_lpn_svole_::<F40b, Sender<F40b>, Receiver<F40b>>();
     }
 
+    #[test]
+    fn test_lpn_svole_insecure_test_ot() {
+        test_lpn_svole_::<
+            F61p,
+            Sender<F61p, UnsafeInsecureTestOt>,
+            Receiver<F61p, UnsafeInsecureTestOt>,
+        >();
+    }
+
     #[test]
     fn test_duplicate_svole() {
         test_duplicate_svole_::<F61p, Sender<F61p>, Receiver<F61p>>();
@@ -552,4 +1005,190 @@ mod tests {
     fn test_duplicate_svole_f40b() {
         test_duplicate_svole_::<F40b, Sender<F40b>, Receiver<F40b>>();
     }
+
+    #[test]
+    fn test_lpn_params_for_output() {
+        assert_eq!(LpnParams::for_output(0, 128).unwrap(), LPN_EXTEND_SMALL);
+        assert_eq!(
+            LpnParams::for_output(LPN_EXTEND_SMALL.output_size(), 128).unwrap(),
+            LPN_EXTEND_SMALL
+        );
+        assert_eq!(
+            LpnParams::for_output(LPN_EXTEND_SMALL.output_size() + 1, 128).unwrap(),
+            LPN_EXTEND_MEDIUM
+        );
+        assert_eq!(
+            LpnParams::for_output(40_000_000, 128).unwrap(),
+            LPN_EXTEND_LARGE
+        );
+        assert!(LpnParams::for_output(1_000, 256).is_err());
+    }
+
+    #[test]
+    fn test_lpn_params_setup() {
+        for (setup, extend, _) in LPN_PRESETS.iter() {
+            assert_eq!(extend.setup_params().unwrap(), *setup);
+            // the setup must produce the VOLEs saved by the extension
+            assert!(setup.cols >= compute_num_saved::<F128b>(*extend));
+        }
+        assert!(LPN_SETUP_SMALL.setup_params().is_err());
+    }
+
+    #[test]
+    fn test_lpn_extend_output_size() {
+        assert_eq!(LPN_EXTEND_SMALL.output_size(), 158_740);
+        assert_eq!(LPN_EXTEND_MEDIUM.output_size(), 10_005_355);
+        assert_eq!(LPN_EXTEND_LARGE.output_size(), 10_214_169);
+        assert_eq!(LPN_SETUP_MEDIUM, LPN_EXTEND_SMALL);
+    }
+
+    #[test]
+    fn test_send_at_least() {
+        let num = LPN_EXTEND_SMALL.output_size() + 1;
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = Channel::new(reader, writer);
+            let mut vole = Sender::<F61p, UnsafeInsecureTestOt>::init(
+                &mut channel,
+                &mut rng,
+                LPN_SETUP_SMALL,
+                LPN_EXTEND_SMALL,
+            )
+            .unwrap();
+            let mut out = Vec::new();
+            let produced = vole
+                .send_at_least(&mut channel, &mut rng, num, &mut out)
+                .unwrap();
+            assert_eq!(produced, out.len());
+            out
+        });
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = Channel::new(reader, writer);
+        let mut vole = Receiver::<F61p, UnsafeInsecureTestOt>::init(
+            &mut channel,
+            &mut rng,
+            LPN_SETUP_SMALL,
+            LPN_EXTEND_SMALL,
+        )
+        .unwrap();
+        let mut vs = Vec::new();
+        let produced = vole
+            .receive_at_least(&mut channel, &mut rng, num, &mut vs)
+            .unwrap();
+        let uws = handle.join().unwrap();
+        // two small extensions
+        let per_extension = LPN_EXTEND_SMALL.cols - compute_num_saved::<F61p>(LPN_EXTEND_SMALL);
+        assert_eq!(produced, 2 * per_extension);
+        assert_eq!(uws.len(), produced);
+        assert_eq!(vs.len(), produced);
+        for ((u, w), v) in uws.iter().zip(vs.iter()) {
+            assert_eq!(*w, *u * vole.delta() + *v);
+        }
+    }
+
+    #[test]
+    fn test_select_extend_params() {
+        let medium_saved = compute_num_saved::<F61p>(LPN_EXTEND_MEDIUM);
+        // the small preset is chosen when it suffices
+        assert_eq!(
+            select_extend_params::<F61p>(LPN_EXTEND_MEDIUM, medium_saved, 50_000),
+            LPN_EXTEND_SMALL
+        );
+        assert_eq!(
+            select_extend_params::<F61p>(LPN_EXTEND_MEDIUM, medium_saved, 1_000_000),
+            LPN_EXTEND_MEDIUM
+        );
+        // the large preset needs more base VOLEs than the medium ones save
+        assert_eq!(
+            select_extend_params::<F61p>(LPN_EXTEND_MEDIUM, medium_saved, 100_000_000),
+            LPN_EXTEND_MEDIUM
+        );
+        let small_saved = compute_num_saved::<F61p>(LPN_EXTEND_SMALL);
+        assert_eq!(
+            select_extend_params::<F61p>(LPN_EXTEND_SMALL, small_saved, 1_000_000),
+            LPN_EXTEND_SMALL
+        );
+    }
+
+    #[test]
+    fn test_lpn_params_validate() {
+        for (setup, extend, _) in LPN_PRESETS.iter() {
+            setup.validate().unwrap();
+            extend.validate().unwrap();
+        }
+        let invalid_field = |params: LpnParams| match params.validate() {
+            Err(Error::InvalidLpnParams { field, .. }) => field,
+            r => panic!("expected an invalid parameter, got {:?}", r),
+        };
+        let params = |weight, cols, rows| LpnParams { weight, cols, rows };
+        assert_eq!(invalid_field(params(0, 9_600, 1_220)), "weight");
+        assert_eq!(invalid_field(params(600, 0, 1_220)), "weight");
+        assert_eq!(invalid_field(params(600, 9_000, 1_220)), "cols");
+        assert_eq!(invalid_field(params(600, 9_600, 0)), "rows");
+        assert_eq!(invalid_field(params(600, 9_600, 9_000)), "cols");
+    }
+
+    #[test]
+    fn test_lpn_params_inconsistent_pair() {
+        let (sender, _receiver) = UnixStream::pair().unwrap();
+        let reader = BufReader::new(sender.try_clone().unwrap());
+        let writer = BufWriter::new(sender);
+        let mut channel = Channel::new(reader, writer);
+        // the medium extension consumes more VOLEs than the small setup produces
+        match check_lpn_params::<F61p, _>(&mut channel, LPN_SETUP_SMALL, LPN_EXTEND_MEDIUM) {
+            Err(Error::InvalidLpnParams { field, .. }) => assert_eq!(field, "lpn_setup.cols"),
+            r => panic!("expected an invalid parameter, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_lpn_params_mismatch() {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut rng = AesRng::new();
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = Channel::new(reader, writer);
+            Sender::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_SMALL, LPN_EXTEND_SMALL).err()
+        });
+        let mut rng = AesRng::new();
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = Channel::new(reader, writer);
+        let result =
+            Receiver::<F61p>::init(&mut channel, &mut rng, LPN_SETUP_MEDIUM, LPN_EXTEND_MEDIUM);
+        assert!(matches!(result, Err(Error::LpnParamsMismatch)));
+        assert!(matches!(
+            handle.join().unwrap(),
+            Some(Error::LpnParamsMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_lpn_presets_shape() {
+        for (setup, extend, security) in LPN_PRESETS.iter() {
+            assert_eq!(*security, 128);
+            for params in [setup, extend] {
+                // the single-point sVOLEs use GGM trees with `cols / weight` leaves
+                assert_eq!(params.cols % params.weight, 0);
+                assert!((params.cols / params.weight).is_power_of_two());
+                // the LPN matrix indices are sampled as `u32`
+                assert!(params.rows <= u32::MAX as usize);
+                assert!(params.rows < params.cols);
+            }
+        }
+    }
+
+    #[test]
+    fn test_malicious_with_chou_orlandi() {
+        fn is_malicious<T: Malicious>() {}
+        // the default base OT is maliciously secure, and so is the sVOLE
+        is_malicious::<Sender<F61p>>();
+        is_malicious::<Receiver<F61p>>();
+    }
 }
@@ -16,6 +16,9 @@ fi
 QUICKSILVER="--quicksilver"
 # QUICKSILVER=""
 
+# One of default, lan, wan, or sweep to run every configuration in turn.
+CHANNEL="sweep"
+
 #tc qdisc add dev eth0 root netem delay 200ms
 
 run_command () {
@@ -26,7 +29,7 @@ run_command () {
     if [[ ! -z "${PROVER}" ]]; then
         sleep 2
     fi
-    cargo run --release --example network_edabits -- -n ${NUM_EDABITS} -b ${NUM_BUCKETS} -m ${NUM_BITS} ${QUICKSILVER} --addr verifier:5527 ${WHOAMI}
+    cargo run --release --example network_edabits -- -n ${NUM_EDABITS} -b ${NUM_BUCKETS} -m ${NUM_BITS} ${QUICKSILVER} --channel ${CHANNEL} --addr verifier:5527 ${WHOAMI}
 }
 
 run_test_b5 () {
@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use scuttlebutt::{bitpack, field::F2, ring::FiniteRing, AesRng};
+
+const LEN: usize = 1 << 16;
+
+fn bench_bitpack(c: &mut Criterion) {
+    let mut rng = AesRng::new();
+    let xs: Vec<F2> = (0..LEN).map(|_| F2::random(&mut rng)).collect();
+
+    c.bench_function("bitpack::pack_f2", |b| {
+        b.iter(|| black_box(bitpack::pack_f2(black_box(&xs))));
+    });
+
+    c.bench_function("bitpack::unpack_f2", |b| {
+        let bytes = bitpack::pack_f2(&xs);
+        let mut out = Vec::with_capacity(LEN);
+        b.iter(|| {
+            out.clear();
+            bitpack::unpack_f2(black_box(&bytes), LEN, &mut out);
+            black_box(&out);
+        });
+    });
+
+    c.bench_function("bitpack::pack_f2_words", |b| {
+        b.iter(|| black_box(bitpack::pack_f2_words(black_box(&xs))));
+    });
+
+    c.bench_function("bitpack::unpack_f2_words", |b| {
+        let words = bitpack::pack_f2_words(&xs);
+        let mut out = Vec::with_capacity(LEN);
+        b.iter(|| {
+            out.clear();
+            bitpack::unpack_f2_words(black_box(&words), LEN, &mut out);
+            black_box(&out);
+        });
+    });
+}
+
+criterion_group! {
+    name = bitpack;
+    config = Criterion::default();
+    targets = bench_bitpack
+}
+criterion_main!(bitpack);
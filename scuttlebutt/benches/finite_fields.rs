@@ -25,6 +25,24 @@ macro_rules! finite_field_benchmarks {
                 });
             }
 
+            fn mul_many(c: &mut Criterion) {
+                c.bench_function(&format!("{}::mul100", stringify!($field)), |b| {
+                    let x: Vec<_> = (0..100)
+                        .map(|_| <$field>::random(&mut rand::thread_rng()))
+                        .collect();
+                    let y: Vec<_> = (0..100)
+                        .map(|_| <$field>::random(&mut rand::thread_rng()))
+                        .collect();
+                    let mut z = vec![<$field>::ZERO; 100];
+                    b.iter(|| {
+                        for ((z, x), y) in z.iter_mut().zip(x.iter()).zip(y.iter()) {
+                            *z = black_box(*x) * black_box(*y);
+                        }
+                        black_box(&z);
+                    });
+                });
+            }
+
             fn div(c: &mut Criterion) {
                 c.bench_function(&format!("{}::div", stringify!($field)), |b| {
                     let x = <$field>::random(&mut rand::thread_rng());
@@ -72,13 +90,14 @@ macro_rules! finite_field_benchmarks {
                 });
             }
 
-            criterion_group!($name, add, mul, div, pow, inverse, random, sum, product);
+            criterion_group!($name, add, mul, mul_many, div, pow, inverse, random, sum, product);
         }
     };
 }
 
 finite_field_benchmarks!(f2, scuttlebutt::field::F2,);
 finite_field_benchmarks!(f61p, scuttlebutt::field::F61p,);
+finite_field_benchmarks!(f127p, scuttlebutt::field::F127p,);
 finite_field_benchmarks!(f64b, scuttlebutt::field::F64b,);
 finite_field_benchmarks!(f128b, scuttlebutt::field::F128b,);
 
@@ -109,6 +128,7 @@ finite_field_benchmarks!(fbn254, scuttlebutt::field::Fbn254,);
 criterion::criterion_main!(
     f2::f2,
     f61p::f61p,
+    f127p::f127p,
     f64b::f64b,
     f128b::f128b,
     f40b::f40b,
@@ -120,6 +140,7 @@ criterion::criterion_main!(
 criterion::criterion_main!(
     f2::f2,
     f61p::f61p,
+    f127p::f127p,
     f64b::f64b,
     f128b::f128b,
     f40b::f40b,
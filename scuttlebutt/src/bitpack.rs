@@ -0,0 +1,171 @@
+//! Packing of `F2` values into bytes and 64-bit words.
+//!
+//! Everything in this module uses a single bit order: the `i`-th value is the bit `i % 8` of the
+//! byte `i / 8` (resp. the bit `i % 64` of the word `i / 64`), starting from the least significant
+//! bit, and the unused bits at the end are zero. As a consequence, writing packed words in little
+//! endian gives the packed bytes, followed by zero bytes up to the next multiple of eight.
+
+use crate::field::F2;
+
+/// The number of bytes needed to pack `len` values.
+#[inline]
+pub fn packed_len(len: usize) -> usize {
+    (len + 7) / 8
+}
+
+/// The number of words needed to pack `len` values.
+#[inline]
+pub fn packed_words_len(len: usize) -> usize {
+    (len + 63) / 64
+}
+
+/// Pack `xs` eight per byte.
+pub fn pack_f2(xs: &[F2]) -> Vec<u8> {
+    xs.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(0, |acc, x| (acc << 1) | u8::from(*x))
+        })
+        .collect()
+}
+
+/// Unpack the first `len` values of `bytes`, and append them to `out`.
+///
+/// # Panics
+/// Panics if `bytes` holds fewer than `len` values.
+pub fn unpack_f2(bytes: &[u8], len: usize, out: &mut Vec<F2>) {
+    assert!(
+        bytes.len() >= packed_len(len),
+        "{} bytes cannot hold {} values",
+        bytes.len(),
+        len
+    );
+    out.reserve(len);
+    out.extend((0..len).map(|i| F2::from((bytes[i / 8] >> (i % 8)) & 1 == 1)));
+}
+
+/// Return whether the bits of `bytes` after the first `len` are all zero.
+pub fn has_zero_padding(bytes: &[u8], len: usize) -> bool {
+    match bytes.get(len / 8..).and_then(|rest| rest.split_first()) {
+        Some((first, rest)) => first >> (len % 8) == 0 && rest.iter().all(|b| *b == 0),
+        None => true,
+    }
+}
+
+/// Pack `xs` sixty-four per word.
+pub fn pack_f2_words(xs: &[F2]) -> Vec<u64> {
+    xs.chunks(64)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(0, |acc, x| (acc << 1) | u64::from(u8::from(*x)))
+        })
+        .collect()
+}
+
+/// Unpack the first `len` values of `words`, and append them to `out`.
+///
+/// # Panics
+/// Panics if `words` holds fewer than `len` values.
+pub fn unpack_f2_words(words: &[u64], len: usize, out: &mut Vec<F2>) {
+    assert!(
+        words.len() >= packed_words_len(len),
+        "{} words cannot hold {} values",
+        words.len(),
+        len
+    );
+    out.reserve(len);
+    out.extend((0..len).map(|i| F2::from((words[i / 64] >> (i % 64)) & 1 == 1)));
+}
+
+/// Return whether the bits of `words` after the first `len` are all zero.
+pub fn has_zero_padding_words(words: &[u64], len: usize) -> bool {
+    match words.get(len / 64..).and_then(|rest| rest.split_first()) {
+        Some((first, rest)) => first >> (len % 64) == 0 && rest.iter().all(|w| *w == 0),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ring::FiniteRing, AesRng};
+
+    const LENS: [usize; 9] = [0, 1, 7, 8, 9, 63, 64, 65, 1000];
+
+    fn random_f2s(len: usize) -> Vec<F2> {
+        let mut rng = AesRng::new();
+        (0..len).map(|_| F2::random(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in LENS {
+            let xs = random_f2s(len);
+            let bytes = pack_f2(&xs);
+            assert_eq!(bytes.len(), packed_len(len));
+            assert!(has_zero_padding(&bytes, len));
+            let mut out = Vec::new();
+            unpack_f2(&bytes, len, &mut out);
+            assert_eq!(out, xs);
+
+            let words = pack_f2_words(&xs);
+            assert_eq!(words.len(), packed_words_len(len));
+            assert!(has_zero_padding_words(&words, len));
+            let mut out = Vec::new();
+            unpack_f2_words(&words, len, &mut out);
+            assert_eq!(out, xs);
+        }
+    }
+
+    #[test]
+    fn test_words_match_bytes() {
+        for len in LENS {
+            let xs = random_f2s(len);
+            let bytes = pack_f2(&xs);
+            let words = pack_f2_words(&xs);
+            let from_words: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+            assert_eq!(&from_words[..bytes.len()], &bytes[..]);
+            assert!(from_words[bytes.len()..].iter().all(|b| *b == 0));
+        }
+    }
+
+    #[test]
+    fn test_bit_order() {
+        let xs: Vec<F2> = [1, 0, 0, 1, 0, 0, 0, 0, 1]
+            .iter()
+            .map(|b| F2::from(*b == 1))
+            .collect();
+        assert_eq!(pack_f2(&xs), vec![0b1001, 0b1]);
+        assert_eq!(pack_f2_words(&xs), vec![0b1_0000_1001]);
+    }
+
+    #[test]
+    fn test_unpack_appends() {
+        let mut out = vec![F2::ONE];
+        unpack_f2(&[0b10], 2, &mut out);
+        assert_eq!(out, vec![F2::ONE, F2::ZERO, F2::ONE]);
+        unpack_f2_words(&[0b1], 1, &mut out);
+        assert_eq!(out, vec![F2::ONE, F2::ZERO, F2::ONE, F2::ONE]);
+    }
+
+    #[test]
+    fn test_padding() {
+        assert!(!has_zero_padding(&[0b100], 2));
+        assert!(has_zero_padding(&[0b100], 3));
+        assert!(!has_zero_padding(&[0b1, 0b0, 0b1], 8));
+        assert!(has_zero_padding(&[], 0));
+        assert!(!has_zero_padding_words(&[1 << 63], 63));
+        assert!(has_zero_padding_words(&[1 << 63], 64));
+        assert!(!has_zero_padding_words(&[0, 1], 64));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unpack_too_short() {
+        unpack_f2(&[0], 9, &mut Vec::new());
+    }
+}
@@ -1,4 +1,16 @@
+#[cfg(feature = "tokio")]
+mod async_channel;
+mod buffered_channel;
+#[cfg(feature = "lz4")]
+mod compressed_channel;
 mod hash_channel;
+mod keepalive_channel;
+mod local_channel;
+mod mux_channel;
+#[cfg(feature = "noise")]
+mod noise_channel;
+mod record_channel;
+mod sim_channel;
 mod sync_channel;
 mod track_channel;
 #[cfg(unix)]
@@ -6,19 +18,33 @@ mod unix_channel;
 #[cfg(windows)]
 mod unix_channel;
 
+#[cfg(feature = "tokio")]
+pub use async_channel::{
+    run_blocking, AsyncAbstractChannel, AsyncChannel, BridgeChannel, ChannelFuture,
+};
+pub use buffered_channel::{tcp_channel, BufferedChannel, ChannelConfig, FlushPolicy};
+#[cfg(feature = "lz4")]
+pub use compressed_channel::CompressedChannel;
 pub use hash_channel::HashChannel;
-pub use sync_channel::SyncChannel;
-pub use track_channel::TrackChannel;
+pub use keepalive_channel::{KeepAliveChannel, KeepAliveConfig, PeerDisconnected};
+pub use local_channel::{local_pair, local_pair_with_capacity, LocalChannel};
+pub use mux_channel::{multiplex, multiplex_with_pumps, MuxChannel, MuxPumps};
+#[cfg(feature = "noise")]
+pub use noise_channel::NoiseChannel;
+pub use record_channel::{RecordChannel, ReplayChannel, Transcript, TranscriptRecord};
+pub use sim_channel::{sim_pair, Jitter, SimChannel, SimParams};
+pub use sync_channel::{ReadHalf, SyncChannel, WriteHalf};
+pub use track_channel::{CommBreakdown, ScopeComm, TrackChannel};
 
 #[cfg(unix)]
 pub use unix_channel::{track_unix_channel_pair, unix_channel_pair, TrackUnixChannel, UnixChannel};
 #[cfg(windows)]
 pub use unix_channel::{track_unix_channel_pair, unix_channel_pair, TrackUnixChannel, UnixChannel};
 
-use crate::{serialization::CanonicalSerialize, Block, Block512};
+use crate::{bitpack, field::F2, serialization::CanonicalSerialize, Block, Block512};
 #[cfg(feature = "curve25519-dalek")]
 use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
-use generic_array::GenericArray;
+use generic_array::{typenum::Unsigned, GenericArray};
 use std::{
     cell::RefCell,
     io::{Read, Result, Write},
@@ -38,6 +64,20 @@ pub trait AbstractChannel {
     fn clone(&self) -> Self
     where
         Self: Sized;
+    /// Attribute the communication that follows to the scope `label`, and
+    /// return the label of the previous scope.
+    ///
+    /// Only channels breaking their communication down per scope, such as
+    /// [`TrackChannel`], keep track of scopes. The default implementation
+    /// does nothing and returns `None`.
+    fn scope(&mut self, _label: &'static str) -> Option<&'static str> {
+        None
+    }
+    /// Return the communication on the channel broken down per scope, or
+    /// `None` if the channel does not keep track of it.
+    fn comm_breakdown(&self) -> Option<CommBreakdown> {
+        None
+    }
     /// Read `nbytes` from the channel, and return it as a `Vec`.
     fn read_vec(&mut self, nbytes: usize) -> Result<Vec<u8>> {
         let mut data = vec![0; nbytes];
@@ -217,6 +257,59 @@ pub trait AbstractChannel {
         self.write_bytes(&x.to_bytes())?;
         Ok(())
     }
+
+    /// Write a slice of `CanonicalSerialize` objects to the channel.
+    ///
+    /// The objects are written in the same format as with a loop of
+    /// `write_serializable`. The default implementation serializes them into
+    /// a single buffer, written at once.
+    fn write_serializable_slice<E: CanonicalSerialize>(&mut self, xs: &[E]) -> Result<()> {
+        let mut bytes = Vec::with_capacity(xs.len() * E::ByteReprLen::USIZE);
+        for x in xs.iter() {
+            bytes.extend_from_slice(&x.to_bytes());
+        }
+        self.write_bytes(&bytes)
+    }
+
+    /// Read `num` `CanonicalSerialize` objects from the channel, written
+    /// with `write_serializable_slice`, and append them to `out`.
+    fn read_serializable_slice<E: CanonicalSerialize>(
+        &mut self,
+        num: usize,
+        out: &mut Vec<E>,
+    ) -> Result<()> {
+        let len = E::ByteReprLen::USIZE;
+        let bytes = self.read_vec(num * len)?;
+        out.reserve(num);
+        for chunk in bytes.chunks_exact(len) {
+            match E::from_bytes(GenericArray::from_slice(chunk)) {
+                Ok(x) => out.push(x),
+                Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a slice of `F2` values packed eight per byte, in the bit order
+    /// of [`crate::bitpack`].
+    fn write_f2_slice_packed(&mut self, xs: &[F2]) -> Result<()> {
+        self.write_bytes(&bitpack::pack_f2(xs))
+    }
+
+    /// Read `len` `F2` values written with `write_f2_slice_packed`, and
+    /// append them to `out`. Fails if the unused bits of the last byte are
+    /// not zero.
+    fn read_f2_slice_packed(&mut self, len: usize, out: &mut Vec<F2>) -> Result<()> {
+        let bytes = self.read_vec(bitpack::packed_len(len))?;
+        if !bitpack::has_zero_padding(&bytes, len) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "non-zero padding in packed bits",
+            ));
+        }
+        bitpack::unpack_f2(&bytes, len, out);
+        Ok(())
+    }
 }
 
 /// A standard read/write channel that implements `AbstractChannel`.
@@ -242,6 +335,24 @@ impl<R: Read, W: Write> Channel<R, W> {
     pub fn writer(self) -> Rc<RefCell<W>> {
         self.writer
     }
+
+    /// Split the channel into a read half and a write half, which can be
+    /// used from different threads. They reunite into a `SyncChannel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel has been cloned, as the halves cannot share the
+    /// reader and writer with a `Channel`.
+    pub fn split(self) -> (ReadHalf<R>, WriteHalf<W>) {
+        let reader = Rc::try_unwrap(self.reader)
+            .unwrap_or_else(|_| panic!("cannot split a channel that has been cloned"));
+        let writer = Rc::try_unwrap(self.writer)
+            .unwrap_or_else(|_| panic!("cannot split a channel that has been cloned"));
+        (
+            ReadHalf::new(reader.into_inner()),
+            WriteHalf::new(writer.into_inner()),
+        )
+    }
 }
 
 impl<R: Read, W: Write> AbstractChannel for Channel<R, W> {
@@ -307,3 +418,88 @@ impl<S: Read + Write> AbstractChannel for SymChannel<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::F61p, ring::FiniteRing, AesRng};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_serializable_slice() {
+        let mut rng = AesRng::new();
+        let xs: Vec<F61p> = (0..100).map(|_| F61p::random(&mut rng)).collect();
+        let mut channel = Channel::new(Cursor::new(Vec::new()), Vec::new());
+        channel.write_serializable_slice(&xs).unwrap();
+        let bytes = channel.writer().borrow().clone();
+        let mut expected = Vec::new();
+        for x in xs.iter() {
+            expected.extend_from_slice(&x.to_bytes());
+        }
+        assert_eq!(bytes, expected);
+
+        let mut channel = Channel::new(Cursor::new(bytes), Vec::new());
+        let mut out = vec![F61p::ONE];
+        channel.read_serializable_slice(xs.len(), &mut out).unwrap();
+        assert_eq!(out[0], F61p::ONE);
+        assert_eq!(&out[1..], &xs[..]);
+        assert!(channel
+            .read_serializable_slice::<F61p>(1, &mut out)
+            .is_err());
+    }
+
+    #[test]
+    fn test_f2_slice_packed() {
+        let mut rng = AesRng::new();
+        for len in [0, 1, 7, 8, 9, 63, 100] {
+            let xs: Vec<F2> = (0..len).map(|_| F2::random(&mut rng)).collect();
+            let mut channel = Channel::new(Cursor::new(Vec::new()), Vec::new());
+            channel.write_f2_slice_packed(&xs).unwrap();
+            let bytes = channel.writer().borrow().clone();
+            assert_eq!(bytes.len(), (len + 7) / 8);
+
+            let mut channel = Channel::new(Cursor::new(bytes), Vec::new());
+            let mut out = Vec::new();
+            channel.read_f2_slice_packed(len, &mut out).unwrap();
+            assert_eq!(out, xs);
+        }
+
+        // bit order
+        let xs: Vec<F2> = [1, 0, 0, 1, 0, 0, 0, 0, 1]
+            .iter()
+            .map(|b| F2::from(*b == 1))
+            .collect();
+        let mut channel = Channel::new(Cursor::new(Vec::new()), Vec::new());
+        channel.write_f2_slice_packed(&xs).unwrap();
+        assert_eq!(*channel.writer().borrow(), vec![0b1001, 0b1]);
+
+        // non-zero padding
+        let mut channel = Channel::new(Cursor::new(vec![0b100]), Vec::new());
+        let mut out = Vec::new();
+        assert!(channel.read_f2_slice_packed(2, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_channel_split() {
+        let channel = Channel::new(Cursor::new(vec![1, 2, 3]), Vec::new());
+        let (mut read_half, mut write_half) = channel.split();
+        let handle = std::thread::spawn(move || {
+            write_half.write_bytes(&[4, 5]).unwrap();
+            write_half.flush().unwrap();
+            write_half
+        });
+        let mut bytes = [0u8; 3];
+        read_half.read_bytes(&mut bytes).unwrap();
+        assert_eq!(bytes, [1, 2, 3]);
+        let channel = read_half.unsplit(handle.join().unwrap());
+        assert_eq!(*channel.writer().lock().unwrap(), vec![4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_channel_split_cloned() {
+        let channel = Channel::new(Cursor::new(Vec::new()), Vec::new());
+        let _clone = AbstractChannel::clone(&channel);
+        let _ = channel.split();
+    }
+}
@@ -0,0 +1,268 @@
+//! Asynchronous channels over tokio I/O, and a bridge running the blocking
+//! protocols written against [`AbstractChannel`] over them.
+
+use crate::AbstractChannel;
+use std::{
+    future::Future,
+    io::{Error, ErrorKind, Result},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+/// Future returned by the methods of [`AsyncAbstractChannel`].
+pub type ChannelFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// The asynchronous counterpart of [`AbstractChannel`].
+pub trait AsyncAbstractChannel: Send {
+    /// Read a slice of `u8`s from the channel.
+    fn read_bytes<'a>(&'a mut self, bytes: &'a mut [u8]) -> ChannelFuture<'a, ()>;
+    /// Write a slice of `u8`s to the channel.
+    fn write_bytes<'a>(&'a mut self, bytes: &'a [u8]) -> ChannelFuture<'a, ()>;
+    /// Flush the channel.
+    fn flush(&mut self) -> ChannelFuture<'_, ()>;
+}
+
+/// An asynchronous channel over a tokio reader and writer.
+pub struct AsyncChannel<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: AsyncRead + Unpin + Send, W: AsyncWrite + Unpin + Send> AsyncChannel<R, W> {
+    /// Make a new `AsyncChannel` from a `reader` and a `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Return the reader and the writer of the channel.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send, W: AsyncWrite + Unpin + Send> AsyncAbstractChannel
+    for AsyncChannel<R, W>
+{
+    fn read_bytes<'a>(&'a mut self, bytes: &'a mut [u8]) -> ChannelFuture<'a, ()> {
+        Box::pin(async move {
+            self.reader.read_exact(bytes).await?;
+            Ok(())
+        })
+    }
+
+    fn write_bytes<'a>(&'a mut self, bytes: &'a [u8]) -> ChannelFuture<'a, ()> {
+        Box::pin(async move { self.writer.write_all(bytes).await })
+    }
+
+    fn flush(&mut self) -> ChannelFuture<'_, ()> {
+        Box::pin(async move { self.writer.flush().await })
+    }
+}
+
+enum Request {
+    Write(Vec<u8>),
+    Flush,
+    Read(usize),
+}
+
+/// A blocking [`AbstractChannel`] whose I/O is performed on an
+/// [`AsyncAbstractChannel`], see [`run_blocking`].
+///
+/// The writes are buffered until the channel is flushed or read from, or
+/// the protocol run by [`run_blocking`] returns.
+pub struct BridgeChannel(Arc<Mutex<BridgeState>>);
+
+struct BridgeState {
+    requests: UnboundedSender<Request>,
+    responses: UnboundedReceiver<Vec<u8>>,
+    pending: Vec<u8>,
+}
+
+fn closed() -> Error {
+    Error::new(
+        ErrorKind::BrokenPipe,
+        "the asynchronous channel has been closed",
+    )
+}
+
+impl BridgeState {
+    fn send(&mut self, request: Request) -> Result<()> {
+        self.requests.send(request).map_err(|_| closed())
+    }
+
+    fn send_pending(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let pending = std::mem::take(&mut self.pending);
+        self.send(Request::Write(pending))
+    }
+}
+
+impl AbstractChannel for BridgeChannel {
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut state = self.0.lock().unwrap();
+        state.send_pending()?;
+        state.send(Request::Read(bytes.len()))?;
+        let response = state.responses.blocking_recv().ok_or_else(closed)?;
+        bytes.copy_from_slice(&response);
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.0.lock().unwrap().pending.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut state = self.0.lock().unwrap();
+        state.send_pending()?;
+        state.send(Request::Flush)
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+// Perform the requests of a `BridgeChannel` on `channel`, until the
+// `BridgeChannel` is dropped.
+async fn serve<A: AsyncAbstractChannel>(
+    channel: &mut A,
+    mut requests: UnboundedReceiver<Request>,
+    responses: UnboundedSender<Vec<u8>>,
+) -> Result<()> {
+    while let Some(request) = requests.recv().await {
+        match request {
+            Request::Write(bytes) => channel.write_bytes(&bytes).await?,
+            Request::Flush => channel.flush().await?,
+            Request::Read(len) => {
+                let mut bytes = vec![0; len];
+                channel.read_bytes(&mut bytes).await?;
+                if responses.send(bytes).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run the blocking protocol `f` on the blocking thread pool of tokio, with
+/// its I/O performed on `channel`.
+///
+/// `f` is given a [`BridgeChannel`] whose reads, writes and flushes are
+/// performed on `channel` by the calling task, so that the runtime is not
+/// blocked while `f` waits on its peer. Returns the output of `f`, or the
+/// error of `channel` if it failed, in which case the I/O of `f` fails as
+/// well. The writes `f` did not flush are written to `channel` once it
+/// returns, but `channel` itself is not flushed. A panic of `f` is
+/// propagated.
+pub async fn run_blocking<A, T, F>(channel: &mut A, f: F) -> Result<T>
+where
+    A: AsyncAbstractChannel,
+    T: Send + 'static,
+    F: FnOnce(&mut BridgeChannel) -> T + Send + 'static,
+{
+    let (requests_sender, requests) = unbounded_channel();
+    let (responses, responses_receiver) = unbounded_channel();
+    let mut bridge = BridgeChannel(Arc::new(Mutex::new(BridgeState {
+        requests: requests_sender,
+        responses: responses_receiver,
+        pending: Vec::new(),
+    })));
+    let task = tokio::task::spawn_blocking(move || {
+        let output = f(&mut bridge);
+        // the writes `f` left unflushed are still performed on `channel`;
+        // if it is closed, `serve` returns its error
+        let _ = bridge.0.lock().unwrap().send_pending();
+        output
+    });
+    let (output, served) = tokio::join!(task, serve(channel, requests, responses));
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => return Err(Error::new(ErrorKind::Other, e)),
+    };
+    served?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_blocking() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (a, b) = tokio::io::duplex(64);
+        let (a_reader, a_writer) = tokio::io::split(a);
+        let (b_reader, b_writer) = tokio::io::split(b);
+        let mut a = AsyncChannel::new(a_reader, a_writer);
+        let mut b = AsyncChannel::new(b_reader, b_writer);
+        runtime.block_on(async {
+            let alice = run_blocking(&mut a, |channel| {
+                for i in 0..100u32 {
+                    channel.write_u32(i)?;
+                }
+                channel.flush()?;
+                channel.read_u64()
+            });
+            let bob = run_blocking(&mut b, |channel| {
+                let mut sum = 0;
+                for _ in 0..100 {
+                    sum += channel.read_u32()? as u64;
+                }
+                channel.write_u64(sum)?;
+                channel.flush()?;
+                Ok::<_, Error>(sum)
+            });
+            let (alice, bob) = tokio::join!(alice, bob);
+            assert_eq!(alice.unwrap().unwrap(), 4950);
+            assert_eq!(bob.unwrap().unwrap(), 4950);
+        });
+    }
+
+    #[test]
+    fn test_run_blocking_unflushed() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (a, b) = tokio::io::duplex(64);
+        let (a_reader, a_writer) = tokio::io::split(a);
+        let (b_reader, b_writer) = tokio::io::split(b);
+        let mut a = AsyncChannel::new(a_reader, a_writer);
+        let mut b = AsyncChannel::new(b_reader, b_writer);
+        runtime.block_on(async {
+            // the last write is not flushed by the protocol
+            run_blocking(&mut a, |channel| channel.write_u32(7))
+                .await
+                .unwrap()
+                .unwrap();
+            a.flush().await.unwrap();
+            let res = run_blocking(&mut b, |channel| channel.read_u32()).await;
+            assert_eq!(res.unwrap().unwrap(), 7);
+        });
+    }
+
+    #[test]
+    fn test_run_blocking_closed() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (a, b) = tokio::io::duplex(64);
+        drop(b);
+        let (a_reader, a_writer) = tokio::io::split(a);
+        let mut a = AsyncChannel::new(a_reader, a_writer);
+        runtime.block_on(async {
+            let res = run_blocking(&mut a, |channel| channel.read_u8()).await;
+            assert!(res.is_err());
+        });
+    }
+}
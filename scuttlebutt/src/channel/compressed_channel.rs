@@ -0,0 +1,159 @@
+use crate::AbstractChannel;
+use lz4_flex::block::{compress, decompress, get_maximum_output_size};
+use std::{
+    io::{Error, ErrorKind, Result},
+    sync::{Arc, Mutex},
+};
+
+/// Maximum number of uncompressed bytes in a frame. Larger writes are split
+/// over several frames.
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// A channel wrapping another channel and compressing the bytes written to
+/// it with lz4.
+///
+/// The writes are buffered and compressed into a frame when the channel is
+/// flushed, or when the buffer holds `MAX_FRAME_LEN` bytes. The peer must
+/// wrap its channel in a `CompressedChannel` as well. Since the bytes
+/// written are only sent by a flush, the protocols run over it must flush
+/// before waiting on their peer, which `AbstractChannel` requires anyway.
+pub struct CompressedChannel<C>(Arc<Mutex<InternalCompressedChannel<C>>>);
+
+struct InternalCompressedChannel<C> {
+    channel: C,
+    // bytes written and not yet compressed
+    pending: Vec<u8>,
+    // decompressed bytes not yet read, from `read_pos`
+    decompressed: Vec<u8>,
+    read_pos: usize,
+}
+
+impl<C: AbstractChannel> CompressedChannel<C> {
+    /// Make a new `CompressedChannel` from `channel`.
+    pub fn new(channel: C) -> Self {
+        let internal = InternalCompressedChannel {
+            channel,
+            pending: Vec::new(),
+            decompressed: Vec::new(),
+            read_pos: 0,
+        };
+        Self(Arc::new(Mutex::new(internal)))
+    }
+}
+
+impl<C: AbstractChannel> InternalCompressedChannel<C> {
+    // Compress the pending bytes into a frame and write it to the
+    // underlying channel.
+    fn write_frame(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let frame = compress(&self.pending);
+        self.channel.write_u32(self.pending.len() as u32)?;
+        self.channel.write_u32(frame.len() as u32)?;
+        self.channel.write_bytes(&frame)?;
+        self.pending.clear();
+        Ok(())
+    }
+
+    // Read the next frame from the underlying channel and decompress it.
+    fn read_frame(&mut self) -> Result<()> {
+        let len = self.channel.read_u32()? as usize;
+        let compressed_len = self.channel.read_u32()? as usize;
+        if len == 0 || len > MAX_FRAME_LEN || compressed_len > get_maximum_output_size(len) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "invalid compressed frame header",
+            ));
+        }
+        let frame = self.channel.read_vec(compressed_len)?;
+        self.decompressed = decompress(&frame, len)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        if self.decompressed.len() != len {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "invalid compressed frame length",
+            ));
+        }
+        self.read_pos = 0;
+        Ok(())
+    }
+}
+
+impl<C: AbstractChannel> AbstractChannel for CompressedChannel<C> {
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        while !bytes.is_empty() {
+            let n = bytes.len().min(MAX_FRAME_LEN - int.pending.len());
+            int.pending.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+            if int.pending.len() == MAX_FRAME_LEN {
+                int.write_frame()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, mut bytes: &mut [u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        while !bytes.is_empty() {
+            if int.read_pos == int.decompressed.len() {
+                int.read_frame()?;
+            }
+            let n = bytes.len().min(int.decompressed.len() - int.read_pos);
+            let (head, tail) = bytes.split_at_mut(n);
+            head.copy_from_slice(&int.decompressed[int.read_pos..int.read_pos + n]);
+            int.read_pos += n;
+            bytes = tail;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        int.write_frame()?;
+        int.channel.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AesRng, Channel};
+    use rand::Rng;
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    #[test]
+    fn test_compressed_channel() {
+        let mut rng = AesRng::new();
+        let random: Vec<u8> = (0..MAX_FRAME_LEN + 1000).map(|_| rng.gen()).collect();
+        let zeros = vec![0u8; 3 * MAX_FRAME_LEN];
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let expected = (random.clone(), zeros.clone());
+        let handle = std::thread::spawn(move || {
+            let reader = BufReader::new(sender.try_clone().unwrap());
+            let writer = BufWriter::new(sender);
+            let mut channel = CompressedChannel::new(Channel::new(reader, writer));
+            channel.write_bytes(&random).unwrap();
+            channel.write_u64(42).unwrap();
+            channel.flush().unwrap();
+            channel.write_bytes(&zeros).unwrap();
+            channel.flush().unwrap();
+            assert_eq!(channel.read_u8().unwrap(), 1);
+        });
+        let reader = BufReader::new(receiver.try_clone().unwrap());
+        let writer = BufWriter::new(receiver);
+        let mut channel = CompressedChannel::new(Channel::new(reader, writer));
+        assert_eq!(channel.read_vec(expected.0.len()).unwrap(), expected.0);
+        assert_eq!(channel.read_u64().unwrap(), 42);
+        assert_eq!(channel.read_vec(expected.1.len()).unwrap(), expected.1);
+        channel.write_u8(1).unwrap();
+        channel.flush().unwrap();
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+use crate::AbstractChannel;
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{Error, ErrorKind, Read, Result, Write},
+    sync::{Arc, Condvar, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+const FRAME_DATA: u8 = 0;
+const FRAME_HEARTBEAT: u8 = 1;
+// Buffered writes are sent as a data frame once this many bytes are pending,
+// even without a flush.
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// The payload of the I/O error returned by a [`KeepAliveChannel`] when the
+/// peer has not been heard from within the configured timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerDisconnected;
+
+impl fmt::Display for PeerDisconnected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "peer disconnected".fmt(f)
+    }
+}
+
+impl std::error::Error for PeerDisconnected {}
+
+impl PeerDisconnected {
+    /// Return whether `e` reports a disconnected peer.
+    pub fn is(e: &Error) -> bool {
+        e.get_ref()
+            .map_or(false, |inner| inner.is::<PeerDisconnected>())
+    }
+}
+
+/// Timing of the heartbeats of a [`KeepAliveChannel`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeepAliveConfig {
+    /// A heartbeat is sent whenever nothing else has been sent for this long.
+    pub interval: Duration,
+    /// The peer is considered disconnected when nothing has been received
+    /// from it for this long. It should span several intervals.
+    pub timeout: Duration,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+struct Incoming {
+    data: VecDeque<u8>,
+    last_seen: Instant,
+    // Set once the reader thread stops, with the kind of the error it hit.
+    closed: Option<ErrorKind>,
+}
+
+struct Outgoing<W> {
+    writer: W,
+    buffer: Vec<u8>,
+    last_sent: Instant,
+}
+
+impl<W: Write> Outgoing<W> {
+    // A frame is written under the lock of `Outgoing`, so heartbeats never
+    // land in the middle of a message.
+    fn send_frame(&mut self, kind: u8) -> Result<()> {
+        self.writer.write_all(&[kind])?;
+        if kind == FRAME_DATA {
+            self.writer
+                .write_all(&(self.buffer.len() as u32).to_le_bytes())?;
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+}
+
+/// A channel exchanging heartbeats with its peer during idle periods, so that
+/// a dead peer is detected within [`KeepAliveConfig::timeout`] instead of
+/// leaving a read blocked until the kernel gives up on the connection.
+///
+/// Both parties must use a `KeepAliveChannel`, as everything written is
+/// framed. Heartbeats are sent and received by background threads; the one
+/// reading stops when the underlying reader reaches EOF or fails. A read that
+/// times out fails with an error whose payload is [`PeerDisconnected`].
+pub struct KeepAliveChannel<W> {
+    incoming: Arc<(Mutex<Incoming>, Condvar)>,
+    outgoing: Arc<Mutex<Outgoing<W>>>,
+    timeout: Duration,
+}
+
+impl<W: Write + Send + 'static> KeepAliveChannel<W> {
+    /// Make a new `KeepAliveChannel` from a `reader` and a `writer`.
+    pub fn new<R: Read + Send + 'static>(reader: R, writer: W, config: KeepAliveConfig) -> Self {
+        let now = Instant::now();
+        let incoming = Arc::new((
+            Mutex::new(Incoming {
+                data: VecDeque::new(),
+                last_seen: now,
+                closed: None,
+            }),
+            Condvar::new(),
+        ));
+        let outgoing = Arc::new(Mutex::new(Outgoing {
+            writer,
+            buffer: Vec::new(),
+            last_sent: now,
+        }));
+        let incoming_ = incoming.clone();
+        std::thread::spawn(move || receive_frames(reader, &incoming_));
+        let outgoing_ = Arc::downgrade(&outgoing);
+        std::thread::spawn(move || send_heartbeats(outgoing_, config.interval));
+        Self {
+            incoming,
+            outgoing,
+            timeout: config.timeout,
+        }
+    }
+}
+
+fn receive_frames<R: Read>(mut reader: R, incoming: &(Mutex<Incoming>, Condvar)) {
+    let (lock, cvar) = incoming;
+    let mut frame = Vec::new();
+    let kind = loop {
+        let mut kind = [0u8];
+        if let Err(e) = reader.read_exact(&mut kind) {
+            break e.kind();
+        }
+        if kind[0] == FRAME_DATA {
+            let mut len = [0u8; 4];
+            if let Err(e) = reader.read_exact(&mut len) {
+                break e.kind();
+            }
+            frame.resize(u32::from_le_bytes(len) as usize, 0);
+            if let Err(e) = reader.read_exact(&mut frame) {
+                break e.kind();
+            }
+        } else if kind[0] != FRAME_HEARTBEAT {
+            break ErrorKind::InvalidData;
+        }
+        let mut incoming = lock.lock().unwrap();
+        incoming.last_seen = Instant::now();
+        incoming.data.extend(frame.drain(..));
+        cvar.notify_all();
+    };
+    lock.lock().unwrap().closed = Some(kind);
+    cvar.notify_all();
+}
+
+// Stops once every clone of the channel has been dropped.
+fn send_heartbeats<W: Write>(outgoing: Weak<Mutex<Outgoing<W>>>, interval: Duration) {
+    let mut wait = interval;
+    loop {
+        std::thread::sleep(wait);
+        let outgoing = match outgoing.upgrade() {
+            Some(outgoing) => outgoing,
+            None => return,
+        };
+        let mut outgoing = outgoing.lock().unwrap();
+        let idle = outgoing.last_sent.elapsed();
+        if idle >= interval {
+            let sent = outgoing.send_frame(FRAME_HEARTBEAT);
+            if sent.and_then(|_| outgoing.writer.flush()).is_err() {
+                return;
+            }
+            wait = interval;
+        } else {
+            wait = interval - idle;
+        }
+    }
+}
+
+impl<W: Write> AbstractChannel for KeepAliveChannel<W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut outgoing = self.outgoing.lock().unwrap();
+        outgoing.buffer.extend_from_slice(bytes);
+        if outgoing.buffer.len() >= MAX_FRAME_LEN {
+            outgoing.send_frame(FRAME_DATA)?;
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let (lock, cvar) = &*self.incoming;
+        let mut incoming = lock.lock().unwrap();
+        let mut filled = 0;
+        while filled < bytes.len() {
+            let n = incoming.data.len().min(bytes.len() - filled);
+            for (b, x) in bytes[filled..filled + n]
+                .iter_mut()
+                .zip(incoming.data.drain(..n))
+            {
+                *b = x;
+            }
+            filled += n;
+            if filled == bytes.len() {
+                break;
+            }
+            if let Some(kind) = incoming.closed {
+                return Err(Error::new(kind, "keep-alive channel closed"));
+            }
+            let deadline = incoming.last_seen + self.timeout;
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::new(ErrorKind::TimedOut, PeerDisconnected));
+            }
+            incoming = cvar.wait_timeout(incoming, deadline - now).unwrap().0;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut outgoing = self.outgoing.lock().unwrap();
+        if !outgoing.buffer.is_empty() {
+            outgoing.send_frame(FRAME_DATA)?;
+        }
+        outgoing.writer.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self {
+            incoming: self.incoming.clone(),
+            outgoing: self.outgoing.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uds_windows::UnixStream;
+
+    fn config() -> KeepAliveConfig {
+        KeepAliveConfig {
+            interval: Duration::from_millis(20),
+            timeout: Duration::from_millis(200),
+        }
+    }
+
+    fn channel(stream: UnixStream) -> KeepAliveChannel<UnixStream> {
+        KeepAliveChannel::new(stream.try_clone().unwrap(), stream, config())
+    }
+
+    #[test]
+    fn test_keepalive_idle() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let mut a = channel(a);
+        let mut b = channel(b);
+        let handle = std::thread::spawn(move || {
+            // Idle for longer than the timeout before answering.
+            std::thread::sleep(Duration::from_millis(500));
+            let x = b.read_u64().unwrap();
+            b.write_u64(x + 1).unwrap();
+            b.flush().unwrap();
+        });
+        a.write_u64(41).unwrap();
+        a.flush().unwrap();
+        assert_eq!(a.read_u64().unwrap(), 42);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_keepalive_dead_peer() {
+        // The peer's end stays open but nothing ever comes out of it, as when
+        // a middlebox drops the connection.
+        let (a, _b) = UnixStream::pair().unwrap();
+        let mut a = channel(a);
+        let start = Instant::now();
+        let e = a.read_u64().unwrap_err();
+        assert!(PeerDisconnected::is(&e));
+        assert!(start.elapsed() >= config().timeout);
+        assert!(start.elapsed() < 5 * config().timeout);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::AbstractChannel;
+use std::{
+    collections::VecDeque,
+    io::{Error, ErrorKind, Result},
+    sync::{Arc, Condvar, Mutex},
+};
+
+// One direction of a local channel pair.
+#[derive(Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    cond: Condvar,
+}
+
+#[derive(Default)]
+struct PipeState {
+    bytes: VecDeque<u8>,
+    closed: bool,
+}
+
+impl Pipe {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.cond.notify_all();
+    }
+}
+
+struct Endpoint {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+    capacity: Option<usize>,
+}
+
+impl Drop for Endpoint {
+    fn drop(&mut self) {
+        self.incoming.close();
+        self.outgoing.close();
+    }
+}
+
+/// One end of an in-memory channel pair, see [`local_pair`].
+///
+/// The writes are available to the other end right away, so that flushing
+/// is a no-op. The reads block until the other end has written enough
+/// bytes, and fail once the other end is dropped. The clones of a
+/// `LocalChannel` share the same end, which is closed when all of them are
+/// dropped.
+pub struct LocalChannel(Arc<Endpoint>);
+
+/// Make a pair of connected in-memory channels, whose ends may live on
+/// different threads.
+pub fn local_pair() -> (LocalChannel, LocalChannel) {
+    make_pair(None)
+}
+
+/// Make a pair of connected in-memory channels, as with [`local_pair`], in
+/// which at most `capacity` bytes can be in flight in each direction.
+///
+/// A write blocks while the other end has `capacity` bytes left to read,
+/// which exercises the backpressure of a real transport.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn local_pair_with_capacity(capacity: usize) -> (LocalChannel, LocalChannel) {
+    assert!(
+        capacity > 0,
+        "the capacity of a local channel must be positive"
+    );
+    make_pair(Some(capacity))
+}
+
+fn make_pair(capacity: Option<usize>) -> (LocalChannel, LocalChannel) {
+    let a_to_b = Arc::new(Pipe::default());
+    let b_to_a = Arc::new(Pipe::default());
+    let a = Endpoint {
+        incoming: b_to_a.clone(),
+        outgoing: a_to_b.clone(),
+        capacity,
+    };
+    let b = Endpoint {
+        incoming: a_to_b,
+        outgoing: b_to_a,
+        capacity,
+    };
+    (LocalChannel(Arc::new(a)), LocalChannel(Arc::new(b)))
+}
+
+impl AbstractChannel for LocalChannel {
+    fn read_bytes(&mut self, mut bytes: &mut [u8]) -> Result<()> {
+        let pipe = &self.0.incoming;
+        let mut state = pipe.state.lock().unwrap();
+        while !bytes.is_empty() {
+            while state.bytes.is_empty() {
+                if state.closed {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "the other end of the local channel was dropped",
+                    ));
+                }
+                state = pipe.cond.wait(state).unwrap();
+            }
+            let n = bytes.len().min(state.bytes.len());
+            let (head, tail) = bytes.split_at_mut(n);
+            for (b, x) in head.iter_mut().zip(state.bytes.drain(..n)) {
+                *b = x;
+            }
+            bytes = tail;
+            // wake up a writer waiting for room
+            pipe.cond.notify_all();
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> Result<()> {
+        let pipe = &self.0.outgoing;
+        let mut state = pipe.state.lock().unwrap();
+        while !bytes.is_empty() {
+            if state.closed {
+                return Err(Error::new(
+                    ErrorKind::BrokenPipe,
+                    "the other end of the local channel was dropped",
+                ));
+            }
+            let room = match self.0.capacity {
+                Some(capacity) => capacity.saturating_sub(state.bytes.len()),
+                None => bytes.len(),
+            };
+            if room == 0 {
+                state = pipe.cond.wait(state).unwrap();
+                continue;
+            }
+            let n = bytes.len().min(room);
+            state.bytes.extend(&bytes[..n]);
+            bytes = &bytes[n..];
+            // wake up the reader
+            pipe.cond.notify_all();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_pair() {
+        let (mut a, mut b) = local_pair();
+        let handle = std::thread::spawn(move || {
+            for i in 0..1000u64 {
+                b.write_u64(i).unwrap();
+            }
+            b.read_u8().unwrap()
+        });
+        for i in 0..1000u64 {
+            assert_eq!(a.read_u64().unwrap(), i);
+        }
+        a.write_u8(7).unwrap();
+        assert_eq!(handle.join().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_local_pair_with_capacity() {
+        let (mut a, mut b) = local_pair_with_capacity(3);
+        let data: Vec<u8> = (0..=255).collect();
+        let expected = data.clone();
+        let handle = std::thread::spawn(move || b.write_bytes(&data).unwrap());
+        assert_eq!(a.read_vec(expected.len()).unwrap(), expected);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_local_pair_dropped() {
+        let (mut a, b) = local_pair();
+        let b_clone = b.clone();
+        drop(b);
+        a.write_u8(1).unwrap();
+        drop(b_clone);
+        assert!(a.read_u8().is_err());
+        assert!(a.write_u8(1).is_err());
+    }
+}
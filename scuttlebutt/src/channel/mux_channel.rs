@@ -0,0 +1,489 @@
+// The bookkeeping of the multiplexer (framing the writes of each stream,
+// picking the next frame to send, dispatching received frames) lives in
+// `WriteState` and `ReadState`, independently of what drives the underlying
+// channel. The pumps driving it here are threads; an async driver only needs
+// to replace `pump_writes` and `pump_reads`.
+
+use crate::AbstractChannel;
+use std::{
+    collections::VecDeque,
+    io::{Error, ErrorKind, Result},
+    sync::{Arc, Condvar, Mutex},
+    thread::JoinHandle,
+};
+
+// Writes are cut into frames of at most this many bytes, so that a stream
+// sending a lot cannot hold the others back for long. A received frame which
+// is longer is rejected before it is read.
+const MAX_FRAME_LEN: usize = 1 << 14;
+// Stream id of the frame sent when every virtual channel has been dropped.
+const CLOSE: u32 = u32::MAX;
+
+struct WriteState {
+    // Bytes written to each stream and not yet cut into a frame.
+    buffers: Vec<Vec<u8>>,
+    queues: Vec<VecDeque<Vec<u8>>>,
+    // Round-robin cursor over the streams.
+    next: usize,
+    // Frame counts per stream.
+    enqueued: Vec<u64>,
+    written: Vec<u64>,
+    flushed: Vec<u64>,
+    // Number of frames of each stream a flush is waiting on.
+    flush_target: Vec<u64>,
+    handles: usize,
+    error: Option<ErrorKind>,
+}
+
+enum WriteAction {
+    Flush,
+    Write(usize, Vec<u8>),
+    Close,
+}
+
+impl WriteState {
+    fn new(num_streams: usize) -> Self {
+        Self {
+            buffers: vec![Vec::new(); num_streams],
+            queues: vec![VecDeque::new(); num_streams],
+            next: 0,
+            enqueued: vec![0; num_streams],
+            written: vec![0; num_streams],
+            flushed: vec![0; num_streams],
+            flush_target: vec![0; num_streams],
+            handles: num_streams,
+            error: None,
+        }
+    }
+
+    fn enqueue(&mut self, id: usize, frame: Vec<u8>) {
+        self.queues[id].push_back(frame);
+        self.enqueued[id] += 1;
+    }
+
+    // Cut the buffered bytes of stream `id` into frames, keeping a partial
+    // frame back unless `all` is set.
+    fn frame(&mut self, id: usize, all: bool) {
+        while self.buffers[id].len() >= MAX_FRAME_LEN {
+            let rest = self.buffers[id].split_off(MAX_FRAME_LEN);
+            let frame = std::mem::replace(&mut self.buffers[id], rest);
+            self.enqueue(id, frame);
+        }
+        if all && !self.buffers[id].is_empty() {
+            let frame = std::mem::take(&mut self.buffers[id]);
+            self.enqueue(id, frame);
+        }
+    }
+
+    fn flush_pending(&self) -> bool {
+        (0..self.queues.len()).any(|id| {
+            self.flush_target[id] > self.flushed[id] && self.written[id] >= self.flush_target[id]
+        })
+    }
+
+    // What the writing pump should do next, if anything. A flush whose
+    // frames have all been written goes first, then frames are taken from
+    // the streams in turn.
+    fn next_action(&mut self) -> Option<WriteAction> {
+        if self.flush_pending() {
+            return Some(WriteAction::Flush);
+        }
+        let n = self.queues.len();
+        for i in 0..n {
+            let id = (self.next + i) % n;
+            if let Some(frame) = self.queues[id].pop_front() {
+                self.next = id + 1;
+                return Some(WriteAction::Write(id, frame));
+            }
+        }
+        if self.handles == 0 {
+            return Some(WriteAction::Close);
+        }
+        None
+    }
+}
+
+struct ReadState {
+    incoming: Vec<VecDeque<u8>>,
+    // Set once the reading pump stops, with the kind of the error it hit.
+    closed: Option<ErrorKind>,
+}
+
+impl ReadState {
+    fn deliver(&mut self, id: u32, frame: Vec<u8>) -> Result<()> {
+        match self.incoming.get_mut(id as usize) {
+            Some(incoming) => {
+                incoming.extend(frame);
+                Ok(())
+            }
+            None => Err(Error::new(ErrorKind::InvalidData, "unknown stream id")),
+        }
+    }
+}
+
+struct Shared {
+    write: Mutex<WriteState>,
+    // Wakes up the writing pump.
+    write_ready: Condvar,
+    // Wakes up the virtual channels waiting on a flush.
+    write_flushed: Condvar,
+    read: Mutex<ReadState>,
+    read_ready: Condvar,
+}
+
+/// One of the virtual channels returned by [`multiplex`].
+pub struct MuxChannel {
+    id: usize,
+    shared: Arc<Shared>,
+}
+
+/// Multiplex `num_streams` virtual channels over `channel`, with a thread
+/// writing to it and one reading from it.
+///
+/// Both parties must call `multiplex` with the same number of streams; the
+/// virtual channel at index `i` talks to the peer's virtual channel at index
+/// `i`. Each virtual channel delivers its bytes in order, writes from
+/// different streams are interleaved frame by frame, and flushing a virtual
+/// channel returns once its frames have been written to `channel` and
+/// `channel` has been flushed.
+///
+/// The pumps use clones of `channel`, so reading from a clone must not block
+/// writing to another, as is the case for `SyncChannel`. They stop once every
+/// virtual channel has been dropped on both sides.
+///
+/// The bytes received for a virtual channel are queued until it reads them,
+/// without bound, so a virtual channel which is not read from holds on to
+/// everything the peer sends to it. Dropping a virtual channel sends what
+/// was written to it and not flushed, which is flushed at the latest once
+/// the multiplexer closes.
+pub fn multiplex<C: AbstractChannel + Send + 'static>(
+    channel: C,
+    num_streams: usize,
+) -> Vec<MuxChannel> {
+    multiplex_with_pumps(channel, num_streams).0
+}
+
+/// The threads of the pumps of a multiplexer, see [`multiplex_with_pumps`].
+pub struct MuxPumps {
+    reader: JoinHandle<Result<()>>,
+    writer: JoinHandle<Result<()>>,
+}
+
+impl MuxPumps {
+    /// Wait for the pumps to stop, once every virtual channel has been
+    /// dropped on both sides, and return the first error they hit.
+    ///
+    /// The pumps read and write nothing past the frame closing the
+    /// multiplexer, so the channel given to [`multiplex_with_pumps`] can be
+    /// used again afterwards.
+    pub fn join(self) -> Result<()> {
+        let written = self.writer.join().expect("the writing pump panicked");
+        let read = self.reader.join().expect("the reading pump panicked");
+        written.and(read)
+    }
+}
+
+/// Like [`multiplex`], also returning the pumps, to wait for them to stop
+/// before using `channel` again.
+pub fn multiplex_with_pumps<C: AbstractChannel + Send + 'static>(
+    channel: C,
+    num_streams: usize,
+) -> (Vec<MuxChannel>, MuxPumps) {
+    let shared = Arc::new(Shared {
+        write: Mutex::new(WriteState::new(num_streams)),
+        write_ready: Condvar::new(),
+        write_flushed: Condvar::new(),
+        read: Mutex::new(ReadState {
+            incoming: vec![VecDeque::new(); num_streams],
+            closed: None,
+        }),
+        read_ready: Condvar::new(),
+    });
+    let reader = channel.clone();
+    let shared_ = shared.clone();
+    let reader = std::thread::spawn(move || pump_reads(reader, &shared_));
+    let shared_ = shared.clone();
+    let writer = std::thread::spawn(move || pump_writes(channel, &shared_));
+    let channels = (0..num_streams)
+        .map(|id| MuxChannel {
+            id,
+            shared: shared.clone(),
+        })
+        .collect();
+    (channels, MuxPumps { reader, writer })
+}
+
+fn pump_writes<C: AbstractChannel>(channel: C, shared: &Shared) -> Result<()> {
+    let res = write_frames(channel, shared);
+    if let Err(e) = &res {
+        shared.write.lock().unwrap().error = Some(e.kind());
+        shared.write_flushed.notify_all();
+    }
+    res
+}
+
+fn write_frames<C: AbstractChannel>(mut channel: C, shared: &Shared) -> Result<()> {
+    loop {
+        let mut state = shared.write.lock().unwrap();
+        let action = loop {
+            match state.next_action() {
+                Some(action) => break action,
+                None => state = shared.write_ready.wait(state).unwrap(),
+            }
+        };
+        drop(state);
+        match action {
+            WriteAction::Flush => {
+                channel.flush()?;
+                // Only this thread writes frames, so `written` has not
+                // changed since the flush started.
+                let mut state = shared.write.lock().unwrap();
+                state.flushed = state.written.clone();
+                shared.write_flushed.notify_all();
+            }
+            WriteAction::Write(id, frame) => {
+                channel.write_u32(id as u32)?;
+                channel.write_u32(frame.len() as u32)?;
+                channel.write_bytes(&frame)?;
+                shared.write.lock().unwrap().written[id] += 1;
+            }
+            WriteAction::Close => {
+                channel.write_u32(CLOSE)?;
+                return channel.flush();
+            }
+        }
+    }
+}
+
+// Once the peer has closed the multiplexer, reading from a virtual channel
+// fails as if the channel had been closed.
+fn pump_reads<C: AbstractChannel>(channel: C, shared: &Shared) -> Result<()> {
+    let res = read_frames(channel, shared);
+    let kind = match &res {
+        Ok(()) => ErrorKind::UnexpectedEof,
+        Err(e) => e.kind(),
+    };
+    shared.read.lock().unwrap().closed = Some(kind);
+    shared.read_ready.notify_all();
+    res
+}
+
+fn read_frames<C: AbstractChannel>(mut channel: C, shared: &Shared) -> Result<()> {
+    loop {
+        let id = channel.read_u32()?;
+        if id == CLOSE {
+            return Ok(());
+        }
+        let len = channel.read_u32()? as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(Error::new(ErrorKind::InvalidData, "frame too long"));
+        }
+        let frame = channel.read_vec(len)?;
+        shared.read.lock().unwrap().deliver(id, frame)?;
+        shared.read_ready.notify_all();
+    }
+}
+
+impl MuxChannel {
+    /// Return the stream id of the virtual channel.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+fn pump_error(kind: ErrorKind) -> Error {
+    Error::new(kind, "multiplexed channel closed")
+}
+
+impl AbstractChannel for MuxChannel {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut state = self.shared.write.lock().unwrap();
+        if let Some(kind) = state.error {
+            return Err(pump_error(kind));
+        }
+        state.buffers[self.id].extend_from_slice(bytes);
+        if state.buffers[self.id].len() >= MAX_FRAME_LEN {
+            state.frame(self.id, false);
+            self.shared.write_ready.notify_one();
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut state = self.shared.read.lock().unwrap();
+        let mut filled = 0;
+        loop {
+            let incoming = &mut state.incoming[self.id];
+            let n = incoming.len().min(bytes.len() - filled);
+            for (b, x) in bytes[filled..filled + n]
+                .iter_mut()
+                .zip(incoming.drain(..n))
+            {
+                *b = x;
+            }
+            filled += n;
+            if filled == bytes.len() {
+                return Ok(());
+            }
+            if let Some(kind) = state.closed {
+                return Err(pump_error(kind));
+            }
+            state = self.shared.read_ready.wait(state).unwrap();
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut state = self.shared.write.lock().unwrap();
+        state.frame(self.id, true);
+        let target = state.enqueued[self.id];
+        state.flush_target[self.id] = target;
+        self.shared.write_ready.notify_one();
+        while state.flushed[self.id] < target {
+            if let Some(kind) = state.error {
+                return Err(pump_error(kind));
+            }
+            state = self.shared.write_flushed.wait(state).unwrap();
+        }
+        Ok(())
+    }
+
+    fn clone(&self) -> Self {
+        self.shared.write.lock().unwrap().handles += 1;
+        Self {
+            id: self.id,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl Drop for MuxChannel {
+    fn drop(&mut self) {
+        let mut state = self.shared.write.lock().unwrap();
+        state.frame(self.id, true);
+        state.handles -= 1;
+        self.shared.write_ready.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_pair;
+
+    const NUM_STREAMS: usize = 4;
+
+    // Every stream answers each message with its bytes incremented, with
+    // messages large enough to span several frames.
+    fn echo(mut channel: MuxChannel, rounds: usize) {
+        for _ in 0..rounds {
+            let len = channel.read_usize().unwrap();
+            let mut data = channel.read_vec(len).unwrap();
+            data.iter_mut().for_each(|x| *x = x.wrapping_add(1));
+            channel.write_bytes(&data).unwrap();
+            channel.flush().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_multiplex() {
+        let (a, b) = local_pair();
+        let rounds = 10;
+        let servers: Vec<_> = multiplex(b, NUM_STREAMS)
+            .into_iter()
+            .map(|channel| std::thread::spawn(move || echo(channel, rounds)))
+            .collect();
+        // every client is spawned before any is joined
+        #[allow(clippy::needless_collect)]
+        let clients: Vec<_> = multiplex(a, NUM_STREAMS)
+            .into_iter()
+            .map(|mut channel| {
+                std::thread::spawn(move || {
+                    let id = channel.id();
+                    for round in 0..rounds {
+                        let len = (id + 1) * 10_000 + round;
+                        let data: Vec<u8> = (0..len).map(|i| (i + id) as u8).collect();
+                        channel.write_usize(len).unwrap();
+                        channel.write_bytes(&data).unwrap();
+                        channel.flush().unwrap();
+                        let echoed = channel.read_vec(len).unwrap();
+                        assert!(echoed
+                            .iter()
+                            .zip(data.iter())
+                            .all(|(y, x)| *y == x.wrapping_add(1)));
+                    }
+                })
+            })
+            .collect();
+        for handle in clients.into_iter().chain(servers) {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_multiplex_close() {
+        let (a, b) = local_pair();
+        let mut a = multiplex(a, 1);
+        let mut b = multiplex(b, 1);
+        a[0].write_u64(42).unwrap();
+        a[0].flush().unwrap();
+        assert_eq!(b[0].read_u64().unwrap(), 42);
+        drop(a);
+        assert!(b[0].read_u64().is_err());
+    }
+
+    #[test]
+    fn test_multiplex_drop_unflushed() {
+        let (a, b) = local_pair();
+        let mut a = multiplex(a, 2);
+        let mut b = multiplex(b, 2);
+        let data: Vec<u8> = (0..MAX_FRAME_LEN + 10).map(|i| i as u8).collect();
+        a[0].write_bytes(&data).unwrap();
+        a[1].write_u64(42).unwrap();
+        // the bytes are sent and flushed when the multiplexer closes
+        drop(a);
+        assert_eq!(b[0].read_vec(data.len()).unwrap(), data);
+        assert_eq!(b[1].read_u64().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_multiplex_frame_too_long() {
+        let (mut a, b) = local_pair();
+        let mut b = multiplex(b, 1);
+        a.write_u32(0).unwrap();
+        a.write_u32(MAX_FRAME_LEN as u32 + 1).unwrap();
+        a.flush().unwrap();
+        let err = b[0].read_u64().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_multiplex_reuse() {
+        let (mut a, mut b) = local_pair();
+        let handle = std::thread::spawn(move || {
+            let (mut channels, pumps) = multiplex_with_pumps(b.clone(), 2);
+            for channel in channels.iter_mut() {
+                let x = channel.read_u64().unwrap();
+                channel.write_u64(x + 1).unwrap();
+                channel.flush().unwrap();
+            }
+            drop(channels);
+            pumps.join().unwrap();
+            // the channel is back to its sole owner after the pumps stop
+            let x = b.read_u64().unwrap();
+            b.write_u64(x + 1).unwrap();
+            b.flush().unwrap();
+        });
+        let (mut channels, pumps) = multiplex_with_pumps(a.clone(), 2);
+        for (i, channel) in channels.iter_mut().enumerate() {
+            channel.write_u64(i as u64).unwrap();
+            channel.flush().unwrap();
+            assert_eq!(channel.read_u64().unwrap(), i as u64 + 1);
+        }
+        drop(channels);
+        pumps.join().unwrap();
+        a.write_u64(42).unwrap();
+        a.flush().unwrap();
+        assert_eq!(a.read_u64().unwrap(), 43);
+        handle.join().unwrap();
+    }
+}
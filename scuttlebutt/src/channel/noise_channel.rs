@@ -0,0 +1,216 @@
+use crate::AbstractChannel;
+use snow::{Builder, TransportState};
+use std::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    sync::{Arc, Mutex},
+};
+
+/// The Noise protocol run by `NoiseChannel`: the parties authenticate each
+/// other with a pre-shared key, and the session keys are derived from an
+/// ephemeral Diffie-Hellman exchange.
+const NOISE_PARAMS: &str = "Noise_NNpsk0_25519_ChaChaPoly_BLAKE2s";
+/// Maximum length of a Noise message.
+const MAX_MESSAGE_LEN: usize = 65535;
+/// Length of the authentication tag of an encrypted Noise message.
+const TAG_LEN: usize = 16;
+/// Maximum number of plaintext bytes in a record.
+const MAX_PAYLOAD_LEN: usize = MAX_MESSAGE_LEN - TAG_LEN;
+
+/// A channel encrypting and authenticating its communication with a Noise
+/// session over a `Read + Write` transport.
+///
+/// The session is established by [`NoiseChannel::initiator`] on one end and
+/// [`NoiseChannel::responder`] on the other, both knowing the same 32-byte
+/// pre-shared key.
+///
+/// The writes are buffered and encrypted into a record, each one prefixed
+/// with its length on two bytes, when the channel is flushed or when the
+/// buffer holds a full record. Every flush thus ends a record: a protocol
+/// flushing after small messages pays the 18 bytes of record overhead for
+/// each of them, and a protocol that does not flush before waiting on its
+/// peer never sends its last bytes.
+pub struct NoiseChannel<S>(Arc<Mutex<InternalNoiseChannel<S>>>);
+
+struct InternalNoiseChannel<S> {
+    stream: S,
+    transport: TransportState,
+    // plaintext written and not yet encrypted
+    pending: Vec<u8>,
+    // decrypted bytes not yet read, from `read_pos`
+    decrypted: Vec<u8>,
+    read_pos: usize,
+    // buffer for the records
+    record: Vec<u8>,
+}
+
+fn noise_error(e: snow::Error) -> Error {
+    Error::new(ErrorKind::InvalidData, e)
+}
+
+fn write_record<S: Write>(stream: &mut S, record: &[u8]) -> Result<()> {
+    stream.write_all(&(record.len() as u16).to_be_bytes())?;
+    stream.write_all(record)
+}
+
+fn read_record<S: Read>(stream: &mut S, record: &mut [u8]) -> Result<usize> {
+    let mut len = [0u8; 2];
+    stream.read_exact(&mut len)?;
+    let len = u16::from_be_bytes(len) as usize;
+    stream.read_exact(&mut record[..len])?;
+    Ok(len)
+}
+
+impl<S: Read + Write> NoiseChannel<S> {
+    /// Establish a session with the pre-shared key `psk` over `stream`, as
+    /// the initiator of the handshake.
+    pub fn initiator(stream: S, psk: &[u8; 32]) -> Result<Self> {
+        Self::handshake(stream, psk, true)
+    }
+
+    /// Establish a session with the pre-shared key `psk` over `stream`, as
+    /// the responder of the handshake.
+    pub fn responder(stream: S, psk: &[u8; 32]) -> Result<Self> {
+        Self::handshake(stream, psk, false)
+    }
+
+    fn handshake(mut stream: S, psk: &[u8; 32], initiator: bool) -> Result<Self> {
+        let builder = Builder::new(NOISE_PARAMS.parse().unwrap()).psk(0, psk);
+        let mut handshake = if initiator {
+            builder.build_initiator()
+        } else {
+            builder.build_responder()
+        }
+        .map_err(noise_error)?;
+        let mut record = vec![0; MAX_MESSAGE_LEN];
+        let mut payload = vec![0; MAX_MESSAGE_LEN];
+        while !handshake.is_handshake_finished() {
+            if handshake.is_my_turn() {
+                let len = handshake
+                    .write_message(&[], &mut record)
+                    .map_err(noise_error)?;
+                write_record(&mut stream, &record[..len])?;
+                stream.flush()?;
+            } else {
+                let len = read_record(&mut stream, &mut record)?;
+                handshake
+                    .read_message(&record[..len], &mut payload)
+                    .map_err(noise_error)?;
+            }
+        }
+        let transport = handshake.into_transport_mode().map_err(noise_error)?;
+        let internal = InternalNoiseChannel {
+            stream,
+            transport,
+            pending: Vec::new(),
+            decrypted: Vec::new(),
+            read_pos: 0,
+            record,
+        };
+        Ok(Self(Arc::new(Mutex::new(internal))))
+    }
+}
+
+impl<S: Read + Write> InternalNoiseChannel<S> {
+    // Encrypt the pending bytes into a record and write it to the stream.
+    fn write_pending(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let len = self
+            .transport
+            .write_message(&self.pending, &mut self.record)
+            .map_err(noise_error)?;
+        write_record(&mut self.stream, &self.record[..len])?;
+        self.pending.clear();
+        Ok(())
+    }
+
+    // Read the next record from the stream and decrypt it.
+    fn read_next(&mut self) -> Result<()> {
+        let len = read_record(&mut self.stream, &mut self.record)?;
+        self.decrypted.resize(MAX_MESSAGE_LEN, 0);
+        let len = self
+            .transport
+            .read_message(&self.record[..len], &mut self.decrypted)
+            .map_err(noise_error)?;
+        self.decrypted.truncate(len);
+        self.read_pos = 0;
+        Ok(())
+    }
+}
+
+impl<S: Read + Write> AbstractChannel for NoiseChannel<S> {
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        while !bytes.is_empty() {
+            let n = bytes.len().min(MAX_PAYLOAD_LEN - int.pending.len());
+            int.pending.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+            if int.pending.len() == MAX_PAYLOAD_LEN {
+                int.write_pending()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, mut bytes: &mut [u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        while !bytes.is_empty() {
+            if int.read_pos == int.decrypted.len() {
+                int.read_next()?;
+            }
+            let n = bytes.len().min(int.decrypted.len() - int.read_pos);
+            let (head, tail) = bytes.split_at_mut(n);
+            head.copy_from_slice(&int.decrypted[int.read_pos..int.read_pos + n]);
+            int.read_pos += n;
+            bytes = tail;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        int.write_pending()?;
+        int.stream.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uds_windows::UnixStream;
+
+    #[test]
+    fn test_noise_channel() {
+        let psk = [7u8; 32];
+        let data: Vec<u8> = (0..3 * MAX_PAYLOAD_LEN).map(|i| i as u8).collect();
+        let expected = data.clone();
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            let mut channel = NoiseChannel::initiator(sender, &psk).unwrap();
+            channel.write_bytes(&data).unwrap();
+            channel.write_u32(42).unwrap();
+            channel.flush().unwrap();
+            assert_eq!(channel.read_u8().unwrap(), 1);
+        });
+        let mut channel = NoiseChannel::responder(receiver, &psk).unwrap();
+        assert_eq!(channel.read_vec(expected.len()).unwrap(), expected);
+        assert_eq!(channel.read_u32().unwrap(), 42);
+        channel.write_u8(1).unwrap();
+        channel.flush().unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_noise_channel_wrong_psk() {
+        let (sender, receiver) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || NoiseChannel::initiator(sender, &[1u8; 32]));
+        assert!(NoiseChannel::responder(receiver, &[2u8; 32]).is_err());
+        // the responder fails without answering, dropping the stream
+        assert!(handle.join().unwrap().is_err());
+    }
+}
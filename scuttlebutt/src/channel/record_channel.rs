@@ -0,0 +1,413 @@
+use crate::{AbstractChannel, CommBreakdown};
+use std::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// Magic bytes and version of the format of the transcripts.
+const TRANSCRIPT_MAGIC: &[u8; 4] = b"SCTR";
+const TRANSCRIPT_VERSION: u8 = 1;
+
+const TAG_READ: u8 = 0;
+const TAG_WRITE: u8 = 1;
+const TAG_SCOPE: u8 = 2;
+const TAG_FLUSH: u8 = 3;
+
+/// An event of a transcript recorded by a [`RecordChannel`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TranscriptRecord {
+    /// Bytes read from the peer.
+    Read {
+        /// Time elapsed since the recording started.
+        at: Duration,
+        /// The bytes read.
+        bytes: Vec<u8>,
+    },
+    /// Bytes written to the peer.
+    Write {
+        /// Time elapsed since the recording started.
+        at: Duration,
+        /// The bytes written.
+        bytes: Vec<u8>,
+    },
+    /// A new scope, e.g. a phase of a protocol, see
+    /// [`AbstractChannel::scope`].
+    Scope {
+        /// Time elapsed since the recording started.
+        at: Duration,
+        /// The label of the scope.
+        label: String,
+    },
+    /// A flush of the channel.
+    Flush {
+        /// Time elapsed since the recording started.
+        at: Duration,
+    },
+}
+
+/// A transcript of the communication of one party, recorded by a
+/// [`RecordChannel`].
+///
+/// A transcript starts with the magic bytes `SCTR` and a version byte,
+/// followed by the records. Each record is a tag byte (0 for a read, 1 for a
+/// write, 2 for a scope and 3 for a flush), the time elapsed since the
+/// recording started in microseconds as 8 little-endian bytes, and for the
+/// reads, writes and scopes, the length of the bytes or of the label as 4
+/// little-endian bytes followed by the bytes or the label.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Transcript {
+    /// The records, in the order they happened.
+    pub records: Vec<TranscriptRecord>,
+}
+
+impl Transcript {
+    /// Parse a transcript written by a `RecordChannel`.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self> {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != TRANSCRIPT_MAGIC || header[4] != TRANSCRIPT_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, "not a transcript"));
+        }
+        let mut records = Vec::new();
+        loop {
+            let mut tag = [0u8; 1];
+            match reader.read_exact(&mut tag) {
+                Ok(()) => (),
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let mut at = [0u8; 8];
+            reader.read_exact(&mut at)?;
+            let at = Duration::from_micros(u64::from_le_bytes(at));
+            let record = match tag[0] {
+                TAG_READ => TranscriptRecord::Read {
+                    at,
+                    bytes: read_payload(&mut reader)?,
+                },
+                TAG_WRITE => TranscriptRecord::Write {
+                    at,
+                    bytes: read_payload(&mut reader)?,
+                },
+                TAG_SCOPE => TranscriptRecord::Scope {
+                    at,
+                    label: String::from_utf8(read_payload(&mut reader)?)
+                        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+                },
+                TAG_FLUSH => TranscriptRecord::Flush { at },
+                tag => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("unknown record tag {}", tag),
+                    ))
+                }
+            };
+            records.push(record);
+        }
+        Ok(Transcript { records })
+    }
+}
+
+fn read_payload<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+/// A channel wrapping another channel for recording its communication to a
+/// log, in the format of [`Transcript`].
+///
+/// The bytes read and written are recorded with the time they were
+/// exchanged, along with the scopes set with [`AbstractChannel::scope`],
+/// e.g. the phases of a protocol, which are also forwarded to the wrapped
+/// channel. The log is flushed on every flush of the channel. The clones of
+/// a `RecordChannel` share their log.
+pub struct RecordChannel<C, W>(Arc<Mutex<InternalRecordChannel<C, W>>>);
+
+struct InternalRecordChannel<C, W> {
+    channel: C,
+    log: W,
+    start: Instant,
+    scope: &'static str,
+}
+
+impl<C: AbstractChannel, W: Write> RecordChannel<C, W> {
+    /// Make a new `RecordChannel` recording the communication on `channel`
+    /// to `log`.
+    pub fn new(channel: C, mut log: W) -> Result<Self> {
+        log.write_all(TRANSCRIPT_MAGIC)?;
+        log.write_all(&[TRANSCRIPT_VERSION])?;
+        let internal = InternalRecordChannel {
+            channel,
+            log,
+            start: Instant::now(),
+            scope: CommBreakdown::UNSCOPED,
+        };
+        Ok(Self(Arc::new(Mutex::new(internal))))
+    }
+
+    /// Flush the log and return it, once the clones of the channel are
+    /// dropped.
+    pub fn into_log(self) -> Result<W> {
+        let internal = Arc::try_unwrap(self.0)
+            .map_err(|_| Error::new(ErrorKind::Other, "the channel is still shared"))?;
+        let mut internal = internal.into_inner().unwrap();
+        internal.log.flush()?;
+        Ok(internal.log)
+    }
+}
+
+impl<C, W: Write> InternalRecordChannel<C, W> {
+    fn record(&mut self, tag: u8, payload: Option<&[u8]>) -> Result<()> {
+        let at = self.start.elapsed().as_micros() as u64;
+        self.log.write_all(&[tag])?;
+        self.log.write_all(&at.to_le_bytes())?;
+        if let Some(payload) = payload {
+            self.log.write_all(&(payload.len() as u32).to_le_bytes())?;
+            self.log.write_all(payload)?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: AbstractChannel, W: Write> AbstractChannel for RecordChannel<C, W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        int.record(TAG_WRITE, Some(bytes))?;
+        int.channel.write_bytes(bytes)
+    }
+
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        int.channel.read_bytes(bytes)?;
+        int.record(TAG_READ, Some(bytes))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        int.record(TAG_FLUSH, None)?;
+        int.log.flush()?;
+        int.channel.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn scope(&mut self, label: &'static str) -> Option<&'static str> {
+        let mut int = self.0.lock().unwrap();
+        // A failure to record the scope shows up on the next record.
+        let _ = int.record(TAG_SCOPE, Some(label.as_bytes()));
+        int.channel.scope(label);
+        Some(std::mem::replace(&mut int.scope, label))
+    }
+
+    fn comm_breakdown(&self) -> Option<CommBreakdown> {
+        self.0.lock().unwrap().channel.comm_breakdown()
+    }
+}
+
+/// A channel replaying a [`Transcript`] to the party which recorded it.
+///
+/// The bytes read are the bytes the party read when recording, and the
+/// bytes written are compared with the bytes it wrote: the first byte that
+/// differs fails the write with an error of kind
+/// [`ErrorKind::InvalidData`], naming the recorded scope the byte was
+/// written in. Reading or writing more than was recorded fails as well.
+/// The party must run with the same randomness as when recording, e.g. with
+/// a seeded RNG, for the replay to match. The clones of a `ReplayChannel`
+/// share their position in the transcript.
+pub struct ReplayChannel(Arc<Mutex<InternalReplayChannel>>);
+
+struct InternalReplayChannel {
+    reads: Vec<u8>,
+    read_pos: usize,
+    writes: Vec<u8>,
+    write_pos: usize,
+    // the recorded scopes, with the number of bytes read and written when
+    // they were entered
+    scopes: Vec<(usize, usize, String)>,
+    scope: &'static str,
+}
+
+impl ReplayChannel {
+    /// Make a new `ReplayChannel` replaying `transcript`.
+    pub fn new(transcript: &Transcript) -> Self {
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+        let mut scopes = Vec::new();
+        for record in transcript.records.iter() {
+            match record {
+                TranscriptRecord::Read { bytes, .. } => reads.extend_from_slice(bytes),
+                TranscriptRecord::Write { bytes, .. } => writes.extend_from_slice(bytes),
+                TranscriptRecord::Scope { label, .. } => {
+                    scopes.push((reads.len(), writes.len(), label.clone()))
+                }
+                TranscriptRecord::Flush { .. } => (),
+            }
+        }
+        let internal = InternalReplayChannel {
+            reads,
+            read_pos: 0,
+            writes,
+            write_pos: 0,
+            scopes,
+            scope: CommBreakdown::UNSCOPED,
+        };
+        Self(Arc::new(Mutex::new(internal)))
+    }
+
+    /// Return the number of recorded bytes not read and not written yet.
+    pub fn remaining(&self) -> (usize, usize) {
+        let int = self.0.lock().unwrap();
+        (
+            int.reads.len() - int.read_pos,
+            int.writes.len() - int.write_pos,
+        )
+    }
+}
+
+impl InternalReplayChannel {
+    // The recorded scope of the byte read or written at `pos`.
+    fn recorded_scope(&self, pos: usize, written: bool) -> &str {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|(read, write, _)| if written { *write } else { *read } <= pos)
+            .map_or(CommBreakdown::UNSCOPED, |(_, _, label)| label)
+    }
+}
+
+impl AbstractChannel for ReplayChannel {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        let start = int.write_pos;
+        let recorded = &int.writes[start..];
+        let diverged = bytes
+            .iter()
+            .zip(recorded.iter())
+            .position(|(written, recorded)| written != recorded);
+        let message = match diverged {
+            Some(i) => format!(
+                "replay diverged at byte {} written, in the recorded scope `{}` (local scope \
+                 `{}`): recorded {:#04x}, written {:#04x}",
+                start + i,
+                int.recorded_scope(start + i, true),
+                int.scope,
+                recorded[i],
+                bytes[i]
+            ),
+            None if bytes.len() > recorded.len() => format!(
+                "replay wrote past the end of the recording, in the local scope `{}`",
+                int.scope
+            ),
+            None => {
+                int.write_pos += bytes.len();
+                return Ok(());
+            }
+        };
+        Err(Error::new(ErrorKind::InvalidData, message))
+    }
+
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut int = self.0.lock().unwrap();
+        let start = int.read_pos;
+        if int.reads.len() - start < bytes.len() {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!(
+                    "replay read past the end of the recording, in the local scope `{}`",
+                    int.scope
+                ),
+            ));
+        }
+        bytes.copy_from_slice(&int.reads[start..start + bytes.len()]);
+        int.read_pos += bytes.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn scope(&mut self, label: &'static str) -> Option<&'static str> {
+        let mut int = self.0.lock().unwrap();
+        Some(std::mem::replace(&mut int.scope, label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Channel;
+    use std::io::Cursor;
+
+    // A small exchange, writing `last` at the end.
+    fn exchange<C: AbstractChannel>(channel: &mut C, last: u32) -> Result<u16> {
+        channel.write_u8(1)?;
+        channel.scope("second");
+        let read = channel.read_u16()?;
+        channel.write_u32(last)?;
+        channel.flush()?;
+        Ok(read)
+    }
+
+    #[test]
+    fn test_record_replay() {
+        let peer = Channel::new(Cursor::new(vec![7u8, 0]), Vec::new());
+        let mut channel = RecordChannel::new(peer, Vec::new()).unwrap();
+        assert_eq!(exchange(&mut channel, 42).unwrap(), 7);
+        let log = channel.into_log().unwrap();
+
+        let transcript = Transcript::read(&log[..]).unwrap();
+        let records: Vec<_> = transcript
+            .records
+            .iter()
+            .map(|record| match record {
+                TranscriptRecord::Read { bytes, .. } => ("read", bytes.clone()),
+                TranscriptRecord::Write { bytes, .. } => ("write", bytes.clone()),
+                TranscriptRecord::Scope { label, .. } => ("scope", label.as_bytes().to_vec()),
+                TranscriptRecord::Flush { .. } => ("flush", Vec::new()),
+            })
+            .collect();
+        assert_eq!(
+            records,
+            [
+                ("write", vec![1]),
+                ("scope", b"second".to_vec()),
+                ("read", vec![7, 0]),
+                ("write", 42u32.to_le_bytes().to_vec()),
+                ("flush", Vec::new()),
+            ]
+        );
+
+        // the honest replay matches byte for byte
+        let mut replay = ReplayChannel::new(&transcript);
+        assert_eq!(exchange(&mut replay, 42).unwrap(), 7);
+        assert_eq!(replay.remaining(), (0, 0));
+
+        // a divergent replay names the recorded scope of the first
+        // divergent byte
+        let mut replay = ReplayChannel::new(&transcript);
+        let err = exchange(&mut replay, 43).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("byte 1 written"));
+        assert!(err.to_string().contains("`second`"));
+
+        // so is reading more than was recorded
+        let mut replay = ReplayChannel::new(&transcript);
+        exchange(&mut replay, 42).unwrap();
+        assert_eq!(
+            replay.read_u8().unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+
+        assert!(Transcript::read(&log[1..]).is_err());
+    }
+}
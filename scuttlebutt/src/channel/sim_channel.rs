@@ -0,0 +1,258 @@
+use crate::{local_pair, AbstractChannel, AesRng, Block, LocalChannel};
+use rand::{Rng, SeedableRng};
+use std::{
+    io::Result,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// Writes are sent as a frame once this many bytes are buffered, even without
+// a flush, so that bulk transfers are spread over the simulated link.
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+/// Random delay added to the delivery of each message of a [`SimChannel`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jitter {
+    /// The delay is drawn uniformly between zero and `max`.
+    pub max: Duration,
+    /// Seed of the delays, so that runs can be reproduced.
+    pub seed: u64,
+}
+
+/// Characteristics of the link simulated by a [`SimChannel`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimParams {
+    /// One-way latency of every message.
+    pub latency: Duration,
+    /// Throughput of the link in bits per second, or `None` if unlimited.
+    pub bandwidth: Option<u64>,
+    /// Optional random delay on top of the latency.
+    pub jitter: Option<Jitter>,
+}
+
+impl SimParams {
+    /// A wide area network with 50 ms of one-way latency and 100 Mbit/s of
+    /// bandwidth.
+    pub fn wan() -> Self {
+        Self {
+            latency: Duration::from_millis(50),
+            bandwidth: Some(100_000_000),
+            jitter: None,
+        }
+    }
+
+    // Time needed to push `len` bytes through the link.
+    fn transmission_time(&self, len: usize) -> Duration {
+        match self.bandwidth {
+            Some(bandwidth) => {
+                Duration::from_nanos((len as u128 * 8 * 1_000_000_000 / bandwidth as u128) as u64)
+            }
+            None => Duration::ZERO,
+        }
+    }
+}
+
+struct Sender {
+    buffer: Vec<u8>,
+    // When the simulated link is done transmitting the previous message.
+    link_free: Instant,
+    last_delivery: Instant,
+    rng: Option<AesRng>,
+}
+
+impl Sender {
+    // Send the buffered bytes as a frame carrying its delivery time.
+    fn send_frame<C: AbstractChannel>(
+        &mut self,
+        inner: &mut C,
+        params: &SimParams,
+        epoch: Instant,
+    ) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let start = self.link_free.max(Instant::now());
+        self.link_free = start + params.transmission_time(self.buffer.len());
+        let jitter = match (params.jitter, self.rng.as_mut()) {
+            (Some(jitter), Some(rng)) => {
+                Duration::from_nanos(rng.gen_range(0..=jitter.max.as_nanos() as u64))
+            }
+            _ => Duration::ZERO,
+        };
+        // Jitter never reorders messages.
+        let delivery = (self.link_free + params.latency + jitter).max(self.last_delivery);
+        self.last_delivery = delivery;
+        inner.write_u64((delivery - epoch).as_nanos() as u64)?;
+        inner.write_usize(self.buffer.len())?;
+        inner.write_bytes(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+struct Receiver {
+    frame: Vec<u8>,
+    pos: usize,
+}
+
+/// A channel simulating a network link with latency, limited bandwidth and
+/// jitter on top of another channel, for measuring protocols in process.
+///
+/// Written bytes are delivered as one message per flush, after the latency
+/// and the time the link needs to transmit them, in order. The two ends must
+/// be made together by [`SimChannel::pair`] or [`sim_pair`], as they share
+/// the clock the delivery times refer to.
+pub struct SimChannel<C> {
+    inner: C,
+    params: SimParams,
+    epoch: Instant,
+    sender: Arc<Mutex<Sender>>,
+    receiver: Arc<Mutex<Receiver>>,
+}
+
+impl<C: AbstractChannel> SimChannel<C> {
+    fn new(inner: C, params: SimParams, epoch: Instant, direction: u128) -> Self {
+        let rng = params.jitter.map(|jitter| {
+            AesRng::from_seed(Block::from(((jitter.seed as u128) << 64) | direction))
+        });
+        let sender = Sender {
+            buffer: Vec::new(),
+            link_free: epoch,
+            last_delivery: epoch,
+            rng,
+        };
+        let receiver = Receiver {
+            frame: Vec::new(),
+            pos: 0,
+        };
+        Self {
+            inner,
+            params,
+            epoch,
+            sender: Arc::new(Mutex::new(sender)),
+            receiver: Arc::new(Mutex::new(receiver)),
+        }
+    }
+
+    /// Make the two ends of a simulated link over the connected channels `a`
+    /// and `b`.
+    pub fn pair(a: C, b: C, params: SimParams) -> (Self, Self) {
+        let epoch = Instant::now();
+        (
+            Self::new(a, params, epoch, 0),
+            Self::new(b, params, epoch, 1),
+        )
+    }
+
+    /// Return the parameters of the simulated link.
+    pub fn params(&self) -> SimParams {
+        self.params
+    }
+}
+
+/// Make the two ends of a simulated link over an in-memory channel pair.
+pub fn sim_pair(params: SimParams) -> (SimChannel<LocalChannel>, SimChannel<LocalChannel>) {
+    let (a, b) = local_pair();
+    SimChannel::pair(a, b, params)
+}
+
+impl<C: AbstractChannel> AbstractChannel for SimChannel<C> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut sender = self.sender.lock().unwrap();
+        sender.buffer.extend_from_slice(bytes);
+        if sender.buffer.len() >= MAX_FRAME_LEN {
+            sender.send_frame(&mut self.inner, &self.params, self.epoch)?;
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        let mut receiver = self.receiver.lock().unwrap();
+        let mut filled = 0;
+        while filled < bytes.len() {
+            if receiver.pos == receiver.frame.len() {
+                let delivery = self.epoch + Duration::from_nanos(self.inner.read_u64()?);
+                let len = self.inner.read_usize()?;
+                receiver.frame.resize(len, 0);
+                self.inner.read_bytes(&mut receiver.frame)?;
+                receiver.pos = 0;
+                let now = Instant::now();
+                if delivery > now {
+                    std::thread::sleep(delivery - now);
+                }
+            }
+            let n = (bytes.len() - filled).min(receiver.frame.len() - receiver.pos);
+            bytes[filled..filled + n]
+                .copy_from_slice(&receiver.frame[receiver.pos..receiver.pos + n]);
+            receiver.pos += n;
+            filled += n;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.sender
+            .lock()
+            .unwrap()
+            .send_frame(&mut self.inner, &self.params, self.epoch)?;
+        self.inner.flush()
+    }
+
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            params: self.params,
+            epoch: self.epoch,
+            sender: self.sender.clone(),
+            receiver: self.receiver.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sim_channel_latency() {
+        let params = SimParams {
+            latency: Duration::from_millis(20),
+            bandwidth: None,
+            jitter: None,
+        };
+        let (mut a, mut b) = sim_pair(params);
+        let handle = std::thread::spawn(move || {
+            let x = b.read_u64().unwrap();
+            b.write_u64(x + 1).unwrap();
+            b.flush().unwrap();
+        });
+        let start = Instant::now();
+        a.write_u64(41).unwrap();
+        a.flush().unwrap();
+        assert_eq!(a.read_u64().unwrap(), 42);
+        assert!(start.elapsed() >= 2 * params.latency);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_sim_channel_bandwidth_and_jitter() {
+        let params = SimParams {
+            latency: Duration::ZERO,
+            bandwidth: Some(1_000_000),
+            jitter: Some(Jitter {
+                max: Duration::from_millis(5),
+                seed: 7,
+            }),
+        };
+        let (mut a, mut b) = sim_pair(params);
+        let data: Vec<u8> = (0..12_500).map(|i| i as u8).collect();
+        let start = Instant::now();
+        for chunk in data.chunks(1250) {
+            a.write_bytes(chunk).unwrap();
+            a.flush().unwrap();
+        }
+        // 100 kbit at 1 Mbit/s, received in order despite the jitter.
+        assert_eq!(b.read_vec(data.len()).unwrap(), data);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
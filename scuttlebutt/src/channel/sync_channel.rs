@@ -27,6 +27,85 @@ impl<R: Read, W: Write> SyncChannel<R, W> {
     pub fn writer(self) -> Arc<Mutex<W>> {
         self.writer
     }
+
+    /// Split the channel into a read half and a write half, which can be
+    /// used from different threads. Other clones of the channel keep sharing
+    /// the reader and the writer with the halves.
+    pub fn split(self) -> (ReadHalf<R>, WriteHalf<W>) {
+        (
+            ReadHalf {
+                reader: self.reader,
+            },
+            WriteHalf {
+                writer: self.writer,
+            },
+        )
+    }
+}
+
+/// The read half of a channel, returned by `split`.
+pub struct ReadHalf<R> {
+    reader: Arc<Mutex<R>>,
+}
+
+/// The write half of a channel, returned by `split`.
+pub struct WriteHalf<W> {
+    writer: Arc<Mutex<W>>,
+}
+
+impl<R: Read> ReadHalf<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader: Arc::new(Mutex::new(reader)),
+        }
+    }
+
+    /// Read exactly `bytes.len()` bytes.
+    pub fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        self.reader.lock().unwrap().read_exact(bytes)
+    }
+
+    /// Put the channel back together from its two halves.
+    pub fn unsplit<W: Write>(self, write_half: WriteHalf<W>) -> SyncChannel<R, W> {
+        SyncChannel {
+            reader: self.reader,
+            writer: write_half.writer,
+        }
+    }
+}
+
+impl<W: Write> WriteHalf<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Write all of `bytes`.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer.lock().unwrap().write_all(bytes)
+    }
+
+    /// Flush the writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+impl<R: Read> Read for ReadHalf<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.reader.lock().unwrap().read(buf)
+    }
+}
+
+impl<W: Write> Write for WriteHalf<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.writer.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
 }
 
 impl<R: Read, W: Write> AbstractChannel for SyncChannel<R, W> {
@@ -55,3 +134,70 @@ impl<R: Read, W: Write> AbstractChannel for SyncChannel<R, W> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, BufWriter};
+    use uds_windows::UnixStream;
+
+    const NUM_MESSAGES: u64 = 20_000;
+
+    fn channel(stream: UnixStream) -> SyncChannel<BufReader<UnixStream>, BufWriter<UnixStream>> {
+        SyncChannel::new(
+            BufReader::new(stream.try_clone().unwrap()),
+            BufWriter::new(stream),
+        )
+    }
+
+    // Write numbered messages of varying lengths and check that the peer
+    // reads them back intact and in order.
+    fn write_messages<W: Write>(mut write_half: WriteHalf<W>, seed: u64) -> WriteHalf<W> {
+        for i in 0..NUM_MESSAGES {
+            let len = (i % 61) as usize;
+            let mut message = vec![(i ^ seed) as u8; len + 9];
+            message[..8].copy_from_slice(&i.to_le_bytes());
+            message[8] = len as u8;
+            write_half.write_bytes(&message).unwrap();
+        }
+        write_half.flush().unwrap();
+        write_half
+    }
+
+    fn read_messages<R: Read>(mut read_half: ReadHalf<R>, seed: u64) -> ReadHalf<R> {
+        for i in 0..NUM_MESSAGES {
+            let mut header = [0u8; 9];
+            read_half.read_bytes(&mut header).unwrap();
+            assert_eq!(u64::from_le_bytes(header[..8].try_into().unwrap()), i);
+            let mut body = vec![0u8; header[8] as usize];
+            read_half.read_bytes(&mut body).unwrap();
+            assert!(body.iter().all(|b| *b == (i ^ seed) as u8));
+        }
+        read_half
+    }
+
+    #[test]
+    fn test_split_concurrent() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let (a_read, a_write) = channel(a).split();
+        let (b_read, b_write) = channel(b).split();
+        // Both directions are busy at the same time, each half on its own
+        // thread.
+        let handles = (
+            std::thread::spawn(move || write_messages(a_write, 1)),
+            std::thread::spawn(move || read_messages(b_read, 1)),
+            std::thread::spawn(move || write_messages(b_write, 2)),
+            std::thread::spawn(move || read_messages(a_read, 2)),
+        );
+        let a_write = handles.0.join().unwrap();
+        let b_read = handles.1.join().unwrap();
+        let b_write = handles.2.join().unwrap();
+        let a_read = handles.3.join().unwrap();
+
+        let mut a = a_read.unsplit(a_write);
+        let mut b = b_read.unsplit(b_write);
+        a.write_u64(42).unwrap();
+        a.flush().unwrap();
+        assert_eq!(b.read_u64().unwrap(), 42);
+    }
+}
@@ -1,16 +1,131 @@
 use crate::AbstractChannel;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
+    fmt,
     io::Result,
     sync::{Arc, Mutex},
 };
 
+/// The number of bytes read from and written to a channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScopeComm {
+    /// Number of bytes read.
+    pub bytes_read: usize,
+    /// Number of bytes written.
+    pub bytes_written: usize,
+}
+
+impl ScopeComm {
+    /// Return the number of bytes read and written.
+    pub fn total(&self) -> usize {
+        self.bytes_read + self.bytes_written
+    }
+}
+
+/// The communication on a channel broken down per scope, see
+/// [`AbstractChannel::scope`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommBreakdown {
+    scopes: BTreeMap<&'static str, ScopeComm>,
+    flushes: BTreeMap<&'static str, usize>,
+}
+
+impl CommBreakdown {
+    /// Label of the communication happening before any scope is entered.
+    pub const UNSCOPED: &'static str = "unscoped";
+
+    /// Return the communication within the scope `label`, zero if there was
+    /// none.
+    pub fn get(&self, label: &str) -> ScopeComm {
+        self.scopes.get(label).copied().unwrap_or_default()
+    }
+
+    /// Iterate over the scopes with some communication, ordered by label.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, ScopeComm)> + '_ {
+        self.scopes.iter().map(|(label, comm)| (*label, *comm))
+    }
+
+    /// Return the number of times the channel was flushed within the scope
+    /// `label`. Every flush ends a message, so this bounds the number of
+    /// rounds of communication in the scope.
+    pub fn flushes(&self, label: &str) -> usize {
+        self.flushes.get(label).copied().unwrap_or_default()
+    }
+
+    /// Return the communication summed over all the scopes.
+    pub fn total(&self) -> ScopeComm {
+        self.scopes
+            .values()
+            .fold(ScopeComm::default(), |acc, comm| ScopeComm {
+                bytes_read: acc.bytes_read + comm.bytes_read,
+                bytes_written: acc.bytes_written + comm.bytes_written,
+            })
+    }
+
+    /// Return the communication that happened since `earlier`, a breakdown
+    /// taken previously on the same channel.
+    pub fn since(&self, earlier: &CommBreakdown) -> CommBreakdown {
+        let scopes = self
+            .scopes
+            .iter()
+            .filter_map(|(label, comm)| {
+                let before = earlier.get(label);
+                let comm = ScopeComm {
+                    bytes_read: comm.bytes_read - before.bytes_read,
+                    bytes_written: comm.bytes_written - before.bytes_written,
+                };
+                if comm.total() == 0 {
+                    None
+                } else {
+                    Some((*label, comm))
+                }
+            })
+            .collect();
+        let flushes = self
+            .flushes
+            .iter()
+            .filter_map(|(label, flushes)| {
+                let flushes = flushes - earlier.flushes(label);
+                if flushes == 0 {
+                    None
+                } else {
+                    Some((*label, flushes))
+                }
+            })
+            .collect();
+        CommBreakdown { scopes, flushes }
+    }
+}
+
+impl fmt::Display for CommBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, comm) in self.iter() {
+            writeln!(
+                f,
+                "{}: {} bytes read, {} bytes written",
+                label, comm.bytes_read, comm.bytes_written
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// A channel wrapping another channel for tracking the number of bits read/written.
+///
+/// The communication is also broken down per scope, the current scope being
+/// set with [`AbstractChannel::scope`]. The clones of a `TrackChannel` share
+/// their counters and current scope.
 pub struct TrackChannel<C>(Arc<Mutex<InternalTrackChannel<C>>>);
 
 struct InternalTrackChannel<C> {
     channel: C,
     nbits_read: usize,
     nbits_written: usize,
+    scope: &'static str,
+    breakdown: CommBreakdown,
 }
 
 impl<C: AbstractChannel> TrackChannel<C> {
@@ -20,15 +135,24 @@ impl<C: AbstractChannel> TrackChannel<C> {
             channel,
             nbits_read: 0,
             nbits_written: 0,
+            scope: CommBreakdown::UNSCOPED,
+            breakdown: CommBreakdown::default(),
         };
         Self(Arc::new(Mutex::new(internal)))
     }
 
-    /// Clear the number of bits read/written.
+    /// Clear the number of bits read/written, and the breakdown per scope.
+    /// The current scope is kept.
     pub fn clear(&mut self) {
         let mut int = self.0.lock().unwrap();
         int.nbits_read = 0;
         int.nbits_written = 0;
+        int.breakdown = CommBreakdown::default();
+    }
+
+    /// Return the communication on the channel broken down per scope.
+    pub fn breakdown(&self) -> CommBreakdown {
+        self.0.lock().unwrap().breakdown.clone()
     }
 
     /// Return the number of kilobits written to the channel.
@@ -63,10 +187,17 @@ impl<C: AbstractChannel> TrackChannel<C> {
     }
 }
 
+impl<C> InternalTrackChannel<C> {
+    fn current(&mut self) -> &mut ScopeComm {
+        self.breakdown.scopes.entry(self.scope).or_default()
+    }
+}
+
 impl<C: AbstractChannel> AbstractChannel for TrackChannel<C> {
     fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
         let mut int = self.0.lock().unwrap();
         int.nbits_written += bytes.len() * 8;
+        int.current().bytes_written += bytes.len();
         int.channel.write_bytes(bytes)?;
         int.channel.flush()?;
         Ok(())
@@ -75,14 +206,82 @@ impl<C: AbstractChannel> AbstractChannel for TrackChannel<C> {
     fn read_bytes(&mut self, mut bytes: &mut [u8]) -> Result<()> {
         let mut int = self.0.lock().unwrap();
         int.nbits_read += bytes.len() * 8;
+        int.current().bytes_read += bytes.len();
         int.channel.read_bytes(&mut bytes)
     }
 
     fn flush(&mut self) -> Result<()> {
-        self.0.lock().unwrap().channel.flush()
+        let mut int = self.0.lock().unwrap();
+        let scope = int.scope;
+        *int.breakdown.flushes.entry(scope).or_default() += 1;
+        int.channel.flush()
     }
 
     fn clone(&self) -> Self {
         Self(self.0.clone())
     }
+
+    fn scope(&mut self, label: &'static str) -> Option<&'static str> {
+        let mut int = self.0.lock().unwrap();
+        Some(std::mem::replace(&mut int.scope, label))
+    }
+
+    fn comm_breakdown(&self) -> Option<CommBreakdown> {
+        Some(self.breakdown())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Channel;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_track_channel_scopes() {
+        let channel = Channel::new(Cursor::new(vec![0u8; 10]), Vec::new());
+        let mut channel = TrackChannel::new(channel);
+        channel.write_u8(1).unwrap();
+        assert_eq!(channel.scope("first"), Some(CommBreakdown::UNSCOPED));
+        channel.write_u32(2).unwrap();
+        channel.read_u16().unwrap();
+        let earlier = channel.breakdown();
+        assert_eq!(channel.scope("second"), Some("first"));
+        channel.read_u64().unwrap();
+        channel.scope("first");
+        channel.write_u16(3).unwrap();
+        channel.flush().unwrap();
+
+        let breakdown = channel.comm_breakdown().unwrap();
+        assert_eq!(
+            breakdown.get(CommBreakdown::UNSCOPED),
+            ScopeComm {
+                bytes_read: 0,
+                bytes_written: 1
+            }
+        );
+        assert_eq!(
+            breakdown.get("first"),
+            ScopeComm {
+                bytes_read: 2,
+                bytes_written: 6
+            }
+        );
+        assert_eq!(breakdown.get("second").bytes_read, 8);
+        assert_eq!(breakdown.flushes("first"), 1);
+        assert_eq!(breakdown.flushes("second"), 0);
+        assert_eq!(
+            (breakdown.total().total() * 8) as f64 / 1000.0,
+            channel.total_kilobits()
+        );
+
+        let since = breakdown.since(&earlier);
+        assert_eq!(since.iter().count(), 2);
+        assert_eq!(since.get("first").bytes_written, 2);
+        assert_eq!(since.get(CommBreakdown::UNSCOPED), ScopeComm::default());
+        assert_eq!(since.flushes("first"), 1);
+
+        channel.clear();
+        assert_eq!(channel.breakdown(), CommBreakdown::default());
+    }
 }
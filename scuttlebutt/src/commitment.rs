@@ -1,5 +1,6 @@
 //! A trait defining a Commitment Scheme and an implementation in the random
-//! oracle model using SHA256.
+//! oracle model using SHA256, along with [`HashCommitment`], a hiding
+//! commitment to byte strings drawing its own randomness.
 //!
 //! # Usage
 //! ```rust
@@ -28,7 +29,12 @@
 //! assert!(ShaCommitment::check(&commitment,&commitment_));
 //! ```
 
+use crate::{AbstractChannel, Block};
+use rand::{CryptoRng, Rng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 
 /// Generic commitment scheme.
 pub trait Commitment {
@@ -79,6 +85,104 @@ impl Commitment for ShaCommitment {
     }
 }
 
+/// A commitment `SHA256(r ‖ m)` to a message `m`, where `r` is 32 random
+/// bytes kept in the [`Opening`].
+///
+/// It is hiding as long as the opening is kept secret, and binding; both in
+/// the random oracle model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HashCommitment([u8; 32]);
+
+/// The randomness opening a [`HashCommitment`], revealed with the message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Opening([u8; 32]);
+
+impl HashCommitment {
+    /// The length in bytes of a commitment.
+    pub const LEN: usize = 32;
+
+    fn hash(bytes: &[u8], opening: &Opening) -> [u8; 32] {
+        let mut h = Sha256::new();
+        h.update(opening.0);
+        h.update(bytes);
+        h.finalize().into()
+    }
+
+    /// Commit to `bytes`.
+    pub fn commit<RNG: CryptoRng + Rng>(rng: &mut RNG, bytes: &[u8]) -> (Self, Opening) {
+        let opening = Opening(rng.gen());
+        (Self(Self::hash(bytes, &opening)), opening)
+    }
+
+    /// Commit to a `Block`.
+    pub fn commit_block<RNG: CryptoRng + Rng>(rng: &mut RNG, block: &Block) -> (Self, Opening) {
+        Self::commit(rng, block.as_ref())
+    }
+
+    /// Check in constant time that the commitment opens to `bytes` with
+    /// `opening`.
+    pub fn verify(&self, bytes: &[u8], opening: &Opening) -> bool {
+        self.0.ct_eq(&Self::hash(bytes, opening)).into()
+    }
+
+    /// Check in constant time that the commitment opens to `block` with
+    /// `opening`.
+    pub fn verify_block(&self, block: &Block, opening: &Opening) -> bool {
+        self.verify(block.as_ref(), opening)
+    }
+
+    /// Return the commitment as bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Make a commitment from bytes.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Write the commitment to `channel`.
+    pub fn write<C: AbstractChannel>(&self, channel: &mut C) -> std::io::Result<()> {
+        channel.write_bytes(&self.0)
+    }
+
+    /// Read a commitment from `channel`.
+    pub fn read<C: AbstractChannel>(channel: &mut C) -> std::io::Result<Self> {
+        let mut bytes = [0u8; 32];
+        channel.read_bytes(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl Opening {
+    /// The length in bytes of an opening.
+    pub const LEN: usize = 32;
+
+    /// Return the opening as bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Make an opening from bytes.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Write the opening to `channel`.
+    pub fn write<C: AbstractChannel>(&self, channel: &mut C) -> std::io::Result<()> {
+        channel.write_bytes(&self.0)
+    }
+
+    /// Read an opening from `channel`.
+    pub fn read<C: AbstractChannel>(channel: &mut C) -> std::io::Result<Self> {
+        let mut bytes = [0u8; 32];
+        channel.read_bytes(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +227,27 @@ mod tests {
 
         assert!(ShaCommitment::check(&commitment, &commitment_));
     }
+
+    #[test]
+    fn hash_commitment() {
+        let mut rng = crate::AesRng::new();
+        let (commitment, opening) = HashCommitment::commit(&mut rng, b"hello world");
+        assert!(commitment.verify(b"hello world", &opening));
+        assert!(!commitment.verify(b"hello world!", &opening));
+        let (_, other) = HashCommitment::commit(&mut rng, b"hello world");
+        assert_ne!(other, opening);
+        assert!(!commitment.verify(b"hello world", &other));
+
+        let block = rng.gen::<Block>();
+        let (commitment, opening) = HashCommitment::commit_block(&mut rng, &block);
+        let mut channel = crate::Channel::new(std::io::Cursor::new(Vec::new()), Vec::new());
+        commitment.write(&mut channel).unwrap();
+        opening.write(&mut channel).unwrap();
+        let bytes = channel.writer().borrow().clone();
+        assert_eq!(bytes.len(), HashCommitment::LEN + Opening::LEN);
+        let mut channel = crate::Channel::new(std::io::Cursor::new(bytes), Vec::new());
+        let commitment = HashCommitment::read(&mut channel).unwrap();
+        let opening = Opening::read(&mut channel).unwrap();
+        assert!(commitment.verify_block(&block, &opening));
+    }
 }
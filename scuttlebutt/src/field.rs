@@ -5,8 +5,9 @@ use crate::{
     generic_array_length::AnyArrayLength,
     ring::{FiniteRing, IsSubRingOf},
 };
-use generic_array::{ArrayLength, GenericArray};
+use generic_array::{typenum::Unsigned, ArrayLength, GenericArray, GenericArrayIter};
 use std::ops::{Div, DivAssign};
+use subtle::ConstantTimeEq;
 
 /// Types that implement this trait are finite fields.
 pub trait FiniteField: FiniteRing + DivAssign<Self> + Div<Self, Output = Self> {
@@ -53,7 +54,98 @@ pub trait FiniteField: FiniteRing + DivAssign<Self> + Div<Self, Output = Self> {
     ///
     /// Invoking the `bit_decomposition` function on `f` should yield the vector $`b`$ where a 0
     /// element of $`b`$ corresponds to `false` and a 1 element corresponds to `true`.
-    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition>;
+    ///
+    /// This is a convenience wrapper around [`Self::bit_decomposition_into`].
+    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
+        let mut out: GenericArray<bool, Self::NumberOfBitsInBitDecomposition> = Default::default();
+        self.bit_decomposition_into(&mut out);
+        out
+    }
+    /// Write the first `out.len()` bits of the [bit decomposition](Self::bit_decomposition) of
+    /// `self` into `out`.
+    ///
+    /// # Panics
+    /// This function will panic if `out` is longer than the bit decomposition.
+    fn bit_decomposition_into(&self, out: &mut [bool]);
+    /// Iterate over the [bit decomposition](Self::bit_decomposition) of `self`, without
+    /// allocating.
+    fn bit_decomposition_iter(
+        &self,
+    ) -> GenericArrayIter<bool, Self::NumberOfBitsInBitDecomposition> {
+        self.bit_decomposition().into_iter()
+    }
+    /// The number of bits of the [bit decomposition](Self::bit_decomposition) which may be
+    /// non-zero.
+    ///
+    /// This is [`Self::NumberOfBitsInBitDecomposition`], except for fields whose modulus is only
+    /// known at runtime, such as [`DynPrimeField`], whose bit decomposition is padded with zeros.
+    fn bit_decomposition_len() -> usize {
+        Self::NumberOfBitsInBitDecomposition::USIZE
+    }
+    /// Write the first `out.len()` bits of the [bit decomposition](Self::bit_decomposition) of
+    /// `self` into `out`, least significant first.
+    ///
+    /// The implementations for [`F61p`], [`F127p`], the small binary fields such as [`F40b`], and the
+    /// fields built on `ff` such as `F128p` run in time independent of the value of `self`, and
+    /// may be used on secrets. The default implementation is constant time if
+    /// [`Self::bit_decomposition`] is.
+    ///
+    /// # Panics
+    /// This function will panic if `out` is longer than the bit decomposition.
+    fn to_le_bits_into(&self, out: &mut [F2]) {
+        let bits = self.bit_decomposition();
+        assert!(
+            out.len() <= bits.len(),
+            "at most {} bits in the bit decomposition",
+            bits.len()
+        );
+        for (dst, bit) in out.iter_mut().zip(bits.iter()) {
+            *dst = F2::from(*bit);
+        }
+    }
+    /// Build the field element whose [bit decomposition](Self::bit_decomposition) starts with
+    /// `bits`, least significant first, the remaining bits being zero.
+    ///
+    /// Return `None` if `bits` is longer than the bit decomposition, or if it does not encode a
+    /// field element, i.e. if a coefficient is not smaller than the characteristic.
+    ///
+    /// Whether `None` is returned is not secret, but otherwise the implementations which
+    /// [`Self::to_le_bits_into`] documents as constant time run in time independent of `bits`.
+    /// The default implementation is constant time if the field operations and
+    /// [`Self::bit_decomposition`] are.
+    fn from_le_bits(bits: &[F2]) -> Option<Self> {
+        let num_bits = Self::NumberOfBitsInBitDecomposition::USIZE;
+        if bits.len() > num_bits {
+            return None;
+        }
+        let degree = Degree::<Self>::USIZE;
+        if degree > 1 {
+            let m = num_bits / degree;
+            let mut components = GenericArray::<Self::PrimeField, Degree<Self>>::default();
+            for (i, component) in components.iter_mut().enumerate() {
+                let chunk = &bits[(i * m).min(bits.len())..((i + 1) * m).min(bits.len())];
+                *component = Self::PrimeField::from_le_bits(chunk)?;
+            }
+            return Some(Self::from_subfield(&components));
+        }
+        let mut out = Self::ZERO;
+        for bit in bits.iter().rev() {
+            out += out;
+            out += Self::conditional_select(&Self::ZERO, &Self::ONE, bit.ct_eq(&F2::ONE));
+        }
+        // The ladder reduces modulo the characteristic, so out of range encodings do not
+        // decompose back into `bits`.
+        let mut valid = true;
+        for (i, bit) in out.bit_decomposition_iter().enumerate() {
+            let expected = bits.get(i).map_or(false, |b| bool::from(*b));
+            valid &= bit == expected;
+        }
+        if valid {
+            Some(out)
+        } else {
+            None
+        }
+    }
     /// Compute the multiplicative inverse of self.
     ///
     /// # Panics
@@ -147,6 +239,7 @@ mod test_utils;
 macro_rules! call_with_big_finite_fields {
     ($f:ident $(, $arg:expr)* $(,)?) => {{
         $f::<$crate::field::F61p>($($arg),*);
+        $f::<$crate::field::F127p>($($arg),*);
         $f::<$crate::field::F64b>($($arg),*);
         $f::<$crate::field::F128b>($($arg),*);
         $f::<$crate::field::F40b>($($arg),*);
@@ -185,15 +278,45 @@ macro_rules! field_ops {
     };
 }
 
-/// Bit decomposition of `bits` into an array.
-pub(crate) fn standard_bit_decomposition<L: ArrayLength<bool>>(
-    bits: u128,
-) -> GenericArray<bool, L> {
-    let mut out: GenericArray<bool, L> = Default::default();
+/// Write the `out.len()` least significant bits of `x` into `out`.
+///
+/// # Panics
+/// Panics if `out` is longer than `num_bits`.
+#[inline]
+pub(crate) fn u128_bit_decomposition_into(x: u128, num_bits: usize, out: &mut [bool]) {
+    assert!(
+        out.len() <= num_bits,
+        "at most {} bits in the bit decomposition",
+        num_bits
+    );
+    for (i, dst) in out.iter_mut().enumerate() {
+        *dst = (x >> i) & 1 == 1;
+    }
+}
+
+/// Write the `out.len()` least significant bits of `x` into `out`, in constant time.
+///
+/// # Panics
+/// Panics if `out` is longer than `num_bits`.
+#[inline]
+pub(crate) fn u128_to_le_bits(x: u128, num_bits: usize, out: &mut [F2]) {
+    assert!(
+        out.len() <= num_bits,
+        "at most {} bits in the bit decomposition",
+        num_bits
+    );
     for (i, dst) in out.iter_mut().enumerate() {
-        *dst = (bits & (1 << (i as u128))) != 0;
+        *dst = F2::from((x >> i) & 1 == 1);
     }
-    out
+}
+
+/// Assemble at most 128 bits, least significant first, in constant time.
+#[inline]
+pub(crate) fn le_bits_to_u128(bits: &[F2]) -> u128 {
+    debug_assert!(bits.len() <= 128);
+    bits.iter()
+        .enumerate()
+        .fold(0, |acc, (i, bit)| acc | (u128::from(u8::from(*bit)) << i))
 }
 
 mod f2;
@@ -211,6 +334,12 @@ pub use small_binary_fields::{F40b, F45b, F56b, F63b, SmallBinaryField};
 mod f61p;
 pub use f61p::F61p;
 
+mod f127p;
+pub use f127p::F127p;
+
+mod dyn_prime_field;
+pub use dyn_prime_field::{DynModulus, DynModulusError, DynPrimeField, Modulus, ModulusCell};
+
 #[cfg(feature = "ff")]
 mod prime_field_using_ff;
 #[cfg(feature = "ff")]
@@ -0,0 +1,904 @@
+use crate::field::{polynomial::Polynomial, FiniteField, PrimeFiniteField, F2};
+use crate::ring::FiniteRing;
+use crate::serialization::{BiggerThanModulus, CanonicalSerialize};
+use crate::{AesRng, Block};
+use generic_array::GenericArray;
+use rand::{Rng, SeedableRng};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::{AddAssign, DivAssign, MulAssign, SubAssign},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+const LIMBS: usize = 4;
+type Limbs = [u64; LIMBS];
+
+// Number of Miller-Rabin rounds with random bases for moduli of more than 64
+// bits, each letting a composite through with probability at most 1/4.
+const MILLER_RABIN_ROUNDS: usize = 32;
+// Bases for which Miller-Rabin is deterministic below 2^64.
+const MILLER_RABIN_SMALL_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// The error which occurs if a modulus cannot be used by a [`DynPrimeField`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynModulusError {
+    /// The modulus does not fit in 256 bits.
+    TooLarge,
+    /// The modulus is not an odd prime.
+    NotOddPrime,
+    /// A different modulus has already been set in the [`ModulusCell`].
+    AlreadySet,
+}
+
+impl std::error::Error for DynModulusError {}
+impl std::fmt::Display for DynModulusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// An odd prime of at most 256 bits chosen at runtime, with the constants
+/// needed for Montgomery multiplication modulo it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynModulus {
+    p: Limbs,
+    bits: usize,
+    // -p^-1 mod 2^64.
+    inv: u64,
+    // R mod p and R^2 mod p, where R = 2^256.
+    r1: Limbs,
+    r2: Limbs,
+}
+
+impl DynModulus {
+    /// Make a new modulus from its little-endian bytes.
+    ///
+    /// The modulus is checked to be prime with the Miller-Rabin test, which is
+    /// deterministic below 2^64 and errs with probability at most 2^-64 above.
+    pub fn new(le_bytes: &[u8]) -> Result<Self, DynModulusError> {
+        let mut p = [0; LIMBS];
+        for (i, byte) in le_bytes.iter().enumerate() {
+            if i >= LIMBS * 8 {
+                if *byte != 0 {
+                    return Err(DynModulusError::TooLarge);
+                }
+            } else {
+                p[i / 8] |= u64::from(*byte) << (8 * (i % 8));
+            }
+        }
+        if p[0] & 1 == 0 || p == [1, 0, 0, 0] {
+            return Err(DynModulusError::NotOddPrime);
+        }
+        let bits = num_bits(&p);
+        // Newton iteration doubling the number of correct low bits of p^-1.
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p[0].wrapping_mul(inv)));
+        }
+        let mut r2 = [1, 0, 0, 0];
+        for _ in 0..2 * 64 * LIMBS {
+            r2 = add_mod(&r2, &r2, &p);
+        }
+        let mut modulus = DynModulus {
+            p,
+            bits,
+            inv: inv.wrapping_neg(),
+            r1: [0; LIMBS],
+            r2,
+        };
+        modulus.r1 = mont_mul(&r2, &[1, 0, 0, 0], &modulus);
+        if !modulus.is_probably_prime() {
+            return Err(DynModulusError::NotOddPrime);
+        }
+        Ok(modulus)
+    }
+
+    /// Return the little-endian bytes of the modulus.
+    pub fn to_le_bytes(&self) -> [u8; LIMBS * 8] {
+        let mut out = [0; LIMBS * 8];
+        for (chunk, limb) in out.chunks_exact_mut(8).zip(self.p.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        out
+    }
+
+    /// Return the number of bits of the modulus.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    // Compute `x^e` for `x < p`, in time independent of `x` but not of `e`.
+    fn pow(&self, x: &Limbs, e: &Limbs) -> Limbs {
+        let x = mont_mul(x, &self.r2, self);
+        let mut acc = self.r1;
+        for i in (0..num_bits(e)).rev() {
+            acc = mont_mul(&acc, &acc, self);
+            if (e[i / 64] >> (i % 64)) & 1 == 1 {
+                acc = mont_mul(&acc, &x, self);
+            }
+        }
+        mont_mul(&acc, &[1, 0, 0, 0], self)
+    }
+
+    fn is_probably_prime(&self) -> bool {
+        let one = [1, 0, 0, 0];
+        let p_minus_one = sub_mod(&[0; LIMBS], &one, &self.p);
+        // p - 1 = d * 2^s with d odd.
+        let s = p_minus_one
+            .iter()
+            .enumerate()
+            .find(|(_, limb)| **limb != 0)
+            .map(|(i, limb)| 64 * i + limb.trailing_zeros() as usize)
+            .unwrap_or(0);
+        let mut d = [0; LIMBS];
+        for (i, limb) in d.iter_mut().enumerate() {
+            let bit = i * 64 + s;
+            let (word, shift) = (bit / 64, bit % 64);
+            if word < LIMBS {
+                *limb = p_minus_one[word] >> shift;
+                if shift > 0 && word + 1 < LIMBS {
+                    *limb |= p_minus_one[word + 1] << (64 - shift);
+                }
+            }
+        }
+        let is_witness = |base: &Limbs| {
+            let mut x = self.pow(base, &d);
+            if x == one || x == p_minus_one {
+                return false;
+            }
+            for _ in 1..s {
+                x = mont_mul(&mont_mul(&x, &x, self), &self.r2, self);
+                if x == p_minus_one {
+                    return false;
+                }
+            }
+            true
+        };
+        if self.bits <= 64 {
+            MILLER_RABIN_SMALL_BASES.iter().all(|base| {
+                let base = [base % self.p[0], 0, 0, 0];
+                base == [0; LIMBS] || !is_witness(&base)
+            })
+        } else {
+            let mut rng = AesRng::new();
+            (0..MILLER_RABIN_ROUNDS).all(|_| {
+                let base = random_limbs(&mut rng, self);
+                base == [0; LIMBS] || !is_witness(&base)
+            })
+        }
+    }
+}
+
+/// Storage for a [`DynModulus`] set at runtime, meant to be put in a `static`
+/// backing a [`Modulus`] handle.
+///
+/// Once set, the modulus is never freed.
+pub struct ModulusCell(AtomicPtr<DynModulus>);
+
+impl ModulusCell {
+    /// Make a new empty cell.
+    pub const fn new() -> Self {
+        ModulusCell(AtomicPtr::new(std::ptr::null_mut()))
+    }
+
+    /// Set the modulus of the cell. Setting the modulus it already holds does
+    /// nothing.
+    pub fn set(&self, modulus: DynModulus) -> Result<(), DynModulusError> {
+        let current = self.get_or_init(|| modulus.clone());
+        if *current == modulus {
+            Ok(())
+        } else {
+            Err(DynModulusError::AlreadySet)
+        }
+    }
+
+    /// Return the modulus of the cell, if it has been set.
+    pub fn get(&self) -> Option<&'static DynModulus> {
+        let ptr = self.0.load(Ordering::Acquire);
+        // Safety: a non-null pointer comes from a leaked box, which is never
+        // freed.
+        unsafe { ptr.as_ref() }
+    }
+
+    /// Return the modulus of the cell, setting it to `f()` if it is empty.
+    pub fn get_or_init(&self, f: impl FnOnce() -> DynModulus) -> &'static DynModulus {
+        if let Some(modulus) = self.get() {
+            return modulus;
+        }
+        let ptr = Box::into_raw(Box::new(f()));
+        match self.0.compare_exchange(
+            std::ptr::null_mut(),
+            ptr,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            // Safety: `ptr` has just been leaked.
+            Ok(_) => unsafe { &*ptr },
+            Err(current) => {
+                // Safety: `ptr` was not published, and `current` is a leaked
+                // box set by another thread.
+                unsafe {
+                    drop(Box::from_raw(ptr));
+                    &*current
+                }
+            }
+        }
+    }
+}
+
+impl Default for ModulusCell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle on the modulus of a [`DynPrimeField`], so that the field type
+/// carries its modulus without storing it in every element.
+///
+/// The handle is usually a unit struct returning the content of a static
+/// [`ModulusCell`]:
+/// ```
+/// use scuttlebutt::field::{DynModulus, DynPrimeField, Modulus, ModulusCell};
+///
+/// static MODULUS: ModulusCell = ModulusCell::new();
+///
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// struct MyModulus;
+///
+/// impl Modulus for MyModulus {
+///     fn modulus() -> &'static DynModulus {
+///         MODULUS.get().expect("the modulus is set")
+///     }
+/// }
+///
+/// MODULUS
+///     .set(DynModulus::new(&((1u64 << 61) - 1).to_le_bytes()).unwrap())
+///     .unwrap();
+/// type MyField = DynPrimeField<MyModulus>;
+/// ```
+pub trait Modulus:
+    'static + Clone + Copy + Send + Sync + Default + Debug + PartialEq + Eq + Hash
+{
+    /// Return the modulus.
+    ///
+    /// # Panics
+    /// This function may panic if the modulus has not been set yet.
+    fn modulus() -> &'static DynModulus;
+}
+
+/// A prime field whose modulus, of at most 256 bits, is only known at runtime.
+///
+/// Elements are stored reduced, and multiplied with two Montgomery
+/// multiplications, one of them undoing the Montgomery factor, so that `ZERO`
+/// and `ONE` do not depend on the modulus.
+///
+/// The type-level sizes of the field are those of a 256-bit modulus: the bit
+/// decomposition has 256 bits, the top ones being zero, and
+/// [`FiniteField::bit_decomposition_len`] returns the actual number of bits.
+/// `GENERATOR` cannot depend on the modulus either: it is `2`, which does not
+/// generate the multiplicative group in general, so this field must not be used
+/// where a generator is needed, such as in FFTs.
+pub struct DynPrimeField<M: Modulus> {
+    limbs: Limbs,
+    modulus: PhantomData<M>,
+}
+
+impl<M: Modulus> DynPrimeField<M> {
+    const fn from_limbs(limbs: Limbs) -> Self {
+        DynPrimeField {
+            limbs,
+            modulus: PhantomData,
+        }
+    }
+}
+
+impl<M: Modulus> Clone for DynPrimeField<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Modulus> Copy for DynPrimeField<M> {}
+
+impl<M: Modulus> Debug for DynPrimeField<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("DynPrimeField").field(&self.limbs).finish()
+    }
+}
+
+impl<M: Modulus> Hash for DynPrimeField<M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.limbs.hash(state)
+    }
+}
+
+impl<M: Modulus> ConstantTimeEq for DynPrimeField<M> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.limbs[..].ct_eq(&other.limbs[..])
+    }
+}
+
+impl<M: Modulus> ConditionallySelectable for DynPrimeField<M> {
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut limbs = [0; LIMBS];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = u64::conditional_select(&a.limbs[i], &b.limbs[i], choice);
+        }
+        Self::from_limbs(limbs)
+    }
+}
+
+impl<M: Modulus> PartialEq for DynPrimeField<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<M: Modulus> Eq for DynPrimeField<M> {}
+
+impl<M: Modulus> Default for DynPrimeField<M> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[inline]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = u128::from(a) + u128::from(b) + u128::from(carry);
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = u128::from(a).wrapping_sub(u128::from(b) + u128::from(borrow));
+    (t as u64, (t >> 127) as u64)
+}
+
+#[inline]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = u128::from(a) + u128::from(b) * u128::from(c) + u128::from(carry);
+    (t as u64, (t >> 64) as u64)
+}
+
+// Return `x - p` if `x` (with its extra top bit `top`) is at least `p`, and
+// `x` otherwise, in constant time.
+#[inline]
+#[allow(clippy::needless_range_loop)]
+fn subtract_if_above(x: &Limbs, top: u64, p: &Limbs) -> Limbs {
+    let mut d = [0; LIMBS];
+    let mut borrow = 0;
+    for i in 0..LIMBS {
+        (d[i], borrow) = sbb(x[i], p[i], borrow);
+    }
+    let mask = 0u64.wrapping_sub(top | (borrow ^ 1));
+    let mut out = [0; LIMBS];
+    for i in 0..LIMBS {
+        out[i] = (d[i] & mask) | (x[i] & !mask);
+    }
+    out
+}
+
+#[inline]
+#[allow(clippy::needless_range_loop)]
+fn add_mod(a: &Limbs, b: &Limbs, p: &Limbs) -> Limbs {
+    let mut s = [0; LIMBS];
+    let mut carry = 0;
+    for i in 0..LIMBS {
+        (s[i], carry) = adc(a[i], b[i], carry);
+    }
+    subtract_if_above(&s, carry, p)
+}
+
+#[inline]
+#[allow(clippy::needless_range_loop)]
+fn sub_mod(a: &Limbs, b: &Limbs, p: &Limbs) -> Limbs {
+    let mut d = [0; LIMBS];
+    let mut borrow = 0;
+    for i in 0..LIMBS {
+        (d[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    let mask = 0u64.wrapping_sub(borrow);
+    let mut carry = 0;
+    for i in 0..LIMBS {
+        (d[i], carry) = adc(d[i], p[i] & mask, carry);
+    }
+    d
+}
+
+// Montgomery multiplication: `a * b / R mod p`, for `a * b < p * R`.
+#[inline]
+#[allow(clippy::needless_range_loop)]
+fn mont_mul(a: &Limbs, b: &Limbs, modulus: &DynModulus) -> Limbs {
+    let p = &modulus.p;
+    let mut t = [0u64; LIMBS + 2];
+    for i in 0..LIMBS {
+        let mut carry = 0;
+        for j in 0..LIMBS {
+            (t[j], carry) = mac(t[j], a[j], b[i], carry);
+        }
+        (t[LIMBS], carry) = adc(t[LIMBS], carry, 0);
+        t[LIMBS + 1] = carry;
+        let m = t[0].wrapping_mul(modulus.inv);
+        let (_, mut carry) = mac(t[0], m, p[0], 0);
+        for j in 1..LIMBS {
+            (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+        }
+        (t[LIMBS - 1], carry) = adc(t[LIMBS], carry, 0);
+        t[LIMBS] = t[LIMBS + 1] + carry;
+    }
+    let mut out = [0; LIMBS];
+    out.copy_from_slice(&t[..LIMBS]);
+    subtract_if_above(&out, t[LIMBS], p)
+}
+
+// Reduce `lo + hi * R`.
+#[inline]
+fn reduce_wide(lo: &Limbs, hi: &Limbs, modulus: &DynModulus) -> Limbs {
+    add_mod(
+        &mont_mul(lo, &modulus.r1, modulus),
+        &mont_mul(hi, &modulus.r2, modulus),
+        &modulus.p,
+    )
+}
+
+// A uniform element with a bias of at most 2^-256.
+fn random_limbs<R: Rng + ?Sized>(rng: &mut R, modulus: &DynModulus) -> Limbs {
+    let lo: Limbs = rng.gen();
+    let hi: Limbs = rng.gen();
+    reduce_wide(&lo, &hi, modulus)
+}
+
+fn num_bits(x: &Limbs) -> usize {
+    x.iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| 64 * (i + 1) - x[i].leading_zeros() as usize)
+}
+
+impl<M: Modulus> FiniteRing for DynPrimeField<M> {
+    /// For moduli of at most 64 bits, this has a 2^-64 probability of being a
+    /// biased draw. Larger moduli seed an `AesRng` with `x` to draw from.
+    #[inline]
+    fn from_uniform_bytes(x: &[u8; 16]) -> Self {
+        let modulus = M::modulus();
+        if modulus.bits <= 64 {
+            let x = u128::from_le_bytes(*x);
+            let limbs = [x as u64, (x >> 64) as u64, 0, 0];
+            Self::from_limbs(mont_mul(&limbs, &modulus.r1, modulus))
+        } else {
+            Self::random(&mut AesRng::from_seed(Block::from(*x)))
+        }
+    }
+
+    #[inline]
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_limbs(random_limbs(rng, M::modulus()))
+    }
+
+    const ZERO: Self = Self::from_limbs([0; LIMBS]);
+    const ONE: Self = Self::from_limbs([1, 0, 0, 0]);
+}
+
+impl<M: Modulus> CanonicalSerialize for DynPrimeField<M> {
+    type Serializer = crate::serialization::ByteElementSerializer<Self>;
+    type Deserializer = crate::serialization::ByteElementDeserializer<Self>;
+    type ByteReprLen = generic_array::typenum::U32;
+    type FromBytesError = BiggerThanModulus;
+
+    #[inline]
+    fn from_bytes(
+        bytes: &GenericArray<u8, Self::ByteReprLen>,
+    ) -> Result<Self, Self::FromBytesError> {
+        let mut limbs = [0; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(<[u8; 8]>::try_from(chunk).unwrap());
+        }
+        let mut borrow = 0;
+        for (limb, p) in limbs.iter().zip(M::modulus().p.iter()) {
+            borrow = sbb(*limb, *p, borrow).1;
+        }
+        if borrow == 1 {
+            Ok(Self::from_limbs(limbs))
+        } else {
+            Err(BiggerThanModulus)
+        }
+    }
+
+    #[inline]
+    fn to_bytes(&self) -> GenericArray<u8, Self::ByteReprLen> {
+        let mut out = GenericArray::default();
+        for (chunk, limb) in out.chunks_exact_mut(8).zip(self.limbs.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        out
+    }
+}
+
+impl<M: Modulus> FiniteField for DynPrimeField<M> {
+    type PrimeField = Self;
+
+    const GENERATOR: Self = Self::from_limbs([2, 0, 0, 0]);
+
+    fn polynomial_modulus() -> Polynomial<Self::PrimeField> {
+        Polynomial::x()
+    }
+
+    type NumberOfBitsInBitDecomposition = generic_array::typenum::U256;
+
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        assert!(
+            out.len() <= LIMBS * 64,
+            "at most {} bits in the bit decomposition",
+            LIMBS * 64
+        );
+        for (i, dst) in out.iter_mut().enumerate() {
+            *dst = (self.limbs[i / 64] >> (i % 64)) & 1 == 1;
+        }
+    }
+
+    fn bit_decomposition_len() -> usize {
+        M::modulus().bits
+    }
+
+    fn to_le_bits_into(&self, out: &mut [F2]) {
+        let num_bits = M::modulus().bits;
+        assert!(
+            out.len() <= num_bits,
+            "at most {} bits in the bit decomposition",
+            num_bits
+        );
+        for (i, dst) in out.iter_mut().enumerate() {
+            *dst = F2::from((self.limbs[i / 64] >> (i % 64)) & 1 == 1);
+        }
+    }
+
+    fn from_le_bits(bits: &[F2]) -> Option<Self> {
+        if bits.len() > M::modulus().bits {
+            return None;
+        }
+        let mut limbs = [0; LIMBS];
+        for (i, bit) in bits.iter().enumerate() {
+            limbs[i / 64] |= u64::from(u8::from(*bit)) << (i % 64);
+        }
+        let mut bytes = GenericArray::default();
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        Self::from_bytes(&bytes).ok()
+    }
+
+    fn inverse(&self) -> Self {
+        if *self == Self::ZERO {
+            panic!("Zero cannot be inverted");
+        }
+        let modulus = M::modulus();
+        let p_minus_two = sub_mod(&modulus.p, &[2, 0, 0, 0], &[0; LIMBS]);
+        Self::from_limbs(modulus.pow(&self.limbs, &p_minus_two))
+    }
+}
+
+impl<M: Modulus> AddAssign<&DynPrimeField<M>> for DynPrimeField<M> {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.limbs = add_mod(&self.limbs, &rhs.limbs, &M::modulus().p);
+    }
+}
+
+impl<M: Modulus> SubAssign<&DynPrimeField<M>> for DynPrimeField<M> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.limbs = sub_mod(&self.limbs, &rhs.limbs, &M::modulus().p);
+    }
+}
+
+impl<M: Modulus> MulAssign<&DynPrimeField<M>> for DynPrimeField<M> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: &Self) {
+        let modulus = M::modulus();
+        let product = mont_mul(&self.limbs, &rhs.limbs, modulus);
+        self.limbs = mont_mul(&product, &modulus.r2, modulus);
+    }
+}
+
+impl<M: Modulus> DivAssign<&DynPrimeField<M>> for DynPrimeField<M> {
+    #[inline]
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn div_assign(&mut self, rhs: &Self) {
+        *self *= rhs.inverse();
+    }
+}
+
+// The operator impls of `ring_ops` and `field_ops`, which only take plain
+// type names.
+macro_rules! dyn_binop {
+    ($trait:ident, $name:ident, $assign_trait:ident, $assign:ident) => {
+        impl<M: Modulus> std::ops::$assign_trait<DynPrimeField<M>> for DynPrimeField<M> {
+            #[inline]
+            fn $assign(&mut self, rhs: Self) {
+                self.$assign(&rhs)
+            }
+        }
+        impl<M: Modulus> std::ops::$trait<DynPrimeField<M>> for DynPrimeField<M> {
+            type Output = Self;
+
+            #[inline]
+            fn $name(mut self, rhs: Self) -> Self {
+                self.$assign(&rhs);
+                self
+            }
+        }
+        impl<'a, M: Modulus> std::ops::$trait<&'a DynPrimeField<M>> for DynPrimeField<M> {
+            type Output = Self;
+
+            #[inline]
+            fn $name(mut self, rhs: &'a Self) -> Self {
+                self.$assign(rhs);
+                self
+            }
+        }
+        impl<'a, M: Modulus> std::ops::$trait<DynPrimeField<M>> for &'a DynPrimeField<M> {
+            type Output = DynPrimeField<M>;
+
+            #[inline]
+            fn $name(self, rhs: DynPrimeField<M>) -> DynPrimeField<M> {
+                let mut this = *self;
+                this.$assign(&rhs);
+                this
+            }
+        }
+        impl<'a, M: Modulus> std::ops::$trait<&'a DynPrimeField<M>> for &'a DynPrimeField<M> {
+            type Output = DynPrimeField<M>;
+
+            #[inline]
+            fn $name(self, rhs: &'a DynPrimeField<M>) -> DynPrimeField<M> {
+                let mut this = *self;
+                this.$assign(rhs);
+                this
+            }
+        }
+    };
+}
+
+dyn_binop!(Add, add, AddAssign, add_assign);
+dyn_binop!(Sub, sub, SubAssign, sub_assign);
+dyn_binop!(Mul, mul, MulAssign, mul_assign);
+dyn_binop!(Div, div, DivAssign, div_assign);
+
+impl<M: Modulus> std::ops::Neg for DynPrimeField<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<M: Modulus> std::iter::Sum for DynPrimeField<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, std::ops::Add::add)
+    }
+}
+
+impl<M: Modulus> std::iter::Product for DynPrimeField<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, std::ops::Mul::mul)
+    }
+}
+
+impl<M: Modulus> num_traits::Zero for DynPrimeField<M> {
+    #[inline]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+
+impl<M: Modulus> num_traits::One for DynPrimeField<M> {
+    #[inline]
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl<M: Modulus> rand::distributions::Distribution<DynPrimeField<M>>
+    for rand::distributions::Standard
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DynPrimeField<M> {
+        DynPrimeField::random(rng)
+    }
+}
+
+impl<M: Modulus> TryFrom<u128> for DynPrimeField<M> {
+    type Error = BiggerThanModulus;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        let mut bytes = GenericArray::default();
+        bytes[..16].copy_from_slice(&value.to_le_bytes());
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl<M: Modulus> PrimeFiniteField for DynPrimeField<M> {}
+
+impl<M: Modulus> serde::Serialize for DynPrimeField<M> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de, M: Modulus> serde::Deserialize<'de> for DynPrimeField<M> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor<M>(PhantomData<M>);
+
+        impl<'de, M: Modulus> serde::de::Visitor<'de> for FieldVisitor<M> {
+            type Value = DynPrimeField<M>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(
+                    formatter,
+                    "a field element {} (32 bytes)",
+                    std::any::type_name::<M>()
+                )
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                if v.len() != LIMBS * 8 {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                DynPrimeField::from_bytes(GenericArray::from_slice(v))
+                    .map_err(serde::de::Error::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut bytes = GenericArray::<u8, generic_array::typenum::U32>::default();
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                DynPrimeField::from_bytes(&bytes).map_err(serde::de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_bytes(FieldVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::F61p;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    static F61P_MODULUS: ModulusCell = ModulusCell::new();
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct F61pModulus;
+
+    impl Modulus for F61pModulus {
+        fn modulus() -> &'static DynModulus {
+            F61P_MODULUS.get_or_init(|| DynModulus::new(&((1u64 << 61) - 1).to_le_bytes()).unwrap())
+        }
+    }
+
+    // The modulus of `Fbn254`.
+    const BN254_MODULUS: &str =
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+    static BN254: ModulusCell = ModulusCell::new();
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct Bn254Modulus;
+
+    impl Modulus for Bn254Modulus {
+        fn modulus() -> &'static DynModulus {
+            BN254.get_or_init(|| {
+                let modulus: BigUint = BN254_MODULUS.parse().unwrap();
+                DynModulus::new(&modulus.to_bytes_le()).unwrap()
+            })
+        }
+    }
+
+    type DynF61p = DynPrimeField<F61pModulus>;
+    type DynBn254 = DynPrimeField<Bn254Modulus>;
+
+    test_field!(
+        test_field_f61p,
+        crate::field::dyn_prime_field::tests::DynF61p
+    );
+    test_field!(
+        test_field_bn254,
+        crate::field::dyn_prime_field::tests::DynBn254
+    );
+
+    fn to_f61p(x: DynF61p) -> F61p {
+        F61p::try_from(u128::from(x.limbs[0])).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn test_same_as_f61p(a in any::<u64>(), b in any::<u64>()) {
+            let (a, b) = (a % ((1 << 61) - 1), b % ((1 << 61) - 1));
+            let (x, y) = (DynF61p::try_from(u128::from(a)).unwrap(), DynF61p::try_from(u128::from(b)).unwrap());
+            let (u, v) = (F61p::try_from(u128::from(a)).unwrap(), F61p::try_from(u128::from(b)).unwrap());
+            prop_assert_eq!(to_f61p(x + y), u + v);
+            prop_assert_eq!(to_f61p(x - y), u - v);
+            prop_assert_eq!(to_f61p(x * y), u * v);
+            if b != 0 {
+                prop_assert_eq!(to_f61p(x / y), u / v);
+            }
+        }
+
+        #[test]
+        fn test_mul_bn254(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+            let modulus: BigUint = BN254_MODULUS.parse().unwrap();
+            let to_big = |x: &Limbs| BigUint::from_slice(&x.iter().flat_map(|l| [*l as u32, (*l >> 32) as u32]).collect::<Vec<_>>());
+            let (a, b) = (to_big(&a) % &modulus, to_big(&b) % &modulus);
+            let from_big = |x: &BigUint| {
+                let mut bytes = x.to_bytes_le();
+                bytes.resize(32, 0);
+                DynBn254::from_bytes(GenericArray::from_slice(&bytes)).unwrap()
+            };
+            let product = from_big(&a) * from_big(&b);
+            prop_assert_eq!(product, from_big(&(a * b % &modulus)));
+        }
+    }
+
+    #[test]
+    fn test_bit_decomposition_len() {
+        assert_eq!(DynF61p::bit_decomposition_len(), 61);
+        assert_eq!(DynBn254::bit_decomposition_len(), 254);
+        assert_eq!(DynF61p::from_le_bits(&[F2::ONE; 61]), None);
+        assert_eq!(DynF61p::from_le_bits(&[F2::ONE; 62]), None);
+    }
+
+    #[test]
+    fn test_invalid_modulus() {
+        assert_eq!(
+            DynModulus::new(&[0; 33]).map(|_| ()),
+            Err(DynModulusError::NotOddPrime)
+        );
+        let mut too_large = [0; 33];
+        too_large[32] = 1;
+        assert_eq!(
+            DynModulus::new(&too_large).map(|_| ()),
+            Err(DynModulusError::TooLarge)
+        );
+        for composite in [1u64, 9, 15, 561, (1 << 61) + 1, 3_215_031_751] {
+            assert_eq!(
+                DynModulus::new(&composite.to_le_bytes()).map(|_| ()),
+                Err(DynModulusError::NotOddPrime)
+            );
+        }
+        let composite: BigUint = BigUint::from((1u64 << 61) - 1) * BigUint::from((1u64 << 61) - 1);
+        assert_eq!(
+            DynModulus::new(&composite.to_bytes_le()).map(|_| ()),
+            Err(DynModulusError::NotOddPrime)
+        );
+        assert!(DynModulus::new(&[3]).is_ok());
+    }
+
+    #[test]
+    fn test_modulus_cell() {
+        let cell = ModulusCell::new();
+        assert!(cell.get().is_none());
+        let f61p = DynModulus::new(&((1u64 << 61) - 1).to_le_bytes()).unwrap();
+        cell.set(f61p.clone()).unwrap();
+        cell.set(f61p.clone()).unwrap();
+        assert_eq!(cell.get(), Some(&f61p));
+        assert_eq!(
+            cell.set(DynModulus::new(&[7]).unwrap()),
+            Err(DynModulusError::AlreadySet)
+        );
+    }
+}
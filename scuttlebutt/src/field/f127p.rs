@@ -0,0 +1,222 @@
+use crate::field::{polynomial::Polynomial, FiniteField, PrimeFiniteField, F2};
+use crate::ring::FiniteRing;
+use crate::serialization::{BiggerThanModulus, CanonicalSerialize};
+use generic_array::GenericArray;
+use rand_core::RngCore;
+use std::ops::{AddAssign, MulAssign, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// A finite field over the Mersenne Prime 2^127 - 1
+#[derive(Clone, Copy, Eq, Debug, Hash)]
+pub struct F127p(u128);
+
+const MODULUS: u128 = (1 << 127) - 1;
+
+impl ConstantTimeEq for F127p {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for F127p {
+    #[inline]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        F127p(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl FiniteRing for F127p {
+    /// This has a 2^-126 probability of being a biased draw.
+    #[inline]
+    fn from_uniform_bytes(x: &[u8; 16]) -> Self {
+        F127p(reduce(u128::from_le_bytes(*x)))
+    }
+
+    /// This has a 2^-126 probability of being a biased draw.
+    #[inline]
+    fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        Self::from_uniform_bytes(&bytes)
+    }
+
+    const ZERO: Self = F127p(0);
+    const ONE: Self = F127p(1);
+}
+
+impl CanonicalSerialize for F127p {
+    type Serializer = crate::serialization::ByteElementSerializer<Self>;
+    type Deserializer = crate::serialization::ByteElementDeserializer<Self>;
+    type ByteReprLen = generic_array::typenum::U16;
+    type FromBytesError = BiggerThanModulus;
+
+    #[inline]
+    fn from_bytes(
+        bytes: &GenericArray<u8, Self::ByteReprLen>,
+    ) -> Result<Self, Self::FromBytesError> {
+        let buf = <[u8; 16]>::from(*bytes);
+        let raw = u128::from_le_bytes(buf);
+        if raw < MODULUS {
+            Ok(F127p(raw))
+        } else {
+            Err(BiggerThanModulus)
+        }
+    }
+
+    #[inline]
+    fn to_bytes(&self) -> GenericArray<u8, Self::ByteReprLen> {
+        self.0.to_le_bytes().into()
+    }
+}
+
+impl FiniteField for F127p {
+    type PrimeField = Self;
+
+    const GENERATOR: Self = F127p(43);
+
+    fn polynomial_modulus() -> Polynomial<Self::PrimeField> {
+        Polynomial::x()
+    }
+
+    type NumberOfBitsInBitDecomposition = generic_array::typenum::U127;
+
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        super::u128_bit_decomposition_into(self.0, 127, out)
+    }
+
+    fn to_le_bits_into(&self, out: &mut [F2]) {
+        super::u128_to_le_bits(self.0, 127, out)
+    }
+
+    fn from_le_bits(bits: &[F2]) -> Option<Self> {
+        if bits.len() > 127 {
+            return None;
+        }
+        let x = super::le_bits_to_u128(bits);
+        if x < MODULUS {
+            Some(F127p(x))
+        } else {
+            None
+        }
+    }
+    fn inverse(&self) -> Self {
+        if *self == Self::ZERO {
+            panic!("Zero cannot be inverted");
+        }
+        self.pow_var_time(MODULUS - 2)
+    }
+}
+
+#[inline]
+fn reduce(k: u128) -> u128 {
+    // Since 2^127 = 1 mod 2^127 - 1, the top bit is folded onto the bottom.
+    let i = (k & MODULUS) + (k >> 127);
+    // `i` is at most 2^127, so a single conditional subtraction is enough.
+    let flag = (i < MODULUS) as u128;
+    let operand = flag.wrapping_sub(1) & MODULUS;
+    i - operand
+}
+
+// Reduce `hi * 2^128 + lo`, for `hi < 2^126`.
+#[inline]
+fn reduce_wide(hi: u128, lo: u128) -> u128 {
+    // 2^128 = 2 mod 2^127 - 1, and the sum is at most 2^128 - 2.
+    reduce((lo & MODULUS) + (lo >> 127) + (hi << 1))
+}
+
+// The full 254-bit product of two elements, as its high and low 128 bits.
+#[inline]
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a0, a1) = (a & MASK, a >> 64);
+    let (b0, b1) = (b & MASK, b >> 64);
+    let ll = a0 * b0;
+    let lh = a0 * b1;
+    let hl = a1 * b0;
+    let hh = a1 * b1;
+    let mid = (ll >> 64) + (lh & MASK) + (hl & MASK);
+    let lo = (ll & MASK) | (mid << 64);
+    let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+impl AddAssign<&F127p> for F127p {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        // Both operands are below 2^127, so the sum can't overflow.
+        self.0 = reduce(self.0 + rhs.0);
+    }
+}
+
+impl SubAssign<&F127p> for F127p {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Self) {
+        // We add modulus so it can't underflow.
+        self.0 = reduce(self.0 + MODULUS - rhs.0);
+    }
+}
+
+impl MulAssign<&F127p> for F127p {
+    #[inline]
+    fn mul_assign(&mut self, rhs: &Self) {
+        let (hi, lo) = mul_wide(self.0, rhs.0);
+        self.0 = reduce_wide(hi, lo);
+    }
+}
+
+impl TryFrom<u128> for F127p {
+    type Error = BiggerThanModulus;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        if value < MODULUS {
+            Ok(F127p(value))
+        } else {
+            Err(BiggerThanModulus)
+        }
+    }
+}
+
+impl PrimeFiniteField for F127p {}
+
+field_ops!(F127p);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    test_field!(test_field, crate::field::F127p);
+
+    #[cfg(test)]
+    proptest! {
+        #[test]
+        fn test_reduce(x in any::<u128>()) {
+            assert_eq!(reduce(x), x % MODULUS);
+        }
+
+        #[test]
+        fn test_mul(a in 0..MODULUS, b in 0..MODULUS) {
+            let expected = BigUint::from(a) * BigUint::from(b) % BigUint::from(MODULUS);
+            let product = F127p(a) * F127p(b);
+            assert_eq!(BigUint::from(product.0), expected);
+        }
+    }
+
+    #[test]
+    fn test_mul_extremes() {
+        let neg1 = -F127p::ONE;
+        assert_eq!(neg1 * neg1, F127p::ONE);
+        assert_eq!(neg1 * F127p::ONE, neg1);
+        assert_eq!(neg1 + neg1, neg1 - F127p::ONE);
+    }
+
+    #[test]
+    fn test_from_le_bits_out_of_range() {
+        assert_eq!(F127p::from_le_bits(&[F2::ONE; 127]), None);
+        let mut bits = [F2::ONE; 127];
+        bits[0] = F2::ZERO;
+        assert_eq!(F127p::from_le_bits(&bits), Some(-F127p::ONE));
+    }
+}
@@ -247,8 +247,8 @@ impl FiniteField for F128b {
 
     type NumberOfBitsInBitDecomposition = generic_array::typenum::U128;
 
-    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-        super::standard_bit_decomposition(self.0)
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        super::u128_bit_decomposition_into(self.0, 128, out)
     }
 
     fn inverse(&self) -> Self {
@@ -97,8 +97,11 @@ impl FiniteField for F2 {
 
     type NumberOfBitsInBitDecomposition = generic_array::typenum::U1;
 
-    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-        [self.0 != 0].into()
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        assert!(out.len() <= 1, "at most 1 bit in the bit decomposition");
+        if let Some(dst) = out.first_mut() {
+            *dst = self.0 != 0;
+        }
     }
 
     fn inverse(&self) -> Self {
@@ -1,4 +1,4 @@
-use crate::field::{polynomial::Polynomial, FiniteField, PrimeFiniteField};
+use crate::field::{polynomial::Polynomial, FiniteField, PrimeFiniteField, F2};
 use crate::ring::FiniteRing;
 use crate::serialization::{BiggerThanModulus, CanonicalSerialize};
 use generic_array::GenericArray;
@@ -81,8 +81,24 @@ impl FiniteField for F61p {
 
     type NumberOfBitsInBitDecomposition = generic_array::typenum::U61;
 
-    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-        super::standard_bit_decomposition(u128::from(self.0))
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        super::u128_bit_decomposition_into(u128::from(self.0), 61, out)
+    }
+
+    fn to_le_bits_into(&self, out: &mut [F2]) {
+        super::u128_to_le_bits(u128::from(self.0), 61, out)
+    }
+
+    fn from_le_bits(bits: &[F2]) -> Option<Self> {
+        if bits.len() > 61 {
+            return None;
+        }
+        let x = super::le_bits_to_u128(bits) as u64;
+        if x < MODULUS {
+            Some(F61p(x))
+        } else {
+            None
+        }
     }
     fn inverse(&self) -> Self {
         if *self == Self::ZERO {
@@ -189,6 +205,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_le_bits_out_of_range() {
+        assert_eq!(F61p::from_le_bits(&[F2::ONE; 61]), None);
+        let mut bits = [F2::ONE; 61];
+        bits[0] = F2::ZERO;
+        assert_eq!(F61p::from_le_bits(&bits), Some(-F61p::ONE));
+    }
+
     #[test]
     fn test_sum_overflow() {
         let neg1 = F61p::ZERO - F61p::ONE;
@@ -136,8 +136,8 @@ impl FiniteField for F64b {
 
     type NumberOfBitsInBitDecomposition = generic_array::typenum::U64;
 
-    fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-        super::standard_bit_decomposition(self.0 as u128)
+    fn bit_decomposition_into(&self, out: &mut [bool]) {
+        super::u128_bit_decomposition_into(self.0 as u128, 64, out)
     }
 
     const GENERATOR: Self = Self(2);
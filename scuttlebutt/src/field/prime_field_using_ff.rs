@@ -88,7 +88,7 @@ macro_rules! prime_field_using_ff {
         $(single_limb_modulus = $single_limb_modulus: expr)?
     ) => {
         mod $mod_name {
-            use crate::field::{FiniteField, Polynomial, PrimeFiniteField};
+            use crate::field::{FiniteField, Polynomial, PrimeFiniteField, F2};
             use crate::serialization::{CanonicalSerialize, BiggerThanModulus};
             use crate::ring::FiniteRing;
             use ff::{Field, PrimeField};
@@ -214,14 +214,34 @@ macro_rules! prime_field_using_ff {
 
                 type NumberOfBitsInBitDecomposition = $num_bits;
 
-                fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-                    let mut out: GenericArray<bool, Self::NumberOfBitsInBitDecomposition> = Default::default();
+                fn bit_decomposition_into(&self, out: &mut [bool]) {
+                    let num_bits = <$num_bits as generic_array::typenum::Unsigned>::USIZE;
+                    assert!(out.len() <= num_bits, "at most {} bits in the bit decomposition", num_bits);
                     let bytes = self.to_bytes();
                     for (i, dst) in out.iter_mut().enumerate() {
-                        let bits = bytes[i / 8];
-                        *dst = (bits & (1 << ((i % 8) as u8))) != 0;
+                        *dst = (bytes[i / 8] >> (i % 8)) & 1 == 1;
                     }
-                    out
+                }
+
+                fn to_le_bits_into(&self, out: &mut [F2]) {
+                    let num_bits = <$num_bits as generic_array::typenum::Unsigned>::USIZE;
+                    assert!(out.len() <= num_bits, "at most {} bits in the bit decomposition", num_bits);
+                    let bytes = self.to_bytes();
+                    for (i, dst) in out.iter_mut().enumerate() {
+                        *dst = F2::from((bytes[i / 8] >> (i % 8)) & 1 == 1);
+                    }
+                }
+
+                fn from_le_bits(bits: &[F2]) -> Option<Self> {
+                    if bits.len() > <$num_bits as generic_array::typenum::Unsigned>::USIZE {
+                        return None;
+                    }
+                    let mut bytes = [0u8; $limbs * 8];
+                    for (i, bit) in bits.iter().enumerate() {
+                        bytes[i / 8] |= u8::from(*bit) << (i % 8);
+                    }
+                    Option::from(Internal::from_repr(InternalRepr(bytes)))
+                        .map(|internal| Self { internal })
                 }
             }
 
@@ -50,13 +50,20 @@ pub unsafe trait SmallBinaryField:
 }
 
 macro_rules! small_binary_field {
+    (@carryless_mul $a:expr, $b:expr) => {
+        U64x2::set_lo($a).carryless_mul::<false, false>(U64x2::set_lo($b))
+    };
+    (@carryless_mul $a:expr, $b:expr, $carryless_mul_fn:ident) => {
+        $carryless_mul_fn($a, $b)
+    };
     (
         $(#[$m:meta])*
         $name:ident, $mod_name:ident,
         num_bits = $num_bits:ty,
         polynomial_modulus = $modulus_fn:ident,
         reduce = $reduce_fn:ident,
-        $(reduce_vectored = $reduce_vectored_fn:ident)?
+        $(reduce_vectored = $reduce_vectored_fn:ident,)?
+        $(carryless_mul = $carryless_mul_fn:ident,)?
     ) => {
         $(#[$m])*
         #[derive(Debug, Clone, Copy, Hash, Eq, bytemuck::Zeroable, TransparentWrapper)]
@@ -93,7 +100,9 @@ macro_rules! small_binary_field {
         impl<'a> MulAssign<&'a $name> for $name {
             #[inline]
             fn mul_assign(&mut self, rhs: &'a $name) {
-                let product = U64x2::set_lo(self.0).carryless_mul::<false, false>(U64x2::set_lo(rhs.0));
+                let product = small_binary_field!(
+                    @carryless_mul self.0, rhs.0 $(, $carryless_mul_fn)?
+                );
                 // Now we reduce the wide product.
                 *self = Self::reduce(product);
             }
@@ -162,8 +171,19 @@ macro_rules! small_binary_field {
 
             type NumberOfBitsInBitDecomposition = $num_bits;
 
-            fn bit_decomposition(&self) -> GenericArray<bool, Self::NumberOfBitsInBitDecomposition> {
-                super::standard_bit_decomposition(u128::from(self.0))
+            fn bit_decomposition_into(&self, out: &mut [bool]) {
+                super::u128_bit_decomposition_into(u128::from(self.0), <$num_bits as Unsigned>::USIZE, out)
+            }
+
+            fn to_le_bits_into(&self, out: &mut [F2]) {
+                super::u128_to_le_bits(u128::from(self.0), <$num_bits as Unsigned>::USIZE, out)
+            }
+
+            fn from_le_bits(bits: &[F2]) -> Option<Self> {
+                if bits.len() > <$num_bits as Unsigned>::USIZE {
+                    return None;
+                }
+                Some($name(super::le_bits_to_u128(bits) as u64))
             }
 
             fn inverse(&self) -> Self {
@@ -321,7 +341,7 @@ small_binary_field!(
     num_bits = generic_array::typenum::U63,
     polynomial_modulus = polynomial_modulus_f63b,
     reduce = reduce_f63b,
-    reduce_vectored = reduce_vectored_f63b
+    reduce_vectored = reduce_vectored_f63b,
 );
 
 #[inline(always)] // due to SIMD
@@ -381,6 +401,51 @@ fn reduce_f40b(product: U64x2) -> F40b {
     F40b(lower_mask & r_lower)
 }
 
+// The carryless product of two `F40b` values, as its lower and upper 64 bits.
+//
+// `vectoreyes` only uses `PCLMULQDQ` when the whole crate is built for a CPU with AVX2, so the
+// instruction (or `PMULL` on aarch64) is picked here at runtime instead, falling back to the
+// portable implementation when the CPU lacks it. The feature checks are cached by `std`, and
+// compile to nothing when the feature is enabled at build time.
+#[inline(always)]
+fn carryless_mul_f40b(a: u64, b: u64) -> U64x2 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::is_x86_feature_detected!("pclmulqdq") {
+            // SAFETY: the CPU supports PCLMULQDQ.
+            return unsafe { carryless_mul_pclmulqdq(a, b) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("aes") {
+            // SAFETY: the CPU supports PMULL.
+            return unsafe { carryless_mul_pmull(a, b) };
+        }
+    }
+    carryless_mul_portable(a, b)
+}
+
+#[inline(always)]
+fn carryless_mul_portable(a: u64, b: u64) -> U64x2 {
+    U64x2::set_lo(a).carryless_mul::<false, false>(U64x2::set_lo(b))
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn carryless_mul_pclmulqdq(a: u64, b: u64) -> U64x2 {
+    use std::arch::x86_64::{_mm_clmulepi64_si128, _mm_cvtsi64_si128};
+    let product =
+        _mm_clmulepi64_si128::<0x00>(_mm_cvtsi64_si128(a as i64), _mm_cvtsi64_si128(b as i64));
+    bytemuck::cast(std::mem::transmute::<_, u128>(product))
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon,aes")]
+unsafe fn carryless_mul_pmull(a: u64, b: u64) -> U64x2 {
+    bytemuck::cast(std::arch::aarch64::vmull_p64(a, b))
+}
+
 fn polynomial_modulus_f40b() -> Polynomial<F2> {
     // x^40 + x^5 + x^4 + x^3 + 1
     let mut coefficients = smallvec![F2::ZERO; 40];
@@ -401,8 +466,74 @@ small_binary_field!(
     num_bits = generic_array::typenum::U40,
     polynomial_modulus = polynomial_modulus_f40b,
     reduce = reduce_f40b,
+    carryless_mul = carryless_mul_f40b,
 );
 
+#[cfg(test)]
+mod f40b_carryless_mul {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Schoolbook multiplication of `F40b` values, one bit at a time.
+    fn mul_reference(a: u64, b: u64) -> u64 {
+        let mut product: u128 = 0;
+        for i in 0..40 {
+            if (b >> i) & 1 == 1 {
+                product ^= (a as u128) << i;
+            }
+        }
+        // x^40 = x^5 + x^4 + x^3 + 1
+        for i in (40..79).rev() {
+            if (product >> i) & 1 == 1 {
+                product ^= ((1 << 40) | 0b111001) << (i - 40);
+            }
+        }
+        product as u64
+    }
+
+    fn check(carryless_mul: impl Fn(u64, u64) -> U64x2, a: u64, b: u64) {
+        let product = reduce_f40b(carryless_mul(a, b));
+        assert_eq!(product.0, mul_reference(a, b));
+    }
+
+    proptest! {
+        #[test]
+        fn test_mul(a in 0..(1_u64 << 40), b in 0..(1_u64 << 40)) {
+            prop_assert_eq!((F40b(a) * F40b(b)).0, mul_reference(a, b));
+        }
+
+        #[test]
+        fn test_portable(a in 0..(1_u64 << 40), b in 0..(1_u64 << 40)) {
+            check(carryless_mul_portable, a, b);
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        #[test]
+        fn test_pclmulqdq(a in 0..(1_u64 << 40), b in 0..(1_u64 << 40)) {
+            if std::is_x86_feature_detected!("pclmulqdq") {
+                check(|a, b| unsafe { carryless_mul_pclmulqdq(a, b) }, a, b);
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        #[test]
+        fn test_pmull(a in 0..(1_u64 << 40), b in 0..(1_u64 << 40)) {
+            if std::arch::is_aarch64_feature_detected!("aes") {
+                check(|a, b| unsafe { carryless_mul_pmull(a, b) }, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_extremes() {
+        let max = (1_u64 << 40) - 1;
+        for (a, b) in [(0, max), (1, max), (max, max), (1 << 39, 1 << 39)] {
+            check(carryless_mul_f40b, a, b);
+            check(carryless_mul_portable, a, b);
+        }
+    }
+}
+
 #[inline(always)] // due to SIMD
 fn reduce_f45b(wide_product: U64x2) -> F45b {
     let wide_product: u128 = bytemuck::cast(wide_product);
@@ -146,6 +146,49 @@ macro_rules! test_field {
                     }
                 }
             }
+            proptest! {
+                #[test]
+                fn bit_decomposition_into_and_iter(x in any_fe()) {
+                    let decomp = x.bit_decomposition();
+                    prop_assert!(x.bit_decomposition_iter().eq(decomp.iter().copied()));
+                    let mut bits = vec![false; decomp.len()];
+                    x.bit_decomposition_into(&mut bits);
+                    prop_assert_eq!(&bits[..], &decomp[..]);
+                    // A prefix only writes the lower bits.
+                    let mut lower = vec![false; decomp.len() / 2];
+                    x.bit_decomposition_into(&mut lower);
+                    prop_assert_eq!(&lower[..], &decomp[..decomp.len() / 2]);
+                }
+            }
+            proptest! {
+                #[test]
+                fn le_bits_round_trip(x in any_fe()) {
+                    use crate::field::F2;
+                    let decomp = x.bit_decomposition();
+                    let num_bits = <$f as FiniteField>::bit_decomposition_len();
+                    let mut bits = vec![F2::ZERO; num_bits];
+                    x.to_le_bits_into(&mut bits);
+                    for (bit, expected) in bits.iter().zip(decomp.iter()) {
+                        prop_assert_eq!(bool::from(*bit), *expected);
+                    }
+                    prop_assert_eq!(<$f>::from_le_bits(&bits), Some(x));
+
+                    // A prefix only writes and reads the lower bits.
+                    let half = num_bits / 2;
+                    let mut lower = vec![F2::ZERO; half];
+                    x.to_le_bits_into(&mut lower);
+                    prop_assert_eq!(&lower[..], &bits[..half]);
+                    if let Some(y) = <$f>::from_le_bits(&lower) {
+                        let mut y_bits = vec![F2::ZERO; num_bits];
+                        y.to_le_bits_into(&mut y_bits);
+                        prop_assert_eq!(&y_bits[..half], &lower[..]);
+                        prop_assert!(y_bits[half..].iter().all(|b| *b == F2::ZERO));
+                    }
+
+                    bits.push(F2::ZERO);
+                    prop_assert_eq!(<$f>::from_le_bits(&bits), None);
+                }
+            }
         }
     };
 }
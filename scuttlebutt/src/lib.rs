@@ -6,6 +6,7 @@
 //! Scuttlebutt provides many utility functions for cryptographic applications.
 
 mod aes;
+pub mod bitpack;
 mod block;
 mod block512;
 pub mod bloomfilter;
@@ -31,11 +32,23 @@ pub use crate::{
     },
     block::Block,
     block512::Block512,
-    channel::{AbstractChannel, Channel, HashChannel, SymChannel, SyncChannel, TrackChannel},
+    channel::{
+        local_pair, sim_pair, tcp_channel, AbstractChannel, BufferedChannel, Channel,
+        ChannelConfig, CommBreakdown, FlushPolicy, HashChannel, LocalChannel, RecordChannel,
+        ReplayChannel, ScopeComm, SimChannel, SimParams, SymChannel, SyncChannel, TrackChannel,
+        Transcript, TranscriptRecord,
+    },
     hash_aes::{AesHash, AES_HASH},
     rand_aes::{vectorized::UniformIntegersUnderBound, AesRng},
 };
 
+#[cfg(feature = "lz4")]
+pub use crate::channel::CompressedChannel;
+#[cfg(feature = "noise")]
+pub use crate::channel::NoiseChannel;
+#[cfg(feature = "tokio")]
+pub use crate::channel::{AsyncAbstractChannel, AsyncChannel};
+
 #[cfg(unix)]
 pub use crate::channel::{
     track_unix_channel_pair, unix_channel_pair, TrackUnixChannel, UnixChannel,
@@ -45,7 +58,6 @@ pub use crate::channel::{
     track_unix_channel_pair, unix_channel_pair, TrackUnixChannel, UnixChannel,
 };
 
-
 /// A marker trait denoting that the given scheme is semi-honest secure.
 pub trait SemiHonest {}
 /// A marker trait denoting that the given scheme is maliciously secure.
@@ -1,6 +1,6 @@
 //! Fixed-key AES random number generator.
 
-use crate::Block;
+use crate::{aes::aes128::Aes128, Block};
 use rand::{CryptoRng, Error, Rng, RngCore, SeedableRng};
 use rand_core::block::{BlockRng64, BlockRngCore};
 use vectoreyes::{
@@ -10,6 +10,19 @@ use vectoreyes::{
 
 pub mod vectorized;
 
+// Domains of the seeds derived by `AesRng::fork` and
+// `AesRng::from_seed_and_stream`, in the upper half of the block encrypted
+// under the parent seed. `AesRng` itself only ever encrypts blocks with a
+// zero upper half, so derived seeds never coincide with its output.
+const FORK_DOMAIN: u64 = 1;
+const STREAM_DOMAIN: u64 = 2;
+
+// Derive the seed of a child stream from `seed`, within `domain`.
+#[inline]
+fn derive_seed(seed: Block, domain: u64, index: u64) -> Block {
+    Aes128::new(seed).encrypt(Block::from(((domain as u128) << 64) | index as u128))
+}
+
 /// Implementation of a random number generator based on fixed-key AES.
 ///
 /// This uses AES in a counter-mode-esque way, but with the counter always
@@ -60,11 +73,29 @@ impl AesRng {
         AesRng::from_seed(seed)
     }
 
-    /// Create a new RNG using a random seed from this one.
+    /// Create a new RNG whose seed is derived from the next block of this
+    /// one's stream.
+    ///
+    /// Assuming AES is a pseudorandom function, the child's stream is
+    /// independent from everything this RNG outputs afterwards, and from
+    /// every other child forked from it; the block its seed comes from is
+    /// consumed and never output. Forking is deterministic: the same parent
+    /// seed forks the same children in the same order.
     #[inline]
     pub fn fork(&mut self) -> Self {
         let seed = self.gen::<Block>();
-        AesRng::from_seed(seed)
+        AesRng::from_seed(derive_seed(seed, FORK_DOMAIN, 0))
+    }
+
+    /// Create the RNG of the stream at `index` derived from `seed`.
+    ///
+    /// Assuming AES is a pseudorandom function, the streams of distinct
+    /// indices are independent from each other and from the stream of
+    /// `AesRng::from_seed(seed)`. This lets a single seed drive any number of
+    /// threads reproducibly, regardless of the order they are created in.
+    #[inline]
+    pub fn from_seed_and_stream(seed: Block, index: u64) -> Self {
+        AesRng::from_seed(derive_seed(seed, STREAM_DOMAIN, index))
     }
 
     /// Generate random bits.
@@ -163,4 +194,57 @@ mod tests {
         let b = rng.gen::<[Block; 8]>();
         assert_ne!(a, b);
     }
+
+    // Check that the first outputs of the given streams are pairwise
+    // distinct, and that neighbouring streams do not trivially correlate:
+    // their xor should have about half its bits set.
+    fn check_streams(mut rngs: Vec<AesRng>) {
+        let outputs: Vec<[u64; 4]> = rngs.iter_mut().map(|rng| rng.gen()).collect();
+        let mut sorted = outputs.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), outputs.len());
+        let ones: u32 = outputs
+            .windows(2)
+            .map(|w| {
+                (0..4)
+                    .map(|i| (w[0][i] ^ w[1][i]).count_ones())
+                    .sum::<u32>()
+            })
+            .sum();
+        let bits = 256 * (outputs.len() as u32 - 1);
+        // The expected count is `bits / 2`, with a standard deviation of
+        // `sqrt(bits) / 2`; allow ten of them.
+        let deviation = (ones as f64 - bits as f64 / 2.0).abs();
+        assert!(deviation < 5.0 * (bits as f64).sqrt());
+    }
+
+    #[test]
+    fn test_fork() {
+        let seed = Block::from(42u128);
+        let mut parent = AesRng::from_seed(seed);
+        let mut rngs: Vec<_> = (0..1000).map(|_| parent.fork()).collect();
+        rngs.push(parent);
+        check_streams(rngs);
+
+        // Deterministic.
+        let mut a = AesRng::from_seed(seed);
+        let mut b = AesRng::from_seed(seed);
+        assert_eq!(a.fork().gen::<Block>(), b.fork().gen::<Block>());
+        assert_eq!(a.gen::<Block>(), b.gen::<Block>());
+    }
+
+    #[test]
+    fn test_from_seed_and_stream() {
+        let seed = Block::from(42u128);
+        let mut rngs: Vec<_> = (0..1000)
+            .map(|i| AesRng::from_seed_and_stream(seed, i))
+            .collect();
+        rngs.push(AesRng::from_seed(seed));
+        check_streams(rngs);
+
+        let mut a = AesRng::from_seed_and_stream(seed, 7);
+        let mut b = AesRng::from_seed_and_stream(seed, 7);
+        assert_eq!(a.gen::<[Block; 4]>(), b.gen::<[Block; 4]>());
+    }
 }